@@ -160,6 +160,27 @@ where
             initialise_metrics(registry),
             Arc::new(Clock::new()),
         ));
+        let network_manager = N::new(context.clone(), network_keypair);
+
+        Self::start_with_network(
+            context,
+            network_manager,
+            protocol_keypair,
+            transaction_verifier,
+            commit_consumer,
+        )
+        .await
+    }
+
+    /// Starts the authority over an already created network manager. Tests use this to run
+    /// authorities over a simulated network, sharing a single clock.
+    pub(crate) async fn start_with_network(
+        context: Arc<Context>,
+        mut network_manager: N,
+        protocol_keypair: ProtocolKeyPair,
+        transaction_verifier: Arc<dyn TransactionVerifier>,
+        commit_consumer: CommitConsumer,
+    ) -> Self {
         let start_time = Instant::now();
 
         let (tx_client, tx_receiver) = TransactionClient::new(context.clone());
@@ -167,7 +188,6 @@ where
 
         let (core_signals, signals_receivers) = CoreSignals::new(context.clone());
 
-        let mut network_manager = N::new(context.clone(), network_keypair);
         let network_client = network_manager.client();
 
         // REQUIRED: Broadcaster must be created before Core, to start listening on the
@@ -340,6 +360,10 @@ where
     }
 }
 
+#[cfg(test)]
+#[path = "tests/simulation_tests.rs"]
+mod simulation_tests;
+
 #[cfg(test)]
 mod tests {
     #![allow(non_snake_case)]
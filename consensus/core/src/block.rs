@@ -615,6 +615,47 @@ pub enum MisbehaviorProof {
     InvalidBlock(BlockRef),
 }
 
+/// Two different blocks signed by the same authority for the same slot. Both blocks carry the
+/// author's signature, so anyone who knows the committee can verify the evidence.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct EquivocationEvidence {
+    pub(crate) first: VerifiedBlock,
+    pub(crate) second: VerifiedBlock,
+}
+
+impl EquivocationEvidence {
+    pub(crate) fn new(first: VerifiedBlock, second: VerifiedBlock) -> Self {
+        assert_eq!(first.slot(), second.slot());
+        assert_ne!(first.digest(), second.digest());
+        Self { first, second }
+    }
+
+    pub(crate) fn slot(&self) -> Slot {
+        self.first.slot()
+    }
+
+    /// Verifies that the blocks are different, for the same slot, and both signed by the author.
+    pub(crate) fn verify(&self, context: &Context) -> ConsensusResult<()> {
+        ensure!(
+            self.first.slot() == self.second.slot(),
+            ConsensusError::InvalidEquivocationEvidence(format!(
+                "blocks are for different slots {} and {}",
+                self.first.slot(),
+                self.second.slot()
+            ))
+        );
+        ensure!(
+            self.first.digest() != self.second.digest(),
+            ConsensusError::InvalidEquivocationEvidence(format!(
+                "blocks are identical {}",
+                self.first.reference()
+            ))
+        );
+        self.first.verify_signature(context)?;
+        self.second.verify_signature(context)
+    }
+}
+
 // TODO: add basic verification for BlockRef and BlockDigest.
 // TODO: add tests for SignedBlock and VerifiedBlock conversion.
 
@@ -625,7 +666,7 @@ mod tests {
     use fastcrypto::error::FastCryptoError;
 
     use crate::{
-        block::{SignedBlock, TestBlock},
+        block::{EquivocationEvidence, SignedBlock, TestBlock, VerifiedBlock},
         context::Context,
         error::ConsensusError,
     };
@@ -660,4 +701,43 @@ mod tests {
             err => panic!("Unexpected error: {err:?}"),
         }
     }
+
+    #[tokio::test]
+    async fn test_verify_equivocation_evidence() {
+        let (context, key_pairs) = Context::new_for_test(4);
+        let context = Arc::new(context);
+
+        let sign = |block, key_index: usize| {
+            let signed_block = SignedBlock::new(block, &key_pairs[key_index].1).unwrap();
+            let serialized = signed_block.serialize().unwrap();
+            VerifiedBlock::new_verified(signed_block, serialized)
+        };
+
+        // Two blocks signed by authority 2 for the same slot.
+        let first = sign(TestBlock::new(10, 2).build(), 2);
+        let second = sign(TestBlock::new(10, 2).set_timestamp_ms(1).build(), 2);
+        let evidence = EquivocationEvidence::new(first.clone(), second);
+        assert!(evidence.verify(&context).is_ok());
+
+        // A conflicting block not signed by the author is no evidence.
+        let forged = sign(TestBlock::new(10, 2).set_timestamp_ms(1).build(), 1);
+        let evidence = EquivocationEvidence {
+            first: first.clone(),
+            second: forged,
+        };
+        assert!(matches!(
+            evidence.verify(&context),
+            Err(ConsensusError::SignatureVerificationFailure(_))
+        ));
+
+        // Neither are blocks for different slots.
+        let evidence = EquivocationEvidence {
+            first,
+            second: sign(TestBlock::new(11, 2).build(), 2),
+        };
+        assert!(matches!(
+            evidence.verify(&context),
+            Err(ConsensusError::InvalidEquivocationEvidence(_))
+        ));
+    }
 }
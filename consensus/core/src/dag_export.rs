@@ -0,0 +1,612 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exports a range of rounds of the DAG persisted by an authority, for debugging. The export can
+//! be serialized, rendered with Graphviz, or turned into the text DSL of `test_dag_parser` to
+//! reproduce an incident in a unit test. It includes the evidence of equivocations the authority
+//! recorded in the range.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+};
+
+use serde::Serialize;
+
+use crate::{
+    block::{BlockAPI as _, BlockRef, EquivocationEvidence, Round, VerifiedBlock},
+    commit::{CommitAPI as _, CommitIndex, CommitRange, TrustedCommit},
+    error::ConsensusResult,
+    storage::rocksdb_store::RocksDBStore,
+};
+
+// Number of commits read from the store at a time.
+const COMMIT_SCAN_BATCH_SIZE: CommitIndex = 1000;
+
+/// A range of rounds of the DAG, with the commits led by blocks in the range.
+#[derive(Clone, Debug, Serialize)]
+pub struct DagExport {
+    /// Size of the committee, as far as it can be told from the store.
+    pub num_authorities: usize,
+    pub start_round: Round,
+    pub end_round: Round,
+    /// Blocks in the range, ordered by round then author.
+    pub blocks: Vec<ExportedBlock>,
+    /// Commits whose leader is in the range, in commit order.
+    pub commits: Vec<ExportedCommit>,
+    /// Outcome of every leader round between the first and the last committed leaders.
+    pub leaders: Vec<ExportedLeader>,
+    /// Reputation scores stored alongside the exported commits.
+    pub reputation_scores: Vec<ExportedReputationScores>,
+    /// Evidence of equivocations in the range, ordered by author then round.
+    pub equivocations: Vec<ExportedEquivocation>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct ExportedBlockRef {
+    pub round: Round,
+    pub author: u32,
+    /// Base64 encoded.
+    pub digest: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ExportedBlock {
+    pub reference: ExportedBlockRef,
+    pub timestamp_ms: u64,
+    pub ancestors: Vec<ExportedBlockRef>,
+    pub num_transactions: usize,
+    pub commit_votes: Vec<CommitIndex>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ExportedCommit {
+    pub index: CommitIndex,
+    /// Base64 encoded.
+    pub digest: String,
+    pub leader: ExportedBlockRef,
+    pub timestamp_ms: u64,
+    pub blocks: Vec<ExportedBlockRef>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ExportedLeader {
+    pub round: Round,
+    pub decision: LeaderDecision,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub enum LeaderDecision {
+    Commit {
+        commit_index: CommitIndex,
+        leader: ExportedBlockRef,
+    },
+    /// No leader of the round was committed, either because it was skipped or because it was
+    /// missing.
+    Skip,
+}
+
+/// Two blocks signed by the same author for the same slot.
+#[derive(Clone, Debug, Serialize)]
+pub struct ExportedEquivocation {
+    pub first: ExportedSignedBlock,
+    pub second: ExportedSignedBlock,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ExportedSignedBlock {
+    pub reference: ExportedBlockRef,
+    /// The block with the signature of its author, BCS serialized then base64 encoded. Its digest
+    /// is the digest of the reference, and the signature can be checked against the protocol key
+    /// of the author in the committee.
+    pub signed_block: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ExportedReputationScores {
+    pub commit_index: CommitIndex,
+    /// Range of commits the scores were calculated from.
+    pub scored_commits: (CommitIndex, CommitIndex),
+    /// Score per authority index.
+    pub scores: Vec<u64>,
+}
+
+/// Exports rounds `start_round..=end_round` of the consensus store at `db_path`. The store is
+/// opened as a read-only secondary, so it can belong to a running authority.
+pub fn export_dag(
+    db_path: &str,
+    start_round: Round,
+    end_round: Round,
+) -> ConsensusResult<DagExport> {
+    let store = RocksDBStore::new_read_only(db_path)?;
+    DagExport::from_store(&store, start_round, end_round)
+}
+
+impl DagExport {
+    pub(crate) fn from_store(
+        store: &RocksDBStore,
+        start_round: Round,
+        end_round: Round,
+    ) -> ConsensusResult<Self> {
+        let blocks = store.scan_blocks_by_round(start_round, end_round)?;
+
+        let mut commits = vec![];
+        // Earlier commits may have been pruned.
+        let mut next_index = store.read_first_commit_index()?;
+        while let Some(start) = next_index {
+            let batch = store.scan_commits(CommitRange::new(
+                start..=start.saturating_add(COMMIT_SCAN_BATCH_SIZE - 1),
+            ))?;
+            let Some(last) = batch.last() else {
+                break;
+            };
+            next_index = last.index().checked_add(1);
+            // Leader rounds increase with commit indices.
+            let past_range = last.leader().round > end_round;
+            commits.extend(batch.into_iter().filter(|c| {
+                let round = c.leader().round;
+                start_round <= round && round <= end_round
+            }));
+            if past_range {
+                break;
+            }
+        }
+
+        let commit_info = match (commits.first(), commits.last()) {
+            (Some(first), Some(last)) => {
+                store.scan_commit_info(CommitRange::new(first.index()..=last.index()))?
+            }
+            _ => vec![],
+        };
+        let reputation_scores = commit_info
+            .iter()
+            .map(|(commit_ref, info)| ExportedReputationScores {
+                commit_index: commit_ref.index,
+                scored_commits: (
+                    info.reputation_scores.commit_range.start(),
+                    info.reputation_scores.commit_range.end(),
+                ),
+                scores: info.reputation_scores.scores_per_authority.clone(),
+            })
+            .collect();
+
+        let num_authorities = commit_info
+            .first()
+            .map(|(_, info)| info.committed_rounds.len())
+            .unwrap_or_else(|| {
+                blocks
+                    .iter()
+                    .flat_map(|b| b.ancestors().iter().map(|a| a.author).chain([b.author()]))
+                    .max()
+                    .map_or(0, |author| author.value() + 1)
+            });
+
+        let equivocations = store
+            .scan_equivocations_by_round(start_round, end_round)?
+            .iter()
+            .map(export_equivocation)
+            .collect();
+
+        Ok(Self {
+            num_authorities,
+            start_round,
+            end_round,
+            blocks: blocks.iter().map(export_block).collect(),
+            leaders: leader_decisions(&commits),
+            commits: commits.iter().map(export_commit).collect(),
+            reputation_scores,
+            equivocations,
+        })
+    }
+
+    /// Renders the DAG in Graphviz DOT, one column per round. Committed leaders are highlighted,
+    /// committed blocks are labelled with their commit index, and equivocating blocks outlined.
+    pub fn to_dot(&self) -> String {
+        let committed = self
+            .commits
+            .iter()
+            .flat_map(|c| c.blocks.iter().map(move |b| (b, c.index)))
+            .collect::<BTreeMap<_, _>>();
+        let leaders = self
+            .commits
+            .iter()
+            .map(|c| &c.leader)
+            .collect::<BTreeSet<_>>();
+        let exported = self
+            .blocks
+            .iter()
+            .map(|b| &b.reference)
+            .collect::<BTreeSet<_>>();
+        let equivocating = self
+            .equivocations
+            .iter()
+            .flat_map(|e| [&e.first.reference, &e.second.reference])
+            .collect::<BTreeSet<_>>();
+
+        let mut dot = String::new();
+        writeln!(dot, "digraph dag {{").unwrap();
+        writeln!(dot, "    rankdir=RL;").unwrap();
+        writeln!(dot, "    node [shape=box, fontname=\"monospace\"];").unwrap();
+        let mut rounds = BTreeMap::<Round, Vec<&ExportedBlock>>::new();
+        for block in &self.blocks {
+            rounds.entry(block.reference.round).or_default().push(block);
+        }
+        for (round, blocks) in rounds {
+            writeln!(dot, "    subgraph round_{round} {{").unwrap();
+            writeln!(dot, "        rank=same;").unwrap();
+            for block in blocks {
+                let reference = &block.reference;
+                let mut label = format!(
+                    "{}\\n{}\\n{} txs",
+                    slot_name(reference),
+                    &reference.digest[..4],
+                    block.num_transactions
+                );
+                let mut style = String::new();
+                if let Some(index) = committed.get(reference) {
+                    write!(label, "\\ncommit {index}").unwrap();
+                    let color = if leaders.contains(reference) {
+                        "gold"
+                    } else {
+                        "lightblue"
+                    };
+                    style = format!(", style=filled, fillcolor={color}");
+                }
+                if equivocating.contains(reference) {
+                    style.push_str(", color=red, penwidth=2");
+                }
+                writeln!(
+                    dot,
+                    "        {} [label=\"{label}\"{style}];",
+                    node_name(reference)
+                )
+                .unwrap();
+            }
+            writeln!(dot, "    }}").unwrap();
+        }
+        for block in &self.blocks {
+            for ancestor in block.ancestors.iter().filter(|a| exported.contains(a)) {
+                writeln!(
+                    dot,
+                    "    {} -> {};",
+                    node_name(&block.reference),
+                    node_name(ancestor)
+                )
+                .unwrap();
+            }
+        }
+        writeln!(dot, "}}").unwrap();
+        dot
+    }
+
+    /// Renders the DAG in the text DSL of `test_dag_parser`, so it can be loaded as a test
+    /// fixture. Ancestors before `start_round`, except genesis, are left out. The DSL has one
+    /// block per slot and names authorities by letter, so only the first block of each slot is
+    /// kept and committees of more than 26 authorities are not supported.
+    pub fn to_dag_dsl(&self) -> Result<String, String> {
+        if self.num_authorities > 26 {
+            return Err(format!(
+                "The DAG DSL supports up to 26 authorities, got {}",
+                self.num_authorities
+            ));
+        }
+
+        let mut dsl = String::new();
+        writeln!(dsl, "DAG {{").unwrap();
+        writeln!(dsl, "    Round 0 : {{ {} }},", self.num_authorities).unwrap();
+        let mut rounds = BTreeMap::<Round, BTreeMap<u32, &ExportedBlock>>::new();
+        for block in &self.blocks {
+            if block.reference.round == 0 {
+                continue;
+            }
+            rounds
+                .entry(block.reference.round)
+                .or_default()
+                .entry(block.reference.author)
+                .or_insert(block);
+        }
+        for (round, blocks) in rounds {
+            writeln!(dsl, "    Round {round} : {{").unwrap();
+            for (author, block) in blocks {
+                let ancestors = block
+                    .ancestors
+                    .iter()
+                    .filter(|a| a.round == 0 || a.round >= self.start_round)
+                    .map(slot_name)
+                    .collect::<BTreeSet<_>>();
+                writeln!(
+                    dsl,
+                    "        {} -> [{}],",
+                    authority_name(author),
+                    ancestors.into_iter().collect::<Vec<_>>().join(", ")
+                )
+                .unwrap();
+            }
+            writeln!(dsl, "    }},").unwrap();
+        }
+        writeln!(dsl, "}}").unwrap();
+        Ok(dsl)
+    }
+}
+
+fn export_ref(block_ref: &BlockRef) -> ExportedBlockRef {
+    ExportedBlockRef {
+        round: block_ref.round,
+        author: block_ref.author.value() as u32,
+        digest: format!("{:?}", block_ref.digest),
+    }
+}
+
+fn export_block(block: &VerifiedBlock) -> ExportedBlock {
+    ExportedBlock {
+        reference: export_ref(&block.reference()),
+        timestamp_ms: block.timestamp_ms(),
+        ancestors: block.ancestors().iter().map(export_ref).collect(),
+        num_transactions: block.transactions().len(),
+        commit_votes: block.commit_votes().iter().map(|v| v.index).collect(),
+    }
+}
+
+fn export_signed_block(block: &VerifiedBlock) -> ExportedSignedBlock {
+    ExportedSignedBlock {
+        reference: export_ref(&block.reference()),
+        signed_block: base64::Engine::encode(
+            &base64::engine::general_purpose::STANDARD,
+            block.serialized(),
+        ),
+    }
+}
+
+fn export_equivocation(evidence: &EquivocationEvidence) -> ExportedEquivocation {
+    ExportedEquivocation {
+        first: export_signed_block(&evidence.first),
+        second: export_signed_block(&evidence.second),
+    }
+}
+
+fn export_commit(commit: &TrustedCommit) -> ExportedCommit {
+    ExportedCommit {
+        index: commit.index(),
+        digest: format!("{:?}", commit.digest()),
+        leader: export_ref(&commit.leader()),
+        timestamp_ms: commit.timestamp_ms(),
+        blocks: commit.blocks().iter().map(export_ref).collect(),
+    }
+}
+
+fn leader_decisions(commits: &[TrustedCommit]) -> Vec<ExportedLeader> {
+    let (Some(first), Some(last)) = (commits.first(), commits.last()) else {
+        return vec![];
+    };
+    let committed = commits
+        .iter()
+        .map(|c| (c.leader().round, c))
+        .collect::<BTreeMap<_, _>>();
+    (first.leader().round..=last.leader().round)
+        .map(|round| ExportedLeader {
+            round,
+            decision: match committed.get(&round) {
+                Some(commit) => LeaderDecision::Commit {
+                    commit_index: commit.index(),
+                    leader: export_ref(&commit.leader()),
+                },
+                None => LeaderDecision::Skip,
+            },
+        })
+        .collect()
+}
+
+// Same as the Display of AuthorityIndex.
+fn authority_name(author: u32) -> String {
+    if author < 26 {
+        ((b'A' + author as u8) as char).to_string()
+    } else {
+        format!("[{author:02}]")
+    }
+}
+
+fn slot_name(block_ref: &ExportedBlockRef) -> String {
+    format!("{}{}", authority_name(block_ref.author), block_ref.round)
+}
+
+fn node_name(block_ref: &ExportedBlockRef) -> String {
+    format!("\"{}_{}\"", slot_name(block_ref), block_ref.digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use consensus_config::AuthorityIndex;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::{
+        block::TestBlock,
+        context::Context,
+        storage::{Store as _, WriteBatch},
+        test_dag_builder::DagBuilder,
+        test_dag_parser::parse_dag,
+    };
+
+    #[test]
+    fn export_and_reimport() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layers(1..=6).build();
+        // Round 7 does not link to A6, so the export is not fully connected.
+        dag_builder
+            .layer(7)
+            .authorities(
+                context
+                    .committee
+                    .authorities()
+                    .map(|(index, _)| index)
+                    .collect(),
+            )
+            .skip_ancestor_links(vec![AuthorityIndex::new_for_test(0)]);
+        dag_builder.layers(8..=10).build();
+
+        // Commits leaders of rounds 3 and 5, skipping round 4.
+        let mut commits = vec![];
+        let mut last_committed_rounds = vec![0; 4];
+        for (index, round) in [(1, 3), (2, 5)] {
+            let leader = dag_builder.leader_block(round).unwrap();
+            let (sub_dag, commit) =
+                dag_builder.get_sub_dag_and_commit(leader, last_committed_rounds.clone(), index);
+            for block in &sub_dag.blocks {
+                let author = block.author().value();
+                last_committed_rounds[author] = last_committed_rounds[author].max(block.round());
+            }
+            commits.push(commit);
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let store = RocksDBStore::new(temp_dir.path().to_str().unwrap());
+        store
+            .write(
+                WriteBatch::default()
+                    .blocks(dag_builder.blocks(1..=10))
+                    .commits(commits),
+            )
+            .unwrap();
+
+        let export = DagExport::from_store(&store, 2, 8).unwrap();
+        assert_eq!(export.num_authorities, 4);
+        assert!(export
+            .blocks
+            .iter()
+            .all(|b| (2..=8).contains(&b.reference.round)));
+        assert_eq!(export.commits.len(), 2);
+        let decisions = export
+            .leaders
+            .iter()
+            .map(|l| (l.round, matches!(l.decision, LeaderDecision::Commit { .. })))
+            .collect::<Vec<_>>();
+        assert_eq!(decisions, vec![(3, true), (4, false), (5, true)]);
+        assert!(export.to_dot().starts_with("digraph dag {"));
+
+        // The DSL reproduces the structure of the exported rounds.
+        let dsl = export.to_dag_dsl().unwrap();
+        let (_, imported) = parse_dag(&dsl).expect("Invalid dag");
+        let slots = |blocks: Vec<VerifiedBlock>| {
+            blocks
+                .iter()
+                .map(|b| {
+                    let ancestors = b
+                        .ancestors()
+                        .iter()
+                        .filter(|a| a.round >= 2)
+                        .map(|a| (a.round, a.author))
+                        .collect::<BTreeSet<_>>();
+                    (b.round(), b.author(), ancestors)
+                })
+                .collect::<BTreeSet<_>>()
+        };
+        assert_eq!(
+            slots(imported.blocks(2..=8)),
+            slots(dag_builder.blocks(2..=8))
+        );
+    }
+
+    #[test]
+    fn export_equivocations() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layers(1..=3).build();
+        let block = dag_builder.blocks(2..=2).remove(1);
+        let conflicting = VerifiedBlock::new_for_test(
+            TestBlock::new(2, block.author().value() as u32)
+                .set_timestamp_ms(block.timestamp_ms() + 1)
+                .build(),
+        );
+        let evidence = EquivocationEvidence::new(block.clone(), conflicting.clone());
+
+        let temp_dir = TempDir::new().unwrap();
+        let store = RocksDBStore::new(temp_dir.path().to_str().unwrap());
+        store
+            .write(
+                WriteBatch::default()
+                    .blocks(
+                        dag_builder
+                            .blocks(1..=3)
+                            .into_iter()
+                            .chain([conflicting.clone()])
+                            .collect(),
+                    )
+                    .equivocations(vec![evidence]),
+            )
+            .unwrap();
+
+        assert!(DagExport::from_store(&store, 3, 3)
+            .unwrap()
+            .equivocations
+            .is_empty());
+        let export = DagExport::from_store(&store, 1, 3).unwrap();
+        assert_eq!(export.equivocations.len(), 1);
+
+        // Both blocks are exported with their signature, and hash to their digest.
+        let equivocation = &export.equivocations[0];
+        for (exported, block) in [
+            (&equivocation.first, &block),
+            (&equivocation.second, &conflicting),
+        ] {
+            assert_eq!(exported.reference, export_ref(&block.reference()));
+            let signed_block = base64::Engine::decode(
+                &base64::engine::general_purpose::STANDARD,
+                &exported.signed_block,
+            )
+            .unwrap();
+            assert_eq!(VerifiedBlock::compute_digest(&signed_block), block.digest());
+        }
+        assert!(export.to_dot().contains("color=red"));
+    }
+
+    #[test]
+    fn export_read_only_after_pruned_commits() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layers(1..=6).build();
+
+        // Commits before the first one in the store were pruned, far past the first scan batch.
+        let first_index = 3 * COMMIT_SCAN_BATCH_SIZE + 1;
+        let mut commits = vec![];
+        let mut last_committed_rounds = vec![0; 4];
+        for (index, round) in [(first_index, 3), (first_index + 1, 4)] {
+            let leader = dag_builder.leader_block(round).unwrap();
+            let (sub_dag, commit) =
+                dag_builder.get_sub_dag_and_commit(leader, last_committed_rounds.clone(), index);
+            for block in &sub_dag.blocks {
+                let author = block.author().value();
+                last_committed_rounds[author] = last_committed_rounds[author].max(block.round());
+            }
+            commits.push(commit);
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("consensus_db");
+        let db_path = db_path.to_str().unwrap();
+        let store = RocksDBStore::new(db_path);
+        store
+            .write(
+                WriteBatch::default()
+                    .blocks(dag_builder.blocks(1..=6))
+                    .commits(commits),
+            )
+            .unwrap();
+
+        // The store is still open, as it would be by a running authority.
+        let export = export_dag(db_path, 1, 6).unwrap();
+        assert_eq!(
+            export.commits.iter().map(|c| c.index).collect::<Vec<_>>(),
+            vec![first_index, first_index + 1]
+        );
+
+        // The secondary does not leave files next to the storage.
+        let entries = std::fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect::<Vec<_>>();
+        assert_eq!(entries, vec!["consensus_db"]);
+    }
+}
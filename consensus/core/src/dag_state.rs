@@ -11,16 +11,16 @@ use std::{
 
 use consensus_config::AuthorityIndex;
 use itertools::Itertools as _;
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 
 use crate::{
     block::{
-        genesis_blocks, BlockAPI, BlockDigest, BlockRef, BlockTimestampMs, Round, Slot,
-        VerifiedBlock, GENESIS_ROUND,
+        genesis_blocks, BlockAPI, BlockDigest, BlockRef, BlockTimestampMs, EquivocationEvidence,
+        Round, Slot, VerifiedBlock, GENESIS_ROUND,
     },
     commit::{
         load_committed_subdag_from_store, CommitAPI as _, CommitDigest, CommitIndex, CommitInfo,
-        CommitRef, CommitVote, CommittedSubDag, TrustedCommit, GENESIS_COMMIT_INDEX,
+        CommitRange, CommitRef, CommitVote, CommittedSubDag, TrustedCommit, GENESIS_COMMIT_INDEX,
     },
     context::Context,
     leader_scoring::ReputationScores,
@@ -78,6 +78,7 @@ pub(crate) struct DagState {
     // next dag state flush. This is okay because we can recover reputation scores
     // & last_committed_rounds from the commits as needed.
     commit_info_to_write: Vec<(CommitRef, CommitInfo)>,
+    equivocations_to_write: Vec<EquivocationEvidence>,
 
     // Persistent storage for blocks, commits and other consensus data.
     store: Arc<dyn Store>,
@@ -158,6 +159,7 @@ impl DagState {
             blocks_to_write: vec![],
             commits_to_write: vec![],
             commit_info_to_write: vec![],
+            equivocations_to_write: vec![],
             unscored_committed_subdags,
             store,
             cached_rounds,
@@ -210,6 +212,8 @@ impl DagState {
                 "Block Rejected! Attempted to add block {block:#?} to own slot where \
                 block(s) {existing_blocks:#?} already exists."
             );
+        } else {
+            self.record_equivocation(&block);
         }
         self.update_block_metadata(&block);
         self.blocks_to_write.push(block);
@@ -226,6 +230,137 @@ impl DagState {
             .inc();
     }
 
+    /// Records evidence if `block` conflicts with accepted blocks of the same slot, against each of
+    /// them. Blocks of evicted rounds are only in storage. This way, every authority that accepted
+    /// two blocks of a slot has the evidence, whatever the order it accepted them in.
+    fn record_equivocation(&mut self, block: &VerifiedBlock) {
+        let slot = block.slot();
+        let mut existing = self.get_uncommitted_blocks_at_slot(slot);
+        if slot.round <= self.authority_eviction_round(slot.authority) {
+            let stored = self
+                .store
+                .scan_blocks_by_author(slot.authority, slot.round)
+                .unwrap_or_else(|e| panic!("Failed to read from storage: {:?}", e));
+            existing.extend(stored.into_iter().take_while(|b| b.round() == slot.round));
+        }
+        for existing in existing
+            .into_iter()
+            .unique_by(|b| b.digest())
+            .filter(|b| b.digest() != block.digest())
+        {
+            warn!(
+                "Authority {} equivocated: blocks {} and {}",
+                block.author(),
+                existing.reference(),
+                block.reference()
+            );
+            let hostname = &self.context.committee.authority(block.author()).hostname;
+            self.context
+                .metrics
+                .node_metrics
+                .equivocations
+                .with_label_values(&[hostname])
+                .inc();
+            self.equivocations_to_write
+                .push(EquivocationEvidence::new(existing, block.clone()));
+        }
+    }
+
+    /// Reads the evidence of equivocations from `start_round`, including evidence not flushed yet.
+    pub(crate) fn read_equivocations(&self, start_round: Round) -> Vec<EquivocationEvidence> {
+        let mut evidence = vec![];
+        for (authority, _) in self.context.committee.authorities() {
+            evidence.extend(
+                self.store
+                    .scan_equivocations(authority, start_round)
+                    .unwrap_or_else(|e| panic!("Failed to read from storage: {:?}", e)),
+            );
+        }
+        evidence.extend(
+            self.equivocations_to_write
+                .iter()
+                .filter(|e| e.slot().round >= start_round)
+                .cloned(),
+        );
+        evidence
+    }
+
+    /// Evidence of equivocations whose blocks are both committed, one of them by the `subdags`.
+    /// Every authority accepts the committed blocks, so has recorded the evidence by the time they
+    /// are committed, and authorities agree on it as they do on the commits.
+    pub(crate) fn committed_equivocations(
+        &self,
+        subdags: &[CommittedSubDag],
+    ) -> Vec<EquivocationEvidence> {
+        let mut committed = subdags
+            .iter()
+            .flat_map(|subdag| subdag.blocks.iter().map(|block| block.reference()))
+            .collect::<BTreeSet<_>>();
+        let (Some(start_round), Some(first)) = (
+            committed.iter().map(|block_ref| block_ref.round).min(),
+            subdags.first(),
+        ) else {
+            return vec![];
+        };
+        let evidence = self
+            .read_equivocations(start_round)
+            .into_iter()
+            .filter(|e| {
+                committed.contains(&e.first.reference())
+                    || committed.contains(&e.second.reference())
+            })
+            .collect::<Vec<_>>();
+        if evidence.iter().any(|e| {
+            !committed.contains(&e.first.reference()) || !committed.contains(&e.second.reference())
+        }) {
+            committed.extend(self.committed_blocks_before(first.commit_ref.index, start_round));
+        }
+        evidence
+            .into_iter()
+            .filter(|e| {
+                committed.contains(&e.first.reference())
+                    && committed.contains(&e.second.reference())
+            })
+            .collect()
+    }
+
+    /// Blocks from `start_round` committed before the commit at index `before`.
+    fn committed_blocks_before(&self, before: CommitIndex, start_round: Round) -> Vec<BlockRef> {
+        const COMMIT_SCAN_BATCH_SIZE: CommitIndex = 100;
+        let mut blocks = vec![];
+        let mut end = before;
+        while end > GENESIS_COMMIT_INDEX + 1 {
+            let start = end
+                .saturating_sub(COMMIT_SCAN_BATCH_SIZE)
+                .max(GENESIS_COMMIT_INDEX + 1);
+            let mut commits = self
+                .store
+                .scan_commits(CommitRange::new(start..=end - 1))
+                .unwrap_or_else(|e| panic!("Failed to read from storage: {:?}", e));
+            commits.extend(
+                self.commits_to_write
+                    .iter()
+                    .filter(|c| start <= c.index() && c.index() < end)
+                    .cloned(),
+            );
+            // Leader rounds increase with commit indices, and blocks are committed after their
+            // round, so earlier commits have no block from `start_round`.
+            let mut done = commits.is_empty();
+            for commit in commits {
+                if commit.leader().round < start_round {
+                    done = true;
+                    continue;
+                }
+                blocks.extend(commit.blocks().iter().filter(|b| b.round >= start_round));
+            }
+            if done {
+                break;
+            }
+            end = start;
+        }
+        blocks
+    }
+
     /// Updates internal metadata for a block.
     fn update_block_metadata(&mut self, block: &VerifiedBlock) {
         let block_ref = block.reference();
@@ -721,6 +856,7 @@ impl DagState {
         // Flush buffered data to storage.
         let blocks = std::mem::take(&mut self.blocks_to_write);
         let commits = std::mem::take(&mut self.commits_to_write);
+        let equivocations = std::mem::take(&mut self.equivocations_to_write);
         let commit_info_to_write = if self
             .context
             .protocol_config
@@ -754,7 +890,12 @@ impl DagState {
                 .join(","),
         );
         self.store
-            .write(WriteBatch::new(blocks, commits, commit_info_to_write))
+            .write(WriteBatch::new(
+                blocks,
+                commits,
+                commit_info_to_write,
+                equivocations,
+            ))
             .unwrap_or_else(|e| panic!("Failed to write to storage: {:?}", e));
         self.context
             .metrics
@@ -1744,4 +1885,103 @@ mod test {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_record_equivocation() {
+        let (context, _) = Context::new_for_test(4);
+        let context = Arc::new(context);
+        let store = Arc::new(MemStore::new());
+        let mut dag_state = DagState::new(context.clone(), store.clone());
+
+        let block = VerifiedBlock::new_for_test(TestBlock::new(1, 1).build());
+        let conflicting =
+            VerifiedBlock::new_for_test(TestBlock::new(1, 1).set_timestamp_ms(1).build());
+        dag_state.accept_block(block.clone());
+        dag_state.accept_block(conflicting.clone());
+        // Accepting the same block again is not an equivocation.
+        dag_state.accept_block(conflicting.clone());
+        dag_state.accept_block(VerifiedBlock::new_for_test(TestBlock::new(1, 2).build()));
+        dag_state.flush();
+
+        let author = AuthorityIndex::new_for_test(1);
+        let evidence = store.scan_equivocations(author, 0).unwrap();
+        assert_eq!(
+            evidence,
+            vec![EquivocationEvidence::new(block, conflicting)]
+        );
+        assert!(store.scan_equivocations(author, 2).unwrap().is_empty());
+        assert!(store
+            .scan_equivocations(AuthorityIndex::new_for_test(2), 0)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            context
+                .metrics
+                .node_metrics
+                .equivocations
+                .with_label_values(&["test_host_1"])
+                .get(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_committed_equivocations() {
+        let (context, _) = Context::new_for_test(4);
+        let context = Arc::new(context);
+        let store = Arc::new(MemStore::new());
+        let mut dag_state = DagState::new(context.clone(), store.clone());
+
+        // Authority 1 signs three blocks for round 1, each one recorded against the others.
+        let blocks = (0..3)
+            .map(|timestamp_ms| {
+                VerifiedBlock::new_for_test(
+                    TestBlock::new(1, 1).set_timestamp_ms(timestamp_ms).build(),
+                )
+            })
+            .collect::<Vec<_>>();
+        dag_state.accept_blocks(blocks.clone());
+        assert_eq!(dag_state.read_equivocations(0).len(), 3);
+        dag_state.flush();
+
+        // Only evidence whose blocks are both committed counts, once the later one is.
+        let subdag = |block: &VerifiedBlock, index| {
+            CommittedSubDag::new(
+                block.reference(),
+                vec![block.clone()],
+                block.timestamp_ms(),
+                CommitRef::new(index, CommitDigest::MIN),
+                vec![],
+            )
+        };
+        assert!(dag_state
+            .committed_equivocations(&[subdag(&blocks[1], 2)])
+            .is_empty());
+        dag_state.add_commit(TrustedCommit::new_for_test(
+            1,
+            CommitDigest::MIN,
+            blocks[0].timestamp_ms(),
+            blocks[0].reference(),
+            vec![blocks[0].reference()],
+        ));
+        assert!(dag_state
+            .committed_equivocations(&[subdag(&blocks[0], 1)])
+            .is_empty());
+        assert_eq!(
+            dag_state.committed_equivocations(&[subdag(&blocks[1], 2)]),
+            vec![EquivocationEvidence::new(
+                blocks[0].clone(),
+                blocks[1].clone()
+            )]
+        );
+
+        // Also once the commit is flushed.
+        dag_state.flush();
+        assert_eq!(
+            dag_state
+                .committed_equivocations(&[subdag(&blocks[1], 2)])
+                .len(),
+            1
+        );
+    }
 }
@@ -99,6 +99,9 @@ pub enum ConsensusError {
     #[error("Invalid transaction: {0}")]
     InvalidTransaction(String),
 
+    #[error("Invalid equivocation evidence: {0}")]
+    InvalidEquivocationEvidence(String),
+
     #[error("Ancestors max timestamp {max_timestamp_ms} > block timestamp {block_timestamp_ms}")]
     InvalidBlockTimestamp {
         max_timestamp_ms: u64,
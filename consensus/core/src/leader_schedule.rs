@@ -134,6 +134,7 @@ impl LeaderSchedule {
 
         let mut dag_state = dag_state.write();
         let unscored_subdags = dag_state.take_unscored_committed_subdags();
+        let equivocations = dag_state.committed_equivocations(&unscored_subdags);
 
         let score_calculation_timer = self
             .context
@@ -147,6 +148,7 @@ impl LeaderSchedule {
             &unscored_subdags,
             self.scoring_strategy.as_ref(),
         )
+        .with_equivocation_evidence(&equivocations)
         .calculate();
         drop(score_calculation_timer);
 
@@ -2,17 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     fmt::Debug,
     ops::Bound::{Excluded, Included},
     sync::Arc,
 };
 
 use consensus_config::AuthorityIndex;
+use itertools::Itertools as _;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    block::{BlockAPI, BlockDigest, BlockRef, Slot, VerifiedBlock},
+    block::{BlockAPI, BlockDigest, BlockRef, EquivocationEvidence, Slot, VerifiedBlock},
     commit::{CommitRange, CommittedSubDag},
     context::Context,
     leader_scoring_strategy::ScoringStrategy,
@@ -36,6 +37,9 @@ pub(crate) struct ReputationScoreCalculator<'a> {
     // For now this is dynamic while we are experimenting but eventually we can
     // replace this with the final strategy that works best.
     scoring_strategy: &'a dyn ScoringStrategy,
+    // Authorities with evidence of equivocations committed along the subdags, on top of the ones
+    // equivocating within them.
+    equivocators: BTreeSet<AuthorityIndex>,
 }
 
 impl<'a> ReputationScoreCalculator<'a> {
@@ -60,9 +64,18 @@ impl<'a> ReputationScoreCalculator<'a> {
             commit_range,
             scoring_strategy,
             scores_per_authority,
+            equivocators: BTreeSet::new(),
         }
     }
 
+    /// Penalizes the authors of `evidence` as well. The evidence must be agreed on by all
+    /// authorities, as scores are, such as the one of committed blocks.
+    pub(crate) fn with_equivocation_evidence(mut self, evidence: &[EquivocationEvidence]) -> Self {
+        self.equivocators
+            .extend(evidence.iter().map(|e| e.slot().authority));
+        self
+    }
+
     pub(crate) fn calculate(&mut self) -> ReputationScores {
         let leaders = self.unscored_subdag.committed_leaders.clone();
         for leader in leaders {
@@ -74,6 +87,21 @@ impl<'a> ReputationScoreCalculator<'a> {
             );
         }
 
+        // Authorities that equivocated in the committed subdags, or with committed evidence,
+        // should not be elected leaders.
+        if self
+            .unscored_subdag
+            .context
+            .protocol_config
+            .mysticeti_penalize_equivocators()
+        {
+            let equivocators = self.unscored_subdag.equivocators();
+            for &authority in equivocators.union(&self.equivocators) {
+                tracing::info!("Zeroing score of equivocating authority {authority}");
+                self.scores_per_authority[authority] = 0;
+            }
+        }
+
         ReputationScores::new(self.commit_range.clone(), self.scores_per_authority.clone())
     }
 
@@ -197,6 +225,17 @@ impl UnscoredSubdag {
         }
     }
 
+    /// Authorities with more than one block for the same slot in the subdag.
+    pub(crate) fn equivocators(&self) -> BTreeSet<AuthorityIndex> {
+        // Blocks are ordered by slot, so conflicting blocks are adjacent.
+        self.blocks
+            .keys()
+            .tuple_windows()
+            .filter(|(a, b)| Slot::from(**a) == Slot::from(**b))
+            .map(|(a, _)| a.author)
+            .collect()
+    }
+
     pub(crate) fn find_supported_leader_block(
         &self,
         leader_slot: Slot,
@@ -313,6 +352,7 @@ mod tests {
     use std::cmp::max;
 
     use super::*;
+    use crate::block::TestBlock;
     use crate::commit::{CommitDigest, CommitRef};
     use crate::{leader_scoring_strategy::VoteScoringStrategy, test_dag_builder::DagBuilder};
 
@@ -416,6 +456,93 @@ mod tests {
         assert_eq!(scores.commit_range, (1..=4).into());
     }
 
+    #[tokio::test]
+    async fn test_reputation_score_calculator_penalizes_equivocators() {
+        telemetry_subscribers::init_for_testing();
+        let (mut context, _) = Context::new_for_test(4);
+        context
+            .protocol_config
+            .set_mysticeti_penalize_equivocators(true);
+        let context = Arc::new(context);
+
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layers(1..=4).build();
+        let leaders = dag_builder
+            .leader_blocks(1..=3)
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        // Any authority but the round 1 leader, whose slot must stay unique.
+        let equivocator = (leaders[0].author().value() + 1) % 4;
+
+        let mut unscored_subdags = vec![];
+        let mut last_committed_rounds = vec![0; 4];
+        for (idx, leader) in leaders.into_iter().enumerate() {
+            let commit_index = idx as u32 + 1;
+            let (subdag, _commit) = dag_builder.get_sub_dag_and_commit(
+                leader,
+                last_committed_rounds.clone(),
+                commit_index,
+            );
+            for block in subdag.blocks.iter() {
+                last_committed_rounds[block.author().value()] =
+                    max(block.round(), last_committed_rounds[block.author().value()]);
+            }
+            unscored_subdags.push(subdag);
+        }
+
+        let scoring_strategy = VoteScoringStrategy {};
+        let scores =
+            ReputationScoreCalculator::new(context.clone(), &unscored_subdags, &scoring_strategy)
+                .calculate();
+        assert!(scores.scores_per_authority[equivocator] > 0);
+
+        // The equivocator signed a second block for round 1, which got committed too.
+        unscored_subdags[1].blocks.push(VerifiedBlock::new_for_test(
+            TestBlock::new(1, equivocator as u32)
+                .set_timestamp_ms(1)
+                .build(),
+        ));
+        let unscored_subdag = UnscoredSubdag::new(context.clone(), &unscored_subdags);
+        assert_eq!(
+            unscored_subdag.equivocators(),
+            BTreeSet::from([AuthorityIndex::new_for_test(equivocator as u32)])
+        );
+        let penalized =
+            ReputationScoreCalculator::new(context.clone(), &unscored_subdags, &scoring_strategy)
+                .calculate();
+        for authority in 0..4 {
+            let expected = if authority == equivocator {
+                0
+            } else {
+                scores.scores_per_authority[authority]
+            };
+            assert_eq!(penalized.scores_per_authority[authority], expected);
+        }
+
+        // Stored evidence of another authority, whose blocks were committed in earlier subdags.
+        let other = (equivocator + 1) % 4;
+        let evidence = EquivocationEvidence::new(
+            VerifiedBlock::new_for_test(TestBlock::new(1, other as u32).build()),
+            VerifiedBlock::new_for_test(
+                TestBlock::new(1, other as u32).set_timestamp_ms(1).build(),
+            ),
+        );
+        let penalized =
+            ReputationScoreCalculator::new(context.clone(), &unscored_subdags, &scoring_strategy)
+                .with_equivocation_evidence(&[evidence])
+                .calculate();
+        for authority in 0..4 {
+            let expected = if authority == equivocator || authority == other {
+                0
+            } else {
+                scores.scores_per_authority[authority]
+            };
+            assert_eq!(penalized.scores_per_authority[authority], expected);
+        }
+    }
+
     #[tokio::test]
     #[should_panic(expected = "Attempted to calculate scores with no unscored subdags")]
     async fn test_reputation_score_calculator_no_subdags() {
@@ -14,6 +14,7 @@ mod commit_syncer;
 mod context;
 mod core;
 mod core_thread;
+mod dag_export;
 mod dag_state;
 mod error;
 mod leader_schedule;
@@ -41,4 +42,8 @@ mod test_dag_parser;
 pub use authority_node::ConsensusAuthority;
 pub use block::{BlockAPI, Round};
 pub use commit::{CommitConsumer, CommitDigest, CommitIndex, CommitRef, CommittedSubDag};
+pub use dag_export::{
+    export_dag, DagExport, ExportedBlock, ExportedBlockRef, ExportedCommit, ExportedLeader,
+    ExportedReputationScores, LeaderDecision,
+};
 pub use transaction::{TransactionClient, TransactionVerifier, ValidationError};
@@ -49,6 +49,8 @@ mod metrics_layer;
 #[cfg(test)]
 mod network_tests;
 #[cfg(test)]
+pub(crate) mod sim_network;
+#[cfg(test)]
 pub(crate) mod test_network;
 pub(crate) mod tonic_network;
 mod tonic_tls;
@@ -0,0 +1,373 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An in-process network for simulating a committee of authorities in tests. Requests are
+//! delivered by calling into the peers' services directly, with faults injected on the way:
+//! partitions between authorities, random latency per message, crashed authorities (whose service
+//! is not installed), and authorities equivocating towards a subset of their peers.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    marker::PhantomData,
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use consensus_config::{AuthorityIndex, Committee, NetworkKeyPair, ProtocolKeyPair};
+use parking_lot::Mutex;
+use rand::{rngs::StdRng, Rng as _, SeedableRng as _};
+use tokio::time::{sleep, timeout};
+
+use crate::{
+    block::{
+        Block, BlockAPI as _, BlockRef, BlockV1, MisbehaviorProof, MisbehaviorReport, SignedBlock,
+        VerifiedBlock,
+    },
+    commit::CommitRange,
+    context::Context,
+    error::{ConsensusError, ConsensusResult},
+    network::{BlockStream, NetworkClient, NetworkManager, NetworkService},
+    Round,
+};
+
+/// Handle to the simulated network, shared by the test driving the faults and all authorities.
+#[derive(Clone)]
+pub(crate) struct SimNetwork {
+    inner: Arc<Mutex<SimNetworkInner>>,
+}
+
+struct SimNetworkInner {
+    authorities: Vec<AuthorityIndex>,
+    // Services of the running authorities.
+    services: BTreeMap<AuthorityIndex, Arc<dyn NetworkService>>,
+    // Directed links that drop all messages.
+    blocked: BTreeSet<(AuthorityIndex, AuthorityIndex)>,
+    // Latency of each message is drawn uniformly from this range.
+    latency: (Duration, Duration),
+    rng: StdRng,
+    equivocators: BTreeMap<AuthorityIndex, Equivocator>,
+}
+
+/// An authority sending a conflicting version of each of its blocks to its victims.
+struct Equivocator {
+    keypair: ProtocolKeyPair,
+    victims: BTreeSet<AuthorityIndex>,
+    // Conflicting block per original block, so all victims receive the same one.
+    forged: BTreeMap<BlockRef, VerifiedBlock>,
+    forged_refs: BTreeSet<BlockRef>,
+}
+
+impl SimNetwork {
+    /// Creates a fully connected network without latency. `seed` determines the latency of each
+    /// message once latency is configured.
+    pub(crate) fn new(committee: &Committee, seed: u64) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(SimNetworkInner {
+                authorities: committee.authorities().map(|(index, _)| index).collect(),
+                services: BTreeMap::new(),
+                blocked: BTreeSet::new(),
+                latency: (Duration::ZERO, Duration::ZERO),
+                rng: StdRng::seed_from_u64(seed),
+                equivocators: BTreeMap::new(),
+            })),
+        }
+    }
+
+    /// Creates the network manager of the authority of `context`.
+    pub(crate) fn manager<S: NetworkService>(&self, context: &Context) -> SimNetworkManager<S> {
+        SimNetworkManager {
+            own_index: context.own_index,
+            network: self.clone(),
+            client: Arc::new(SimNetworkClient {
+                own_index: context.own_index,
+                network: self.clone(),
+            }),
+            _service: PhantomData,
+        }
+    }
+
+    /// Drops all messages between `group` and the rest of the committee.
+    pub(crate) fn isolate(&self, group: &[AuthorityIndex]) {
+        let mut inner = self.inner.lock();
+        let others = inner
+            .authorities
+            .iter()
+            .filter(|a| !group.contains(a))
+            .copied()
+            .collect::<Vec<_>>();
+        for a in group {
+            for b in &others {
+                inner.blocked.insert((*a, *b));
+                inner.blocked.insert((*b, *a));
+            }
+        }
+    }
+
+    /// Drops all messages between `a` and `b`.
+    pub(crate) fn disconnect(&self, a: AuthorityIndex, b: AuthorityIndex) {
+        let mut inner = self.inner.lock();
+        inner.blocked.insert((a, b));
+        inner.blocked.insert((b, a));
+    }
+
+    /// Restores all links.
+    pub(crate) fn heal(&self) {
+        self.inner.lock().blocked.clear();
+    }
+
+    /// Delays every message by a random duration between `min` and `max`.
+    pub(crate) fn set_latency(&self, min: Duration, max: Duration) {
+        assert!(min <= max);
+        self.inner.lock().latency = (min, max);
+    }
+
+    /// Makes `authority` equivocate: `victims` receive a conflicting, validly signed version of
+    /// every block it sends them.
+    pub(crate) fn equivocate(
+        &self,
+        authority: AuthorityIndex,
+        keypair: ProtocolKeyPair,
+        victims: &[AuthorityIndex],
+    ) {
+        self.inner.lock().equivocators.insert(
+            authority,
+            Equivocator {
+                keypair,
+                victims: victims.iter().copied().collect(),
+                forged: BTreeMap::new(),
+                forged_refs: BTreeSet::new(),
+            },
+        );
+    }
+
+    fn latency(&self) -> Duration {
+        let mut inner = self.inner.lock();
+        let (min, max) = inner.latency;
+        inner.rng.gen_range(min..=max)
+    }
+
+    /// Waits for a message from `from` to reach `to`, and returns the service of `to`.
+    async fn connect(
+        &self,
+        from: AuthorityIndex,
+        to: AuthorityIndex,
+    ) -> ConsensusResult<Arc<dyn NetworkService>> {
+        sleep(self.latency()).await;
+        let inner = self.inner.lock();
+        if inner.blocked.contains(&(from, to)) {
+            return Err(ConsensusError::PeerDisconnected(format!(
+                "{to} is partitioned from {from}"
+            )));
+        }
+        inner
+            .services
+            .get(&to)
+            .cloned()
+            .ok_or_else(|| ConsensusError::PeerDisconnected(format!("{to} is not running")))
+    }
+
+    /// Waits for the response from `from` to reach `to`.
+    async fn respond(&self, from: AuthorityIndex, to: AuthorityIndex) -> ConsensusResult<()> {
+        sleep(self.latency()).await;
+        let inner = self.inner.lock();
+        if inner.blocked.contains(&(from, to)) || !inner.services.contains_key(&to) {
+            return Err(ConsensusError::NetworkRequest(format!(
+                "Response from {from} to {to} was lost"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Applies equivocation to serialized blocks sent from `from` to `to`: blocks authored by
+    /// `from` are replaced when it equivocates towards `to`, and equivocators never get to see
+    /// their own forged blocks, which they would reject as conflicting with their own.
+    fn deliver_blocks(
+        &self,
+        from: AuthorityIndex,
+        to: AuthorityIndex,
+        blocks: Vec<Bytes>,
+    ) -> Vec<Bytes> {
+        let mut inner = self.inner.lock();
+        if inner.equivocators.is_empty() {
+            return blocks;
+        }
+        blocks
+            .into_iter()
+            .filter_map(|serialized| {
+                let Ok(signed) = bcs::from_bytes::<SignedBlock>(&serialized) else {
+                    return Some(serialized);
+                };
+                let block = VerifiedBlock::new_verified(signed, serialized);
+                if let Some(equivocator) = inner.equivocators.get(&to) {
+                    if equivocator.forged_refs.contains(&block.reference()) {
+                        return None;
+                    }
+                }
+                match inner.equivocators.get_mut(&from) {
+                    Some(equivocator)
+                        if block.author() == from && equivocator.victims.contains(&to) =>
+                    {
+                        Some(equivocator.forge(&block).serialized().clone())
+                    }
+                    _ => Some(block.serialized().clone()),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Equivocator {
+    fn forge(&mut self, block: &VerifiedBlock) -> VerifiedBlock {
+        if let Some(forged) = self.forged.get(&block.reference()) {
+            return forged.clone();
+        }
+        // The forged block only differs from the original by an extra misbehavior report, so it
+        // passes verification just like the original.
+        let mut misbehavior_reports = block.misbehavior_reports().to_vec();
+        misbehavior_reports.push(MisbehaviorReport {
+            target: block.author(),
+            proof: MisbehaviorProof::InvalidBlock(block.reference()),
+        });
+        let forged = BlockV1::new(
+            block.epoch(),
+            block.round(),
+            block.author(),
+            block.timestamp_ms(),
+            block.ancestors().to_vec(),
+            block.transactions().to_vec(),
+            block.commit_votes().to_vec(),
+            misbehavior_reports,
+        );
+        let signed = SignedBlock::new(Block::V1(forged), &self.keypair)
+            .expect("Failed to sign forged block");
+        let serialized = signed
+            .serialize()
+            .expect("Failed to serialize forged block");
+        let forged = VerifiedBlock::new_verified(signed, serialized);
+        self.forged.insert(block.reference(), forged.clone());
+        self.forged_refs.insert(forged.reference());
+        forged
+    }
+}
+
+pub(crate) struct SimNetworkClient {
+    own_index: AuthorityIndex,
+    network: SimNetwork,
+}
+
+#[async_trait]
+impl NetworkClient for SimNetworkClient {
+    const SUPPORT_STREAMING: bool = false;
+
+    async fn send_block(
+        &self,
+        peer: AuthorityIndex,
+        block: &VerifiedBlock,
+        timeout_duration: Duration,
+    ) -> ConsensusResult<()> {
+        let serialized = block.serialized().clone();
+        timeout(timeout_duration, async {
+            let service = self.network.connect(self.own_index, peer).await?;
+            let Some(serialized) = self
+                .network
+                .deliver_blocks(self.own_index, peer, vec![serialized])
+                .pop()
+            else {
+                return Ok(());
+            };
+            service.handle_send_block(self.own_index, serialized).await
+        })
+        .await
+        .map_err(|_| ConsensusError::NetworkRequestTimeout(format!("send_block to {peer}")))?
+    }
+
+    async fn subscribe_blocks(
+        &self,
+        _peer: AuthorityIndex,
+        _last_received: Round,
+        _timeout: Duration,
+    ) -> ConsensusResult<BlockStream> {
+        unimplemented!("Streaming is not supported by the simulated network")
+    }
+
+    async fn fetch_blocks(
+        &self,
+        peer: AuthorityIndex,
+        block_refs: Vec<BlockRef>,
+        highest_accepted_rounds: Vec<Round>,
+        timeout_duration: Duration,
+    ) -> ConsensusResult<Vec<Bytes>> {
+        timeout(timeout_duration, async {
+            let service = self.network.connect(self.own_index, peer).await?;
+            let blocks = service
+                .handle_fetch_blocks(self.own_index, block_refs, highest_accepted_rounds)
+                .await?;
+            self.network.respond(peer, self.own_index).await?;
+            Ok(self.network.deliver_blocks(peer, self.own_index, blocks))
+        })
+        .await
+        .map_err(|_| ConsensusError::NetworkRequestTimeout(format!("fetch_blocks from {peer}")))?
+    }
+
+    async fn fetch_commits(
+        &self,
+        peer: AuthorityIndex,
+        commit_range: CommitRange,
+        timeout_duration: Duration,
+    ) -> ConsensusResult<(Vec<Bytes>, Vec<Bytes>)> {
+        timeout(timeout_duration, async {
+            let service = self.network.connect(self.own_index, peer).await?;
+            let (commits, certifier_blocks) = service
+                .handle_fetch_commits(self.own_index, commit_range)
+                .await?;
+            self.network.respond(peer, self.own_index).await?;
+            let commits = commits.iter().map(|c| c.serialized().clone()).collect();
+            let certifier_blocks = certifier_blocks
+                .iter()
+                .map(|b| b.serialized().clone())
+                .collect();
+            Ok((
+                commits,
+                self.network
+                    .deliver_blocks(peer, self.own_index, certifier_blocks),
+            ))
+        })
+        .await
+        .map_err(|_| ConsensusError::NetworkRequestTimeout(format!("fetch_commits from {peer}")))?
+    }
+}
+
+/// Network manager of one authority on a `SimNetwork`. Its service receives requests from the
+/// moment it is installed until the manager stops, i.e. until the authority crashes.
+pub(crate) struct SimNetworkManager<S> {
+    own_index: AuthorityIndex,
+    network: SimNetwork,
+    client: Arc<SimNetworkClient>,
+    _service: PhantomData<S>,
+}
+
+impl<S: NetworkService> NetworkManager<S> for SimNetworkManager<S> {
+    type Client = SimNetworkClient;
+
+    fn new(_context: Arc<Context>, _network_keypair: NetworkKeyPair) -> Self {
+        unimplemented!("Use SimNetwork::manager() to join a simulated network")
+    }
+
+    fn client(&self) -> Arc<Self::Client> {
+        self.client.clone()
+    }
+
+    async fn install_service(&mut self, service: Arc<S>) {
+        self.network
+            .inner
+            .lock()
+            .services
+            .insert(self.own_index, service);
+    }
+
+    async fn stop(&mut self) {
+        self.network.inner.lock().services.remove(&self.own_index);
+    }
+}
@@ -11,7 +11,9 @@ use parking_lot::RwLock;
 
 use super::{Store, WriteBatch};
 use crate::{
-    block::{BlockAPI as _, BlockDigest, BlockRef, Round, Slot, VerifiedBlock},
+    block::{
+        BlockAPI as _, BlockDigest, BlockRef, EquivocationEvidence, Round, Slot, VerifiedBlock,
+    },
     commit::{
         CommitAPI as _, CommitDigest, CommitIndex, CommitInfo, CommitRange, CommitRef,
         TrustedCommit,
@@ -30,6 +32,8 @@ struct Inner {
     commits: BTreeMap<(CommitIndex, CommitDigest), TrustedCommit>,
     commit_votes: BTreeSet<(CommitIndex, CommitDigest, BlockRef)>,
     commit_info: BTreeMap<(CommitIndex, CommitDigest), CommitInfo>,
+    equivocations:
+        BTreeMap<(AuthorityIndex, Round, BlockDigest, BlockDigest), EquivocationEvidence>,
 }
 
 impl MemStore {
@@ -42,6 +46,7 @@ impl MemStore {
                 commits: BTreeMap::new(),
                 commit_votes: BTreeSet::new(),
                 commit_info: BTreeMap::new(),
+                equivocations: BTreeMap::new(),
             }),
         }
     }
@@ -81,6 +86,19 @@ impl Store for MemStore {
                 .insert((commit_ref.index, commit_ref.digest), commit_info);
         }
 
+        for evidence in write_batch.equivocations {
+            let slot = evidence.slot();
+            inner.equivocations.insert(
+                (
+                    slot.authority,
+                    slot.round,
+                    evidence.first.digest(),
+                    evidence.second.digest(),
+                ),
+                evidence,
+            );
+        }
+
         Ok(())
     }
 
@@ -211,4 +229,20 @@ impl Store for MemStore {
             .last_key_value()
             .map(|(k, v)| (CommitRef::new(k.0, k.1), v.clone())))
     }
+
+    fn scan_equivocations(
+        &self,
+        authority: AuthorityIndex,
+        start_round: Round,
+    ) -> ConsensusResult<Vec<EquivocationEvidence>> {
+        let inner = self.inner.read();
+        Ok(inner
+            .equivocations
+            .range((
+                Included((authority, start_round, BlockDigest::MIN, BlockDigest::MIN)),
+                Included((authority, Round::MAX, BlockDigest::MAX, BlockDigest::MAX)),
+            ))
+            .map(|(_, evidence)| evidence.clone())
+            .collect())
+    }
 }
@@ -10,7 +10,7 @@ mod store_tests;
 use consensus_config::AuthorityIndex;
 
 use crate::{
-    block::{BlockRef, Round, Slot, VerifiedBlock},
+    block::{BlockRef, EquivocationEvidence, Round, Slot, VerifiedBlock},
     commit::{CommitInfo, CommitRange, CommitRef, TrustedCommit},
     error::ConsensusResult,
     CommitIndex,
@@ -58,6 +58,13 @@ pub(crate) trait Store: Send + Sync {
 
     /// Reads the last commit info, written atomically with the last commit.
     fn read_last_commit_info(&self) -> ConsensusResult<Option<(CommitRef, CommitInfo)>>;
+
+    /// Reads evidence of equivocations by an authority, from start_round.
+    fn scan_equivocations(
+        &self,
+        authority: AuthorityIndex,
+        start_round: Round,
+    ) -> ConsensusResult<Vec<EquivocationEvidence>>;
 }
 
 /// Represents data to be written to the store together atomically.
@@ -66,6 +73,7 @@ pub(crate) struct WriteBatch {
     pub(crate) blocks: Vec<VerifiedBlock>,
     pub(crate) commits: Vec<TrustedCommit>,
     pub(crate) commit_info: Vec<(CommitRef, CommitInfo)>,
+    pub(crate) equivocations: Vec<EquivocationEvidence>,
 }
 
 impl WriteBatch {
@@ -73,11 +81,13 @@ impl WriteBatch {
         blocks: Vec<VerifiedBlock>,
         commits: Vec<TrustedCommit>,
         commit_info: Vec<(CommitRef, CommitInfo)>,
+        equivocations: Vec<EquivocationEvidence>,
     ) -> Self {
         WriteBatch {
             blocks,
             commits,
             commit_info,
+            equivocations,
         }
     }
 
@@ -100,4 +110,10 @@ impl WriteBatch {
         self.commit_info = commit_info;
         self
     }
+
+    #[cfg(test)]
+    pub(crate) fn equivocations(mut self, equivocations: Vec<EquivocationEvidence>) -> Self {
+        self.equivocations = equivocations;
+        self
+    }
 }
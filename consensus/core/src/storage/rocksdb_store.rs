@@ -1,21 +1,28 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::VecDeque, ops::Bound::Included, time::Duration};
+use std::{collections::VecDeque, ops::Bound::Included, path::Path, sync::Arc, time::Duration};
 
 use bytes::Bytes;
 use consensus_config::AuthorityIndex;
 use sui_macros::fail_point;
+use tempfile::TempDir;
 use typed_store::{
     metrics::SamplingInterval,
     reopen,
-    rocks::{default_db_options, open_cf_opts, DBMap, MetricConf, ReadWriteOptions},
-    Map as _,
+    rocks::{
+        default_db_options, open_cf_opts, open_cf_opts_secondary, DBMap, MetricConf,
+        ReadWriteOptions, RocksDB,
+    },
+    Map as _, TypedStoreError,
 };
 
 use super::{CommitInfo, Store, WriteBatch};
 use crate::{
-    block::{BlockAPI as _, BlockDigest, BlockRef, Round, SignedBlock, Slot, VerifiedBlock},
+    block::{
+        BlockAPI as _, BlockDigest, BlockRef, EquivocationEvidence, Round, SignedBlock, Slot,
+        VerifiedBlock,
+    },
     commit::{CommitAPI as _, CommitDigest, CommitIndex, CommitRange, CommitRef, TrustedCommit},
     error::{ConsensusError, ConsensusResult},
 };
@@ -33,6 +40,11 @@ pub(crate) struct RocksDBStore {
     commit_votes: DBMap<(CommitIndex, CommitDigest, BlockRef), ()>,
     /// Stores info related to Commit that helps recovery.
     commit_info: DBMap<(CommitIndex, CommitDigest), CommitInfo>,
+    /// Pairs of conflicting blocks by authorities. The blocks themselves are in `blocks`.
+    equivocations: DBMap<(AuthorityIndex, Round, BlockDigest, BlockDigest), ()>,
+    /// Private files of a read-only secondary instance, removed when the store is dropped.
+    /// Declared last so that the tables are closed first.
+    _secondary_dir: Option<TempDir>,
 }
 
 impl RocksDBStore {
@@ -41,9 +53,27 @@ impl RocksDBStore {
     const COMMITS_CF: &'static str = "commits";
     const COMMIT_VOTES_CF: &'static str = "commit_votes";
     const COMMIT_INFO_CF: &'static str = "commit_info";
+    const EQUIVOCATIONS_CF: &'static str = "equivocations";
 
     /// Creates a new instance of RocksDB storage.
     pub(crate) fn new(path: &str) -> Self {
+        let rocksdb = Self::open(path, None).expect("Cannot open database");
+        Self::reopen_tables(&rocksdb, None)
+    }
+
+    /// Opens the storage of another, possibly running, instance as a read-only secondary. The
+    /// secondary keeps its own files in a temporary directory, rather than next to the storage.
+    pub(crate) fn new_read_only(path: &str) -> ConsensusResult<Self> {
+        let secondary_dir = tempfile::tempdir().map_err(|e| {
+            ConsensusError::RocksDBFailure(TypedStoreError::RocksDBError(format!(
+                "Cannot create secondary directory: {e}"
+            )))
+        })?;
+        let rocksdb = Self::open(path, Some(secondary_dir.path()))?;
+        Ok(Self::reopen_tables(&rocksdb, Some(secondary_dir)))
+    }
+
+    fn open(path: &str, secondary_path: Option<&Path>) -> Result<Arc<RocksDB>, TypedStoreError> {
         // Consensus data has high write throughput (all transactions) and is rarely read
         // (only during recovery and when helping peers catch up).
         let db_options = default_db_options().optimize_db_for_write_throughput(2);
@@ -64,21 +94,34 @@ impl RocksDBStore {
             (Self::COMMITS_CF, cf_options.clone()),
             (Self::COMMIT_VOTES_CF, cf_options.clone()),
             (Self::COMMIT_INFO_CF, cf_options.clone()),
+            (Self::EQUIVOCATIONS_CF, cf_options.clone()),
         ];
-        let rocksdb = open_cf_opts(
-            path,
-            Some(db_options.options),
-            metrics_conf,
-            &column_family_options,
-        )
-        .expect("Cannot open database");
+        if let Some(secondary_path) = secondary_path {
+            open_cf_opts_secondary(
+                Path::new(path),
+                Some(secondary_path),
+                Some(db_options.options),
+                metrics_conf,
+                &column_family_options,
+            )
+        } else {
+            open_cf_opts(
+                path,
+                Some(db_options.options),
+                metrics_conf,
+                &column_family_options,
+            )
+        }
+    }
 
-        let (blocks, digests_by_authorities, commits, commit_votes, commit_info) = reopen!(&rocksdb,
+    fn reopen_tables(rocksdb: &Arc<RocksDB>, secondary_dir: Option<TempDir>) -> Self {
+        let (blocks, digests_by_authorities, commits, commit_votes, commit_info, equivocations) = reopen!(rocksdb,
             Self::BLOCKS_CF;<(Round, AuthorityIndex, BlockDigest), bytes::Bytes>,
             Self::DIGESTS_BY_AUTHORITIES_CF;<(AuthorityIndex, Round, BlockDigest), ()>,
             Self::COMMITS_CF;<(CommitIndex, CommitDigest), Bytes>,
             Self::COMMIT_VOTES_CF;<(CommitIndex, CommitDigest, BlockRef), ()>,
-            Self::COMMIT_INFO_CF;<(CommitIndex, CommitDigest), CommitInfo>
+            Self::COMMIT_INFO_CF;<(CommitIndex, CommitDigest), CommitInfo>,
+            Self::EQUIVOCATIONS_CF;<(AuthorityIndex, Round, BlockDigest, BlockDigest), ()>
         );
 
         Self {
@@ -87,7 +130,99 @@ impl RocksDBStore {
             commits,
             commit_votes,
             commit_info,
+            equivocations,
+            _secondary_dir: secondary_dir,
+        }
+    }
+
+    /// Index of the first commit still in the store.
+    pub(crate) fn read_first_commit_index(&self) -> ConsensusResult<Option<CommitIndex>> {
+        let Some(result) = self.commits.safe_iter().next() else {
+            return Ok(None);
+        };
+        let ((index, _digest), _serialized) = result?;
+        Ok(Some(index))
+    }
+
+    /// Reads all blocks from start_round to end_round inclusive, ordered by round then author.
+    pub(crate) fn scan_blocks_by_round(
+        &self,
+        start_round: Round,
+        end_round: Round,
+    ) -> ConsensusResult<Vec<VerifiedBlock>> {
+        let mut blocks = vec![];
+        for kv in self.blocks.safe_range_iter((
+            Included((start_round, AuthorityIndex::MIN, BlockDigest::MIN)),
+            Included((end_round, AuthorityIndex::MAX, BlockDigest::MAX)),
+        )) {
+            let ((round, author, digest), serialized) = kv?;
+            let signed_block: SignedBlock =
+                bcs::from_bytes(&serialized).map_err(ConsensusError::MalformedBlock)?;
+            let block = VerifiedBlock::new_verified(signed_block, serialized);
+            assert_eq!(BlockRef::new(round, author, digest), block.reference());
+            blocks.push(block);
+        }
+        Ok(blocks)
+    }
+
+    /// Reads the commit info of commits in the range.
+    pub(crate) fn scan_commit_info(
+        &self,
+        range: CommitRange,
+    ) -> ConsensusResult<Vec<(CommitRef, CommitInfo)>> {
+        let mut commit_info = vec![];
+        for kv in self.commit_info.safe_range_iter((
+            Included((range.start(), CommitDigest::MIN)),
+            Included((range.end(), CommitDigest::MAX)),
+        )) {
+            let ((index, digest), info) = kv?;
+            commit_info.push((CommitRef::new(index, digest), info));
         }
+        Ok(commit_info)
+    }
+
+    /// Reads evidence of equivocations by any authority from start_round to end_round inclusive,
+    /// ordered by author then round.
+    pub(crate) fn scan_equivocations_by_round(
+        &self,
+        start_round: Round,
+        end_round: Round,
+    ) -> ConsensusResult<Vec<EquivocationEvidence>> {
+        let mut keys = vec![];
+        for kv in self.equivocations.safe_iter() {
+            let (key, _) = kv?;
+            if start_round <= key.1 && key.1 <= end_round {
+                keys.push(key);
+            }
+        }
+        self.read_equivocations(keys)
+    }
+
+    fn read_equivocations(
+        &self,
+        keys: Vec<(AuthorityIndex, Round, BlockDigest, BlockDigest)>,
+    ) -> ConsensusResult<Vec<EquivocationEvidence>> {
+        let refs = keys
+            .into_iter()
+            .flat_map(|(authority, round, first, second)| {
+                [
+                    BlockRef::new(round, authority, first),
+                    BlockRef::new(round, authority, second),
+                ]
+            })
+            .collect::<Vec<_>>();
+        let results = self.read_blocks(refs.as_slice())?;
+        let blocks = refs
+            .into_iter()
+            .zip(results)
+            .map(|(r, block)| {
+                block.unwrap_or_else(|| panic!("Storage inconsistency: block {:?} not found!", r))
+            })
+            .collect::<Vec<_>>();
+        Ok(blocks
+            .chunks_exact(2)
+            .map(|pair| EquivocationEvidence::new(pair[0].clone(), pair[1].clone()))
+            .collect())
     }
 }
 
@@ -141,6 +276,24 @@ impl Store for RocksDBStore {
                 .map_err(ConsensusError::RocksDBFailure)?;
         }
 
+        for evidence in write_batch.equivocations {
+            let slot = evidence.slot();
+            batch
+                .insert_batch(
+                    &self.equivocations,
+                    [(
+                        (
+                            slot.authority,
+                            slot.round,
+                            evidence.first.digest(),
+                            evidence.second.digest(),
+                        ),
+                        (),
+                    )],
+                )
+                .map_err(ConsensusError::RocksDBFailure)?;
+        }
+
         batch.write()?;
         fail_point!("consensus-store-after-write");
         Ok(())
@@ -296,4 +449,19 @@ impl Store for RocksDBStore {
         let (key, commit_info) = result.map_err(ConsensusError::RocksDBFailure)?;
         Ok(Some((CommitRef::new(key.0, key.1), commit_info)))
     }
+
+    fn scan_equivocations(
+        &self,
+        authority: AuthorityIndex,
+        start_round: Round,
+    ) -> ConsensusResult<Vec<EquivocationEvidence>> {
+        let mut keys = vec![];
+        for kv in self.equivocations.safe_range_iter((
+            Included((authority, start_round, BlockDigest::MIN, BlockDigest::MIN)),
+            Included((authority, Round::MAX, BlockDigest::MAX, BlockDigest::MAX)),
+        )) {
+            keys.push(kv?.0);
+        }
+        self.read_equivocations(keys)
+    }
 }
@@ -7,7 +7,9 @@ use tempfile::TempDir;
 
 use super::{mem_store::MemStore, rocksdb_store::RocksDBStore, Store, WriteBatch};
 use crate::{
-    block::{BlockAPI, BlockDigest, BlockRef, Slot, TestBlock, VerifiedBlock},
+    block::{
+        BlockAPI, BlockDigest, BlockRef, EquivocationEvidence, Slot, TestBlock, VerifiedBlock,
+    },
     commit::{CommitDigest, TrustedCommit},
 };
 
@@ -297,3 +299,41 @@ async fn read_and_scan_commits(
         assert_eq!(scanned_commits, written_commits,);
     }
 }
+
+#[rstest]
+#[tokio::test]
+async fn read_equivocations(
+    #[values(new_rocksdb_teststore(), new_mem_teststore())] test_store: TestStore,
+) {
+    let store = test_store.store();
+
+    let blocks = vec![
+        VerifiedBlock::new_for_test(TestBlock::new(1, 1).build()),
+        VerifiedBlock::new_for_test(TestBlock::new(1, 1).set_timestamp_ms(1).build()),
+        VerifiedBlock::new_for_test(TestBlock::new(3, 1).build()),
+        VerifiedBlock::new_for_test(TestBlock::new(3, 1).set_timestamp_ms(1).build()),
+        VerifiedBlock::new_for_test(TestBlock::new(2, 2).build()),
+    ];
+    let equivocations = vec![
+        EquivocationEvidence::new(blocks[0].clone(), blocks[1].clone()),
+        EquivocationEvidence::new(blocks[2].clone(), blocks[3].clone()),
+    ];
+    store
+        .write(
+            WriteBatch::default()
+                .blocks(blocks)
+                .equivocations(equivocations.clone()),
+        )
+        .unwrap();
+
+    let author = AuthorityIndex::new_for_test(1);
+    assert_eq!(store.scan_equivocations(author, 0).unwrap(), equivocations);
+    assert_eq!(
+        store.scan_equivocations(author, 2).unwrap(),
+        equivocations[1..].to_vec()
+    );
+    assert!(store
+        .scan_equivocations(AuthorityIndex::new_for_test(2), 0)
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,345 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Runs a committee of full authorities in one process over a `SimNetwork`, on a single threaded
+//! runtime with paused time, while injecting partitions, latency, crashes and equivocation.
+//! Every test checks that the committed sub-dag sequences of all authorities agree (safety), and
+//! that commits keep being produced once faults are lifted (liveness).
+
+use std::{sync::Arc, time::Duration};
+
+use consensus_config::{
+    local_committee_and_keys, AuthorityIndex, Committee, NetworkKeyPair, Parameters,
+    ProtocolKeyPair,
+};
+use mysten_metrics::monitored_mpsc::unbounded_channel;
+use parking_lot::Mutex;
+use prometheus::Registry;
+use rand::{rngs::StdRng, seq::SliceRandom as _, Rng as _, SeedableRng as _};
+use rstest::rstest;
+use sui_protocol_config::ProtocolConfig;
+use tempfile::TempDir;
+use tokio::{task::JoinHandle, time::sleep};
+use typed_store::DBMetrics;
+
+use super::AuthorityNode;
+use crate::{
+    authority_service::AuthorityService,
+    block::BlockAPI as _,
+    context::{Clock, Context},
+    core_thread::ChannelCoreThreadDispatcher,
+    metrics::test_metrics,
+    network::sim_network::{SimNetwork, SimNetworkManager},
+    transaction::NoopTransactionVerifier,
+    CommitConsumer, CommittedSubDag,
+};
+
+type SimAuthority = AuthorityNode<SimNetworkManager<AuthorityService<ChannelCoreThreadDispatcher>>>;
+
+struct RunningAuthority {
+    authority: SimAuthority,
+    output: JoinHandle<()>,
+}
+
+struct Simulation {
+    committee: Committee,
+    keypairs: Vec<(NetworkKeyPair, ProtocolKeyPair)>,
+    network: SimNetwork,
+    // Shared by all authorities, so their timestamps stay comparable across restarts while time
+    // is paused.
+    clock: Arc<Clock>,
+    db_dirs: Vec<TempDir>,
+    authorities: Vec<Option<RunningAuthority>>,
+    // Sub-dags committed by each authority, across restarts.
+    commits: Vec<Arc<Mutex<Vec<CommittedSubDag>>>>,
+}
+
+impl Simulation {
+    async fn new(num_authorities: usize, seed: u64) -> Self {
+        let db_registry = Registry::new();
+        DBMetrics::init(&db_registry);
+
+        let (committee, keypairs) = local_committee_and_keys(0, vec![1; num_authorities]);
+        let network = SimNetwork::new(&committee, seed);
+        network.set_latency(Duration::from_millis(10), Duration::from_millis(100));
+        let mut simulation = Self {
+            committee,
+            keypairs,
+            network,
+            clock: Arc::new(Clock::new()),
+            db_dirs: (0..num_authorities)
+                .map(|_| TempDir::new().unwrap())
+                .collect(),
+            authorities: (0..num_authorities).map(|_| None).collect(),
+            commits: (0..num_authorities).map(|_| Default::default()).collect(),
+        };
+        for index in simulation.indices() {
+            simulation.start(index).await;
+        }
+        simulation
+    }
+
+    fn indices(&self) -> Vec<AuthorityIndex> {
+        self.committee
+            .authorities()
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Starts `index`, recovering from its store if it ran before.
+    async fn start(&mut self, index: AuthorityIndex) {
+        assert!(self.authorities[index].is_none(), "{index} is running");
+        let parameters = Parameters {
+            db_path: Some(self.db_dirs[index.value()].path().to_path_buf()),
+            // Cache less blocks to exercise commit sync.
+            dag_state_cached_rounds: 5,
+            commit_sync_parallel_fetches: 3,
+            commit_sync_batch_size: 3,
+            ..Default::default()
+        };
+        let context = Arc::new(Context::new(
+            index,
+            self.committee.clone(),
+            parameters,
+            ProtocolConfig::get_for_max_version_UNSAFE(),
+            test_metrics(),
+            self.clock.clone(),
+        ));
+
+        // Replay commits committed but not yet recorded before a crash.
+        let commits = self.commits[index].clone();
+        let (last_processed_commit_round, last_processed_commit_index) = commits
+            .lock()
+            .last()
+            .map(|c| (c.leader.round, c.commit_ref.index))
+            .unwrap_or_default();
+        let (sender, mut receiver) = unbounded_channel("consensus_output");
+        let commit_consumer = CommitConsumer::new(
+            sender,
+            last_processed_commit_round,
+            last_processed_commit_index,
+        );
+        let output = tokio::spawn(async move {
+            while let Some(subdag) = receiver.recv().await {
+                commits.lock().push(subdag);
+            }
+        });
+
+        let network_manager = self.network.manager(&context);
+        let authority = AuthorityNode::start_with_network(
+            context,
+            network_manager,
+            self.keypairs[index].1.clone(),
+            Arc::new(NoopTransactionVerifier {}),
+            commit_consumer,
+        )
+        .await;
+        self.authorities[index] = Some(RunningAuthority { authority, output });
+    }
+
+    /// Crashes `index`. Its store is kept for the restart.
+    async fn stop(&mut self, index: AuthorityIndex) {
+        let running = self.authorities[index]
+            .take()
+            .unwrap_or_else(|| panic!("{index} is not running"));
+        running.authority.stop().await;
+        running.output.abort();
+    }
+
+    async fn stop_all(&mut self) {
+        for index in self.indices() {
+            if self.authorities[index].is_some() {
+                self.stop(index).await;
+            }
+        }
+    }
+
+    fn num_commits(&self, index: AuthorityIndex) -> usize {
+        self.commits[index].lock().len()
+    }
+
+    /// Checks that every authority committed the same sequence of sub-dags, up to the shortest
+    /// sequence.
+    fn assert_safety(&self) {
+        let sequences = self
+            .commits
+            .iter()
+            .map(|commits| {
+                commits
+                    .lock()
+                    .iter()
+                    .map(|c| {
+                        let blocks = c.blocks.iter().map(|b| b.reference()).collect::<Vec<_>>();
+                        (c.commit_ref, c.leader, blocks)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        for (index, sequence) in sequences.iter().enumerate() {
+            for (i, (commit_ref, _, _)) in sequence.iter().enumerate() {
+                assert_eq!(
+                    commit_ref.index as usize,
+                    i + 1,
+                    "Authority {index} skipped or repeated a commit"
+                );
+            }
+        }
+        for (a, sequence_a) in sequences.iter().enumerate() {
+            for (b, sequence_b) in sequences.iter().enumerate().skip(a + 1) {
+                for (commit_a, commit_b) in sequence_a.iter().zip(sequence_b.iter()) {
+                    assert_eq!(
+                        commit_a, commit_b,
+                        "Authorities {a} and {b} committed different sub-dags"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Checks that every authority in `indices` commits at least `min_commits` more sub-dags
+    /// within `duration`.
+    async fn assert_liveness(
+        &self,
+        indices: &[AuthorityIndex],
+        min_commits: usize,
+        duration: Duration,
+    ) {
+        let before = indices
+            .iter()
+            .map(|index| self.num_commits(*index))
+            .collect::<Vec<_>>();
+        sleep(duration).await;
+        for (index, before) in indices.iter().zip(before) {
+            let after = self.num_commits(*index);
+            assert!(
+                after >= before + min_commits,
+                "Authority {index} only committed {} sub-dags in {duration:?}",
+                after - before,
+            );
+        }
+    }
+}
+
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+async fn test_partition_and_heal() {
+    let mut simulation = Simulation::new(4, 0).await;
+    let all = simulation.indices();
+    simulation
+        .assert_liveness(&all, 10, Duration::from_secs(10))
+        .await;
+
+    // The isolated authority falls behind, while the other three keep committing.
+    simulation.network.isolate(&all[3..]);
+    simulation
+        .assert_liveness(&all[..3], 10, Duration::from_secs(10))
+        .await;
+
+    // No side of an even split has a quorum.
+    simulation.network.heal();
+    simulation.network.isolate(&all[..2]);
+    sleep(Duration::from_secs(10)).await;
+
+    // Once healed, all authorities commit again, including the one isolated before.
+    simulation.network.heal();
+    simulation
+        .assert_liveness(&all, 10, Duration::from_secs(30))
+        .await;
+
+    simulation.stop_all().await;
+    simulation.assert_safety();
+}
+
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+async fn test_crash_and_recover() {
+    let mut simulation = Simulation::new(4, 0).await;
+    let all = simulation.indices();
+    simulation
+        .assert_liveness(&all, 10, Duration::from_secs(10))
+        .await;
+
+    // The committee keeps committing while one authority is down.
+    simulation.stop(all[1]).await;
+    let running = [all[0], all[2], all[3]];
+    simulation
+        .assert_liveness(&running, 10, Duration::from_secs(10))
+        .await;
+
+    // The restarted authority recovers from its store and catches up with the others.
+    simulation.start(all[1]).await;
+    simulation
+        .assert_liveness(&all, 10, Duration::from_secs(30))
+        .await;
+
+    simulation.stop_all().await;
+    simulation.assert_safety();
+}
+
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+async fn test_equivocating_authority() {
+    let mut simulation = Simulation::new(4, 0).await;
+    let all = simulation.indices();
+
+    // Authority 3 sends different blocks to authority 0 than to everyone else.
+    let keypair = simulation.keypairs[all[3]].1.clone();
+    simulation.network.equivocate(all[3], keypair, &all[..1]);
+
+    // The equivocator itself might be unable to follow, but honest authorities keep committing
+    // the same sub-dags.
+    simulation
+        .assert_liveness(&all[..3], 10, Duration::from_secs(30))
+        .await;
+
+    simulation.stop_all().await;
+    simulation.assert_safety();
+}
+
+/// Runs a random schedule of faults, with at most one authority crashed or isolated at a time so
+/// that a quorum remains, then lifts all faults and checks that everyone commits again.
+#[rstest]
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+async fn test_random_faults(#[values(1, 2, 3, 4, 5)] seed: u64) {
+    let mut simulation = Simulation::new(4, seed).await;
+    let all = simulation.indices();
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut crashed = None;
+    for _ in 0..10 {
+        if let Some(index) = crashed.take() {
+            simulation.start(index).await;
+        }
+        simulation.network.heal();
+        let faulty = *all.choose(&mut rng).unwrap();
+        match rng.gen_range(0..4) {
+            0 => {
+                simulation.stop(faulty).await;
+                crashed = Some(faulty);
+            }
+            1 => simulation.network.isolate(&[faulty]),
+            2 => {
+                let peer = *all.choose(&mut rng).unwrap();
+                if peer != faulty {
+                    simulation.network.disconnect(faulty, peer);
+                }
+            }
+            _ => {
+                let max_latency = Duration::from_millis(rng.gen_range(10..1_000));
+                simulation.network.set_latency(Duration::ZERO, max_latency);
+            }
+        }
+        sleep(Duration::from_millis(rng.gen_range(1_000..10_000))).await;
+    }
+
+    if let Some(index) = crashed.take() {
+        simulation.start(index).await;
+    }
+    simulation.network.heal();
+    simulation
+        .network
+        .set_latency(Duration::from_millis(10), Duration::from_millis(100));
+    simulation
+        .assert_liveness(&all, 10, Duration::from_secs(30))
+        .await;
+
+    simulation.stop_all().await;
+    simulation.assert_safety();
+}
@@ -19,6 +19,7 @@ use sui_types::{
         SuiSystemState, SuiSystemStateTrait,
     },
     transaction::{TransactionDataAPI, VerifiedTransaction},
+    type_resolver::LayoutResolver,
 };
 
 use crate::SimulatorStore;
@@ -79,6 +80,13 @@ impl EpochState {
         &self.epoch_start_state
     }
 
+    pub fn type_layout_resolver<'a>(
+        &'a self,
+        store: &'a dyn SimulatorStore,
+    ) -> Box<dyn LayoutResolver + 'a> {
+        self.executor.type_layout_resolver(Box::new(store))
+    }
+
     pub fn protocol_version(&self) -> ProtocolVersion {
         self.protocol_config().version
     }
@@ -26,10 +26,11 @@ use sui_swarm_config::network_config_builder::ConfigBuilder;
 use sui_types::base_types::{AuthorityName, ObjectID, VersionNumber};
 use sui_types::crypto::AuthoritySignature;
 use sui_types::digests::ConsensusCommitDigest;
-use sui_types::object::Object;
+use sui_types::object::{Object, Owner};
 use sui_types::storage::{ObjectStore, ReadStore, RestStateReader};
 use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemState;
 use sui_types::transaction::EndOfEpochTransactionKind;
+use sui_types::type_resolver::LayoutResolver;
 use sui_types::{
     base_types::SuiAddress,
     committee::Committee,
@@ -307,6 +308,12 @@ impl<R, S: store::SimulatorStore> Simulacrum<R, S> {
         self.epoch_state.epoch_start_state()
     }
 
+    /// Return a resolver for the layouts of types published on this chain, e.g. to inspect the
+    /// contents of objects in the store.
+    pub fn type_layout_resolver(&self) -> Box<dyn LayoutResolver + '_> {
+        self.epoch_state.type_layout_resolver(&self.store)
+    }
+
     /// Return a handle to the internally held RNG.
     ///
     /// Returns a handle to the RNG used to create this Simulacrum for use as a source of
@@ -576,6 +583,101 @@ impl<T: Send + Sync, V: store::SimulatorStore + Send + Sync> RestStateReader for
             .to_owned()
             .into())
     }
+
+    fn account_owned_objects_info_iter(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<ObjectID>,
+    ) -> sui_types::storage::error::Result<
+        Box<dyn Iterator<Item = sui_types::storage::AccountOwnedObjectInfo> + '_>,
+    > {
+        let mut objects = self
+            .store()
+            .owned_objects(owner)
+            .filter(|object| cursor.map_or(true, |cursor| object.id() >= cursor))
+            .filter_map(|object| {
+                Some(sui_types::storage::AccountOwnedObjectInfo {
+                    owner,
+                    object_id: object.id(),
+                    version: object.version(),
+                    type_: object.data.try_as_move()?.type_().clone(),
+                })
+            })
+            .collect::<Vec<_>>();
+        objects.sort_by_key(|info| info.object_id);
+        Ok(Box::new(objects.into_iter()))
+    }
+
+    fn dynamic_field_iter(
+        &self,
+        parent: ObjectID,
+        cursor: Option<ObjectID>,
+    ) -> sui_types::storage::error::Result<
+        Box<
+            dyn Iterator<
+                    Item = (
+                        sui_types::storage::DynamicFieldKey,
+                        sui_types::storage::DynamicFieldIndexInfo,
+                    ),
+                > + '_,
+        >,
+    > {
+        use sui_types::dynamic_field::{DynamicFieldInfo, DynamicFieldType};
+        use sui_types::storage::error::Error as StorageError;
+        use sui_types::storage::{DynamicFieldIndexInfo, DynamicFieldKey};
+
+        let mut resolver = self.type_layout_resolver();
+        let mut fields = vec![];
+        for object in self.store().live_objects() {
+            if object.owner != Owner::ObjectOwner(parent.into())
+                || cursor.is_some_and(|cursor| object.id() < cursor)
+            {
+                continue;
+            }
+            let Some(move_object) = object.data.try_as_move() else {
+                continue;
+            };
+            if !move_object.type_().is_dynamic_field() {
+                continue;
+            }
+
+            let layout = sui_types::type_resolver::into_struct_layout(
+                resolver
+                    .get_annotated_layout(&move_object.type_().clone().into())
+                    .map_err(StorageError::custom)?,
+            )
+            .map_err(StorageError::custom)?;
+            let move_struct = move_object
+                .to_move_struct(&layout)
+                .map_err(StorageError::serialization)?;
+            let (name_value, dynamic_field_type, object_id) =
+                DynamicFieldInfo::parse_move_object(&move_struct).map_err(StorageError::custom)?;
+            let name_type = move_object
+                .type_()
+                .try_extract_field_name(&dynamic_field_type)
+                .map_err(StorageError::custom)?;
+            let name_value = name_value
+                .undecorate()
+                .simple_serialize()
+                .expect("serialization cannot fail");
+            let dynamic_object_id = match dynamic_field_type {
+                DynamicFieldType::DynamicObject => Some(object_id),
+                DynamicFieldType::DynamicField => None,
+            };
+
+            fields.push((
+                DynamicFieldKey::new(parent, object.id()),
+                DynamicFieldIndexInfo {
+                    dynamic_field_type,
+                    name_type,
+                    name_value,
+                    dynamic_object_id,
+                },
+            ));
+        }
+        fields.sort_by_key(|(key, _)| key.field_id);
+        Ok(Box::new(fields.into_iter()))
+    }
 }
 
 impl Simulacrum {
@@ -127,12 +127,15 @@ impl InMemoryStore {
     }
 
     pub fn owned_objects(&self, owner: SuiAddress) -> impl Iterator<Item = &Object> {
+        self.live_objects().filter(
+            move |object| matches!(object.owner, Owner::AddressOwner(addr) if addr == owner),
+        )
+    }
+
+    pub fn live_objects(&self) -> impl Iterator<Item = &Object> {
         self.live_objects
             .iter()
             .flat_map(|(id, version)| self.get_object_at_version(id, *version))
-            .filter(
-                move |object| matches!(object.owner, Owner::AddressOwner(addr) if addr == owner),
-            )
     }
 }
 
@@ -448,6 +451,10 @@ impl SimulatorStore for InMemoryStore {
         Box::new(self.owned_objects(owner).cloned())
     }
 
+    fn live_objects(&self) -> Box<dyn Iterator<Item = Object> + '_> {
+        Box::new(self.live_objects().cloned())
+    }
+
     fn insert_checkpoint(&mut self, checkpoint: VerifiedCheckpoint) {
         self.insert_checkpoint(checkpoint)
     }
@@ -92,6 +92,8 @@ pub trait SimulatorStore:
 
     fn owned_objects(&self, owner: SuiAddress) -> Box<dyn Iterator<Item = Object> + '_>;
 
+    fn live_objects(&self) -> Box<dyn Iterator<Item = Object> + '_>;
+
     fn insert_checkpoint(&mut self, checkpoint: VerifiedCheckpoint);
 
     fn insert_checkpoint_contents(&mut self, contents: CheckpointContents);
@@ -11,6 +11,7 @@ pub struct AnalyticsMetrics {
     pub total_received: IntCounterVec,
     pub last_uploaded_checkpoint: IntGaugeVec,
     pub max_checkpoint_on_store: IntGaugeVec,
+    pub delta_table_version: IntGaugeVec,
 }
 
 impl AnalyticsMetrics {
@@ -37,6 +38,13 @@ impl AnalyticsMetrics {
                 registry,
             )
             .unwrap(),
+            delta_table_version: register_int_gauge_vec_with_registry!(
+                "delta_table_version",
+                "Latest committed version of the delta table.",
+                &["data_type"],
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -22,11 +22,12 @@ use sui_storage::object_store::util::{copy_file, path_to_filesystem};
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 use crate::analytics_metrics::AnalyticsMetrics;
+use crate::delta_log::{DeltaFile, DeltaLog};
 use crate::handlers::AnalyticsHandler;
 use crate::writers::AnalyticsWriter;
 use crate::{
-    join_paths, AnalyticsIndexerConfig, FileMetadata, MaxCheckpointReader, ParquetSchema,
-    EPOCH_DIR_PREFIX,
+    join_paths, AnalyticsIndexerConfig, FileFormat, FileMetadata, MaxCheckpointReader,
+    ParquetSchema, TableFormat, EPOCH_DIR_PREFIX,
 };
 
 struct State<S: Serialize + ParquetSchema> {
@@ -116,6 +117,20 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
         };
         let local_object_store = local_store_config.make()?;
         let remote_object_store = config.remote_store_config.make()?;
+        let delta_log = match config.table_format {
+            Some(TableFormat::DELTA) => {
+                if config.file_format != FileFormat::PARQUET {
+                    anyhow::bail!("Delta tables require the parquet file format");
+                }
+                Some(DeltaLog::new(
+                    remote_object_store.clone(),
+                    config
+                        .file_type
+                        .table_root(config.remote_store_path_prefix.clone()),
+                ))
+            }
+            None => None,
+        };
         let (kill_sender, kill_receiver) = oneshot::channel::<()>();
         let (sender, receiver) = mpsc::channel::<FileMetadata>(100);
         let name: String = handler.name().parse()?;
@@ -126,6 +141,7 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
             local_object_store.clone(),
             checkpoint_dir,
             config.remote_store_path_prefix.clone(),
+            delta_log,
             receiver,
             kill_receiver,
             cloned_metrics,
@@ -221,6 +237,7 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
         local_object_store: Arc<DynObjectStore>,
         local_staging_root_dir: PathBuf,
         remote_store_path_prefix: Option<Path>,
+        mut delta_log: Option<DeltaLog>,
         mut file_recv: mpsc::Receiver<FileMetadata>,
         mut recv: oneshot::Receiver<()>,
         metrics: AnalyticsMetrics,
@@ -233,6 +250,14 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
                     if let Some(file_metadata) = file {
                         info!("Received {name} file with checkpoints: {:?}", &file_metadata.checkpoint_seq_range);
                         let checkpoint_seq_num = file_metadata.checkpoint_seq_range.end;
+                        // Described before syncing, as the local file is removed once uploaded.
+                        let delta_file = delta_log.as_ref().map(|_| {
+                            DeltaFile::from_parquet_file(
+                                &path_to_filesystem(local_staging_root_dir.clone(), &file_metadata.file_path())?,
+                                file_metadata.table_path(),
+                                file_metadata.checkpoint_seq_range.clone(),
+                            )
+                        }).transpose().expect("Reading parquet file metadata should not fail");
                         Self::sync_file_to_remote(
                                 local_staging_root_dir.clone(),
                                 file_metadata.file_path(),
@@ -242,6 +267,13 @@ impl<S: Serialize + ParquetSchema + 'static> AnalyticsProcessor<S> {
                             )
                             .await
                             .expect("Syncing checkpoint should not fail");
+                        if let (Some(delta_log), Some(delta_file)) = (&mut delta_log, &delta_file) {
+                            let version = delta_log
+                                .commit(delta_file)
+                                .await
+                                .expect("Committing file to delta table should not fail");
+                            metrics.delta_table_version.with_label_values(&[&name]).set(version as i64);
+                        }
                         metrics.last_uploaded_checkpoint.with_label_values(&[&name]).set(checkpoint_seq_num as i64);
                     } else {
                         info!("Terminating upload sync loop");
@@ -0,0 +1,822 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A minimal writer for the Delta Lake transaction log.
+//!
+//! Each Parquet file uploaded by the analytics indexer is committed to the table of its file type
+//! as a new table version: a JSON file in the table's `_delta_log` directory containing an `add`
+//! action for the file (and a `metaData` action, when the file's schema differs from the table's).
+//! Versions are created with `copy_if_not_exists`, so a version is never overwritten, and readers
+//! only see files once they are committed. Remote stores need to support that operation (S3
+//! requires a copy-if-not-exists configuration).
+//!
+//! Every `CHECKPOINT_INTERVAL` versions, the state of the table (its protocol, metadata and files)
+//! is also written to a Parquet log checkpoint, and `_last_checkpoint` is pointed at it, so that
+//! reading the table only needs the latest log checkpoint and the versions committed after it.
+//!
+//! Only the parts of the protocol that the indexer needs are written: tables are unpartitioned,
+//! and files are never removed.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::ops::Range;
+use std::path::Path as FsPath;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Context, Result};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::json::{LineDelimitedWriter, ReaderBuilder};
+use bytes::Bytes;
+use object_store::path::Path;
+use object_store::DynObjectStore;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use serde::{Deserialize, Serialize};
+use tokio_stream::StreamExt;
+use tracing::{info, warn};
+
+const DELTA_LOG_DIR: &str = "_delta_log";
+const MIN_READER_VERSION: u32 = 1;
+const MIN_WRITER_VERSION: u32 = 2;
+const MAX_COMMIT_ATTEMPTS: usize = 10;
+/// Number of versions between log checkpoints, the default `delta.checkpointInterval`.
+const CHECKPOINT_INTERVAL: u64 = 10;
+const LAST_CHECKPOINT_FILE: &str = "_last_checkpoint";
+
+/// Tags recorded on each `add` action, used to find where the indexer left off.
+const CHECKPOINT_START_TAG: &str = "sui_checkpoint_start";
+const CHECKPOINT_END_TAG: &str = "sui_checkpoint_end";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeltaField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub nullable: bool,
+    pub metadata: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct DeltaSchema {
+    #[serde(rename = "type")]
+    type_: String,
+    fields: Vec<DeltaField>,
+}
+
+/// A Parquet file to be committed to a table.
+#[derive(Debug, Clone)]
+pub struct DeltaFile {
+    /// Path of the file, relative to the table root.
+    pub path: String,
+    pub size: u64,
+    pub num_records: i64,
+    pub schema: Vec<DeltaField>,
+    pub checkpoint_range: Range<u64>,
+}
+
+impl DeltaFile {
+    /// Describe the Parquet file at `local_path`, which is uploaded to `path` under the table
+    /// root, reading its size, row count and schema from the file.
+    pub fn from_parquet_file(
+        local_path: &FsPath,
+        path: String,
+        checkpoint_range: Range<u64>,
+    ) -> Result<Self> {
+        let file = File::open(local_path)
+            .with_context(|| format!("Failed to open {}", local_path.display()))?;
+        let size = file.metadata()?.len();
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file)?;
+        let num_records = builder.metadata().file_metadata().num_rows();
+        let schema = delta_fields(builder.schema())?;
+        Ok(Self {
+            path,
+            size,
+            num_records,
+            schema,
+            checkpoint_range,
+        })
+    }
+}
+
+/// Map an Arrow schema, as written by the Parquet writer, to Delta fields.
+fn delta_fields(schema: &Schema) -> Result<Vec<DeltaField>> {
+    schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let type_ = match field.data_type() {
+                DataType::Boolean => "boolean",
+                // Delta has no unsigned types, so these are exposed as signed longs.
+                DataType::Int64 | DataType::UInt64 => "long",
+                DataType::Utf8 | DataType::LargeUtf8 => "string",
+                other => bail!("Unsupported column type for {}: {other}", field.name()),
+            };
+            Ok(DeltaField {
+                name: field.name().clone(),
+                type_: type_.to_string(),
+                nullable: field.is_nullable(),
+                metadata: HashMap::new(),
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Protocol {
+    min_reader_version: u32,
+    min_writer_version: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Format {
+    provider: String,
+    options: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Metadata {
+    id: String,
+    format: Format,
+    schema_string: String,
+    partition_columns: Vec<String>,
+    configuration: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_time: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Add {
+    path: String,
+    partition_values: HashMap<String, String>,
+    size: u64,
+    modification_time: i64,
+    data_change: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<String>,
+    #[serde(default)]
+    tags: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommitInfo {
+    timestamp: i64,
+    operation: String,
+    operation_parameters: HashMap<String, String>,
+}
+
+/// A line of a commit file, holding a single action. Kinds of actions that the indexer does not
+/// need to understand (written by other writers) are skipped over.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Action {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protocol: Option<Protocol>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    meta_data: Option<Metadata>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    add: Option<Add>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commit_info: Option<CommitInfo>,
+}
+
+/// Contents of `_last_checkpoint`, pointing at the latest log checkpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LastCheckpoint {
+    version: u64,
+    size: u64,
+}
+
+/// The state of the table as of a version, reconstructed from its log.
+#[derive(Debug, Default)]
+struct Snapshot {
+    version: u64,
+    protocol: Option<Protocol>,
+    metadata: Option<Metadata>,
+    /// Files in the table, by path.
+    files: BTreeMap<String, Add>,
+}
+
+impl Snapshot {
+    fn apply(&mut self, actions: Vec<Action>) {
+        for action in actions {
+            if let Some(protocol) = action.protocol {
+                self.protocol = Some(protocol);
+            }
+            if let Some(metadata) = action.meta_data {
+                self.metadata = Some(metadata);
+            }
+            if let Some(add) = action.add {
+                self.files.insert(add.path.clone(), add);
+            }
+        }
+    }
+
+    /// The actions making up a log checkpoint of this state.
+    fn into_actions(self) -> Vec<Action> {
+        let mut actions = vec![
+            Action {
+                protocol: self.protocol,
+                ..Default::default()
+            },
+            Action {
+                meta_data: self.metadata,
+                ..Default::default()
+            },
+        ];
+        actions.extend(self.files.into_values().map(|add| Action {
+            add: Some(add),
+            ..Default::default()
+        }));
+        actions
+    }
+}
+
+pub struct DeltaLog {
+    store: Arc<DynObjectStore>,
+    table_root: Path,
+    /// Latest committed version, and the table's metadata as of that version, once read.
+    state: Option<(u64, Metadata)>,
+}
+
+impl DeltaLog {
+    pub fn new(store: Arc<DynObjectStore>, table_root: Path) -> Self {
+        Self {
+            store,
+            table_root,
+            state: None,
+        }
+    }
+
+    /// Commit `file` to the table as a new version, creating the table if it does not exist yet,
+    /// and updating its schema if the file's schema is different. Returns the committed version.
+    pub async fn commit(&mut self, file: &DeltaFile) -> Result<u64> {
+        for _ in 0..MAX_COMMIT_ATTEMPTS {
+            if self.state.is_none() {
+                self.state = self.read_state().await?;
+            }
+
+            let now = now_ms();
+            let mut actions = vec![];
+            let metadata = match &self.state {
+                Some((_, metadata)) if schema_fields(metadata)? == file.schema => metadata.clone(),
+                Some((_, metadata)) => {
+                    info!("Updating schema of delta table {}", self.table_root);
+                    Metadata {
+                        schema_string: schema_string(&file.schema)?,
+                        ..metadata.clone()
+                    }
+                }
+                None => {
+                    actions.push(Action {
+                        protocol: Some(Protocol {
+                            min_reader_version: MIN_READER_VERSION,
+                            min_writer_version: MIN_WRITER_VERSION,
+                        }),
+                        ..Default::default()
+                    });
+                    Metadata {
+                        id: uuid::Uuid::new_v4().to_string(),
+                        format: Format {
+                            provider: "parquet".to_string(),
+                            options: HashMap::new(),
+                        },
+                        schema_string: schema_string(&file.schema)?,
+                        partition_columns: vec![],
+                        configuration: HashMap::new(),
+                        created_time: Some(now),
+                    }
+                }
+            };
+            let schema_changed = !matches!(
+                &self.state,
+                Some((_, current)) if current.schema_string == metadata.schema_string
+            );
+            if schema_changed {
+                actions.push(Action {
+                    meta_data: Some(metadata.clone()),
+                    ..Default::default()
+                });
+            }
+
+            let add = Add {
+                path: file.path.clone(),
+                partition_values: HashMap::new(),
+                size: file.size,
+                modification_time: now,
+                data_change: true,
+                stats: Some(format!("{{\"numRecords\":{}}}", file.num_records)),
+                tags: HashMap::from([
+                    (
+                        CHECKPOINT_START_TAG.to_string(),
+                        file.checkpoint_range.start.to_string(),
+                    ),
+                    (
+                        CHECKPOINT_END_TAG.to_string(),
+                        file.checkpoint_range.end.to_string(),
+                    ),
+                ]),
+            };
+            let commit_info = CommitInfo {
+                timestamp: now,
+                operation: "WRITE".to_string(),
+                operation_parameters: HashMap::from([("mode".to_string(), "Append".to_string())]),
+            };
+            actions.push(Action {
+                add: Some(add),
+                ..Default::default()
+            });
+            actions.push(Action {
+                commit_info: Some(commit_info),
+                ..Default::default()
+            });
+
+            let version = self.state.as_ref().map_or(0, |(version, _)| version + 1);
+            if self.try_commit(version, &actions).await? {
+                self.state = Some((version, metadata));
+                if version > 0 && version % CHECKPOINT_INTERVAL == 0 {
+                    // The version is committed either way, so a failed log checkpoint only means
+                    // that readers replay more of the log.
+                    if let Err(e) = self.checkpoint(version).await {
+                        warn!(
+                            "Failed to write log checkpoint for version {version} of delta table {}: {e:#}",
+                            self.table_root
+                        );
+                    }
+                }
+                return Ok(version);
+            }
+
+            // Another writer committed this version first, so catch up with the log and retry.
+            warn!(
+                "Conflict committing version {version} of delta table {}, retrying",
+                self.table_root
+            );
+            self.state = None;
+        }
+
+        Err(anyhow!(
+            "Failed to commit {} to delta table {} after {MAX_COMMIT_ATTEMPTS} attempts",
+            file.path,
+            self.table_root,
+        ))
+    }
+
+    /// The checkpoint to resume indexing from: the end of the latest checkpoint range of the files
+    /// in the table, or `None` if the table has no versions.
+    pub async fn next_checkpoint(&self) -> Result<Option<u64>> {
+        let Some(snapshot) = self.read_snapshot(None).await? else {
+            return Ok(None);
+        };
+        Ok(snapshot
+            .files
+            .values()
+            .filter_map(|add| add.tags.get(CHECKPOINT_END_TAG)?.parse::<u64>().ok())
+            .max())
+    }
+
+    /// Write `actions` as `version` of the table. Returns false if that version already exists.
+    async fn try_commit(&self, version: u64, actions: &[Action]) -> Result<bool> {
+        let mut contents = String::new();
+        for action in actions {
+            contents.push_str(&serde_json::to_string(action)?);
+            contents.push('\n');
+        }
+
+        // Stage the commit next to the log, and only make it visible by copying it to the
+        // version's path if nothing is there yet.
+        let staged = self.log_dir().child(format!(
+            "_commit_{}.json.tmp",
+            uuid::Uuid::new_v4().simple()
+        ));
+        self.store.put(&staged, Bytes::from(contents)).await?;
+        let result = self
+            .store
+            .copy_if_not_exists(&staged, &self.version_path(version))
+            .await;
+        self.store.delete(&staged).await?;
+
+        match result {
+            Ok(()) => Ok(true),
+            Err(object_store::Error::AlreadyExists { .. }) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Find the latest version, and the table metadata as of that version.
+    async fn read_state(&self) -> Result<Option<(u64, Metadata)>> {
+        let Some(snapshot) = self.read_snapshot(None).await? else {
+            return Ok(None);
+        };
+        let metadata = snapshot
+            .metadata
+            .ok_or_else(|| anyhow!("Delta table {} has no metadata in its log", self.table_root))?;
+        Ok(Some((snapshot.version, metadata)))
+    }
+
+    /// Reconstruct the state of the table as of `version` (or the latest version), starting from
+    /// the latest log checkpoint at or before it. Returns `None` if the table has no versions.
+    async fn read_snapshot(&self, version: Option<u64>) -> Result<Option<Snapshot>> {
+        let last_checkpoint = self
+            .read_last_checkpoint()
+            .await?
+            .filter(|checkpoint| version.map_or(true, |version| checkpoint.version <= version));
+        let checkpoint_version = last_checkpoint.map(|checkpoint| checkpoint.version);
+
+        let latest = match version {
+            Some(version) => version,
+            None => match self.latest_version(checkpoint_version).await? {
+                Some(latest) => latest,
+                None => return Ok(None),
+            },
+        };
+
+        let (mut snapshot, first) = match checkpoint_version {
+            Some(checkpoint_version) => {
+                let mut snapshot = Snapshot::default();
+                snapshot.apply(self.read_checkpoint(checkpoint_version).await?);
+                (snapshot, checkpoint_version + 1)
+            }
+            None => (Snapshot::default(), 0),
+        };
+        for version in first..=latest {
+            snapshot.apply(self.read_version(version).await?);
+        }
+        snapshot.version = latest;
+        Ok(Some(snapshot))
+    }
+
+    /// Write a log checkpoint of the table as of `version`, unless there is already one at or
+    /// after it.
+    async fn checkpoint(&self, version: u64) -> Result<()> {
+        if self
+            .read_last_checkpoint()
+            .await?
+            .is_some_and(|checkpoint| checkpoint.version >= version)
+        {
+            return Ok(());
+        }
+
+        let snapshot = self
+            .read_snapshot(Some(version))
+            .await?
+            .ok_or_else(|| anyhow!("Delta table {} has no versions", self.table_root))?;
+        let actions = snapshot.into_actions();
+        self.store
+            .put(&self.checkpoint_path(version), encode_checkpoint(&actions)?)
+            .await?;
+
+        let last_checkpoint = LastCheckpoint {
+            version,
+            size: actions.len() as u64,
+        };
+        self.store
+            .put(
+                &self.log_dir().child(LAST_CHECKPOINT_FILE),
+                Bytes::from(serde_json::to_vec(&last_checkpoint)?),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn read_last_checkpoint(&self) -> Result<Option<LastCheckpoint>> {
+        let path = self.log_dir().child(LAST_CHECKPOINT_FILE);
+        let bytes = match self.store.get(&path).await {
+            Ok(result) => result.bytes().await?,
+            Err(object_store::Error::NotFound { .. }) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Some(serde_json::from_slice(&bytes)?))
+    }
+
+    /// The latest version in the log, only listing the versions after `after` when it is known to
+    /// exist.
+    async fn latest_version(&self, after: Option<u64>) -> Result<Option<u64>> {
+        let log_dir = self.log_dir();
+        let mut listing = match after {
+            Some(after) => {
+                self.store
+                    .list_with_offset(Some(&log_dir), &self.version_path(after))
+                    .await?
+            }
+            None => self.store.list(Some(&log_dir)).await?,
+        };
+
+        let mut latest = after;
+        while let Some(object) = listing.next().await {
+            let version = object?
+                .location
+                .filename()
+                .and_then(|name| name.strip_suffix(".json"))
+                .and_then(|version| version.parse::<u64>().ok());
+            latest = latest.max(version);
+        }
+        Ok(latest)
+    }
+
+    async fn read_version(&self, version: u64) -> Result<Vec<Action>> {
+        let bytes = self
+            .store
+            .get(&self.version_path(version))
+            .await?
+            .bytes()
+            .await?;
+        parse_actions(&bytes)
+    }
+
+    async fn read_checkpoint(&self, version: u64) -> Result<Vec<Action>> {
+        let bytes = self
+            .store
+            .get(&self.checkpoint_path(version))
+            .await?
+            .bytes()
+            .await?;
+        decode_checkpoint(bytes)
+    }
+
+    fn log_dir(&self) -> Path {
+        self.table_root.child(DELTA_LOG_DIR)
+    }
+
+    fn version_path(&self, version: u64) -> Path {
+        self.log_dir().child(format!("{version:020}.json"))
+    }
+
+    fn checkpoint_path(&self, version: u64) -> Path {
+        self.log_dir()
+            .child(format!("{version:020}.checkpoint.parquet"))
+    }
+}
+
+/// Parse the actions of a commit file, one per line.
+fn parse_actions(bytes: &[u8]) -> Result<Vec<Action>> {
+    std::str::from_utf8(bytes)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Ok(serde_json::from_str(line)?))
+        .collect()
+}
+
+/// Encode `actions` as a log checkpoint: a Parquet file with a row per action, and a nullable
+/// struct column per kind of action.
+fn encode_checkpoint(actions: &[Action]) -> Result<Bytes> {
+    let schema = Arc::new(checkpoint_schema());
+    let mut decoder = ReaderBuilder::new(schema.clone())
+        .with_batch_size(actions.len().max(1))
+        .build_decoder()?;
+    decoder.serialize(actions)?;
+    let batch = decoder
+        .flush()?
+        .ok_or_else(|| anyhow!("Log checkpoint has no actions"))?;
+
+    let mut buffer = vec![];
+    let mut writer = ArrowWriter::try_new(&mut buffer, schema, None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(Bytes::from(buffer))
+}
+
+/// Decode the actions of a log checkpoint, going through their JSON representation, which is the
+/// same as in commit files.
+fn decode_checkpoint(bytes: Bytes) -> Result<Vec<Action>> {
+    let reader = ParquetRecordBatchReaderBuilder::try_new(bytes)?.build()?;
+    let mut writer = LineDelimitedWriter::new(vec![]);
+    for batch in reader {
+        writer.write(&batch?)?;
+    }
+    writer.finish()?;
+    parse_actions(&writer.into_inner())
+}
+
+/// The schema of log checkpoints, covering the kinds of actions that make up a table's state.
+fn checkpoint_schema() -> Schema {
+    fn nullable(name: &str, data_type: DataType) -> Field {
+        Field::new(name, data_type, true)
+    }
+    fn struct_of(fields: Vec<Field>) -> DataType {
+        DataType::Struct(fields.into())
+    }
+    let string_map = || {
+        DataType::Map(
+            Arc::new(Field::new(
+                "key_value",
+                struct_of(vec![
+                    Field::new("key", DataType::Utf8, false),
+                    nullable("value", DataType::Utf8),
+                ]),
+                false,
+            )),
+            false,
+        )
+    };
+
+    Schema::new(vec![
+        nullable(
+            "txn",
+            struct_of(vec![
+                nullable("appId", DataType::Utf8),
+                nullable("version", DataType::Int64),
+                nullable("lastUpdated", DataType::Int64),
+            ]),
+        ),
+        nullable(
+            "add",
+            struct_of(vec![
+                nullable("path", DataType::Utf8),
+                nullable("partitionValues", string_map()),
+                nullable("size", DataType::Int64),
+                nullable("modificationTime", DataType::Int64),
+                nullable("dataChange", DataType::Boolean),
+                nullable("stats", DataType::Utf8),
+                nullable("tags", string_map()),
+            ]),
+        ),
+        nullable(
+            "remove",
+            struct_of(vec![
+                nullable("path", DataType::Utf8),
+                nullable("deletionTimestamp", DataType::Int64),
+                nullable("dataChange", DataType::Boolean),
+            ]),
+        ),
+        nullable(
+            "metaData",
+            struct_of(vec![
+                nullable("id", DataType::Utf8),
+                nullable("name", DataType::Utf8),
+                nullable("description", DataType::Utf8),
+                nullable(
+                    "format",
+                    struct_of(vec![
+                        nullable("provider", DataType::Utf8),
+                        nullable("options", string_map()),
+                    ]),
+                ),
+                nullable("schemaString", DataType::Utf8),
+                nullable(
+                    "partitionColumns",
+                    DataType::List(Arc::new(nullable("element", DataType::Utf8))),
+                ),
+                nullable("configuration", string_map()),
+                nullable("createdTime", DataType::Int64),
+            ]),
+        ),
+        nullable(
+            "protocol",
+            struct_of(vec![
+                nullable("minReaderVersion", DataType::Int32),
+                nullable("minWriterVersion", DataType::Int32),
+            ]),
+        ),
+    ])
+}
+
+fn schema_string(fields: &[DeltaField]) -> Result<String> {
+    Ok(serde_json::to_string(&DeltaSchema {
+        type_: "struct".to_string(),
+        fields: fields.to_vec(),
+    })?)
+}
+
+fn schema_fields(metadata: &Metadata) -> Result<Vec<DeltaField>> {
+    let schema: DeltaSchema = serde_json::from_str(&metadata.schema_string)?;
+    Ok(schema.fields)
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use object_store::memory::InMemory;
+
+    fn field(name: &str, type_: &str) -> DeltaField {
+        DeltaField {
+            name: name.to_string(),
+            type_: type_.to_string(),
+            nullable: false,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn file(schema: Vec<DeltaField>, checkpoint_range: Range<u64>) -> DeltaFile {
+        DeltaFile {
+            path: format!(
+                "epoch_0/{}_{}.parquet",
+                checkpoint_range.start, checkpoint_range.end
+            ),
+            size: 100,
+            num_records: 10,
+            schema,
+            checkpoint_range,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_commit_and_resume() -> Result<()> {
+        let store: Arc<DynObjectStore> = Arc::new(InMemory::new());
+        let root = Path::from("checkpoints");
+        let schema = vec![field("checkpoint", "long"), field("digest", "string")];
+
+        let mut log = DeltaLog::new(store.clone(), root.clone());
+        assert_eq!(log.next_checkpoint().await?, None);
+        assert_eq!(log.commit(&file(schema.clone(), 0..10)).await?, 0);
+        assert_eq!(log.commit(&file(schema.clone(), 10..20)).await?, 1);
+
+        // Only the first version carries the protocol and metadata.
+        let first = log.read_version(0).await?;
+        assert!(first[0].protocol.is_some());
+        assert!(first[1].meta_data.is_some());
+        let second = log.read_version(1).await?;
+        assert!(second[0].add.is_some());
+
+        // A new writer picks up where the log left off.
+        let mut log = DeltaLog::new(store, root);
+        assert_eq!(log.next_checkpoint().await?, Some(20));
+
+        // Adding a column updates the table's schema.
+        let mut evolved = schema.clone();
+        evolved.push(field("epoch", "long"));
+        assert_eq!(log.commit(&file(evolved.clone(), 20..30)).await?, 2);
+        let third = log.read_version(2).await?;
+        let Some(metadata) = &third[0].meta_data else {
+            panic!("Expected a metadata action, got {:?}", third[0]);
+        };
+        assert_eq!(schema_fields(metadata)?, evolved);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_log_checkpoints() -> Result<()> {
+        let store: Arc<DynObjectStore> = Arc::new(InMemory::new());
+        let root = Path::from("transactions");
+        let schema = vec![field("checkpoint", "long")];
+
+        let mut log = DeltaLog::new(store.clone(), root.clone());
+        for i in 0..25 {
+            assert_eq!(
+                log.commit(&file(schema.clone(), i * 10..(i + 1) * 10))
+                    .await?,
+                i
+            );
+        }
+
+        // Log checkpoints are written every `CHECKPOINT_INTERVAL` versions.
+        let last_checkpoint = log.read_last_checkpoint().await?.unwrap();
+        assert_eq!(last_checkpoint.version, 20);
+        assert_eq!(last_checkpoint.size, 23);
+        assert!(log.read_checkpoint(10).await.is_ok());
+        let actions = log.read_checkpoint(20).await?;
+        assert_eq!(actions.len(), 23);
+        assert_eq!(
+            actions[0].protocol.as_ref().unwrap().min_writer_version,
+            MIN_WRITER_VERSION
+        );
+        assert_eq!(
+            schema_fields(actions[1].meta_data.as_ref().unwrap())?,
+            schema
+        );
+        let adds: Vec<_> = actions
+            .iter()
+            .filter_map(|action| action.add.as_ref())
+            .collect();
+        assert_eq!(adds.len(), 21);
+        assert!(adds.iter().all(|add| add.size == 100));
+        assert!(adds.iter().any(|add| add.tags[CHECKPOINT_END_TAG] == "210"));
+
+        // Readers start from the latest log checkpoint, so the versions before it are not needed.
+        for version in 0..=20 {
+            store.delete(&log.version_path(version)).await?;
+        }
+        let mut log = DeltaLog::new(store, root);
+        assert_eq!(log.next_checkpoint().await?, Some(250));
+        assert_eq!(log.commit(&file(schema, 250..260)).await?, 25);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_commit_conflict() -> Result<()> {
+        let store: Arc<DynObjectStore> = Arc::new(InMemory::new());
+        let root = Path::from("events");
+        let schema = vec![field("checkpoint", "long")];
+
+        let mut log = DeltaLog::new(store.clone(), root.clone());
+        let mut other = DeltaLog::new(store, root);
+        assert_eq!(log.commit(&file(schema.clone(), 0..10)).await?, 0);
+        assert_eq!(other.commit(&file(schema.clone(), 10..20)).await?, 1);
+
+        // `log` still thinks version 0 is the latest, so its first attempt conflicts.
+        assert_eq!(log.commit(&file(schema, 20..30)).await?, 2);
+        assert_eq!(log.next_checkpoint().await?, Some(30));
+        Ok(())
+    }
+}
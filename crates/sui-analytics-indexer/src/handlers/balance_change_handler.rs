@@ -0,0 +1,209 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, Result};
+use sui_data_ingestion_core::Worker;
+use tokio::sync::Mutex;
+
+use sui_json_rpc::{get_balance_changes_from_effect, ObjectProvider};
+use sui_rest_api::{CheckpointData, CheckpointTransaction};
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::object::Object;
+use sui_types::transaction::TransactionDataAPI;
+
+use crate::handlers::{get_owner_address, get_owner_type, AnalyticsHandler};
+use crate::tables::BalanceChangeEntry;
+use crate::FileType;
+
+pub struct BalanceChangeHandler {
+    state: Mutex<State>,
+}
+
+struct State {
+    balance_changes: Vec<BalanceChangeEntry>,
+}
+
+#[async_trait::async_trait]
+impl Worker for BalanceChangeHandler {
+    async fn process_checkpoint(&self, checkpoint_data: CheckpointData) -> Result<()> {
+        let CheckpointData {
+            checkpoint_summary,
+            transactions: checkpoint_transactions,
+            ..
+        } = checkpoint_data;
+        let mut state = self.state.lock().await;
+        for checkpoint_transaction in checkpoint_transactions {
+            self.process_transaction(
+                checkpoint_summary.epoch,
+                checkpoint_summary.sequence_number,
+                checkpoint_summary.timestamp_ms,
+                &checkpoint_transaction,
+                &mut state,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl AnalyticsHandler<BalanceChangeEntry> for BalanceChangeHandler {
+    async fn read(&self) -> Result<Vec<BalanceChangeEntry>> {
+        let mut state = self.state.lock().await;
+        let cloned = state.balance_changes.clone();
+        state.balance_changes.clear();
+        Ok(cloned)
+    }
+
+    fn file_type(&self) -> Result<FileType> {
+        Ok(FileType::BalanceChange)
+    }
+
+    fn name(&self) -> &str {
+        "balance_change"
+    }
+}
+
+impl BalanceChangeHandler {
+    pub fn new() -> Self {
+        let state = State {
+            balance_changes: vec![],
+        };
+        Self {
+            state: Mutex::new(state),
+        }
+    }
+
+    async fn process_transaction(
+        &self,
+        epoch: u64,
+        checkpoint: u64,
+        timestamp_ms: u64,
+        checkpoint_transaction: &CheckpointTransaction,
+        state: &mut State,
+    ) -> Result<()> {
+        let transaction_digest = checkpoint_transaction.transaction.digest();
+        let input_objects = checkpoint_transaction
+            .transaction
+            .transaction_data()
+            .input_objects()
+            .map_err(|e| {
+                anyhow!("Checkpointed transaction {transaction_digest} has invalid inputs: {e}")
+            })?;
+        let balance_changes = get_balance_changes_from_effect(
+            &TransactionObjectProvider(checkpoint_transaction),
+            &checkpoint_transaction.effects,
+            input_objects,
+            None,
+        )
+        .await?;
+
+        for balance_change in balance_changes {
+            state.balance_changes.push(BalanceChangeEntry {
+                transaction_digest: transaction_digest.base58_encode(),
+                checkpoint,
+                epoch,
+                timestamp_ms,
+                owner_type: get_owner_type(&balance_change.owner),
+                owner_address: get_owner_address(&balance_change.owner),
+                coin_type: balance_change.coin_type.to_string(),
+                amount: balance_change.amount.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Serves the objects a transaction read and wrote, which are all that is needed to compute its
+/// balance changes.
+struct TransactionObjectProvider<'a>(&'a CheckpointTransaction);
+
+impl TransactionObjectProvider<'_> {
+    fn objects(&self) -> impl Iterator<Item = &Object> {
+        self.0
+            .input_objects
+            .iter()
+            .chain(self.0.output_objects.iter())
+    }
+}
+
+#[async_trait::async_trait]
+impl ObjectProvider for TransactionObjectProvider<'_> {
+    type Error = anyhow::Error;
+
+    async fn get_object(&self, id: &ObjectID, version: &SequenceNumber) -> Result<Object> {
+        self.objects()
+            .find(|o| o.id() == *id && o.version() == *version)
+            .cloned()
+            .ok_or_else(|| {
+                anyhow!(
+                    "Object {id} at version {version} not found in transaction {}",
+                    self.0.transaction.digest()
+                )
+            })
+    }
+
+    async fn find_object_lt_or_eq_version(
+        &self,
+        id: &ObjectID,
+        version: &SequenceNumber,
+    ) -> Result<Option<Object>> {
+        Ok(self
+            .objects()
+            .filter(|o| o.id() == *id && o.version() <= *version)
+            .max_by_key(|o| o.version())
+            .cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::handlers::balance_change_handler::BalanceChangeHandler;
+    use simulacrum::Simulacrum;
+    use sui_data_ingestion_core::Worker;
+    use sui_types::base_types::SuiAddress;
+    use sui_types::effects::TransactionEffectsAPI;
+    use sui_types::gas_coin::GAS;
+    use sui_types::storage::ReadStore;
+    use sui_types::transaction::TransactionDataAPI;
+
+    #[tokio::test]
+    pub async fn test_balance_change_handler() -> anyhow::Result<()> {
+        let mut sim = Simulacrum::new();
+
+        // Execute a simple transfer.
+        let transfer_recipient = SuiAddress::random_for_testing_only();
+        let (transaction, transfer_amount) = sim.transfer_txn(transfer_recipient);
+        let sender = transaction.transaction_data().sender();
+        let (effects, err) = sim.execute_transaction(transaction.clone()).unwrap();
+        assert!(err.is_none());
+
+        let checkpoint = sim.create_checkpoint();
+        let checkpoint_data = sim.get_checkpoint_data(
+            checkpoint.clone(),
+            sim.get_checkpoint_contents_by_digest(&checkpoint.content_digest)?
+                .unwrap(),
+        )?;
+        let handler = BalanceChangeHandler::new();
+        handler.process_checkpoint(checkpoint_data).await?;
+        let entries = handler.state.lock().await.balance_changes.clone();
+        assert_eq!(entries.len(), 2);
+
+        // The recipient receives the transferred amount, and the sender pays for it, and for gas.
+        let gas_used = effects.gas_cost_summary().net_gas_usage() as i128;
+        for entry in &entries {
+            assert_eq!(entry.transaction_digest, transaction.digest().to_string());
+            assert_eq!(entry.checkpoint, checkpoint.sequence_number);
+            assert_eq!(entry.coin_type, GAS::type_tag().to_string());
+
+            let expected = if entry.owner_address == Some(transfer_recipient.to_string()) {
+                transfer_amount as i128
+            } else {
+                assert_eq!(entry.owner_address, Some(sender.to_string()));
+                -(transfer_amount as i128) - gas_used
+            };
+            assert_eq!(entry.amount, expected.to_string());
+        }
+        Ok(())
+    }
+}
@@ -20,6 +20,7 @@ use sui_types::transaction::TransactionDataAPI;
 use crate::tables::{InputObjectKind, ObjectStatus, OwnerType};
 use crate::FileType;
 
+pub mod balance_change_handler;
 pub mod checkpoint_handler;
 pub mod df_handler;
 pub mod event_handler;
@@ -57,8 +58,8 @@ fn initial_shared_version(object: &Object) -> Option<u64> {
     }
 }
 
-fn get_owner_type(object: &Object) -> OwnerType {
-    match object.owner {
+fn get_owner_type(owner: &Owner) -> OwnerType {
+    match owner {
         Owner::AddressOwner(_) => OwnerType::AddressOwner,
         Owner::ObjectOwner(_) => OwnerType::ObjectOwner,
         Owner::Shared { .. } => OwnerType::Shared,
@@ -66,8 +67,8 @@ fn get_owner_type(object: &Object) -> OwnerType {
     }
 }
 
-fn get_owner_address(object: &Object) -> Option<String> {
-    match object.owner {
+fn get_owner_address(owner: &Owner) -> Option<String> {
+    match owner {
         Owner::AddressOwner(address) => Some(address.to_string()),
         Owner::ObjectOwner(address) => Some(address.to_string()),
         Owner::Shared { .. } => None,
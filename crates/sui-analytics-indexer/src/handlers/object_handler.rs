@@ -187,8 +187,8 @@ impl ObjectHandler {
             checkpoint,
             epoch,
             timestamp_ms,
-            owner_type: Some(get_owner_type(object)),
-            owner_address: get_owner_address(object),
+            owner_type: Some(get_owner_type(&object.owner)),
+            owner_address: get_owner_address(&object.owner),
             object_status: object_status_tracker
                 .get_object_status(&object_id)
                 .expect("Object must be in output objects"),
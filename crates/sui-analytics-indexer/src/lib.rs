@@ -29,6 +29,8 @@ use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 use crate::analytics_metrics::AnalyticsMetrics;
 use crate::analytics_processor::AnalyticsProcessor;
+use crate::delta_log::DeltaLog;
+use crate::handlers::balance_change_handler::BalanceChangeHandler;
 use crate::handlers::checkpoint_handler::CheckpointHandler;
 use crate::handlers::df_handler::DynamicFieldHandler;
 use crate::handlers::event_handler::EventHandler;
@@ -40,8 +42,8 @@ use crate::handlers::transaction_objects_handler::TransactionObjectsHandler;
 use crate::handlers::wrapped_object_handler::WrappedObjectHandler;
 use crate::handlers::AnalyticsHandler;
 use crate::tables::{
-    CheckpointEntry, DynamicFieldEntry, EventEntry, InputObjectKind, MoveCallEntry,
-    MovePackageEntry, ObjectEntry, ObjectStatus, OwnerType, TransactionEntry,
+    BalanceChangeEntry, CheckpointEntry, DynamicFieldEntry, EventEntry, InputObjectKind,
+    MoveCallEntry, MovePackageEntry, ObjectEntry, ObjectStatus, OwnerType, TransactionEntry,
     TransactionObjectEntry, WrappedObjectEntry,
 };
 use crate::writers::csv_writer::CSVWriter;
@@ -50,6 +52,7 @@ use crate::writers::AnalyticsWriter;
 
 pub mod analytics_metrics;
 pub mod analytics_processor;
+mod delta_log;
 pub mod errors;
 mod handlers;
 mod package_store;
@@ -67,6 +70,7 @@ const MOVE_PACKAGE_PREFIX: &str = "move_package";
 const DYNAMIC_FIELD_PREFIX: &str = "dynamic_field";
 
 const WRAPPED_OBJECT_PREFIX: &str = "wrapped_object";
+const BALANCE_CHANGE_PREFIX: &str = "balance_change";
 
 #[derive(Parser, Clone, Debug)]
 #[clap(
@@ -108,6 +112,9 @@ pub struct AnalyticsIndexerConfig {
     // File format to store data in i.e. csv, parquet, etc
     #[clap(long, value_enum, default_value = "csv", global = true)]
     pub file_format: FileFormat,
+    // Table format to commit uploaded files to i.e. delta. Requires the parquet file format
+    #[clap(long, value_enum, default_value = None, global = true)]
+    pub table_format: Option<TableFormat>,
     // Type of data to write i.e. checkpoint, object, transaction, etc
     #[clap(long, value_enum, long, global = true)]
     pub file_type: FileType,
@@ -303,6 +310,29 @@ impl FileFormat {
     }
 }
 
+/// Table formats that uploaded files can be committed to, so that each file type is exposed as a
+/// single table that only ever changes atomically, rather than as a set of loose files.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    strum_macros::Display,
+    ValueEnum,
+    Serialize,
+    Deserialize,
+    TryFromPrimitive,
+    IntoPrimitive,
+    EnumIter,
+)]
+#[repr(u8)]
+pub enum TableFormat {
+    /// Delta Lake table, with the transaction log kept in `_delta_log` under each file type's
+    /// directory.
+    DELTA = 0,
+}
+
 #[derive(
     Copy,
     Clone,
@@ -327,6 +357,7 @@ pub enum FileType {
     MovePackage,
     DynamicField,
     WrappedObject,
+    BalanceChange,
 }
 
 impl FileType {
@@ -341,6 +372,7 @@ impl FileType {
             FileType::MovePackage => Path::from(MOVE_PACKAGE_PREFIX),
             FileType::DynamicField => Path::from(DYNAMIC_FIELD_PREFIX),
             FileType::WrappedObject => Path::from(WRAPPED_OBJECT_PREFIX),
+            FileType::BalanceChange => Path::from(BALANCE_CHANGE_PREFIX),
         }
     }
 
@@ -359,6 +391,11 @@ impl FileType {
                 file_format.file_suffix()
             ))
     }
+
+    /// Root of the table that files of this type are committed to, when a table format is used.
+    pub fn table_root(&self, prefix: Option<Path>) -> Path {
+        join_paths(prefix, &self.dir_prefix())
+    }
 }
 
 pub enum ParquetValue {
@@ -484,6 +521,12 @@ impl FileMetadata {
             self.checkpoint_seq_range.clone(),
         )
     }
+
+    /// Path of the file relative to the root of its file type's table.
+    pub fn table_path(&self) -> String {
+        let dir_parts = self.file_type.dir_prefix().parts().count();
+        Path::from_iter(self.file_path().parts().skip(dir_parts)).to_string()
+    }
 }
 
 pub struct Processor {
@@ -857,6 +900,31 @@ pub async fn make_wrapped_object_processor(
     .await
 }
 
+pub async fn make_balance_change_processor(
+    config: AnalyticsIndexerConfig,
+    metrics: AnalyticsMetrics,
+) -> Result<Processor> {
+    let handler: Box<dyn AnalyticsHandler<BalanceChangeEntry>> =
+        Box::new(BalanceChangeHandler::new());
+    let starting_checkpoint_seq_num =
+        get_starting_checkpoint_seq_num(config.clone(), FileType::BalanceChange).await?;
+    let writer = make_writer::<BalanceChangeEntry>(
+        config.clone(),
+        FileType::BalanceChange,
+        starting_checkpoint_seq_num,
+    )?;
+    let max_checkpoint_reader = make_max_checkpoint_reader(&config).await?;
+    Processor::new::<BalanceChangeEntry>(
+        handler,
+        writer,
+        max_checkpoint_reader,
+        starting_checkpoint_seq_num,
+        metrics,
+        config,
+    )
+    .await
+}
+
 pub fn make_writer<S: Serialize + ParquetSchema>(
     config: AnalyticsIndexerConfig,
     file_type: FileType,
@@ -882,6 +950,14 @@ pub async fn get_starting_checkpoint_seq_num(
 ) -> Result<u64> {
     let checkpoint = if let Some(starting_checkpoint_seq_num) = config.starting_checkpoint_seq_num {
         starting_checkpoint_seq_num
+    } else if let Some(TableFormat::DELTA) = config.table_format {
+        // Files may have been uploaded without being committed to the table, in which case they
+        // need to be written again.
+        let delta_log = DeltaLog::new(
+            config.remote_store_config.make()?,
+            file_type.table_root(config.remote_store_path_prefix),
+        );
+        delta_log.next_checkpoint().await?.unwrap_or(0)
     } else {
         read_store_for_checkpoint(
             config.remote_store_config.clone(),
@@ -907,6 +983,7 @@ pub async fn make_analytics_processor(
         FileType::MovePackage => make_move_package_processor(config, metrics).await,
         FileType::DynamicField => make_dynamic_field_processor(config, metrics).await,
         FileType::WrappedObject => make_wrapped_object_processor(config, metrics).await,
+        FileType::BalanceChange => make_balance_change_processor(config, metrics).await,
     }
 }
 
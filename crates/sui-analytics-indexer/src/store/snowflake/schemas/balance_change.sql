@@ -0,0 +1,50 @@
+CREATE OR REPLACE TABLE BALANCE_CHANGE(
+                                          transaction_digest STRING NOT NULL,
+                                          checkpoint NUMBER(20, 0) NOT NULL,
+                                          epoch NUMBER(20, 0) NOT NULL,
+                                          timestamp_ms NUMBER(20, 0) NOT NULL,
+                                          owner_type STRING NOT NULL,
+                                          owner_address STRING,
+                                          coin_type STRING NOT NULL,
+                                          amount NUMBER(38, 0) NOT NULL
+) STAGE_FILE_FORMAT = parquet_format
+    STAGE_COPY_OPTIONS =
+(
+    ABORT_STATEMENT
+)
+    ENABLE_SCHEMA_EVOLUTION = TRUE
+    CLUSTER BY
+(
+    timestamp_ms
+);
+
+// Define the object stage
+CREATE OR REPLACE STAGE balance_change_parquet_stage
+    URL = '&{checkpoints_bucket}/balance_change/'
+    STORAGE_INTEGRATION = checkpoints_data_loader
+    FILE_FORMAT = parquet_format;
+
+// Set up the checkpoint auto ingestion pipe
+CREATE
+    OR REPLACE PIPE balance_change_pipe
+    AUTO_INGEST = true
+    INTEGRATION = 'CHECKPOINTS_DATA_LOADER_NOTIFICATION'
+    AS
+        copy into BALANCE_CHANGE (transaction_digest,
+                         checkpoint,
+                         epoch,
+                         timestamp_ms,
+                         owner_type,
+                         owner_address,
+                         coin_type,
+                         amount)
+            from (SELECT t.$1:transaction_digest     as transaction_digest,
+                         t.$1:checkpoint             as checkpoint,
+                         t.$1:epoch                  as epoch,
+                         t.$1:timestamp_ms           as timestamp_ms,
+                         t.$1:owner_type             as owner_type,
+                         t.$1:owner_address          as owner_address,
+                         t.$1:coin_type              as coin_type,
+                         t.$1:amount::NUMBER(38, 0)  as amount
+                  from @balance_change_parquet_stage (file_format => 'parquet_format', pattern => '.*[.]parquet') t)
+            file_format = parquet_format;
@@ -269,3 +269,21 @@ pub(crate) struct WrappedObjectEntry {
     pub(crate) json_path: String,
     pub(crate) struct_tag: Option<String>,
 }
+
+// Balance change of a single owner and coin type, caused by a transaction.
+// Computed the same way as the `balanceChanges` returned by the JSON-RPC API.
+#[derive(Serialize, Clone, SerializeParquet)]
+pub(crate) struct BalanceChangeEntry {
+    // indexes
+    pub(crate) transaction_digest: String,
+    pub(crate) checkpoint: u64,
+    pub(crate) epoch: u64,
+    pub(crate) timestamp_ms: u64,
+    // balance change info
+    pub(crate) owner_type: OwnerType,
+    pub(crate) owner_address: Option<String>,
+    pub(crate) coin_type: String,
+    // Changes can exceed the range of an i64 (they are i128 in the JSON-RPC API), so the
+    // amount is represented as a decimal string.
+    pub(crate) amount: String,
+}
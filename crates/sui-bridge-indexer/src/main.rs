@@ -14,7 +14,7 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 use sui_bridge::{
-    abi::{EthBridgeCommittee, EthSuiBridge},
+    abi::{EthBridgeCommittee, EthBridgeConfig, EthSuiBridge},
     eth_client::EthClient,
     eth_syncer::EthSyncer,
 };
@@ -25,6 +25,7 @@ use sui_bridge_indexer::{
 use sui_data_ingestion_core::{
     DataIngestionMetrics, FileProgressStore, IndexerExecutor, ReaderOptions, WorkerPool,
 };
+use sui_types::bridge::BridgeChainId;
 use tokio::sync::oneshot;
 use tracing::info;
 
@@ -88,6 +89,8 @@ async fn main() -> Result<()> {
     let vault_address: EthAddress = sui_bridge.vault().call().await?;
     let committee = EthBridgeCommittee::new(committee_address, provider.clone());
     let config_address: EthAddress = committee.config().call().await?;
+    let bridge_config = EthBridgeConfig::new(config_address, provider.clone());
+    let bridge_chain_id = BridgeChainId::try_from(bridge_config.chain_id().call().await?)?;
 
     let eth_client = Arc::new(
         EthClient::<ethers::providers::Http>::new(
@@ -99,6 +102,7 @@ async fn main() -> Result<()> {
                 limiter_address,
                 vault_address,
             ]),
+            bridge_chain_id,
         )
         .await?,
     );
@@ -31,7 +31,10 @@ use crate::{
     storage::BridgeOrchestratorTables,
     sui_client::{SuiClient, SuiClientInner},
     sui_transaction_builder::build_sui_transaction,
-    types::{BridgeAction, BridgeActionStatus, VerifiedCertifiedBridgeAction},
+    types::{
+        BridgeAction, BridgeActionLifecycleStatus, BridgeActionStatus,
+        VerifiedCertifiedBridgeAction,
+    },
 };
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -267,6 +270,7 @@ where
                     .unwrap_or_else(|e| {
                         panic!("Write to DB should not fail: {:?}", e);
                     });
+                record_action_status(store, action, BridgeActionLifecycleStatus::Finalized(None));
                 true
             }
             // Although theoretically a legit SuiToEthBridgeAction should not have
@@ -302,6 +306,11 @@ where
         }
         match auth_agg.request_committee_signatures(action.clone()).await {
             Ok(certificate) => {
+                record_action_status(
+                    &store,
+                    &action,
+                    BridgeActionLifecycleStatus::SignaturesCollected,
+                );
                 execution_queue_sender
                     .send(CertifiedBridgeActionExecutionWrapper(certificate, 0))
                     .await
@@ -316,6 +325,14 @@ where
                 // TODO: spawn a task for this
                 if attempt_times >= MAX_SIGNING_ATTEMPTS {
                     error!("Manual intervention is required. Failed to collect sigs for bridge action after {MAX_SIGNING_ATTEMPTS} attempts: {:?}", e);
+                    record_action_status(
+                        &store,
+                        &action,
+                        BridgeActionLifecycleStatus::Failed(format!(
+                            "Failed to collect signatures after {MAX_SIGNING_ATTEMPTS} attempts: {:?}",
+                            e
+                        )),
+                    );
                     return;
                 }
                 delay(attempt_times).await;
@@ -426,6 +443,14 @@ where
                 );
                 // This should not happen, but in case it does, we do not want to
                 // panic, instead we log here for manual intervention.
+                record_action_status(
+                    store,
+                    action,
+                    BridgeActionLifecycleStatus::Failed(format!(
+                        "Failed to build transaction: {:?}",
+                        err
+                    )),
+                );
                 return;
             }
         };
@@ -445,6 +470,11 @@ where
         }
 
         info!(?tx_digest, ?gas_object_ref, "Sending transaction to Sui");
+        record_action_status(
+            store,
+            action,
+            BridgeActionLifecycleStatus::Submitted(tx_digest),
+        );
         match sui_client
             .execute_transaction_block_with_effects(signed_tx)
             .await
@@ -462,6 +492,7 @@ where
                 );
                 metrics.err_sui_transaction_submission.inc();
                 let metrics_clone = metrics.clone();
+                let store_clone = store.clone();
                 // Do this in a separate task so we won't deadlock here
                 let sender_clone = execution_queue_sender.clone();
                 spawn_logged_monitored_task!(async move {
@@ -471,6 +502,14 @@ where
                         .inc();
                     if attempt_times >= MAX_EXECUTION_ATTEMPTS {
                         error!("Manual intervention is required. Failed to collect execute transaction for bridge action after {MAX_EXECUTION_ATTEMPTS} attempts: {:?}", err);
+                        record_action_status(
+                            &store_clone,
+                            certificate.data(),
+                            BridgeActionLifecycleStatus::Failed(format!(
+                                "Failed to execute transaction after {MAX_EXECUTION_ATTEMPTS} attempts: {:?}",
+                                err
+                            )),
+                        );
                         return;
                     }
                     delay(attempt_times).await;
@@ -522,7 +561,12 @@ where
                     .remove_pending_actions(&[action.digest()])
                     .unwrap_or_else(|e| {
                         panic!("Write to DB should not fail: {:?}", e);
-                    })
+                    });
+                record_action_status(
+                    store,
+                    action,
+                    BridgeActionLifecycleStatus::Finalized(Some(tx_digest)),
+                );
             }
             SuiExecutionStatus::Failure { error } => {
                 // In practice the transaction could fail because of running out of gas, but really
@@ -533,6 +577,13 @@ where
 
                 metrics.err_sui_transaction_execution.inc();
                 error!(?tx_digest, "Manual intervention is needed. Sui transaction executed and failed with error: {error:?}");
+                record_action_status(
+                    store,
+                    action,
+                    BridgeActionLifecycleStatus::Failed(format!(
+                        "Sui transaction {tx_digest} failed: {error}"
+                    )),
+                );
             }
         }
     }
@@ -560,6 +611,18 @@ where
     }
 }
 
+fn record_action_status(
+    store: &BridgeOrchestratorTables,
+    action: &BridgeAction,
+    status: BridgeActionLifecycleStatus,
+) {
+    store
+        .record_action_status(action, status)
+        .unwrap_or_else(|e| {
+            panic!("Write to DB should not fail: {:?}", e);
+        });
+}
+
 pub async fn submit_to_executor(
     tx: &mysten_metrics::metered_channel::Sender<BridgeActionExecutionWrapper>,
     action: BridgeAction,
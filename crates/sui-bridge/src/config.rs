@@ -18,6 +18,7 @@ use std::collections::HashSet;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 use sui_config::Config;
 use sui_json_rpc_types::Coin;
 use sui_keys::keypair_file::read_key;
@@ -58,6 +59,11 @@ pub struct EthConfig {
     /// reprocess the events from this block number every time it starts.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub eth_contracts_start_block_override: Option<u64>,
+    /// When set, blocks this many blocks below the chain head are considered finalized,
+    /// instead of the block returned for the `finalized` tag. Useful for EVM chains that
+    /// don't support the `finalized` tag.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eth_finality_depth: Option<u64>,
 }
 
 #[serde_as]
@@ -111,24 +117,48 @@ pub struct BridgeNodeConfig {
     pub sui: SuiConfig,
     /// Eth configuration
     pub eth: EthConfig,
+    /// Other EVM chains the bridge is deployed on, that the node watches and signs for in
+    /// addition to `eth`. Each of them needs a distinct `eth-bridge-chain-id`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_evm_chains: Vec<EthConfig>,
+    /// Number of days the audit trails of finalized actions are kept for, after their last
+    /// update. When not set, trails are kept forever and grow with every action the client
+    /// processes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit_trail_retention_days: Option<u64>,
 }
 
 impl Config for BridgeNodeConfig {}
 
 impl BridgeNodeConfig {
+    /// All EVM chains the node watches and signs for, `eth` first.
+    pub fn evm_chains(&self) -> impl Iterator<Item = &EthConfig> {
+        std::iter::once(&self.eth).chain(self.additional_evm_chains.iter())
+    }
+
     pub async fn validate(
         &self,
     ) -> anyhow::Result<(BridgeServerConfig, Option<BridgeClientConfig>)> {
-        if !is_route_valid(
-            BridgeChainId::try_from(self.sui.sui_bridge_chain_id)?,
-            BridgeChainId::try_from(self.eth.eth_bridge_chain_id)?,
-        ) {
-            return Err(anyhow!(
-                "Route between Sui chain id {} and Eth chain id {} is not valid",
-                self.sui.sui_bridge_chain_id,
-                self.eth.eth_bridge_chain_id,
-            ));
-        };
+        let sui_chain_id = BridgeChainId::try_from(self.sui.sui_bridge_chain_id)?;
+        let mut eth_chain_ids = HashSet::new();
+        for eth in self.evm_chains() {
+            if !is_route_valid(
+                sui_chain_id,
+                BridgeChainId::try_from(eth.eth_bridge_chain_id)?,
+            ) {
+                return Err(anyhow!(
+                    "Route between Sui chain id {} and Eth chain id {} is not valid",
+                    self.sui.sui_bridge_chain_id,
+                    eth.eth_bridge_chain_id,
+                ));
+            };
+            if !eth_chain_ids.insert(eth.eth_bridge_chain_id) {
+                return Err(anyhow!(
+                    "Eth chain id {} is configured more than once",
+                    eth.eth_bridge_chain_id,
+                ));
+            }
+        }
 
         let bridge_authority_key = match read_key(&self.bridge_authority_key_path, true)? {
             SuiKeyPair::Secp256k1(key) => key,
@@ -148,7 +178,11 @@ impl BridgeNodeConfig {
             ));
         }
 
-        let (eth_client, eth_contracts) = self.prepare_for_eth().await?;
+        let mut evm_chains = vec![];
+        for eth in self.evm_chains() {
+            let (eth_client, eth_contracts) = self.prepare_for_eth(eth).await?;
+            evm_chains.push((eth, eth_client, eth_contracts));
+        }
         let bridge_summary = sui_client
             .get_bridge_summary()
             .await
@@ -177,7 +211,10 @@ impl BridgeNodeConfig {
             metrics_port: self.metrics_port,
             server_listen_port: self.server_listen_port,
             sui_client: sui_client.clone(),
-            eth_client: eth_client.clone(),
+            eth_clients: evm_chains
+                .iter()
+                .map(|(_, eth_client, _)| eth_client.clone())
+                .collect(),
             approved_governance_actions,
         };
         if !self.run_client {
@@ -199,18 +236,25 @@ impl BridgeNodeConfig {
             gas_object_ref,
             metrics_port: self.metrics_port,
             sui_client: sui_client.clone(),
-            eth_client: eth_client.clone(),
             db_path,
-            eth_contracts,
-            // in `prepare_for_eth` we check if this is None when `run_client` is true. Safe to unwrap here.
-            eth_contracts_start_block_fallback: self
-                .eth
-                .eth_contracts_start_block_fallback
-                .unwrap(),
-            eth_contracts_start_block_override: self.eth.eth_contracts_start_block_override,
+            evm_chains: evm_chains
+                .into_iter()
+                .map(|(eth, eth_client, eth_contracts)| EvmChainClientConfig {
+                    eth_client,
+                    eth_contracts,
+                    // in `prepare_for_eth` we check if this is None when `run_client` is true. Safe to unwrap here.
+                    eth_contracts_start_block_fallback: eth
+                        .eth_contracts_start_block_fallback
+                        .unwrap(),
+                    eth_contracts_start_block_override: eth.eth_contracts_start_block_override,
+                })
+                .collect(),
             sui_bridge_module_last_processed_event_id_override: self
                 .sui
                 .sui_bridge_module_last_processed_event_id_override,
+            audit_trail_retention: self
+                .audit_trail_retention_days
+                .map(|days| Duration::from_secs(days * 24 * 60 * 60)),
         };
 
         Ok((bridge_server_config, Some(bridge_client_config)))
@@ -218,10 +262,11 @@ impl BridgeNodeConfig {
 
     async fn prepare_for_eth(
         &self,
+        eth: &EthConfig,
     ) -> anyhow::Result<(Arc<EthClient<ethers::providers::Http>>, Vec<EthAddress>)> {
-        let bridge_proxy_address = EthAddress::from_str(&self.eth.eth_bridge_proxy_address)?;
+        let bridge_proxy_address = EthAddress::from_str(&eth.eth_bridge_proxy_address)?;
         let provider = Arc::new(
-            ethers::prelude::Provider::<ethers::providers::Http>::try_from(&self.eth.eth_rpc_url)
+            ethers::prelude::Provider::<ethers::providers::Http>::try_from(&eth.eth_rpc_url)
                 .unwrap()
                 .interval(std::time::Duration::from_millis(2000)),
         );
@@ -230,7 +275,7 @@ impl BridgeNodeConfig {
             get_eth_contract_addresses(bridge_proxy_address, &provider).await?;
         let config = EthBridgeConfig::new(config_address, provider.clone());
 
-        if self.run_client && self.eth.eth_contracts_start_block_fallback.is_none() {
+        if self.run_client && eth.eth_contracts_start_block_fallback.is_none() {
             return Err(anyhow!(
                 "eth_contracts_start_block_fallback is required when run_client is true"
             ));
@@ -239,10 +284,10 @@ impl BridgeNodeConfig {
         // If bridge chain id is Eth Mainent or Sepolia, we expect to see chain
         // identifier to match accordingly.
         let bridge_chain_id: u8 = config.chain_id().call().await?;
-        if self.eth.eth_bridge_chain_id != bridge_chain_id {
+        if eth.eth_bridge_chain_id != bridge_chain_id {
             return Err(anyhow!(
                 "Bridge chain id mismatch: expected {}, but connected to {}",
-                self.eth.eth_bridge_chain_id,
+                eth.eth_bridge_chain_id,
                 bridge_chain_id
             ));
         }
@@ -266,7 +311,7 @@ impl BridgeNodeConfig {
 
         let eth_client = Arc::new(
             EthClient::<ethers::providers::Http>::new(
-                &self.eth.eth_rpc_url,
+                &eth.eth_rpc_url,
                 HashSet::from_iter(vec![
                     bridge_proxy_address,
                     committee_address,
@@ -274,8 +319,10 @@ impl BridgeNodeConfig {
                     limiter_address,
                     vault_address,
                 ]),
+                BridgeChainId::try_from(bridge_chain_id)?,
             )
-            .await?,
+            .await?
+            .with_finality_depth(eth.eth_finality_depth),
         );
         let contract_addresses = vec![
             bridge_proxy_address,
@@ -362,7 +409,8 @@ pub struct BridgeServerConfig {
     pub server_listen_port: u16,
     pub metrics_port: u16,
     pub sui_client: Arc<SuiClient<SuiSdkClient>>,
-    pub eth_client: Arc<EthClient<ethers::providers::Http>>,
+    /// Clients of all EVM chains the node signs for.
+    pub eth_clients: Vec<Arc<EthClient<ethers::providers::Http>>>,
     /// A list of approved governance actions. Action in this list will be signed when requested by client.
     pub approved_governance_actions: Vec<BridgeAction>,
 }
@@ -374,13 +422,20 @@ pub struct BridgeClientConfig {
     pub gas_object_ref: ObjectRef,
     pub metrics_port: u16,
     pub sui_client: Arc<SuiClient<SuiSdkClient>>,
-    pub eth_client: Arc<EthClient<ethers::providers::Http>>,
     pub db_path: PathBuf,
+    /// All EVM chains the client watches.
+    pub evm_chains: Vec<EvmChainClientConfig>,
+    pub sui_bridge_module_last_processed_event_id_override: Option<EventID>,
+    /// How long the audit trails of finalized actions are kept for, forever if `None`.
+    pub audit_trail_retention: Option<Duration>,
+}
+
+pub struct EvmChainClientConfig {
+    pub eth_client: Arc<EthClient<ethers::providers::Http>>,
     pub eth_contracts: Vec<EthAddress>,
-    // See `BridgeNodeConfig` for the explanation of following two fields.
+    // See `EthConfig` for the explanation of following two fields.
     pub eth_contracts_start_block_fallback: u64,
     pub eth_contracts_start_block_override: Option<u64>,
-    pub sui_bridge_module_last_processed_event_id_override: Option<EventID>,
 }
 
 #[serde_as]
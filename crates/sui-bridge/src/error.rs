@@ -67,6 +67,8 @@ pub enum BridgeError {
     StorageError(String),
     // Rest API Error
     RestAPIError(String),
+    // No audit trail is recorded for the requested action
+    ActionNotFound,
     // Uncategorized error
     Generic(String),
 }
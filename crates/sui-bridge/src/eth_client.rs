@@ -5,10 +5,13 @@ use std::collections::HashSet;
 
 use crate::abi::EthBridgeEvent;
 use crate::error::{BridgeError, BridgeResult};
+use crate::evm_chain::EvmChainClient;
 use crate::types::{BridgeAction, EthLog};
+use async_trait::async_trait;
 use ethers::providers::{Http, JsonRpcClient, Middleware, Provider};
 use ethers::types::TxHash;
 use ethers::types::{Block, Filter};
+use sui_types::bridge::BridgeChainId;
 use tap::TapFallible;
 
 #[cfg(test)]
@@ -17,17 +20,24 @@ use ethers::types::Address as EthAddress;
 pub struct EthClient<P> {
     provider: Provider<P>,
     contract_addresses: HashSet<EthAddress>,
+    bridge_chain_id: BridgeChainId,
+    // When set, blocks this deep below the chain head are considered final, for chains
+    // that do not support the `finalized` block tag.
+    finality_depth: Option<u64>,
 }
 
 impl EthClient<Http> {
     pub async fn new(
         provider_url: &str,
         contract_addresses: HashSet<EthAddress>,
+        bridge_chain_id: BridgeChainId,
     ) -> anyhow::Result<Self> {
         let provider = Provider::try_from(provider_url)?;
         let self_ = Self {
             provider,
             contract_addresses,
+            bridge_chain_id,
+            finality_depth: None,
         };
         self_.describe().await?;
         Ok(self_)
@@ -37,10 +47,20 @@ impl EthClient<Http> {
 #[cfg(test)]
 impl EthClient<EthMockProvider> {
     pub fn new_mocked(provider: EthMockProvider, contract_addresses: HashSet<EthAddress>) -> Self {
+        Self::new_mocked_for_chain(provider, contract_addresses, BridgeChainId::EthCustom)
+    }
+
+    pub fn new_mocked_for_chain(
+        provider: EthMockProvider,
+        contract_addresses: HashSet<EthAddress>,
+        bridge_chain_id: BridgeChainId,
+    ) -> Self {
         let provider = Provider::new(provider);
         Self {
             provider,
             contract_addresses,
+            bridge_chain_id,
+            finality_depth: None,
         }
     }
 }
@@ -49,12 +69,20 @@ impl<P> EthClient<P>
 where
     P: JsonRpcClient,
 {
+    /// Consider blocks `finality_depth` below the chain head final, instead of relying on the
+    /// `finalized` block tag.
+    pub fn with_finality_depth(mut self, finality_depth: Option<u64>) -> Self {
+        self.finality_depth = finality_depth;
+        self
+    }
+
     // TODO assert chain identifier
     async fn describe(&self) -> anyhow::Result<()> {
         let chain_id = self.provider.get_chainid().await?;
         let block_number = self.provider.get_block_number().await?;
         tracing::info!(
-            "EthClient is connected to chain {chain_id}, current block number: {block_number}"
+            "EthClient is connected to chain {chain_id} (bridge chain id {:?}), current block number: {block_number}",
+            self.bridge_chain_id
         );
         Ok(())
     }
@@ -104,6 +132,14 @@ where
     }
 
     pub async fn get_last_finalized_block_id(&self) -> BridgeResult<u64> {
+        if let Some(finality_depth) = self.finality_depth {
+            let block_number = self
+                .provider
+                .get_block_number()
+                .await
+                .map_err(BridgeError::from)?;
+            return Ok(block_number.as_u64().saturating_sub(finality_depth));
+        }
         let block: Result<Option<Block<ethers::types::TxHash>>, ethers::prelude::ProviderError> =
             self.provider
                 .request("eth_getBlockByNumber", ("finalized", false))
@@ -225,6 +261,41 @@ where
     }
 }
 
+#[async_trait]
+impl<P> EvmChainClient for EthClient<P>
+where
+    P: JsonRpcClient + 'static,
+{
+    fn bridge_chain_id(&self) -> BridgeChainId {
+        self.bridge_chain_id
+    }
+
+    fn contract_addresses(&self) -> &HashSet<EthAddress> {
+        &self.contract_addresses
+    }
+
+    async fn get_last_finalized_block_id(&self) -> BridgeResult<u64> {
+        EthClient::get_last_finalized_block_id(self).await
+    }
+
+    async fn get_events_in_range(
+        &self,
+        address: EthAddress,
+        start_block: u64,
+        end_block: u64,
+    ) -> BridgeResult<Vec<EthLog>> {
+        EthClient::get_events_in_range(self, address, start_block, end_block).await
+    }
+
+    async fn get_finalized_bridge_action_maybe(
+        &self,
+        tx_hash: TxHash,
+        event_idx: u16,
+    ) -> BridgeResult<BridgeAction> {
+        EthClient::get_finalized_bridge_action_maybe(self, tx_hash, event_idx).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use ethers::types::{Address as EthAddress, Log, TransactionReceipt, U64};
@@ -371,4 +442,32 @@ mod tests {
             .unwrap();
         assert_eq!(action, bridge_action);
     }
+
+    #[tokio::test]
+    async fn test_last_finalized_block_with_finality_depth() {
+        telemetry_subscribers::init_for_testing();
+        let registry = Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        let mock_provider = EthMockProvider::new();
+        mock_last_finalized_block(&mock_provider, 777);
+        mock_provider
+            .add_response("eth_blockNumber", (), U64::from(1000))
+            .unwrap();
+
+        let client = EthClient::new_mocked_for_chain(
+            mock_provider.clone(),
+            HashSet::from_iter(vec![EthAddress::zero()]),
+            BridgeChainId::EthSepolia,
+        );
+        assert_eq!(client.bridge_chain_id(), BridgeChainId::EthSepolia);
+        // Without a finality depth, the `finalized` block tag is used
+        assert_eq!(client.get_last_finalized_block_id().await.unwrap(), 777);
+
+        let client = client.with_finality_depth(Some(64));
+        assert_eq!(client.get_last_finalized_block_id().await.unwrap(), 936);
+
+        // Finality depth larger than the chain height
+        let client = client.with_finality_depth(Some(2000));
+        assert_eq!(client.get_last_finalized_block_id().await.unwrap(), 0);
+    }
 }
@@ -5,9 +5,11 @@
 //! concerned contracts. Each contract is associated with a start block number, and the syncer will
 //! only query from that block number onwards. The syncer also keeps track of the last finalized
 //! block on Ethereum and will only query for events up to that block number.
+//! The syncer works with any EVM chain through `EvmChainClient`; a bridge node runs one syncer
+//! per chain.
 
 use crate::error::BridgeResult;
-use crate::eth_client::EthClient;
+use crate::evm_chain::EvmChainClient;
 use crate::retry_with_max_elapsed_time;
 use crate::types::EthLog;
 use ethers::types::Address as EthAddress;
@@ -23,8 +25,8 @@ const ETH_LOG_QUERY_MAX_BLOCK_RANGE: u64 = 1000;
 const ETH_EVENTS_CHANNEL_SIZE: usize = 1000;
 const FINALIZED_BLOCK_QUERY_INTERVAL: Duration = Duration::from_secs(2);
 
-pub struct EthSyncer<P> {
-    eth_client: Arc<EthClient<P>>,
+pub struct EthSyncer<C> {
+    eth_client: Arc<C>,
     contract_addresses: EthTargetAddresses,
 }
 
@@ -32,11 +34,11 @@ pub struct EthSyncer<P> {
 pub type EthTargetAddresses = HashMap<EthAddress, u64>;
 
 #[allow(clippy::new_without_default)]
-impl<P> EthSyncer<P>
+impl<C> EthSyncer<C>
 where
-    P: ethers::providers::JsonRpcClient + 'static,
+    C: EvmChainClient + 'static,
 {
-    pub fn new(eth_client: Arc<EthClient<P>>, contract_addresses: EthTargetAddresses) -> Self {
+    pub fn new(eth_client: Arc<C>, contract_addresses: EthTargetAddresses) -> Self {
         Self {
             eth_client,
             contract_addresses,
@@ -84,9 +86,12 @@ where
 
     async fn run_finalized_block_refresh_task(
         last_finalized_block_sender: watch::Sender<u64>,
-        eth_client: Arc<EthClient<P>>,
+        eth_client: Arc<C>,
     ) {
-        tracing::info!("Starting finalized block refresh task.");
+        tracing::info!(
+            "Starting finalized block refresh task for chain {:?}.",
+            eth_client.bridge_chain_id()
+        );
         let mut last_block_number = 0;
         let mut interval = time::interval(FINALIZED_BLOCK_QUERY_INTERVAL);
         interval.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
@@ -119,7 +124,7 @@ where
         mut start_block: u64,
         mut last_finalized_block_receiver: watch::Receiver<u64>,
         events_sender: mysten_metrics::metered_channel::Sender<(EthAddress, u64, Vec<EthLog>)>,
-        eth_client: Arc<EthClient<P>>,
+        eth_client: Arc<C>,
     ) {
         tracing::info!(contract_address=?contract_address, "Starting eth events listening task from block {start_block}");
         let mut more_blocks = false;
@@ -188,6 +193,7 @@ mod tests {
     use tokio::sync::mpsc::error::TryRecvError;
 
     use crate::{
+        eth_client::EthClient,
         eth_mock_provider::EthMockProvider,
         test_utils::{mock_get_logs, mock_last_finalized_block},
     };
@@ -0,0 +1,44 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The EVM side of the bridge. A bridge node may watch and sign for several EVM chains, each
+//! reached through an `EvmChainClient`. `EthClient` is the implementation for any chain speaking
+//! the Ethereum JSON-RPC API.
+
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use ethers::types::{Address as EthAddress, TxHash};
+use sui_types::bridge::BridgeChainId;
+
+use crate::error::BridgeResult;
+use crate::types::{BridgeAction, EthLog};
+
+#[async_trait]
+pub trait EvmChainClient: Send + Sync {
+    /// The BridgeChainId of the chain this client talks to.
+    fn bridge_chain_id(&self) -> BridgeChainId;
+
+    /// The bridge contracts deployed on the chain. Events from other contracts are ignored.
+    fn contract_addresses(&self) -> &HashSet<EthAddress>;
+
+    /// The last block that is considered final on the chain. Blocks up to and including it
+    /// are never reorged.
+    async fn get_last_finalized_block_id(&self) -> BridgeResult<u64>;
+
+    /// Events emitted by `address` between `start_block` and `end_block`, both inclusive.
+    async fn get_events_in_range(
+        &self,
+        address: EthAddress,
+        start_block: u64,
+        end_block: u64,
+    ) -> BridgeResult<Vec<EthLog>>;
+
+    /// Returns the BridgeAction of the `event_idx`th event of transaction `tx_hash`, if the
+    /// transaction is finalized and the event is an actionable bridge event.
+    async fn get_finalized_bridge_action_maybe(
+        &self,
+        tx_hash: TxHash,
+        event_idx: u16,
+    ) -> BridgeResult<BridgeAction>;
+}
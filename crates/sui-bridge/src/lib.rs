@@ -12,6 +12,7 @@ pub mod eth_client;
 pub mod eth_syncer;
 pub mod eth_transaction_builder;
 pub mod events;
+pub mod evm_chain;
 pub mod metrics;
 pub mod node;
 pub mod orchestrator;
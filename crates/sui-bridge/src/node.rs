@@ -7,6 +7,7 @@ use crate::{
     config::{BridgeClientConfig, BridgeNodeConfig},
     eth_syncer::EthSyncer,
     events::init_all_struct_tags,
+    evm_chain::EvmChainClient,
     metrics::BridgeMetrics,
     orchestrator::BridgeOrchestrator,
     server::{handler::BridgeRequestHandler, run_server, BridgeNodePublicMetadata},
@@ -18,15 +19,15 @@ use std::{
     collections::HashMap,
     net::{IpAddr, Ipv4Addr, SocketAddr},
     sync::Arc,
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use sui_types::{
-    bridge::{BRIDGE_COMMITTEE_MODULE_NAME, BRIDGE_MODULE_NAME},
+    bridge::{BridgeChainId, BRIDGE_COMMITTEE_MODULE_NAME, BRIDGE_MODULE_NAME},
     event::EventID,
     Identifier,
 };
 use tokio::task::JoinHandle;
-use tracing::info;
+use tracing::{info, warn};
 
 pub async fn run_bridge_node(
     config: BridgeNodeConfig,
@@ -38,11 +39,14 @@ pub async fn run_bridge_node(
     let (server_config, client_config) = config.validate().await?;
 
     // Start Client
-    let _handles = if let Some(client_config) = client_config {
-        start_client_components(client_config, metrics.clone()).await
+    let (_handles, store) = if let Some(client_config) = client_config {
+        let store = BridgeOrchestratorTables::new(&client_config.db_path.join("client"));
+        let handles =
+            start_client_components(client_config, store.clone(), metrics.clone()).await?;
+        (handles, Some(store))
     } else {
-        Ok(vec![])
-    }?;
+        (vec![], None)
+    };
 
     // Start Server
     let socket_address = SocketAddr::new(
@@ -54,8 +58,13 @@ pub async fn run_bridge_node(
         BridgeRequestHandler::new(
             server_config.key,
             server_config.sui_client,
-            server_config.eth_client,
+            server_config
+                .eth_clients
+                .into_iter()
+                .map(|eth_client| eth_client as Arc<dyn EvmChainClient>)
+                .collect(),
             server_config.approved_governance_actions,
+            store,
         ),
         metrics,
         Arc::new(metadata),
@@ -65,30 +74,51 @@ pub async fn run_bridge_node(
 // TODO: is there a way to clean up the overrides after it's stored in DB?
 async fn start_client_components(
     client_config: BridgeClientConfig,
+    store: Arc<BridgeOrchestratorTables>,
     metrics: Arc<BridgeMetrics>,
 ) -> anyhow::Result<Vec<JoinHandle<()>>> {
-    let store: std::sync::Arc<BridgeOrchestratorTables> =
-        BridgeOrchestratorTables::new(&client_config.db_path.join("client"));
     let sui_modules_to_watch = get_sui_modules_to_watch(
         &store,
         client_config.sui_bridge_module_last_processed_event_id_override,
     );
-    let eth_contracts_to_watch = get_eth_contracts_to_watch(
-        &store,
-        &client_config.eth_contracts,
-        client_config.eth_contracts_start_block_fallback,
-        client_config.eth_contracts_start_block_override,
-    );
 
     let sui_client = client_config.sui_client.clone();
 
+    // Cursors written before cursors were kept per EVM chain belong to the primary eth chain,
+    // which is always the first one.
+    if let Some(primary_chain) = client_config.evm_chains.first() {
+        let migrated = store
+            .migrate_legacy_eth_event_cursors(primary_chain.eth_client.bridge_chain_id())
+            .expect("Failed to migrate legacy eth event cursors");
+        if migrated > 0 {
+            info!("Migrated {} legacy eth event cursors", migrated);
+        }
+    }
+
     let mut all_handles = vec![];
-    let (task_handles, eth_events_rx, _) =
-        EthSyncer::new(client_config.eth_client.clone(), eth_contracts_to_watch)
-            .run()
-            .await
-            .expect("Failed to start eth syncer");
-    all_handles.extend(task_handles);
+    if let Some(retention) = client_config.audit_trail_retention {
+        all_handles.push(spawn_audit_trail_pruner(store.clone(), retention));
+    }
+
+    // One syncer per EVM chain
+    let mut eth_events_rxs = vec![];
+    for evm_chain in client_config.evm_chains {
+        let chain_id = evm_chain.eth_client.bridge_chain_id();
+        let eth_contracts_to_watch = get_eth_contracts_to_watch(
+            &store,
+            chain_id,
+            &evm_chain.eth_contracts,
+            evm_chain.eth_contracts_start_block_fallback,
+            evm_chain.eth_contracts_start_block_override,
+        );
+        let (task_handles, eth_events_rx, _) =
+            EthSyncer::new(evm_chain.eth_client, eth_contracts_to_watch)
+                .run()
+                .await
+                .expect("Failed to start eth syncer");
+        all_handles.extend(task_handles);
+        eth_events_rxs.push((chain_id, eth_events_rx));
+    }
 
     let (task_handles, sui_events_rx) =
         SuiSyncer::new(client_config.sui_client, sui_modules_to_watch)
@@ -119,7 +149,7 @@ async fn start_client_components(
     let orchestrator = BridgeOrchestrator::new(
         sui_client,
         sui_events_rx,
-        eth_events_rx,
+        eth_events_rxs,
         store.clone(),
         metrics,
     );
@@ -128,6 +158,28 @@ async fn start_client_components(
     Ok(all_handles)
 }
 
+/// Periodically prune the audit trails of actions finalized more than `retention` ago.
+fn spawn_audit_trail_pruner(
+    store: Arc<BridgeOrchestratorTables>,
+    retention: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60 * 60));
+        loop {
+            interval.tick().await;
+            let cutoff_ms = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .saturating_sub(retention)
+                .as_millis() as u64;
+            match store.prune_action_audit_trails(cutoff_ms) {
+                Ok(pruned) => info!("Pruned {} action audit trails", pruned),
+                Err(e) => warn!("Failed to prune action audit trails: {:?}", e),
+            }
+        }
+    })
+}
+
 fn get_sui_modules_to_watch(
     store: &std::sync::Arc<BridgeOrchestratorTables>,
     sui_bridge_module_last_processed_event_id_override: Option<EventID>,
@@ -166,12 +218,13 @@ fn get_sui_modules_to_watch(
 
 fn get_eth_contracts_to_watch(
     store: &std::sync::Arc<BridgeOrchestratorTables>,
+    chain_id: BridgeChainId,
     eth_contracts: &[EthAddress],
     eth_contracts_start_block_fallback: u64,
     eth_contracts_start_block_override: Option<u64>,
 ) -> HashMap<EthAddress, u64> {
     let stored_eth_cursors = store
-        .get_eth_event_cursors(eth_contracts)
+        .get_eth_event_cursors(chain_id, eth_contracts)
         .expect("Failed to get eth event cursors from storage");
     let mut eth_contracts_to_watch = HashMap::new();
     for (contract, stored_cursor) in eth_contracts.iter().zip(stored_eth_cursors) {
@@ -181,8 +234,8 @@ fn get_eth_contracts_to_watch(
             (Some(override_), _) => {
                 eth_contracts_to_watch.insert(*contract, override_);
                 info!(
-                    "Overriding cursor for eth bridge contract {} to {}. Stored cursor: {:?}",
-                    contract, override_, stored_cursor
+                    "Overriding cursor for eth bridge contract {} on chain {:?} to {}. Stored cursor: {:?}",
+                    contract, chain_id, override_, stored_cursor
                 );
             }
             (None, Some(stored_cursor)) => {
@@ -233,7 +286,8 @@ mod tests {
         let store = BridgeOrchestratorTables::new(temp_dir.path());
 
         // No override, no watermark found in DB, use fallback
-        let contracts = get_eth_contracts_to_watch(&store, &eth_contracts, 10, None);
+        let contracts =
+            get_eth_contracts_to_watch(&store, BridgeChainId::EthCustom, &eth_contracts, 10, None);
         assert_eq!(
             contracts,
             vec![(eth_contracts[0], 10), (eth_contracts[1], 10)]
@@ -242,7 +296,13 @@ mod tests {
         );
 
         // no watermark found in DB, use override
-        let contracts = get_eth_contracts_to_watch(&store, &eth_contracts, 10, Some(420));
+        let contracts = get_eth_contracts_to_watch(
+            &store,
+            BridgeChainId::EthCustom,
+            &eth_contracts,
+            10,
+            Some(420),
+        );
         assert_eq!(
             contracts,
             vec![(eth_contracts[0], 420), (eth_contracts[1], 420)]
@@ -251,14 +311,15 @@ mod tests {
         );
 
         store
-            .update_eth_event_cursor(eth_contracts[0], 100)
+            .update_eth_event_cursor(BridgeChainId::EthCustom, eth_contracts[0], 100)
             .unwrap();
         store
-            .update_eth_event_cursor(eth_contracts[1], 102)
+            .update_eth_event_cursor(BridgeChainId::EthCustom, eth_contracts[1], 102)
             .unwrap();
 
         // No override, found watermarks in DB, use +1
-        let contracts = get_eth_contracts_to_watch(&store, &eth_contracts, 10, None);
+        let contracts =
+            get_eth_contracts_to_watch(&store, BridgeChainId::EthCustom, &eth_contracts, 10, None);
         assert_eq!(
             contracts,
             vec![(eth_contracts[0], 101), (eth_contracts[1], 103)]
@@ -267,7 +328,13 @@ mod tests {
         );
 
         // use override
-        let contracts = get_eth_contracts_to_watch(&store, &eth_contracts, 10, Some(200));
+        let contracts = get_eth_contracts_to_watch(
+            &store,
+            BridgeChainId::EthCustom,
+            &eth_contracts,
+            10,
+            Some(200),
+        );
         assert_eq!(
             contracts,
             vec![(eth_contracts[0], 200), (eth_contracts[1], 200)]
@@ -382,7 +449,10 @@ mod tests {
                 eth_bridge_chain_id: BridgeChainId::EthCustom as u8,
                 eth_contracts_start_block_fallback: None,
                 eth_contracts_start_block_override: None,
+                eth_finality_depth: None,
             },
+            additional_evm_chains: vec![],
+            audit_trail_retention_days: None,
             approved_governance_actions: vec![],
             run_client: false,
             db_path: None,
@@ -445,7 +515,10 @@ mod tests {
                 eth_bridge_chain_id: BridgeChainId::EthCustom as u8,
                 eth_contracts_start_block_fallback: Some(0),
                 eth_contracts_start_block_override: None,
+                eth_finality_depth: None,
             },
+            additional_evm_chains: vec![],
+            audit_trail_retention_days: None,
             approved_governance_actions: vec![],
             run_client: true,
             db_path: Some(db_path),
@@ -519,7 +592,10 @@ mod tests {
                 eth_bridge_chain_id: BridgeChainId::EthCustom as u8,
                 eth_contracts_start_block_fallback: Some(0),
                 eth_contracts_start_block_override: Some(0),
+                eth_finality_depth: None,
             },
+            additional_evm_chains: vec![],
+            audit_trail_retention_days: None,
             approved_governance_actions: vec![],
             run_client: true,
             db_path: Some(db_path),
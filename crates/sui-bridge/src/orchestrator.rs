@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! `BridgeOrchestrator` is the component that:
-//! 1. monitors Sui and Ethereum events with the help of `SuiSyncer` and `EthSyncer`
+//! 1. monitors Sui and EVM chain events with the help of `SuiSyncer` and `EthSyncer`
 //! 2. updates WAL table and cursor tables
 //! 2. hands actions to `BridgeExecutor` for execution
 
@@ -20,6 +20,7 @@ use ethers::types::Address as EthAddress;
 use mysten_metrics::spawn_logged_monitored_task;
 use std::sync::Arc;
 use sui_json_rpc_types::SuiEvent;
+use sui_types::bridge::BridgeChainId;
 use sui_types::Identifier;
 use tokio::task::JoinHandle;
 use tracing::{error, info};
@@ -27,7 +28,11 @@ use tracing::{error, info};
 pub struct BridgeOrchestrator<C> {
     _sui_client: Arc<SuiClient<C>>,
     sui_events_rx: mysten_metrics::metered_channel::Receiver<(Identifier, Vec<SuiEvent>)>,
-    eth_events_rx: mysten_metrics::metered_channel::Receiver<(EthAddress, u64, Vec<EthLog>)>,
+    // One receiver per EVM chain, fed by the `EthSyncer` of that chain.
+    eth_events_rxs: Vec<(
+        BridgeChainId,
+        mysten_metrics::metered_channel::Receiver<(EthAddress, u64, Vec<EthLog>)>,
+    )>,
     store: Arc<BridgeOrchestratorTables>,
     metrics: Arc<BridgeMetrics>,
 }
@@ -39,14 +44,17 @@ where
     pub fn new(
         sui_client: Arc<SuiClient<C>>,
         sui_events_rx: mysten_metrics::metered_channel::Receiver<(Identifier, Vec<SuiEvent>)>,
-        eth_events_rx: mysten_metrics::metered_channel::Receiver<(EthAddress, u64, Vec<EthLog>)>,
+        eth_events_rxs: Vec<(
+            BridgeChainId,
+            mysten_metrics::metered_channel::Receiver<(EthAddress, u64, Vec<EthLog>)>,
+        )>,
         store: Arc<BridgeOrchestratorTables>,
         metrics: Arc<BridgeMetrics>,
     ) -> Self {
         Self {
             _sui_client: sui_client,
             sui_events_rx,
-            eth_events_rx,
+            eth_events_rxs,
             store,
             metrics,
         }
@@ -84,13 +92,18 @@ where
                 .expect("Submit to executor should not fail");
         }
 
-        let metrics_clone = self.metrics.clone();
-        task_handles.push(spawn_logged_monitored_task!(Self::run_eth_watcher(
-            store_clone,
-            executor_sender,
-            self.eth_events_rx,
-            metrics_clone,
-        )));
+        for (chain_id, eth_events_rx) in self.eth_events_rxs {
+            let store_clone = store_clone.clone();
+            let executor_sender_clone = executor_sender.clone();
+            let metrics_clone = self.metrics.clone();
+            task_handles.push(spawn_logged_monitored_task!(Self::run_eth_watcher(
+                chain_id,
+                store_clone,
+                executor_sender_clone,
+                eth_events_rx,
+                metrics_clone,
+            )));
+        }
 
         // TODO: spawn bridge committee change watcher task
         task_handles
@@ -142,7 +155,7 @@ where
                 metrics
                     .sui_watcher_received_actions
                     .inc_by(actions.len() as u64);
-                // Write action to pending WAL
+                // Write action to pending WAL, recording it as observed
                 store
                     .insert_pending_actions(&actions)
                     .expect("Store operation should not fail");
@@ -163,6 +176,7 @@ where
     }
 
     async fn run_eth_watcher(
+        chain_id: BridgeChainId,
         store: Arc<BridgeOrchestratorTables>,
         executor_tx: mysten_metrics::metered_channel::Sender<BridgeActionExecutionWrapper>,
         mut eth_events_rx: mysten_metrics::metered_channel::Receiver<(
@@ -172,16 +186,21 @@ where
         )>,
         metrics: Arc<BridgeMetrics>,
     ) {
-        info!("Starting eth watcher task");
+        info!("Starting eth watcher task for chain {:?}", chain_id);
         while let Some((contract, end_block, logs)) = eth_events_rx.recv().await {
             if logs.is_empty() {
                 store
-                    .update_eth_event_cursor(contract, end_block)
+                    .update_eth_event_cursor(chain_id, contract, end_block)
                     .expect("Store operation should not fail");
                 continue;
             }
 
-            info!("Received {} Eth events: {:?}", logs.len(), logs);
+            info!(
+                "Received {} Eth events from chain {:?}: {:?}",
+                logs.len(),
+                chain_id,
+                logs
+            );
             metrics
                 .eth_watcher_received_events
                 .inc_by(logs.len() as u64);
@@ -217,7 +236,7 @@ where
                 metrics
                     .eth_watcher_received_actions
                     .inc_by(actions.len() as u64);
-                // Write action to pending WAL
+                // Write action to pending WAL, recording it as observed
                 store
                     .insert_pending_actions(&actions)
                     .expect("Store operation should not fail");
@@ -230,10 +249,10 @@ where
             }
 
             store
-                .update_eth_event_cursor(contract, end_block)
+                .update_eth_event_cursor(chain_id, contract, end_block)
                 .expect("Store operation should not fail");
         }
-        panic!("Eth event channel was closed");
+        panic!("Eth event channel of chain {:?} was closed", chain_id);
     }
 }
 
@@ -265,7 +284,7 @@ mod tests {
         let _handles = BridgeOrchestrator::new(
             Arc::new(sui_client),
             sui_events_rx,
-            eth_events_rx,
+            vec![(BridgeChainId::EthCustom, eth_events_rx)],
             store.clone(),
             metrics,
         )
@@ -320,7 +339,7 @@ mod tests {
         let _handles = BridgeOrchestrator::new(
             Arc::new(sui_client),
             sui_events_rx,
-            eth_events_rx,
+            vec![(BridgeChainId::EthCustom, eth_events_rx)],
             store.clone(),
             metrics,
         )
@@ -362,7 +381,10 @@ mod tests {
             let action = actions.get(&bridge_action.digest()).unwrap();
             assert_eq!(action, &bridge_action);
             assert_eq!(
-                store.get_eth_event_cursors(&[address]).unwrap()[0].unwrap(),
+                store
+                    .get_eth_event_cursors(BridgeChainId::EthCustom, &[address])
+                    .unwrap()[0]
+                    .unwrap(),
                 end_block_num,
             );
             break;
@@ -397,7 +419,7 @@ mod tests {
         let _handles = BridgeOrchestrator::new(
             Arc::new(sui_client),
             sui_events_rx,
-            eth_events_rx,
+            vec![(BridgeChainId::EthCustom, eth_events_rx)],
             store.clone(),
             metrics,
         )
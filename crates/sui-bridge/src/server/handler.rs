@@ -5,12 +5,15 @@
 
 use crate::crypto::{BridgeAuthorityKeyPair, BridgeAuthoritySignInfo};
 use crate::error::{BridgeError, BridgeResult};
-use crate::eth_client::EthClient;
+use crate::evm_chain::EvmChainClient;
+use crate::storage::BridgeOrchestratorTables;
 use crate::sui_client::{SuiClient, SuiClientInner};
-use crate::types::{BridgeAction, SignedBridgeAction};
+use crate::types::{
+    BridgeAction, BridgeActionAuditTrail, BridgeActionDigest, BridgeActionSource,
+    SignedBridgeAction,
+};
 use async_trait::async_trait;
 use axum::Json;
-use ethers::providers::JsonRpcClient;
 use ethers::types::TxHash;
 use lru::LruCache;
 use std::num::NonZeroUsize;
@@ -19,7 +22,7 @@ use std::sync::Arc;
 use sui_types::digests::TransactionDigest;
 use tap::TapFallible;
 use tokio::sync::{oneshot, Mutex};
-use tracing::info;
+use tracing::{info, warn};
 
 use super::governance_verifier::GovernanceVerifier;
 
@@ -47,6 +50,20 @@ pub trait BridgeRequestHandlerTrait {
         &self,
         action: BridgeAction,
     ) -> Result<Json<SignedBridgeAction>, BridgeError>;
+
+    /// Handles a request for the lifecycle of a BridgeAction on this node,
+    /// by the digest of the action.
+    async fn handle_get_action_audit_trail(
+        &self,
+        action_digest: BridgeActionDigest,
+    ) -> Result<Json<BridgeActionAuditTrail>, BridgeError>;
+
+    /// Handles a request for the lifecycle of a token transfer action on this
+    /// node, by the transaction and event that emitted it.
+    async fn handle_get_action_audit_trail_by_source(
+        &self,
+        source: BridgeActionSource,
+    ) -> Result<Json<BridgeActionAuditTrail>, BridgeError>;
 }
 
 #[async_trait::async_trait]
@@ -58,8 +75,11 @@ struct SuiActionVerifier<C> {
     sui_client: Arc<SuiClient<C>>,
 }
 
-struct EthActionVerifier<P> {
-    eth_client: Arc<EthClient<P>>,
+// Verifies actions against every EVM chain the node signs for. Tx hashes are unique across
+// chains in practice, so the first chain that knows the tx decides the outcome. A chain that
+// fails to answer does not stop the others from being asked.
+struct EthActionVerifier {
+    eth_clients: Vec<Arc<dyn EvmChainClient>>,
 }
 
 #[async_trait::async_trait]
@@ -77,16 +97,37 @@ where
 }
 
 #[async_trait::async_trait]
-impl<C> ActionVerifier<(TxHash, u16)> for EthActionVerifier<C>
-where
-    C: JsonRpcClient + Send + Sync + 'static,
-{
+impl ActionVerifier<(TxHash, u16)> for EthActionVerifier {
     async fn verify(&self, key: (TxHash, u16)) -> BridgeResult<BridgeAction> {
         let (tx_hash, event_idx) = key;
-        self.eth_client
-            .get_finalized_bridge_action_maybe(tx_hash, event_idx)
-            .await
-            .tap_ok(|action| info!("Eth action found: {:?}", action))
+        let mut first_error = None;
+        for eth_client in &self.eth_clients {
+            match eth_client
+                .get_finalized_bridge_action_maybe(tx_hash, event_idx)
+                .await
+            {
+                Ok(action) => {
+                    info!(
+                        "Eth action found on chain {:?}: {:?}",
+                        eth_client.bridge_chain_id(),
+                        action
+                    );
+                    return Ok(action);
+                }
+                Err(BridgeError::TxNotFound) => continue,
+                Err(e) => {
+                    warn!(
+                        "Failed to verify eth tx {:?} on chain {:?}: {:?}",
+                        tx_hash,
+                        eth_client.bridge_chain_id(),
+                        e
+                    );
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        // Only report TxNotFound if every chain answered that the tx is unknown.
+        Err(first_error.unwrap_or(BridgeError::TxNotFound))
     }
 }
 
@@ -202,17 +243,17 @@ pub struct BridgeRequestHandler {
         BridgeAction,
         oneshot::Sender<BridgeResult<SignedBridgeAction>>,
     )>,
+    // Only present when the node runs the bridge client, which records action lifecycles.
+    store: Option<Arc<BridgeOrchestratorTables>>,
 }
 
 impl BridgeRequestHandler {
-    pub fn new<
-        SC: SuiClientInner + Send + Sync + 'static,
-        EP: JsonRpcClient + Send + Sync + 'static,
-    >(
+    pub fn new<SC: SuiClientInner + Send + Sync + 'static>(
         signer: BridgeAuthorityKeyPair,
         sui_client: Arc<SuiClient<SC>>,
-        eth_client: Arc<EthClient<EP>>,
+        eth_clients: Vec<Arc<dyn EvmChainClient>>,
         approved_governance_actions: Vec<BridgeAction>,
+        store: Option<Arc<BridgeOrchestratorTables>>,
     ) -> Self {
         let (sui_signer_tx, sui_rx) = mysten_metrics::metered_channel::channel(
             1000,
@@ -238,7 +279,7 @@ impl BridgeRequestHandler {
         let signer = Arc::new(signer);
 
         SignerWithCache::new(signer.clone(), SuiActionVerifier { sui_client }).spawn(sui_rx);
-        SignerWithCache::new(signer.clone(), EthActionVerifier { eth_client }).spawn(eth_rx);
+        SignerWithCache::new(signer.clone(), EthActionVerifier { eth_clients }).spawn(eth_rx);
         SignerWithCache::new(
             signer.clone(),
             GovernanceVerifier::new(approved_governance_actions).unwrap(),
@@ -249,8 +290,18 @@ impl BridgeRequestHandler {
             sui_signer_tx,
             eth_signer_tx,
             governance_signer_tx,
+            store,
         }
     }
+
+    fn store(&self) -> BridgeResult<&BridgeOrchestratorTables> {
+        self.store.as_deref().ok_or_else(|| {
+            BridgeError::Generic(
+                "Action audit trails are only recorded by nodes running the bridge client"
+                    .to_string(),
+            )
+        })
+    }
 }
 
 #[async_trait]
@@ -308,6 +359,27 @@ impl BridgeRequestHandlerTrait for BridgeRequestHandler {
         })?;
         Ok(Json(signed_action))
     }
+
+    async fn handle_get_action_audit_trail(
+        &self,
+        action_digest: BridgeActionDigest,
+    ) -> Result<Json<BridgeActionAuditTrail>, BridgeError> {
+        self.store()?
+            .get_action_audit_trail(&action_digest)?
+            .map(Json)
+            .ok_or(BridgeError::ActionNotFound)
+    }
+
+    async fn handle_get_action_audit_trail_by_source(
+        &self,
+        source: BridgeActionSource,
+    ) -> Result<Json<BridgeActionAuditTrail>, BridgeError> {
+        let action_digest = self
+            .store()?
+            .get_action_digest_by_source(&source)?
+            .ok_or(BridgeError::ActionNotFound)?;
+        self.handle_get_action_audit_trail(action_digest).await
+    }
 }
 
 #[cfg(test)]
@@ -316,6 +388,7 @@ mod tests {
 
     use super::*;
     use crate::{
+        eth_client::EthClient,
         eth_mock_provider::EthMockProvider,
         events::{init_all_struct_tags, MoveTokenDepositedEvent, SuiToEthTokenBridgeV1},
         sui_mock_client::SuiMockClient,
@@ -472,7 +545,7 @@ mod tests {
             HashSet::from_iter(vec![contract_address]),
         );
         let eth_verifier = EthActionVerifier {
-            eth_client: Arc::new(eth_client),
+            eth_clients: vec![Arc::new(eth_client)],
         };
         let mut eth_signer_with_cache = SignerWithCache::new(signer.clone(), eth_verifier);
 
@@ -531,6 +604,115 @@ mod tests {
         entry_.unwrap().lock().await.clone().unwrap().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_eth_action_verifier_with_multiple_chains() {
+        // Each chain is backed by its own mock provider
+        let sepolia_mock_provider = EthMockProvider::default();
+        let sepolia_contract_address = EthAddress::random();
+        let sepolia_client = EthClient::new_mocked_for_chain(
+            sepolia_mock_provider.clone(),
+            HashSet::from_iter(vec![sepolia_contract_address]),
+            BridgeChainId::EthSepolia,
+        );
+        let custom_mock_provider = EthMockProvider::default();
+        let custom_contract_address = EthAddress::random();
+        let custom_client = EthClient::new_mocked_for_chain(
+            custom_mock_provider.clone(),
+            HashSet::from_iter(vec![custom_contract_address]),
+            BridgeChainId::EthCustom,
+        );
+        let eth_verifier = EthActionVerifier {
+            eth_clients: vec![Arc::new(sepolia_client), Arc::new(custom_client)],
+        };
+
+        // The tx only exists on the second chain
+        let eth_tx_hash = TxHash::random();
+        let eth_event_idx = 0;
+        let (log, action) =
+            get_test_log_and_action(custom_contract_address, eth_tx_hash, eth_event_idx);
+        sepolia_mock_provider
+            .add_response::<[TxHash; 1], Option<TransactionReceipt>, Option<TransactionReceipt>>(
+                "eth_getTransactionReceipt",
+                [eth_tx_hash],
+                None,
+            )
+            .unwrap();
+        custom_mock_provider
+            .add_response::<[TxHash; 1], TransactionReceipt, TransactionReceipt>(
+                "eth_getTransactionReceipt",
+                [eth_tx_hash],
+                TransactionReceipt {
+                    block_number: log.block_number,
+                    logs: vec![log.clone()],
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        mock_last_finalized_block(&custom_mock_provider, log.block_number.unwrap().as_u64());
+        assert_eq!(
+            eth_verifier
+                .verify((eth_tx_hash, eth_event_idx))
+                .await
+                .unwrap(),
+            action
+        );
+
+        // The tx exists on no chain
+        let eth_tx_hash = TxHash::random();
+        for mock_provider in [&sepolia_mock_provider, &custom_mock_provider] {
+            mock_provider
+                .add_response::<[TxHash; 1], Option<TransactionReceipt>, Option<TransactionReceipt>>(
+                    "eth_getTransactionReceipt",
+                    [eth_tx_hash],
+                    None,
+                )
+                .unwrap();
+        }
+        assert!(matches!(
+            eth_verifier.verify((eth_tx_hash, eth_event_idx)).await,
+            Err(BridgeError::TxNotFound)
+        ));
+
+        // The first chain fails to answer (no mocked response), the second one knows the tx
+        let eth_tx_hash = TxHash::random();
+        let (log, action) =
+            get_test_log_and_action(custom_contract_address, eth_tx_hash, eth_event_idx);
+        custom_mock_provider
+            .add_response::<[TxHash; 1], TransactionReceipt, TransactionReceipt>(
+                "eth_getTransactionReceipt",
+                [eth_tx_hash],
+                TransactionReceipt {
+                    block_number: log.block_number,
+                    logs: vec![log.clone()],
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            eth_verifier
+                .verify((eth_tx_hash, eth_event_idx))
+                .await
+                .unwrap(),
+            action
+        );
+
+        // The first chain fails to answer and the second one doesn't know the tx: the failure
+        // is reported rather than TxNotFound
+        let eth_tx_hash = TxHash::random();
+        custom_mock_provider
+            .add_response::<[TxHash; 1], Option<TransactionReceipt>, Option<TransactionReceipt>>(
+                "eth_getTransactionReceipt",
+                [eth_tx_hash],
+                None,
+            )
+            .unwrap();
+        let err = eth_verifier
+            .verify((eth_tx_hash, eth_event_idx))
+            .await
+            .unwrap_err();
+        assert!(!matches!(err, BridgeError::TxNotFound), "{:?}", err);
+    }
+
     #[tokio::test]
     async fn test_signer_with_governace_verifier() {
         let action_1 = BridgeAction::EmergencyAction(EmergencyAction {
@@ -15,7 +15,9 @@ use crate::error::BridgeError;
 use crate::error::BridgeResult;
 use crate::metrics::BridgeMetrics;
 use crate::server::BridgeNodePublicMetadata;
-use crate::types::SignedBridgeAction;
+use crate::types::{
+    BridgeActionAuditTrail, BridgeActionDigest, BridgeActionSource, SignedBridgeAction,
+};
 use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use axum::Json;
@@ -126,6 +128,20 @@ impl BridgeRequestHandlerTrait for BridgeRequestMockHandler {
         let signed_action = SignedBridgeAction::new_from_data_and_sig(action, sig);
         Ok(Json(signed_action))
     }
+
+    async fn handle_get_action_audit_trail(
+        &self,
+        _action_digest: BridgeActionDigest,
+    ) -> Result<Json<BridgeActionAuditTrail>, BridgeError> {
+        unimplemented!()
+    }
+
+    async fn handle_get_action_audit_trail_by_source(
+        &self,
+        _source: BridgeActionSource,
+    ) -> Result<Json<BridgeActionAuditTrail>, BridgeError> {
+        unimplemented!()
+    }
 }
 
 pub fn run_mock_server(
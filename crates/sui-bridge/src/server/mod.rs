@@ -10,8 +10,9 @@ use crate::{
     server::handler::{BridgeRequestHandler, BridgeRequestHandlerTrait},
     types::{
         AddTokensOnEvmAction, AddTokensOnSuiAction, AssetPriceUpdateAction,
-        BlocklistCommitteeAction, BlocklistType, BridgeAction, EmergencyAction,
-        EmergencyActionType, EvmContractUpgradeAction, LimitUpdateAction, SignedBridgeAction,
+        BlocklistCommitteeAction, BlocklistType, BridgeAction, BridgeActionAuditTrail,
+        BridgeActionDigest, BridgeActionSource, EmergencyAction, EmergencyActionType,
+        EthTransactionHash, EvmContractUpgradeAction, LimitUpdateAction, SignedBridgeAction,
     },
 };
 use axum::{
@@ -26,7 +27,7 @@ use fastcrypto::{
 };
 use std::sync::Arc;
 use std::{net::SocketAddr, str::FromStr};
-use sui_types::{bridge::BridgeChainId, TypeTag};
+use sui_types::{bridge::BridgeChainId, digests::TransactionDigest, TypeTag};
 use tracing::{info, instrument};
 
 pub mod governance_verifier;
@@ -58,6 +59,12 @@ pub const ADD_TOKENS_ON_SUI_PATH: &str =
 pub const ADD_TOKENS_ON_EVM_PATH: &str =
     "/sign/add_tokens_on_evm/:chain_id/:nonce/:native/:token_ids/:token_addresses/:token_sui_decimals/:token_prices";
 
+// Lifecycle of the BridgeActions processed by this node, by action digest (hex) or by the
+// transaction and event that emitted the token transfer.
+pub const ACTION_AUDIT_TRAIL_PATH: &str = "/actions/digest/:action_digest";
+pub const ETH_TO_SUI_ACTION_AUDIT_TRAIL_PATH: &str = "/actions/eth/sui/:tx_hash/:event_index";
+pub const SUI_TO_ETH_ACTION_AUDIT_TRAIL_PATH: &str = "/actions/sui/eth/:tx_digest/:event_index";
+
 /// BridgeNode's public metadata that is acceesible via the `/ping` endpoint.
 // Be careful with what to put here, as it is public.
 #[derive(serde::Serialize, Clone)]
@@ -117,17 +124,26 @@ pub(crate) fn make_router(
         )
         .route(ADD_TOKENS_ON_SUI_PATH, get(handle_add_tokens_on_sui))
         .route(ADD_TOKENS_ON_EVM_PATH, get(handle_add_tokens_on_evm))
+        .route(ACTION_AUDIT_TRAIL_PATH, get(handle_get_action_audit_trail))
+        .route(
+            ETH_TO_SUI_ACTION_AUDIT_TRAIL_PATH,
+            get(handle_get_eth_to_sui_action_audit_trail),
+        )
+        .route(
+            SUI_TO_ETH_ACTION_AUDIT_TRAIL_PATH,
+            get(handle_get_sui_to_eth_action_audit_trail),
+        )
         .with_state((handler, metrics, metadata))
 }
 
 impl axum::response::IntoResponse for BridgeError {
     // TODO: distinguish client error.
     fn into_response(self) -> axum::response::Response {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Something went wrong: {:?}", self),
-        )
-            .into_response()
+        let status = match self {
+            BridgeError::ActionNotFound => StatusCode::NOT_FOUND,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, format!("Something went wrong: {:?}", self)).into_response()
     }
 }
 
@@ -567,6 +583,80 @@ async fn handle_add_tokens_on_evm(
     with_metrics!(metrics.clone(), "handle_add_tokens_on_evm", future).await
 }
 
+#[instrument(level = "error", skip_all, fields(action_digest_hex=action_digest_hex))]
+async fn handle_get_action_audit_trail(
+    Path(action_digest_hex): Path<String>,
+    State((handler, metrics, _metadata)): State<(
+        Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
+        Arc<BridgeMetrics>,
+        Arc<BridgeNodePublicMetadata>,
+    )>,
+) -> Result<Json<BridgeActionAuditTrail>, BridgeError> {
+    let future = async {
+        let digest: [u8; 32] = Hex::decode(&action_digest_hex)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| {
+                BridgeError::InvalidBridgeClientRequest(format!(
+                    "Invalid action digest: {}",
+                    action_digest_hex
+                ))
+            })?;
+        handler
+            .handle_get_action_audit_trail(BridgeActionDigest::new(digest))
+            .await
+    };
+    with_metrics!(metrics.clone(), "handle_get_action_audit_trail", future).await
+}
+
+#[instrument(level = "error", skip_all, fields(tx_hash_hex=tx_hash_hex, event_idx=event_idx))]
+async fn handle_get_eth_to_sui_action_audit_trail(
+    Path((tx_hash_hex, event_idx)): Path<(String, u16)>,
+    State((handler, metrics, _metadata)): State<(
+        Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
+        Arc<BridgeMetrics>,
+        Arc<BridgeNodePublicMetadata>,
+    )>,
+) -> Result<Json<BridgeActionAuditTrail>, BridgeError> {
+    let future = async {
+        let tx_hash =
+            EthTransactionHash::from_str(&tx_hash_hex).map_err(|_| BridgeError::InvalidTxHash)?;
+        handler
+            .handle_get_action_audit_trail_by_source(BridgeActionSource::Eth(tx_hash, event_idx))
+            .await
+    };
+    with_metrics!(
+        metrics.clone(),
+        "handle_get_eth_to_sui_action_audit_trail",
+        future
+    )
+    .await
+}
+
+#[instrument(level = "error", skip_all, fields(tx_digest_base58=tx_digest_base58, event_idx=event_idx))]
+async fn handle_get_sui_to_eth_action_audit_trail(
+    Path((tx_digest_base58, event_idx)): Path<(String, u16)>,
+    State((handler, metrics, _metadata)): State<(
+        Arc<impl BridgeRequestHandlerTrait + Sync + Send>,
+        Arc<BridgeMetrics>,
+        Arc<BridgeNodePublicMetadata>,
+    )>,
+) -> Result<Json<BridgeActionAuditTrail>, BridgeError> {
+    let future = async {
+        let tx_digest = TransactionDigest::from_str(&tx_digest_base58)
+            .map_err(|_| BridgeError::InvalidTxHash)?;
+        handler
+            .handle_get_action_audit_trail_by_source(BridgeActionSource::Sui(tx_digest, event_idx))
+            .await
+    };
+    with_metrics!(
+        metrics.clone(),
+        "handle_get_sui_to_eth_action_audit_trail",
+        future
+    )
+    .await
+}
+
 #[macro_export]
 macro_rules! with_metrics {
     ($metrics:expr, $type_:expr, $func:expr) => {
@@ -609,9 +699,20 @@ mod tests {
 
     use super::*;
     use crate::client::bridge_client::BridgeClient;
+    use crate::crypto::BridgeAuthorityKeyPair;
     use crate::server::mock_handler::BridgeRequestMockHandler;
-    use crate::test_utils::get_test_authorities_and_run_mock_bridge_server;
-    use crate::types::BridgeCommittee;
+    use crate::storage::BridgeOrchestratorTables;
+    use crate::sui_client::SuiClient;
+    use crate::sui_mock_client::SuiMockClient;
+    use crate::test_utils::{
+        get_test_authorities_and_run_mock_bridge_server, get_test_log_and_action,
+        get_test_sui_to_eth_bridge_action,
+    };
+    use crate::types::{BridgeActionLifecycleStatus, BridgeCommittee};
+    use ethers::types::TxHash;
+    use std::net::{IpAddr, Ipv4Addr};
+    use sui_config::local_ip_utils;
+    use sui_types::crypto::get_key_pair;
 
     #[tokio::test]
     async fn test_bridge_server_handle_blocklist_update_action_path() {
@@ -731,6 +832,106 @@ mod tests {
         client.request_sign_bridge_action(action).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_bridge_server_handle_action_audit_trail_paths() {
+        let registry = prometheus::Registry::new();
+        mysten_metrics::init_metrics(&registry);
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let (_, kp): (_, BridgeAuthorityKeyPair) = get_key_pair();
+        let handler = BridgeRequestHandler::new(
+            kp,
+            Arc::new(SuiClient::new_for_testing(SuiMockClient::default())),
+            vec![],
+            vec![],
+            Some(store.clone()),
+        );
+        let localhost = local_ip_utils::localhost_for_testing();
+        let port = local_ip_utils::get_available_port(&localhost);
+        let _handle = run_server(
+            &SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), port),
+            handler,
+            Arc::new(BridgeMetrics::new_for_testing()),
+            Arc::new(BridgeNodePublicMetadata::empty_for_testing()),
+        );
+        let get = |path: String| async move {
+            reqwest::get(format!("http://127.0.0.1:{}{}", port, path))
+                .await
+                .unwrap()
+        };
+
+        let sui_tx_digest = TransactionDigest::random();
+        let sui_action = get_test_sui_to_eth_bridge_action(
+            Some(sui_tx_digest),
+            Some(3),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let (_, eth_action) =
+            get_test_log_and_action(EthAddress::repeat_byte(1), TxHash::random(), 5);
+        let BridgeAction::EthToSuiBridgeAction(eth_to_sui) = &eth_action else {
+            unreachable!()
+        };
+        let eth_tx_hash = eth_to_sui.eth_tx_hash;
+
+        // Actions not processed by the node are not found
+        let sui_digest_path = format!("/actions/digest/{}", Hex::encode(sui_action.digest()));
+        let sui_source_path = format!("/actions/sui/eth/{}/3", sui_tx_digest);
+        let eth_source_path = format!("/actions/eth/sui/{:?}/5", eth_tx_hash);
+        for path in [&sui_digest_path, &sui_source_path, &eth_source_path] {
+            assert_eq!(get(path.clone()).await.status(), StatusCode::NOT_FOUND);
+        }
+
+        store
+            .insert_pending_actions(&[sui_action.clone(), eth_action.clone()])
+            .unwrap();
+        store
+            .record_action_status(
+                &sui_action,
+                BridgeActionLifecycleStatus::SignaturesCollected,
+            )
+            .unwrap();
+
+        // The trail can be looked up by action digest or by the source of the action
+        for path in [&sui_digest_path, &sui_source_path] {
+            let response = get(path.clone()).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let trail: BridgeActionAuditTrail =
+                serde_json::from_str(&response.text().await.unwrap()).unwrap();
+            assert_eq!(trail.action, sui_action);
+            assert_eq!(
+                trail
+                    .updates
+                    .into_iter()
+                    .map(|u| u.status)
+                    .collect::<Vec<_>>(),
+                vec![
+                    BridgeActionLifecycleStatus::Observed,
+                    BridgeActionLifecycleStatus::SignaturesCollected,
+                ]
+            );
+        }
+        let response = get(eth_source_path).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let trail: BridgeActionAuditTrail =
+            serde_json::from_str(&response.text().await.unwrap()).unwrap();
+        assert_eq!(trail.action, eth_action);
+        assert_eq!(trail.updates.len(), 1);
+
+        // Malformed digests and sources are rejected
+        for path in [
+            "/actions/digest/abcd".to_string(),
+            "/actions/digest/not_hex".to_string(),
+            "/actions/sui/eth/not_a_digest/3".to_string(),
+            "/actions/eth/sui/not_a_hash/5".to_string(),
+        ] {
+            assert!(!get(path).await.status().is_success());
+        }
+    }
+
     fn setup() -> BridgeClient {
         let mock = BridgeRequestMockHandler::new();
         let (_handles, authorities, mut secrets) =
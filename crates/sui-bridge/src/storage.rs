@@ -1,20 +1,30 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashMap;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
 use sui_types::Identifier;
 
+use sui_types::bridge::BridgeChainId;
 use sui_types::event::EventID;
-use typed_store::rocks::{DBMap, MetricConf};
+use typed_store::rocks::{DBBatch, DBMap, MetricConf};
 use typed_store::traits::TableSummary;
 use typed_store::traits::TypedStoreDebug;
 use typed_store::Map;
 use typed_store_derive::DBMapUtils;
 
 use crate::error::{BridgeError, BridgeResult};
-use crate::types::{BridgeAction, BridgeActionDigest};
+use crate::types::{
+    BridgeAction, BridgeActionAuditTrail, BridgeActionDigest, BridgeActionLifecycleStatus,
+    BridgeActionSource, BridgeActionStatusUpdate,
+};
+
+/// Serializes the read-modify-writes of action audit trails. The store can only be opened by one
+/// process at a time, so a process wide lock is enough.
+static AUDIT_TRAILS_LOCK: Mutex<()> = Mutex::new(());
 
 #[derive(DBMapUtils)]
 pub struct BridgeOrchestratorTables {
@@ -22,8 +32,16 @@ pub struct BridgeOrchestratorTables {
     pub(crate) pending_actions: DBMap<BridgeActionDigest, BridgeAction>,
     /// module identifier to the last processed EventID
     pub(crate) sui_syncer_cursors: DBMap<Identifier, EventID>,
-    /// contract address to the last processed block
+    /// contract address to the last processed block, written before cursors were kept per
+    /// EVM chain. Moved into `evm_syncer_cursors` for the primary eth chain at startup.
     pub(crate) eth_syncer_cursors: DBMap<ethers::types::Address, u64>,
+    /// (bridge chain id, contract address) to the last processed block
+    pub(crate) evm_syncer_cursors: DBMap<(BridgeChainId, ethers::types::Address), u64>,
+    /// lifecycle of every BridgeAction that orchestrator received, including executed ones.
+    /// Grows with every action unless pruned with `prune_action_audit_trails`.
+    pub(crate) action_audit_trails: DBMap<BridgeActionDigest, BridgeActionAuditTrail>,
+    /// source transaction and event of token transfer actions to their digest
+    pub(crate) action_digests_by_source: DBMap<BridgeActionSource, BridgeActionDigest>,
 }
 
 impl BridgeOrchestratorTables {
@@ -36,7 +54,10 @@ impl BridgeOrchestratorTables {
         ))
     }
 
+    /// Write `actions` to the pending WAL, and record them as observed in their audit trails, in
+    /// a single batch.
     pub(crate) fn insert_pending_actions(&self, actions: &[BridgeAction]) -> BridgeResult<()> {
+        let _guard = AUDIT_TRAILS_LOCK.lock().unwrap();
         let mut batch = self.pending_actions.batch();
         batch
             .insert_batch(
@@ -46,6 +67,12 @@ impl BridgeOrchestratorTables {
             .map_err(|e| {
                 BridgeError::StorageError(format!("Couldn't insert into pending_actions: {:?}", e))
             })?;
+        self.append_action_statuses(
+            &mut batch,
+            actions
+                .iter()
+                .map(|action| (action, BridgeActionLifecycleStatus::Observed)),
+        )?;
         batch
             .write()
             .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))
@@ -88,16 +115,20 @@ impl BridgeOrchestratorTables {
 
     pub(crate) fn update_eth_event_cursor(
         &self,
+        chain_id: BridgeChainId,
         contract_address: ethers::types::Address,
         cursor: u64,
     ) -> BridgeResult<()> {
-        let mut batch = self.eth_syncer_cursors.batch();
+        let mut batch = self.evm_syncer_cursors.batch();
 
         batch
-            .insert_batch(&self.eth_syncer_cursors, [(contract_address, cursor)])
+            .insert_batch(
+                &self.evm_syncer_cursors,
+                [((chain_id, contract_address), cursor)],
+            )
             .map_err(|e| {
                 BridgeError::StorageError(format!(
-                    "Coudln't insert into eth_syncer_cursors: {:?}",
+                    "Coudln't insert into evm_syncer_cursors: {:?}",
                     e
                 ))
             })?;
@@ -106,6 +137,140 @@ impl BridgeOrchestratorTables {
             .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))
     }
 
+    /// Append `status` to the audit trail of `action`.
+    pub(crate) fn record_action_status(
+        &self,
+        action: &BridgeAction,
+        status: BridgeActionLifecycleStatus,
+    ) -> BridgeResult<()> {
+        let _guard = AUDIT_TRAILS_LOCK.lock().unwrap();
+        let mut batch = self.action_audit_trails.batch();
+        self.append_action_statuses(&mut batch, [(action, status)])?;
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))
+    }
+
+    /// Add the writes appending `updates` to the audit trails of their actions to `batch`. The
+    /// caller must hold `AUDIT_TRAILS_LOCK` until the batch is written, so that concurrent updates
+    /// of the same trail are not lost.
+    fn append_action_statuses<'a>(
+        &self,
+        batch: &mut DBBatch,
+        updates: impl IntoIterator<Item = (&'a BridgeAction, BridgeActionLifecycleStatus)>,
+    ) -> BridgeResult<()> {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+        // The same action may be updated more than once in the batch.
+        let mut trails = BTreeMap::new();
+        let mut sources = vec![];
+        for (action, status) in updates {
+            let digest = action.digest();
+            let trail = match trails.entry(digest) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let trail = self.get_action_audit_trail(&digest)?.unwrap_or_else(|| {
+                        BridgeActionAuditTrail {
+                            action: action.clone(),
+                            updates: vec![],
+                        }
+                    });
+                    if let Some(source) = action.source() {
+                        sources.push((source, digest));
+                    }
+                    entry.insert(trail)
+                }
+            };
+            trail.updates.push(BridgeActionStatusUpdate {
+                timestamp_ms,
+                status,
+            });
+        }
+
+        batch
+            .insert_batch(&self.action_audit_trails, trails)
+            .map_err(|e| {
+                BridgeError::StorageError(format!(
+                    "Couldn't insert into action_audit_trails: {:?}",
+                    e
+                ))
+            })?;
+        batch
+            .insert_batch(&self.action_digests_by_source, sources)
+            .map_err(|e| {
+                BridgeError::StorageError(format!(
+                    "Couldn't insert into action_digests_by_source: {:?}",
+                    e
+                ))
+            })?;
+        Ok(())
+    }
+
+    /// Delete the audit trails of finalized actions whose last update is older than `cutoff_ms`,
+    /// along with their source index entries. Trails of actions that are still in flight or that
+    /// failed are kept. Returns the number of deleted trails.
+    pub(crate) fn prune_action_audit_trails(&self, cutoff_ms: u64) -> BridgeResult<usize> {
+        let _guard = AUDIT_TRAILS_LOCK.lock().unwrap();
+        let mut digests = vec![];
+        let mut sources = vec![];
+        for (digest, trail) in self.action_audit_trails.unbounded_iter() {
+            let Some(last) = trail.updates.last() else {
+                continue;
+            };
+            if last.timestamp_ms >= cutoff_ms
+                || !matches!(last.status, BridgeActionLifecycleStatus::Finalized(_))
+            {
+                continue;
+            }
+            if let Some(source) = trail.action.source() {
+                sources.push(source);
+            }
+            digests.push(digest);
+        }
+
+        let mut batch = self.action_audit_trails.batch();
+        batch
+            .delete_batch(&self.action_audit_trails, &digests)
+            .map_err(|e| {
+                BridgeError::StorageError(format!(
+                    "Couldn't delete from action_audit_trails: {:?}",
+                    e
+                ))
+            })?;
+        batch
+            .delete_batch(&self.action_digests_by_source, sources)
+            .map_err(|e| {
+                BridgeError::StorageError(format!(
+                    "Couldn't delete from action_digests_by_source: {:?}",
+                    e
+                ))
+            })?;
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))?;
+        Ok(digests.len())
+    }
+
+    pub fn get_action_audit_trail(
+        &self,
+        digest: &BridgeActionDigest,
+    ) -> BridgeResult<Option<BridgeActionAuditTrail>> {
+        self.action_audit_trails.get(digest).map_err(|e| {
+            BridgeError::StorageError(format!("Couldn't get action_audit_trails: {:?}", e))
+        })
+    }
+
+    pub fn get_action_digest_by_source(
+        &self,
+        source: &BridgeActionSource,
+    ) -> BridgeResult<Option<BridgeActionDigest>> {
+        self.action_digests_by_source.get(source).map_err(|e| {
+            BridgeError::StorageError(format!("Couldn't get action_digests_by_source: {:?}", e))
+        })
+    }
+
     pub fn get_all_pending_actions(&self) -> HashMap<BridgeActionDigest, BridgeAction> {
         self.pending_actions.unbounded_iter().collect()
     }
@@ -121,13 +286,74 @@ impl BridgeOrchestratorTables {
 
     pub fn get_eth_event_cursors(
         &self,
+        chain_id: BridgeChainId,
         contract_addresses: &[ethers::types::Address],
     ) -> BridgeResult<Vec<Option<u64>>> {
-        self.eth_syncer_cursors
-            .multi_get(contract_addresses)
+        let cursors = self
+            .evm_syncer_cursors
+            .multi_get(
+                contract_addresses
+                    .iter()
+                    .map(|address| (chain_id, *address)),
+            )
             .map_err(|e| {
-                BridgeError::StorageError(format!("Couldn't get sui_syncer_cursors: {:?}", e))
-            })
+                BridgeError::StorageError(format!("Couldn't get evm_syncer_cursors: {:?}", e))
+            })?;
+        Ok(cursors)
+    }
+
+    /// Moves cursors written before cursors were kept per EVM chain into `evm_syncer_cursors`
+    /// under `chain_id`, which must be the chain the node watched back then. Cursors already
+    /// stored for `chain_id` are kept. Returns the number of migrated cursors.
+    pub(crate) fn migrate_legacy_eth_event_cursors(
+        &self,
+        chain_id: BridgeChainId,
+    ) -> BridgeResult<usize> {
+        let legacy_cursors: Vec<_> = self.eth_syncer_cursors.unbounded_iter().collect();
+        if legacy_cursors.is_empty() {
+            return Ok(0);
+        }
+        let existing = self
+            .evm_syncer_cursors
+            .multi_get(
+                legacy_cursors
+                    .iter()
+                    .map(|(address, _)| (chain_id, *address)),
+            )
+            .map_err(|e| {
+                BridgeError::StorageError(format!("Couldn't get evm_syncer_cursors: {:?}", e))
+            })?;
+        let mut batch = self.evm_syncer_cursors.batch();
+        batch
+            .insert_batch(
+                &self.evm_syncer_cursors,
+                legacy_cursors
+                    .iter()
+                    .zip(existing)
+                    .filter(|(_, existing)| existing.is_none())
+                    .map(|((address, cursor), _)| ((chain_id, *address), *cursor)),
+            )
+            .map_err(|e| {
+                BridgeError::StorageError(format!(
+                    "Couldn't insert into evm_syncer_cursors: {:?}",
+                    e
+                ))
+            })?;
+        batch
+            .delete_batch(
+                &self.eth_syncer_cursors,
+                legacy_cursors.iter().map(|(address, _)| *address),
+            )
+            .map_err(|e| {
+                BridgeError::StorageError(format!(
+                    "Couldn't delete from eth_syncer_cursors: {:?}",
+                    e
+                ))
+            })?;
+        batch
+            .write()
+            .map_err(|e| BridgeError::StorageError(format!("Couldn't write batch: {:?}", e)))?;
+        Ok(legacy_cursors.len())
     }
 }
 
@@ -215,19 +441,71 @@ mod tests {
         let eth_contract_address = ethers::types::Address::random();
         let eth_block_num = 199999u64;
         assert!(store
-            .get_eth_event_cursors(&[eth_contract_address])
+            .get_eth_event_cursors(BridgeChainId::EthSepolia, &[eth_contract_address])
             .unwrap()[0]
             .is_none());
         store
-            .update_eth_event_cursor(eth_contract_address, eth_block_num)
+            .update_eth_event_cursor(
+                BridgeChainId::EthSepolia,
+                eth_contract_address,
+                eth_block_num,
+            )
             .unwrap();
         assert_eq!(
             store
-                .get_eth_event_cursors(&[eth_contract_address])
+                .get_eth_event_cursors(BridgeChainId::EthSepolia, &[eth_contract_address])
                 .unwrap()[0]
                 .unwrap(),
             eth_block_num
         );
+        // Cursors are kept per chain
+        assert!(store
+            .get_eth_event_cursors(BridgeChainId::EthCustom, &[eth_contract_address])
+            .unwrap()[0]
+            .is_none());
+
+        // Cursors written before cursors were kept per chain are migrated to the primary chain
+        // only, without overwriting its existing cursors
+        let legacy_contract_address = ethers::types::Address::random();
+        store
+            .eth_syncer_cursors
+            .insert(&legacy_contract_address, &100)
+            .unwrap();
+        store
+            .eth_syncer_cursors
+            .insert(&eth_contract_address, &100)
+            .unwrap();
+        assert!(store
+            .get_eth_event_cursors(BridgeChainId::EthSepolia, &[legacy_contract_address])
+            .unwrap()[0]
+            .is_none());
+        assert_eq!(
+            store
+                .migrate_legacy_eth_event_cursors(BridgeChainId::EthSepolia)
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            store
+                .get_eth_event_cursors(
+                    BridgeChainId::EthSepolia,
+                    &[legacy_contract_address, eth_contract_address]
+                )
+                .unwrap(),
+            vec![Some(100), Some(eth_block_num)]
+        );
+        assert!(store
+            .get_eth_event_cursors(BridgeChainId::EthCustom, &[legacy_contract_address])
+            .unwrap()[0]
+            .is_none());
+        assert!(store.eth_syncer_cursors.is_empty());
+        // Migrating again is a no-op
+        assert_eq!(
+            store
+                .migrate_legacy_eth_event_cursors(BridgeChainId::EthSepolia)
+                .unwrap(),
+            0
+        );
 
         // update sui event cursor
         let sui_module = Identifier::from_str("test").unwrap();
@@ -244,4 +522,158 @@ mod tests {
             sui_cursor
         );
     }
+
+    // async: existing runtime is required with typed-store
+    #[tokio::test]
+    async fn test_bridge_action_audit_trail() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+
+        let action = get_test_sui_to_eth_bridge_action(
+            None,
+            Some(0),
+            Some(99),
+            Some(10000),
+            None,
+            None,
+            None,
+        );
+        let source = action.source().unwrap();
+        assert!(store
+            .get_action_audit_trail(&action.digest())
+            .unwrap()
+            .is_none());
+        assert!(store
+            .get_action_digest_by_source(&source)
+            .unwrap()
+            .is_none());
+
+        // Inserting the pending action records it as observed
+        store.insert_pending_actions(&[action.clone()]).unwrap();
+        let tx_digest = TransactionDigest::random();
+        let statuses = vec![
+            BridgeActionLifecycleStatus::Observed,
+            BridgeActionLifecycleStatus::SignaturesCollected,
+            BridgeActionLifecycleStatus::Submitted(tx_digest),
+            BridgeActionLifecycleStatus::Finalized(Some(tx_digest)),
+        ];
+        for status in &statuses[1..] {
+            store.record_action_status(&action, status.clone()).unwrap();
+        }
+
+        // the trail stays queryable after the action is no longer pending
+        store.remove_pending_actions(&[action.digest()]).unwrap();
+        assert_eq!(
+            store.get_action_digest_by_source(&source).unwrap(),
+            Some(action.digest())
+        );
+        let trail = store
+            .get_action_audit_trail(&action.digest())
+            .unwrap()
+            .unwrap();
+        assert_eq!(trail.action, action);
+        assert_eq!(
+            trail
+                .updates
+                .into_iter()
+                .map(|u| u.status)
+                .collect::<Vec<_>>(),
+            statuses
+        );
+    }
+
+    // async: existing runtime is required with typed-store
+    #[tokio::test]
+    async fn test_prune_action_audit_trails() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let finalized = get_test_sui_to_eth_bridge_action(
+            None,
+            Some(0),
+            Some(99),
+            Some(10000),
+            None,
+            None,
+            None,
+        );
+        let pending = get_test_sui_to_eth_bridge_action(
+            None,
+            Some(1),
+            Some(100),
+            Some(10000),
+            None,
+            None,
+            None,
+        );
+        store
+            .insert_pending_actions(&[finalized.clone(), pending.clone()])
+            .unwrap();
+        store
+            .record_action_status(&finalized, BridgeActionLifecycleStatus::Finalized(None))
+            .unwrap();
+
+        // Nothing is older than the cutoff
+        assert_eq!(store.prune_action_audit_trails(0).unwrap(), 0);
+
+        // Only the finalized action is pruned, along with its source
+        assert_eq!(store.prune_action_audit_trails(u64::MAX).unwrap(), 1);
+        assert!(store
+            .get_action_audit_trail(&finalized.digest())
+            .unwrap()
+            .is_none());
+        assert!(store
+            .get_action_digest_by_source(&finalized.source().unwrap())
+            .unwrap()
+            .is_none());
+        assert!(store
+            .get_action_audit_trail(&pending.digest())
+            .unwrap()
+            .is_some());
+        assert_eq!(
+            store
+                .get_action_digest_by_source(&pending.source().unwrap())
+                .unwrap(),
+            Some(pending.digest())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bridge_action_audit_trail_concurrent_updates() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = BridgeOrchestratorTables::new(temp_dir.path());
+        let action = get_test_sui_to_eth_bridge_action(
+            None,
+            Some(0),
+            Some(99),
+            Some(10000),
+            None,
+            None,
+            None,
+        );
+
+        // No update is lost when the trail is updated from several threads at once
+        let threads = (0..8)
+            .map(|_| {
+                let (store, action) = (store.clone(), action.clone());
+                std::thread::spawn(move || {
+                    for _ in 0..10 {
+                        store
+                            .record_action_status(
+                                &action,
+                                BridgeActionLifecycleStatus::SignaturesCollected,
+                            )
+                            .unwrap();
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        let trail = store
+            .get_action_audit_trail(&action.digest())
+            .unwrap()
+            .unwrap();
+        assert_eq!(trail.updates.len(), 80);
+    }
 }
@@ -350,6 +350,20 @@ impl BridgeAction {
         BridgeActionDigest::new(hasher.finalize().into())
     }
 
+    /// The transaction and event the action was emitted by, if it is a token transfer.
+    pub fn source(&self) -> Option<BridgeActionSource> {
+        match self {
+            BridgeAction::SuiToEthBridgeAction(a) => Some(BridgeActionSource::Sui(
+                a.sui_tx_digest,
+                a.sui_tx_event_index,
+            )),
+            BridgeAction::EthToSuiBridgeAction(a) => {
+                Some(BridgeActionSource::Eth(a.eth_tx_hash, a.eth_event_index))
+            }
+            _ => None,
+        }
+    }
+
     pub fn key(&self) -> BridgeActionKey {
         BridgeActionKey {
             action_type: self.action_type(),
@@ -442,6 +456,47 @@ impl BridgeActionDigest {
     }
 }
 
+impl AsRef<[u8]> for BridgeActionDigest {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+/// The transaction and event index on the source chain that emitted a token transfer action.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum BridgeActionSource {
+    Sui(TransactionDigest, u16),
+    Eth(EthTransactionHash, u16),
+}
+
+/// A step in the lifecycle of a BridgeAction processed by this node.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum BridgeActionLifecycleStatus {
+    /// The action was observed on its source chain and is pending.
+    Observed,
+    /// A quorum of committee signatures was collected for the action.
+    SignaturesCollected,
+    /// A transaction executing the action was submitted to Sui.
+    Submitted(TransactionDigest),
+    /// The action was executed on chain, by the given transaction, or by someone else if `None`.
+    Finalized(Option<TransactionDigest>),
+    /// The node gave up on the action for the given reason, manual intervention is required.
+    Failed(String),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BridgeActionStatusUpdate {
+    pub timestamp_ms: u64,
+    pub status: BridgeActionLifecycleStatus,
+}
+
+/// The lifecycle of a BridgeAction processed by this node, oldest update first.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BridgeActionAuditTrail {
+    pub action: BridgeAction,
+    pub updates: Vec<BridgeActionStatusUpdate>,
+}
+
 #[derive(Debug, Clone)]
 pub struct BridgeCommitteeValiditySignInfo {
     pub signatures: BTreeMap<BridgeAuthorityPublicKeyBytes, BridgeAuthorityRecoverableSignature>,
@@ -79,6 +79,13 @@ pub struct NodeConfig {
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub remove_deprecated_tables: bool,
 
+    /// Index the balance of every owner and coin type at each checkpoint, to serve historical
+    /// balance queries. The index is seeded in the background from the balances in the coin index
+    /// when it is first enabled, and balances are only available from the checkpoint before, or
+    /// the highest synced one, once it is.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub enable_balance_history_index: bool,
+
     #[serde(default)]
     /// Determines the jsonrpc server type as either:
     /// - 'websocket' for a websocket based service (deprecated)
@@ -726,6 +733,10 @@ pub struct StateSnapshotConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub object_store_config: Option<ObjectStoreConfig>,
     pub concurrency: usize,
+    /// If set, a full state snapshot is only written every `full_snapshot_interval_epochs` epochs,
+    /// and snapshots of the epochs in between are written as deltas against the last full one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_snapshot_interval_epochs: Option<u64>,
 }
 
 #[derive(Default, Debug, Clone, Deserialize, Serialize)]
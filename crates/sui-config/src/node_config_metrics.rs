@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::transaction_deny_config::TransactionDenyConfig;
 use crate::NodeConfig;
 use prometheus::{register_int_gauge_with_registry, IntGauge, Registry};
 use std::sync::Arc;
@@ -65,19 +66,23 @@ impl NodeConfigMetrics {
     }
 
     pub fn record_metrics(&self, config: &NodeConfig) {
+        self.record_transaction_deny_config_metrics(&config.transaction_deny_config);
+    }
+
+    pub fn record_transaction_deny_config_metrics(&self, config: &TransactionDenyConfig) {
         self.tx_deny_config_user_transaction_disabled
-            .set(config.transaction_deny_config.user_transaction_disabled() as i64);
+            .set(config.user_transaction_disabled() as i64);
         self.tx_deny_config_shared_object_disabled
-            .set(config.transaction_deny_config.shared_object_disabled() as i64);
+            .set(config.shared_object_disabled() as i64);
         self.tx_deny_config_package_publish_disabled
-            .set(config.transaction_deny_config.package_publish_disabled() as i64);
+            .set(config.package_publish_disabled() as i64);
         self.tx_deny_config_package_upgrade_disabled
-            .set(config.transaction_deny_config.package_upgrade_disabled() as i64);
+            .set(config.package_upgrade_disabled() as i64);
         self.tx_deny_config_num_denied_objects
-            .set(config.transaction_deny_config.get_object_deny_set().len() as i64);
+            .set(config.get_object_deny_set().len() as i64);
         self.tx_deny_config_num_denied_packages
-            .set(config.transaction_deny_config.get_package_deny_set().len() as i64);
+            .set(config.get_package_deny_set().len() as i64);
         self.tx_deny_config_num_denied_addresses
-            .set(config.transaction_deny_config.get_address_deny_set().len() as i64);
+            .set(config.get_address_deny_set().len() as i64);
     }
 }
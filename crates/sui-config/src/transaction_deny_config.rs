@@ -7,6 +7,8 @@ use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 use sui_types::base_types::{ObjectID, SuiAddress};
 
+use crate::Config;
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct TransactionDenyConfig {
@@ -75,7 +77,96 @@ pub struct TransactionDenyConfig {
     // TODO: We could also consider disable more types of commands, such as transfer, split and etc.
 }
 
+impl Config for TransactionDenyConfig {}
+
 impl TransactionDenyConfig {
+    /// Returns a new config with `patch` applied on top of this one. Entries are added to and
+    /// removed from the deny lists (removals are applied after additions), and kill switches are
+    /// only changed if the patch sets them.
+    pub fn patched(&self, patch: &TransactionDenyConfigPatch) -> Self {
+        fn patch_list<T: Clone + PartialEq>(list: &[T], add: &[T], remove: &[T]) -> Vec<T> {
+            let mut list = list.to_vec();
+            for item in add {
+                if !list.contains(item) {
+                    list.push(item.clone());
+                }
+            }
+            list.retain(|item| !remove.contains(item));
+            list
+        }
+
+        let mut zklogin_disabled_providers = self.zklogin_disabled_providers.clone();
+        zklogin_disabled_providers.extend(patch.add_zklogin_disabled_providers.iter().cloned());
+        for provider in &patch.remove_zklogin_disabled_providers {
+            zklogin_disabled_providers.remove(provider);
+        }
+
+        // Built from scratch, rather than by cloning `self`, so that the lookup sets are
+        // re-derived from the patched lists.
+        Self {
+            object_deny_list: patch_list(
+                &self.object_deny_list,
+                &patch.add_denied_objects,
+                &patch.remove_denied_objects,
+            ),
+            package_deny_list: patch_list(
+                &self.package_deny_list,
+                &patch.add_denied_packages,
+                &patch.remove_denied_packages,
+            ),
+            address_deny_list: patch_list(
+                &self.address_deny_list,
+                &patch.add_denied_addresses,
+                &patch.remove_denied_addresses,
+            ),
+            package_publish_disabled: patch
+                .package_publish_disabled
+                .unwrap_or(self.package_publish_disabled),
+            package_upgrade_disabled: patch
+                .package_upgrade_disabled
+                .unwrap_or(self.package_upgrade_disabled),
+            shared_object_disabled: patch
+                .shared_object_disabled
+                .unwrap_or(self.shared_object_disabled),
+            user_transaction_disabled: patch
+                .user_transaction_disabled
+                .unwrap_or(self.user_transaction_disabled),
+            object_deny_set: OnceCell::new(),
+            package_deny_set: OnceCell::new(),
+            address_deny_set: OnceCell::new(),
+            receiving_objects_disabled: patch
+                .receiving_objects_disabled
+                .unwrap_or(self.receiving_objects_disabled),
+            zklogin_sig_disabled: patch
+                .zklogin_sig_disabled
+                .unwrap_or(self.zklogin_sig_disabled),
+            zklogin_disabled_providers,
+        }
+    }
+
+    /// Returns a config denying everything that either this config or `other` denies: deny lists
+    /// are merged, and kill switches are on if either config turns them on.
+    pub fn merged(&self, other: &Self) -> Self {
+        let on = |disabled: bool| disabled.then_some(true);
+        self.patched(&TransactionDenyConfigPatch {
+            add_denied_objects: other.object_deny_list.clone(),
+            add_denied_packages: other.package_deny_list.clone(),
+            add_denied_addresses: other.address_deny_list.clone(),
+            package_publish_disabled: on(other.package_publish_disabled),
+            package_upgrade_disabled: on(other.package_upgrade_disabled),
+            shared_object_disabled: on(other.shared_object_disabled),
+            user_transaction_disabled: on(other.user_transaction_disabled),
+            receiving_objects_disabled: on(other.receiving_objects_disabled),
+            zklogin_sig_disabled: on(other.zklogin_sig_disabled),
+            add_zklogin_disabled_providers: other
+                .zklogin_disabled_providers
+                .iter()
+                .cloned()
+                .collect(),
+            ..Default::default()
+        })
+    }
+
     pub fn get_object_deny_set(&self) -> &HashSet<ObjectID> {
         self.object_deny_set
             .get_or_init(|| self.object_deny_list.iter().cloned().collect())
@@ -120,6 +211,46 @@ impl TransactionDenyConfig {
     }
 }
 
+/// A partial update to a [TransactionDenyConfig], applied with [TransactionDenyConfig::patched].
+/// Deny lists are edited by listing entries to add or remove, and kill switches that are left
+/// unset keep their current value.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct TransactionDenyConfigPatch {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub add_denied_objects: Vec<ObjectID>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_denied_objects: Vec<ObjectID>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub add_denied_packages: Vec<ObjectID>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_denied_packages: Vec<ObjectID>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub add_denied_addresses: Vec<SuiAddress>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_denied_addresses: Vec<SuiAddress>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_publish_disabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_upgrade_disabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shared_object_disabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_transaction_disabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receiving_objects_disabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zklogin_sig_disabled: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub add_zklogin_disabled_providers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_zklogin_disabled_providers: Vec<String>,
+}
+
 #[derive(Default)]
 pub struct TransactionDenyConfigBuilder {
     config: TransactionDenyConfig,
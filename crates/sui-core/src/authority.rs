@@ -43,6 +43,7 @@ use std::{
     vec,
 };
 use sui_config::node::{AuthorityOverloadConfig, StateDebugDumpConfig};
+use sui_config::transaction_deny_config::TransactionDenyConfig;
 use sui_config::NodeConfig;
 use sui_types::crypto::RandomnessRound;
 use sui_types::execution_status::ExecutionStatus;
@@ -777,6 +778,10 @@ pub struct AuthorityState {
 
     pub config: NodeConfig,
 
+    /// The transaction deny config in effect, which starts out as the one in `config`, but can be
+    /// replaced while the node is running (see [AuthorityState::set_transaction_deny_config]).
+    transaction_deny_config: ArcSwap<TransactionDenyConfig>,
+
     /// Current overload status in this authority. Updated periodically.
     pub overload_info: AuthorityOverloadInfo,
 }
@@ -796,6 +801,18 @@ impl AuthorityState {
         !self.is_validator(epoch_store)
     }
 
+    /// The transaction deny config currently used to check transactions.
+    pub fn transaction_deny_config(&self) -> Arc<TransactionDenyConfig> {
+        self.transaction_deny_config.load_full()
+    }
+
+    /// Replace the transaction deny config. Transactions checked after this call see the new
+    /// config, while `self.config` keeps the one the node was started with.
+    pub fn set_transaction_deny_config(&self, transaction_deny_config: TransactionDenyConfig) {
+        self.transaction_deny_config
+            .store(Arc::new(transaction_deny_config));
+    }
+
     pub fn committee_store(&self) -> &Arc<CommitteeStore> {
         &self.committee_store
     }
@@ -837,7 +854,7 @@ impl AuthorityState {
             transaction.tx_signatures(),
             &input_object_kinds,
             &receiving_objects_refs,
-            &self.config.transaction_deny_config,
+            &self.transaction_deny_config.load(),
             self.get_backing_package_store().as_ref(),
         )?;
 
@@ -1663,7 +1680,7 @@ impl AuthorityState {
             &[],
             &input_object_kinds,
             &receiving_object_refs,
-            &self.config.transaction_deny_config,
+            &self.transaction_deny_config.load(),
             self.get_backing_package_store().as_ref(),
         )?;
 
@@ -1883,7 +1900,7 @@ impl AuthorityState {
             &[],
             &input_object_kinds,
             &receiving_object_refs,
-            &self.config.transaction_deny_config,
+            &self.transaction_deny_config.load(),
             self.get_backing_package_store().as_ref(),
         )?;
 
@@ -2643,6 +2660,7 @@ impl AuthorityState {
             _pruner,
             _authority_per_epoch_pruner,
             db_checkpoint_config: db_checkpoint_config.clone(),
+            transaction_deny_config: ArcSwap::from_pointee(config.transaction_deny_config.clone()),
             config,
             overload_info: AuthorityOverloadInfo::default(),
         });
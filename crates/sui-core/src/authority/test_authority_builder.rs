@@ -270,14 +270,18 @@ impl<'a> TestAuthorityBuilder<'a> {
         let index_store = if self.disable_indexer {
             None
         } else {
-            Some(Arc::new(IndexStore::new(
-                path.join("indexes"),
-                &registry,
-                epoch_store
-                    .protocol_config()
-                    .max_move_identifier_len_as_option(),
-                false,
-            )))
+            Some(Arc::new(
+                IndexStore::new(
+                    path.join("indexes"),
+                    &registry,
+                    epoch_store
+                        .protocol_config()
+                        .max_move_identifier_len_as_option(),
+                    false,
+                    None,
+                )
+                .unwrap(),
+            ))
         };
         let rest_index = if self.disable_indexer {
             None
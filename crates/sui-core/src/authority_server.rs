@@ -739,6 +739,7 @@ impl ValidatorService {
     fn handle_traffic_resp<T>(
         &self,
         client: Option<IpAddr>,
+        route: &str,
         response: &Result<tonic::Response<T>, tonic::Status>,
     ) {
         let error: Option<SuiError> = if let Err(status) = response {
@@ -752,6 +753,7 @@ impl ValidatorService {
                 direct: client,
                 through_fullnode: None,
                 error_weight: error.map(normalize).unwrap_or(Weight::zero()),
+                route: Some(route.to_string()),
                 timestamp: SystemTime::now(),
             })
         }
@@ -858,7 +860,11 @@ macro_rules! handle_with_decoration {
         // handle request
         let response = $self.$func_name($request).await;
         // handle response tallying
-        $self.handle_traffic_resp(client, &response);
+        $self.handle_traffic_resp(
+            client,
+            stringify!($func_name).trim_end_matches("_impl"),
+            &response,
+        );
         response
     }};
 }
@@ -1319,7 +1319,11 @@ async fn finalize_checkpoint(
     let checkpoint_acc =
         accumulator.accumulate_checkpoint(effects, checkpoint.sequence_number, epoch_store)?;
 
-    if data_ingestion_dir.is_some() || state.rest_index.is_some() {
+    let index_balance_history = state
+        .indexes
+        .as_ref()
+        .is_some_and(|indexes| indexes.is_balance_history_enabled());
+    if data_ingestion_dir.is_some() || state.rest_index.is_some() || index_balance_history {
         let checkpoint_data = load_checkpoint_data(
             checkpoint,
             object_cache_reader,
@@ -1338,6 +1342,11 @@ async fn finalize_checkpoint(
             rest_index.index_checkpoint(&checkpoint_data, layout_resolver.as_mut())?;
         }
 
+        if let Some(indexes) = &state.indexes {
+            // Waits for the previous checkpoints, which may still be finalizing.
+            indexes.index_balance_history(&checkpoint_data).await?;
+        }
+
         if let Some(path) = data_ingestion_dir {
             store_checkpoint_locally(path, &checkpoint_data)?;
         }
@@ -5,7 +5,7 @@ use parking_lot::Mutex;
 use std::sync::Arc;
 use sui_types::storage::ObjectStore;
 
-use sui_types::base_types::TransactionDigest;
+use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
 use sui_types::committee::Committee;
 use sui_types::committee::EpochId;
 use sui_types::digests::TransactionEventsDigest;
@@ -20,8 +20,10 @@ use sui_types::messages_checkpoint::VerifiedCheckpoint;
 use sui_types::messages_checkpoint::VerifiedCheckpointContents;
 use sui_types::object::Object;
 use sui_types::storage::error::Error as StorageError;
-use sui_types::storage::RestStateReader;
 use sui_types::storage::WriteStore;
+use sui_types::storage::{
+    AccountOwnedObjectInfo, DynamicFieldIndexInfo, DynamicFieldKey, RestStateReader,
+};
 use sui_types::storage::{ObjectKey, ReadStore};
 use sui_types::transaction::VerifiedTransaction;
 
@@ -29,6 +31,7 @@ use crate::authority::AuthorityState;
 use crate::checkpoints::CheckpointStore;
 use crate::epoch::committee_store::CommitteeStore;
 use crate::execution_cache::ExecutionCacheTraitPointers;
+use crate::rest_index::RestIndexStore;
 
 #[derive(Clone)]
 pub struct RocksDbStore {
@@ -497,4 +500,48 @@ impl RestStateReader for RestReadStore {
             .get_chain_identifier()
             .ok_or_else(|| StorageError::missing("unable to query chain identifier"))
     }
+
+    fn account_owned_objects_info_iter(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<ObjectID>,
+    ) -> sui_types::storage::error::Result<Box<dyn Iterator<Item = AccountOwnedObjectInfo> + '_>>
+    {
+        let iter = self
+            .rest_index()?
+            .owner_iter(owner, cursor)
+            .map_err(StorageError::custom)?
+            .map(|(key, info)| AccountOwnedObjectInfo {
+                owner: key.owner,
+                object_id: key.object_id,
+                version: info.version,
+                type_: info.type_,
+            });
+
+        Ok(Box::new(iter) as _)
+    }
+
+    fn dynamic_field_iter(
+        &self,
+        parent: ObjectID,
+        cursor: Option<ObjectID>,
+    ) -> sui_types::storage::error::Result<
+        Box<dyn Iterator<Item = (DynamicFieldKey, DynamicFieldIndexInfo)> + '_>,
+    > {
+        let iter = self
+            .rest_index()?
+            .dynamic_field_iter(parent, cursor)
+            .map_err(StorageError::custom)?;
+
+        Ok(Box::new(iter) as _)
+    }
+}
+
+impl RestReadStore {
+    fn rest_index(&self) -> sui_types::storage::error::Result<&RestIndexStore> {
+        self.state
+            .rest_index
+            .as_deref()
+            .ok_or_else(|| StorageError::custom("rest index store is disabled"))
+    }
 }
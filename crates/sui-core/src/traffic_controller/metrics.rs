@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use prometheus::{
-    register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
-    Registry,
+    register_int_counter_vec_with_registry, register_int_counter_with_registry,
+    register_int_gauge_with_registry, IntCounter, IntCounterVec, IntGauge, Registry,
 };
 
 #[derive(Clone)]
@@ -18,6 +18,11 @@ pub struct TrafficControllerMetrics {
     pub num_dry_run_blocked_requests: IntCounter,
     pub tally_handled: IntCounter,
     pub error_tally_handled: IntCounter,
+    pub token_bucket_tracked_clients: IntGauge,
+    pub token_bucket_throttled: IntCounterVec,
+    pub token_bucket_untracked_clients: IntCounter,
+    pub composite_policy_tallies_skipped: IntCounterVec,
+    pub composite_policy_blocks: IntCounterVec,
 }
 
 impl TrafficControllerMetrics {
@@ -85,6 +90,39 @@ impl TrafficControllerMetrics {
                 registry
             )
             .unwrap(),
+            token_bucket_tracked_clients: register_int_gauge_with_registry!(
+                "traffic_control_token_bucket_tracked_clients",
+                "Number of clients with a token bucket tracked by token bucket policies",
+                registry
+            )
+            .unwrap(),
+            token_bucket_throttled: register_int_counter_vec_with_registry!(
+                "traffic_control_token_bucket_throttled",
+                "Number of tallies for which a client had run out of tokens, by client type",
+                &["client_type"],
+                registry
+            )
+            .unwrap(),
+            token_bucket_untracked_clients: register_int_counter_with_registry!(
+                "traffic_control_token_bucket_untracked_clients",
+                "Number of tallies not rate limited because too many clients were already tracked",
+                registry
+            )
+            .unwrap(),
+            composite_policy_tallies_skipped: register_int_counter_vec_with_registry!(
+                "traffic_control_composite_policy_tallies_skipped",
+                "Number of tallies not sampled into each policy of a composite policy",
+                &["policy"],
+                registry
+            )
+            .unwrap(),
+            composite_policy_blocks: register_int_counter_vec_with_registry!(
+                "traffic_control_composite_policy_blocks",
+                "Number of block responses from each policy of a composite policy",
+                &["policy"],
+                registry
+            )
+            .unwrap(),
         }
     }
 
@@ -214,8 +214,10 @@ async fn run_tally_loop(
     metrics: Arc<TrafficControllerMetrics>,
     mut mem_drainfile_present: bool,
 ) {
-    let mut spam_policy = TrafficControlPolicy::from_spam_config(policy_config.clone()).await;
-    let mut error_policy = TrafficControlPolicy::from_error_config(policy_config.clone()).await;
+    let mut spam_policy =
+        TrafficControlPolicy::from_spam_config(policy_config.clone(), metrics.clone()).await;
+    let mut error_policy =
+        TrafficControlPolicy::from_error_config(policy_config.clone(), metrics.clone()).await;
     let spam_blocklists = Arc::new(blocklists.clone());
     let error_blocklists = Arc::new(blocklists);
     let node_fw_client = fw_config
@@ -363,6 +365,8 @@ async fn handle_policy_response(
     let PolicyResponse {
         block_client,
         block_proxied_client,
+        client_block_ttl,
+        proxied_client_block_ttl,
     } = response;
     let PolicyConfig {
         connection_blocklist_ttl_sec,
@@ -370,12 +374,10 @@ async fn handle_policy_response(
         ..
     } = policy_config;
     if let Some(client) = block_client {
+        let ttl = client_block_ttl.unwrap_or(Duration::from_secs(*connection_blocklist_ttl_sec));
         if blocklists
             .clients
-            .insert(
-                client,
-                SystemTime::now() + Duration::from_secs(*connection_blocklist_ttl_sec),
-            )
+            .insert(client, SystemTime::now() + ttl)
             .is_none()
         {
             // Only increment the metric if the client was not already blocked
@@ -384,12 +386,10 @@ async fn handle_policy_response(
         }
     }
     if let Some(client) = block_proxied_client {
+        let ttl = proxied_client_block_ttl.unwrap_or(Duration::from_secs(*proxy_blocklist_ttl_sec));
         if blocklists
             .proxied_clients
-            .insert(
-                client,
-                SystemTime::now() + Duration::from_secs(*proxy_blocklist_ttl_sec),
-            )
+            .insert(client, SystemTime::now() + ttl)
             .is_none()
         {
             // Only increment the metric if the client was not already blocked
@@ -409,6 +409,8 @@ async fn delegate_policy_response(
     let PolicyResponse {
         block_client,
         block_proxied_client,
+        client_block_ttl,
+        proxied_client_block_ttl,
     } = response;
     let PolicyConfig {
         connection_blocklist_ttl_sec,
@@ -421,7 +423,9 @@ async fn delegate_policy_response(
         addresses.push(BlockAddress {
             source_address: client_id.to_string(),
             destination_port,
-            ttl: *connection_blocklist_ttl_sec,
+            ttl: client_block_ttl
+                .map(firewall_ttl_secs)
+                .unwrap_or(*connection_blocklist_ttl_sec),
         });
     }
     if let Some(ip) = block_proxied_client {
@@ -429,7 +433,9 @@ async fn delegate_policy_response(
         addresses.push(BlockAddress {
             source_address: ip.to_string(),
             destination_port,
-            ttl: *proxy_blocklist_ttl_sec,
+            ttl: proxied_client_block_ttl
+                .map(firewall_ttl_secs)
+                .unwrap_or(*proxy_blocklist_ttl_sec),
         });
     }
     if addresses.is_empty() {
@@ -451,6 +457,12 @@ async fn delegate_policy_response(
     }
 }
 
+/// The firewall only accepts TTLs in whole seconds, so round policy TTLs up, so that the
+/// client is not unblocked before the policy would allow it.
+fn firewall_ttl_secs(ttl: Duration) -> u64 {
+    ttl.as_secs() + u64::from(ttl.subsec_nanos() > 0)
+}
+
 #[derive(Debug, Clone)]
 pub struct TrafficSimMetrics {
     pub num_requests: u64,
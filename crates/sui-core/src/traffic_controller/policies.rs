@@ -5,23 +5,39 @@
 use std::{collections::HashMap, net::IpAddr, sync::Arc};
 
 use count_min_sketch::CountMinSketch32;
+use futures::future::BoxFuture;
 use mysten_metrics::spawn_monitored_task;
 use parking_lot::RwLock;
+use rand::Rng;
 use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::time::Duration;
 use std::time::{Instant, SystemTime};
-use sui_types::traffic_control::{FreqThresholdConfig, PolicyConfig, PolicyType, Weight};
-use tracing::info;
+use sui_types::traffic_control::{
+    CompositeConfig, CompositePolicyEntry, FreqThresholdConfig, PolicyConfig, PolicyType,
+    TokenBucketConfig, Weight,
+};
+use tracing::{info, warn};
+
+use super::metrics::TrafficControllerMetrics;
 
 /// The type of request client.
-#[derive(Hash, Eq, PartialEq, Debug)]
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]
 enum ClientType {
     Direct,
     ThroughFullnode,
 }
 
+impl ClientType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ClientType::Direct => "direct",
+            ClientType::ThroughFullnode => "through_fullnode",
+        }
+    }
+}
+
 #[derive(Hash, Eq, PartialEq, Debug)]
 struct SketchKey(IpAddr, ClientType);
 
@@ -137,6 +153,9 @@ pub struct TrafficTally {
     pub direct: Option<IpAddr>,
     pub through_fullnode: Option<IpAddr>,
     pub error_weight: Weight,
+    /// The route (e.g. gRPC handler or JSON-RPC method) that served the request, used to
+    /// weight tallies in composite policies.
+    pub route: Option<String>,
     pub timestamp: SystemTime,
 }
 
@@ -150,15 +169,67 @@ impl TrafficTally {
             direct,
             through_fullnode,
             error_weight,
+            route: None,
             timestamp: SystemTime::now(),
         }
     }
+
+    pub fn with_route(mut self, route: impl Into<String>) -> Self {
+        self.route = Some(route.into());
+        self
+    }
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct PolicyResponse {
     pub block_client: Option<IpAddr>,
     pub block_proxied_client: Option<IpAddr>,
+    /// How long to block the client for. If unset, the `connection_blocklist_ttl_sec`
+    /// from the `PolicyConfig` is used.
+    pub client_block_ttl: Option<Duration>,
+    /// How long to block the proxied client for. If unset, the `proxy_blocklist_ttl_sec`
+    /// from the `PolicyConfig` is used.
+    pub proxied_client_block_ttl: Option<Duration>,
+}
+
+impl PolicyResponse {
+    fn is_block(&self) -> bool {
+        self.block_client.is_some() || self.block_proxied_client.is_some()
+    }
+
+    /// Combine the responses of two policies, blocking each client that either of them
+    /// blocks. When both block a client, a block for the configured TTL takes precedence
+    /// over an explicit one, and otherwise the longer of the two TTLs is used.
+    fn merge(self, other: PolicyResponse) -> PolicyResponse {
+        fn merge_ttl(
+            a: (Option<IpAddr>, Option<Duration>),
+            b: (Option<IpAddr>, Option<Duration>),
+        ) -> (Option<IpAddr>, Option<Duration>) {
+            match (a, b) {
+                ((Some(client), Some(a)), (Some(_), Some(b))) => (Some(client), Some(a.max(b))),
+                ((Some(client), _), (Some(_), _)) => (Some(client), None),
+                ((Some(client), ttl), (None, _)) | ((None, _), (Some(client), ttl)) => {
+                    (Some(client), ttl)
+                }
+                ((None, _), (None, _)) => (None, None),
+            }
+        }
+
+        let (block_client, client_block_ttl) = merge_ttl(
+            (self.block_client, self.client_block_ttl),
+            (other.block_client, other.client_block_ttl),
+        );
+        let (block_proxied_client, proxied_client_block_ttl) = merge_ttl(
+            (self.block_proxied_client, self.proxied_client_block_ttl),
+            (other.block_proxied_client, other.proxied_client_block_ttl),
+        );
+        PolicyResponse {
+            block_client,
+            block_proxied_client,
+            client_block_ttl,
+            proxied_client_block_ttl,
+        }
+    }
 }
 
 pub trait Policy {
@@ -172,6 +243,8 @@ pub trait Policy {
 // not object safe, so we can't use a trait object instead
 pub enum TrafficControlPolicy {
     FreqThreshold(FreqThresholdPolicy),
+    TokenBucket(TokenBucketPolicy),
+    Composite(CompositePolicy),
     NoOp(NoOpPolicy),
     // Test policies below this point
     TestNConnIP(TestNConnIPPolicy),
@@ -183,6 +256,8 @@ impl Policy for TrafficControlPolicy {
         match self {
             TrafficControlPolicy::NoOp(policy) => policy.handle_tally(tally),
             TrafficControlPolicy::FreqThreshold(policy) => policy.handle_tally(tally),
+            TrafficControlPolicy::TokenBucket(policy) => policy.handle_tally(tally),
+            TrafficControlPolicy::Composite(policy) => policy.handle_tally(tally),
             TrafficControlPolicy::TestNConnIP(policy) => policy.handle_tally(tally),
             TrafficControlPolicy::TestPanicOnInvocation(policy) => policy.handle_tally(tally),
         }
@@ -192,6 +267,8 @@ impl Policy for TrafficControlPolicy {
         match self {
             TrafficControlPolicy::NoOp(policy) => policy.policy_config(),
             TrafficControlPolicy::FreqThreshold(policy) => policy.policy_config(),
+            TrafficControlPolicy::TokenBucket(policy) => policy.policy_config(),
+            TrafficControlPolicy::Composite(policy) => policy.policy_config(),
             TrafficControlPolicy::TestNConnIP(policy) => policy.policy_config(),
             TrafficControlPolicy::TestPanicOnInvocation(policy) => policy.policy_config(),
         }
@@ -199,18 +276,44 @@ impl Policy for TrafficControlPolicy {
 }
 
 impl TrafficControlPolicy {
-    pub async fn from_spam_config(policy_config: PolicyConfig) -> Self {
-        Self::from_config(policy_config.clone().spam_policy_type, policy_config).await
+    pub async fn from_spam_config(
+        policy_config: PolicyConfig,
+        metrics: Arc<TrafficControllerMetrics>,
+    ) -> Self {
+        Self::from_config(
+            policy_config.clone().spam_policy_type,
+            policy_config,
+            metrics,
+        )
+        .await
     }
-    pub async fn from_error_config(policy_config: PolicyConfig) -> Self {
-        Self::from_config(policy_config.clone().error_policy_type, policy_config).await
+    pub async fn from_error_config(
+        policy_config: PolicyConfig,
+        metrics: Arc<TrafficControllerMetrics>,
+    ) -> Self {
+        Self::from_config(
+            policy_config.clone().error_policy_type,
+            policy_config,
+            metrics,
+        )
+        .await
     }
-    pub async fn from_config(policy_type: PolicyType, policy_config: PolicyConfig) -> Self {
+    pub async fn from_config(
+        policy_type: PolicyType,
+        policy_config: PolicyConfig,
+        metrics: Arc<TrafficControllerMetrics>,
+    ) -> Self {
         match policy_type {
             PolicyType::NoOp => Self::NoOp(NoOpPolicy::new(policy_config)),
             PolicyType::FreqThreshold(freq_threshold_config) => Self::FreqThreshold(
                 FreqThresholdPolicy::new(policy_config, freq_threshold_config),
             ),
+            PolicyType::TokenBucket(token_bucket_config) => Self::TokenBucket(
+                TokenBucketPolicy::new(policy_config, token_bucket_config, metrics),
+            ),
+            PolicyType::Composite(composite_config) => Self::Composite(
+                CompositePolicy::new(policy_config, composite_config, metrics).await,
+            ),
             PolicyType::TestNConnIP(n) => {
                 Self::TestNConnIP(TestNConnIPPolicy::new(policy_config, n).await)
             }
@@ -219,6 +322,27 @@ impl TrafficControlPolicy {
             }
         }
     }
+
+    // Composite policies are built from other policies, which requires boxing the
+    // recursive future.
+    fn from_config_boxed(
+        policy_type: PolicyType,
+        policy_config: PolicyConfig,
+        metrics: Arc<TrafficControllerMetrics>,
+    ) -> BoxFuture<'static, Self> {
+        Box::pin(Self::from_config(policy_type, policy_config, metrics))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TrafficControlPolicy::NoOp(_) => "NoOp",
+            TrafficControlPolicy::FreqThreshold(_) => "FreqThreshold",
+            TrafficControlPolicy::TokenBucket(_) => "TokenBucket",
+            TrafficControlPolicy::Composite(_) => "Composite",
+            TrafficControlPolicy::TestNConnIP(_) => "TestNConnIP",
+            TrafficControlPolicy::TestPanicOnInvocation(_) => "TestPanicOnInvocation",
+        }
+    }
 }
 
 ////////////// *** Policy definitions *** //////////////
@@ -284,6 +408,176 @@ impl FreqThresholdPolicy {
         PolicyResponse {
             block_client,
             block_proxied_client,
+            ..Default::default()
+        }
+    }
+
+    fn policy_config(&self) -> &PolicyConfig {
+        &self.config
+    }
+}
+
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn full(capacity: f64, now: Instant) -> Self {
+        Self {
+            tokens: capacity,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, capacity: f64, refill_rate: f64, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * refill_rate).min(capacity);
+        self.last_refill = now;
+    }
+
+    /// Take a token from the bucket. If the bucket is empty, returns how long it will take
+    /// for the next token to become available instead.
+    fn try_take(&mut self, capacity: f64, refill_rate: f64, now: Instant) -> Result<(), Duration> {
+        self.refill(capacity, refill_rate, now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / refill_rate))
+        }
+    }
+}
+
+pub struct TokenBucketPolicy {
+    config: PolicyConfig,
+    client_capacity: f64,
+    client_refill_rate: f64,
+    proxied_client_capacity: f64,
+    proxied_client_refill_rate: f64,
+    max_tracked_clients: usize,
+    buckets: HashMap<(IpAddr, ClientType), TokenBucket>,
+    metrics: Arc<TrafficControllerMetrics>,
+}
+
+impl TokenBucketPolicy {
+    pub fn new(
+        config: PolicyConfig,
+        TokenBucketConfig {
+            client_capacity,
+            client_refill_rate,
+            proxied_client_capacity,
+            proxied_client_refill_rate,
+            max_tracked_clients,
+        }: TokenBucketConfig,
+        metrics: Arc<TrafficControllerMetrics>,
+    ) -> Self {
+        assert!(
+            client_refill_rate > 0.0 && proxied_client_refill_rate > 0.0,
+            "Token bucket refill rates must be positive"
+        );
+        Self {
+            config,
+            client_capacity: client_capacity as f64,
+            client_refill_rate,
+            proxied_client_capacity: proxied_client_capacity as f64,
+            proxied_client_refill_rate,
+            max_tracked_clients,
+            buckets: HashMap::new(),
+            metrics,
+        }
+    }
+
+    fn handle_tally(&mut self, tally: TrafficTally) -> PolicyResponse {
+        let now = Instant::now();
+        let (block_client, client_block_ttl) = match tally.direct {
+            Some(client) => self.take_token(client, ClientType::Direct, now),
+            None => (None, None),
+        };
+        let (block_proxied_client, proxied_client_block_ttl) = match tally.through_fullnode {
+            Some(client) => self.take_token(client, ClientType::ThroughFullnode, now),
+            None => (None, None),
+        };
+        PolicyResponse {
+            block_client,
+            block_proxied_client,
+            client_block_ttl,
+            proxied_client_block_ttl,
+        }
+    }
+
+    fn take_token(
+        &mut self,
+        client: IpAddr,
+        client_type: ClientType,
+        now: Instant,
+    ) -> (Option<IpAddr>, Option<Duration>) {
+        let (capacity, refill_rate) = match client_type {
+            ClientType::Direct => (self.client_capacity, self.client_refill_rate),
+            ClientType::ThroughFullnode => (
+                self.proxied_client_capacity,
+                self.proxied_client_refill_rate,
+            ),
+        };
+
+        let key = (client, client_type);
+        if !self.buckets.contains_key(&key) && !self.make_room(now) {
+            self.metrics.token_bucket_untracked_clients.inc();
+            return (None, None);
+        }
+
+        let bucket = self.buckets.entry(key).or_insert_with(|| {
+            self.metrics.token_bucket_tracked_clients.inc();
+            TokenBucket::full(capacity, now)
+        });
+        match bucket.try_take(capacity, refill_rate, now) {
+            Ok(()) => (None, None),
+            Err(wait) => {
+                self.metrics
+                    .token_bucket_throttled
+                    .with_label_values(&[client_type.as_str()])
+                    .inc();
+                (Some(client), Some(wait))
+            }
+        }
+    }
+
+    /// Ensure there is room to track another bucket, evicting buckets that have refilled
+    /// completely (and so behave exactly like a new bucket would) if necessary. Returns
+    /// false if there is still no room.
+    fn make_room(&mut self, now: Instant) -> bool {
+        if self.buckets.len() < self.max_tracked_clients {
+            return true;
+        }
+
+        let before = self.buckets.len();
+        let (client_capacity, client_refill_rate) = (self.client_capacity, self.client_refill_rate);
+        let (proxied_client_capacity, proxied_client_refill_rate) = (
+            self.proxied_client_capacity,
+            self.proxied_client_refill_rate,
+        );
+        self.buckets.retain(|(_, client_type), bucket| {
+            let (capacity, refill_rate) = match client_type {
+                ClientType::Direct => (client_capacity, client_refill_rate),
+                ClientType::ThroughFullnode => {
+                    (proxied_client_capacity, proxied_client_refill_rate)
+                }
+            };
+            bucket.refill(capacity, refill_rate, now);
+            bucket.tokens < capacity
+        });
+        self.metrics
+            .token_bucket_tracked_clients
+            .sub((before - self.buckets.len()) as i64);
+
+        if self.buckets.len() < self.max_tracked_clients {
+            true
+        } else {
+            warn!(
+                "Token bucket policy is tracking the maximum of {} clients, not rate limiting new clients",
+                self.max_tracked_clients,
+            );
+            false
         }
     }
 
@@ -292,6 +586,100 @@ impl FreqThresholdPolicy {
     }
 }
 
+impl Drop for TokenBucketPolicy {
+    fn drop(&mut self) {
+        self.metrics
+            .token_bucket_tracked_clients
+            .sub(self.buckets.len() as i64);
+    }
+}
+
+struct WeightedPolicy {
+    policy: TrafficControlPolicy,
+    route_weights: HashMap<String, Weight>,
+    default_weight: Weight,
+}
+
+impl WeightedPolicy {
+    fn weight(&self, route: Option<&str>) -> &Weight {
+        route
+            .and_then(|route| self.route_weights.get(route))
+            .unwrap_or(&self.default_weight)
+    }
+}
+
+pub struct CompositePolicy {
+    config: PolicyConfig,
+    policies: Vec<WeightedPolicy>,
+    metrics: Arc<TrafficControllerMetrics>,
+}
+
+impl CompositePolicy {
+    pub async fn new(
+        config: PolicyConfig,
+        CompositeConfig { policies }: CompositeConfig,
+        metrics: Arc<TrafficControllerMetrics>,
+    ) -> Self {
+        let mut weighted = Vec::with_capacity(policies.len());
+        for CompositePolicyEntry {
+            policy_type,
+            route_weights,
+            default_weight,
+        } in policies
+        {
+            let policy = TrafficControlPolicy::from_config_boxed(
+                policy_type,
+                config.clone(),
+                metrics.clone(),
+            )
+            .await;
+            weighted.push(WeightedPolicy {
+                policy,
+                route_weights,
+                default_weight,
+            });
+        }
+        Self {
+            config,
+            policies: weighted,
+            metrics,
+        }
+    }
+
+    fn handle_tally(&mut self, tally: TrafficTally) -> PolicyResponse {
+        let mut response = PolicyResponse::default();
+        let mut rng = rand::thread_rng();
+        for weighted in &mut self.policies {
+            let name = weighted.policy.name();
+            let weight = weighted.weight(tally.route.as_deref()).value();
+            // Same sampling as `Weight::is_sampled`, which can't be used from this
+            // synchronous context, except that a weight of zero never samples.
+            let sampled = weight > 0.0 && rng.gen_range(0.0..1.0) <= weight;
+            if !sampled {
+                self.metrics
+                    .composite_policy_tallies_skipped
+                    .with_label_values(&[name])
+                    .inc();
+                continue;
+            }
+
+            let policy_response = weighted.policy.handle_tally(tally.clone());
+            if policy_response.is_block() {
+                self.metrics
+                    .composite_policy_blocks
+                    .with_label_values(&[name])
+                    .inc();
+            }
+            response = response.merge(policy_response);
+        }
+        response
+    }
+
+    fn policy_config(&self) -> &PolicyConfig {
+        &self.config
+    }
+}
+
 ////////////// *** Test policies below this point *** //////////////
 
 #[derive(Clone)]
@@ -353,6 +741,7 @@ impl TestNConnIPPolicy {
                 None
             },
             block_proxied_client: None,
+            ..Default::default()
         }
     }
 
@@ -418,18 +807,21 @@ mod tests {
             direct: Some(IpAddr::V4(Ipv4Addr::new(8, 7, 6, 5))),
             through_fullnode: Some(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4))),
             error_weight: Weight::zero(),
+            route: None,
             timestamp: SystemTime::now(),
         };
         let bob = TrafficTally {
             direct: Some(IpAddr::V4(Ipv4Addr::new(8, 7, 6, 5))),
             through_fullnode: Some(IpAddr::V4(Ipv4Addr::new(4, 3, 2, 1))),
             error_weight: Weight::zero(),
+            route: None,
             timestamp: SystemTime::now(),
         };
         let charlie = TrafficTally {
             direct: Some(IpAddr::V4(Ipv4Addr::new(8, 7, 6, 5))),
             through_fullnode: Some(IpAddr::V4(Ipv4Addr::new(5, 6, 7, 8))),
             error_weight: Weight::zero(),
+            route: None,
             timestamp: SystemTime::now(),
         };
 
@@ -502,6 +894,118 @@ mod tests {
         }
     }
 
+    #[sim_test]
+    async fn test_token_bucket_policy() {
+        // Proxied clients may burst 3 requests, and then make 1 request per second.
+        let mut policy = TrafficControlPolicy::TokenBucket(TokenBucketPolicy::new(
+            PolicyConfig::default(),
+            TokenBucketConfig {
+                client_capacity: 100,
+                client_refill_rate: 100.0,
+                proxied_client_capacity: 3,
+                proxied_client_refill_rate: 1.0,
+                ..Default::default()
+            },
+            Arc::new(TrafficControllerMetrics::new_for_tests()),
+        ));
+        // alice and bob connect through the same fullnode
+        let alice = TrafficTally::new(
+            Some(IpAddr::V4(Ipv4Addr::new(8, 7, 6, 5))),
+            Some(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4))),
+            Weight::zero(),
+        );
+        let bob = TrafficTally::new(
+            Some(IpAddr::V4(Ipv4Addr::new(8, 7, 6, 5))),
+            Some(IpAddr::V4(Ipv4Addr::new(4, 3, 2, 1))),
+            Weight::zero(),
+        );
+
+        for i in 0..3 {
+            let response = policy.handle_tally(alice.clone());
+            assert_eq!(response.block_client, None, "Blocked at i = {}", i);
+            assert_eq!(response.block_proxied_client, None, "Blocked at i = {}", i);
+        }
+
+        // alice has used up her burst, and is held back only until her next token arrives
+        let response = policy.handle_tally(alice.clone());
+        assert_eq!(response.block_client, None);
+        assert_eq!(response.block_proxied_client, alice.through_fullnode);
+        let ttl = response.proxied_client_block_ttl.unwrap();
+        assert!(ttl <= Duration::from_secs(1), "ttl = {:?}", ttl);
+
+        // bob has a bucket of his own
+        let response = policy.handle_tally(bob.clone());
+        assert_eq!(response.block_client, None);
+        assert_eq!(response.block_proxied_client, None);
+
+        // once a token has been refilled, alice may send again, but only once
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let response = policy.handle_tally(alice.clone());
+        assert_eq!(response.block_proxied_client, None);
+        let response = policy.handle_tally(alice.clone());
+        assert_eq!(response.block_proxied_client, alice.through_fullnode);
+    }
+
+    #[sim_test]
+    async fn test_composite_policy() {
+        // Only transactions are rate limited, but all routes count towards the
+        // connection threshold.
+        let composite_config = CompositeConfig {
+            policies: vec![
+                CompositePolicyEntry {
+                    policy_type: PolicyType::TokenBucket(TokenBucketConfig {
+                        client_capacity: 2,
+                        client_refill_rate: 1.0,
+                        ..Default::default()
+                    }),
+                    route_weights: [("transaction".to_string(), Weight::one())]
+                        .into_iter()
+                        .collect(),
+                    default_weight: Weight::zero(),
+                },
+                CompositePolicyEntry {
+                    policy_type: PolicyType::TestNConnIP(7),
+                    route_weights: HashMap::new(),
+                    default_weight: Weight::one(),
+                },
+            ],
+        };
+        let mut policy = TrafficControlPolicy::from_config(
+            PolicyType::Composite(composite_config),
+            PolicyConfig {
+                connection_blocklist_ttl_sec: 60,
+                ..Default::default()
+            },
+            Arc::new(TrafficControllerMetrics::new_for_tests()),
+        )
+        .await;
+
+        let client = Some(IpAddr::V4(Ipv4Addr::new(8, 7, 6, 5)));
+        let read = TrafficTally::new(client, None, Weight::zero()).with_route("object_info");
+        let transaction = TrafficTally::new(client, None, Weight::zero()).with_route("transaction");
+
+        // reads are not rate limited
+        for i in 0..3 {
+            let response = policy.handle_tally(read.clone());
+            assert_eq!(response.block_client, None, "Blocked at i = {}", i);
+        }
+
+        // transactions are, and are blocked only until the bucket refills
+        for i in 0..2 {
+            let response = policy.handle_tally(transaction.clone());
+            assert_eq!(response.block_client, None, "Blocked at i = {}", i);
+        }
+        let response = policy.handle_tally(transaction.clone());
+        assert_eq!(response.block_client, client);
+        assert!(response.client_block_ttl.unwrap() <= Duration::from_secs(1));
+
+        // the seventh request hits the connection threshold, and its block uses the configured
+        // TTL, even though the token bucket would also block the client
+        let response = policy.handle_tally(transaction.clone());
+        assert_eq!(response.block_client, client);
+        assert_eq!(response.client_block_ttl, None);
+    }
+
     #[sim_test]
     async fn test_traffic_sketch_mem_estimate() {
         // Test for getting a rough estimate of memory usage for the traffic sketch
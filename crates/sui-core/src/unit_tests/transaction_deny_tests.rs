@@ -13,7 +13,9 @@ use move_core_types::ident_str;
 use std::path::PathBuf;
 use std::sync::Arc;
 use sui_config::certificate_deny_config::CertificateDenyConfigBuilder;
-use sui_config::transaction_deny_config::{TransactionDenyConfig, TransactionDenyConfigBuilder};
+use sui_config::transaction_deny_config::{
+    TransactionDenyConfig, TransactionDenyConfigBuilder, TransactionDenyConfigPatch,
+};
 use sui_swarm_config::genesis_config::{AccountConfig, DEFAULT_GAS_AMOUNT};
 use sui_swarm_config::network_config::NetworkConfig;
 use sui_test_transaction_builder::TestTransactionBuilder;
@@ -240,6 +242,40 @@ async fn test_signer_denied() {
     assert_denied(&transfer_with_account(&accounts[2], &accounts[1], &state).await);
 }
 
+#[tokio::test]
+async fn test_deny_config_updated_at_runtime() {
+    let (network_config, state) = setup_test(TransactionDenyConfigBuilder::new().build()).await;
+    let accounts = get_accounts_and_coins(&network_config, &state);
+
+    // Deny the sender without re-creating the state.
+    let patch = TransactionDenyConfigPatch {
+        add_denied_addresses: vec![accounts[0].0],
+        ..Default::default()
+    };
+    state.set_transaction_deny_config(state.transaction_deny_config().patched(&patch));
+    assert_denied(&transfer_with_account(&accounts[0], &accounts[0], &state).await);
+    assert!(transfer_with_account(&accounts[1], &accounts[1], &state)
+        .await
+        .is_ok());
+
+    // Lift the address ban, but turn on the kill switch for all user transactions.
+    let patch = TransactionDenyConfigPatch {
+        remove_denied_addresses: vec![accounts[0].0],
+        user_transaction_disabled: Some(true),
+        ..Default::default()
+    };
+    let config = state.transaction_deny_config().patched(&patch);
+    assert!(config.get_address_deny_set().is_empty());
+    state.set_transaction_deny_config(config);
+    assert_denied(&transfer_with_account(&accounts[2], &accounts[2], &state).await);
+
+    // Replace the config wholesale, re-enabling user transactions.
+    state.set_transaction_deny_config(TransactionDenyConfigBuilder::new().build());
+    assert!(transfer_with_account(&accounts[0], &accounts[0], &state)
+        .await
+        .is_ok());
+}
+
 #[tokio::test]
 async fn test_shared_object_transaction_disabled() {
     let (network_config, state) = setup_test(
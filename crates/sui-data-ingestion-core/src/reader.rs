@@ -100,26 +100,17 @@ impl CheckpointReader {
     }
 
     async fn remote_fetch_checkpoint_internal(
-        store: &RemoteStore,
+        store: &dyn ObjectStore,
         checkpoint_number: CheckpointSequenceNumber,
     ) -> Result<(CheckpointData, usize)> {
-        match store {
-            RemoteStore::ObjectStore(store) => {
-                let path = Path::from(format!("{}.chk", checkpoint_number));
-                let response = store.get(&path).await?;
-                let bytes = response.bytes().await?;
-                Ok((Blob::from_bytes::<CheckpointData>(&bytes)?, bytes.len()))
-            }
-            RemoteStore::Rest(client) => {
-                let checkpoint = client.get_full_checkpoint(checkpoint_number).await?;
-                let size = bcs::serialized_size(&checkpoint)?;
-                Ok((checkpoint, size))
-            }
-        }
+        let path = Path::from(format!("{}.chk", checkpoint_number));
+        let response = store.get(&path).await?;
+        let bytes = response.bytes().await?;
+        Ok((Blob::from_bytes::<CheckpointData>(&bytes)?, bytes.len()))
     }
 
     async fn remote_fetch_checkpoint(
-        store: &RemoteStore,
+        store: &dyn ObjectStore,
         checkpoint_number: CheckpointSequenceNumber,
     ) -> Result<(CheckpointData, usize)> {
         let mut backoff = backoff::ExponentialBackoff::default();
@@ -168,15 +159,76 @@ impl CheckpointReader {
         };
 
         spawn_monitored_task!(async move {
-            let mut checkpoint_stream = (start_checkpoint..u64::MAX)
-                .map(|checkpoint_number| Self::remote_fetch_checkpoint(&store, checkpoint_number))
-                .pipe(futures::stream::iter)
-                .buffered(batch_size);
+            match store {
+                RemoteStore::ObjectStore(store) => {
+                    let mut checkpoint_stream = (start_checkpoint..u64::MAX)
+                        .map(|checkpoint_number| {
+                            Self::remote_fetch_checkpoint(store.as_ref(), checkpoint_number)
+                        })
+                        .pipe(futures::stream::iter)
+                        .buffered(batch_size);
 
-            while let Some(checkpoint) = checkpoint_stream.next().await {
-                if sender.send(checkpoint).await.is_err() {
-                    info!("remote reader dropped");
-                    break;
+                    while let Some(checkpoint) = checkpoint_stream.next().await {
+                        if sender.send(checkpoint).await.is_err() {
+                            info!("remote reader dropped");
+                            break;
+                        }
+                    }
+                }
+                RemoteStore::Rest(client) => {
+                    // The node pushes checkpoints as they are executed, so there is no need to
+                    // poll for them. If the stream fails or ends, it is reopened after the last
+                    // checkpoint received, backing off while the node is unavailable.
+                    let mut next_checkpoint = start_checkpoint;
+                    let mut backoff = backoff::ExponentialBackoff {
+                        initial_interval: Duration::from_millis(100),
+                        current_interval: Duration::from_millis(100),
+                        max_interval: Duration::from_secs(10),
+                        max_elapsed_time: None,
+                        ..Default::default()
+                    };
+                    loop {
+                        let err = match client.stream_full_checkpoints(next_checkpoint).await {
+                            Ok(checkpoint_stream) => {
+                                futures::pin_mut!(checkpoint_stream);
+                                loop {
+                                    let checkpoint = match checkpoint_stream.next().await {
+                                        Some(Ok(checkpoint)) => checkpoint,
+                                        Some(Err(err)) => break err,
+                                        None => break anyhow::anyhow!("checkpoint stream ended"),
+                                    };
+                                    let size = match bcs::serialized_size(&checkpoint) {
+                                        Ok(size) => size,
+                                        Err(err) => break err.into(),
+                                    };
+                                    backoff.reset();
+                                    next_checkpoint =
+                                        checkpoint.checkpoint_summary.sequence_number + 1;
+                                    if sender.send(Ok((checkpoint, size))).await.is_err() {
+                                        info!("remote reader dropped");
+                                        return;
+                                    }
+                                }
+                            }
+                            Err(err) => err,
+                        };
+
+                        let duration = backoff
+                            .next_backoff()
+                            .expect("backoff has no maximum elapsed time");
+                        error!(
+                            "remote reader transient error {:?}, reconnecting in {} ms",
+                            err,
+                            duration.as_millis()
+                        );
+                        tokio::select! {
+                            _ = tokio::time::sleep(duration) => {}
+                            _ = sender.closed() => {
+                                info!("remote reader dropped");
+                                return;
+                            }
+                        }
+                    }
                 }
             }
         });
@@ -10,6 +10,9 @@ use rand::rngs::OsRng;
 use std::sync::Arc;
 use sui::client_commands::{OptsWithGas, SuiClientCommandResult, SuiClientCommands};
 use sui_config::node::RunWithRange;
+use sui_config::transaction_deny_config::{
+    TransactionDenyConfigBuilder, TransactionDenyConfigPatch,
+};
 use sui_json_rpc_types::{EventFilter, TransactionFilter};
 use sui_json_rpc_types::{
     EventPage, SuiEvent, SuiExecutionStatus, SuiTransactionBlockEffectsAPI,
@@ -1405,3 +1408,55 @@ async fn test_full_node_run_with_range_epoch() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[sim_test]
+async fn test_transaction_deny_config_persists_across_restart() {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let denied_by_config = SuiAddress::random_for_testing_only();
+    let denied_by_admin = SuiAddress::random_for_testing_only();
+
+    let mut config = test_cluster
+        .fullnode_config_builder()
+        .build(&mut OsRng, test_cluster.swarm.config());
+    config.transaction_deny_config = TransactionDenyConfigBuilder::new()
+        .add_denied_address(denied_by_config)
+        .build();
+    let name = config.protocol_public_key();
+    let fullnode = test_cluster
+        .start_fullnode_from_config(config)
+        .await
+        .sui_node;
+
+    // Lift the node config's ban through the admin API, and add another one.
+    let patch = TransactionDenyConfigPatch {
+        add_denied_addresses: vec![denied_by_admin],
+        remove_denied_addresses: vec![denied_by_config],
+        package_publish_disabled: Some(true),
+        ..Default::default()
+    };
+    let deny_config = fullnode
+        .with_async(|node| async move { node.patch_transaction_deny_config(&patch).await })
+        .await
+        .unwrap();
+    assert!(!deny_config
+        .get_address_deny_set()
+        .contains(&denied_by_config));
+
+    let node = test_cluster.swarm.node(&name).unwrap();
+    node.stop();
+    node.start().await.unwrap();
+
+    // The admin API changes survive the restart, but cannot lift the node config's ban.
+    let deny_config = node
+        .get_node_handle()
+        .unwrap()
+        .with(|node| node.transaction_deny_config());
+    assert!(deny_config
+        .get_address_deny_set()
+        .contains(&denied_by_admin));
+    assert!(deny_config
+        .get_address_deny_set()
+        .contains(&denied_by_config));
+    assert!(deny_config.package_publish_disabled());
+    assert!(!deny_config.user_transaction_disabled());
+}
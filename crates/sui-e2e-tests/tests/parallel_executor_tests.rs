@@ -0,0 +1,155 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use futures::future::join_all;
+use std::time::Duration;
+use sui_json_rpc_types::{
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+};
+use sui_macros::sim_test;
+use sui_sdk::error::SuiRpcResult;
+use sui_sdk::parallel_executor::{ParallelExecutor, ParallelExecutorConfig};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{get_key_pair, Ed25519KeyPair, SuiKeyPair};
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::transaction::ProgrammableTransaction;
+use test_cluster::{TestCluster, TestClusterBuilder};
+use tokio::time::{sleep, timeout};
+
+/// An executor for a new address, funded with a single coin of `amount` MIST.
+async fn funded_executor(
+    test_cluster: &TestCluster,
+    amount: u64,
+    config: ParallelExecutorConfig,
+) -> ParallelExecutor {
+    let (address, keypair) = get_key_pair::<Ed25519KeyPair>();
+    test_cluster
+        .transfer_sui_must_exceed(test_cluster.get_address_0(), address, amount)
+        .await;
+    ParallelExecutor::new(
+        test_cluster.sui_client().clone(),
+        SuiKeyPair::Ed25519(keypair),
+        config,
+    )
+    .await
+    .unwrap()
+}
+
+fn pay_sui(recipient: SuiAddress, amount: u64) -> ProgrammableTransaction {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.pay_sui(vec![recipient], vec![amount]).unwrap();
+    builder.finish()
+}
+
+fn assert_success(response: SuiRpcResult<SuiTransactionBlockResponse>) {
+    let response = response.unwrap();
+    let effects = response.effects.unwrap();
+    assert!(effects.status().is_ok(), "{:?}", effects.status());
+}
+
+/// Balances of every coin of `address`.
+async fn coin_balances(test_cluster: &TestCluster, address: SuiAddress) -> Vec<u64> {
+    test_cluster
+        .sui_client()
+        .coin_read_api()
+        .get_coins(address, None, None, None)
+        .await
+        .unwrap()
+        .data
+        .into_iter()
+        .map(|coin| coin.balance)
+        .collect()
+}
+
+/// Wait for the fullnode to catch up with the pool of `executor`: rebalancing transactions only
+/// wait for their effects to be certified.
+async fn wait_for_pool(test_cluster: &TestCluster, executor: &ParallelExecutor) -> Vec<u64> {
+    let min_coin_balance = executor.config().min_coin_balance;
+    timeout(Duration::from_secs(30), async {
+        loop {
+            let balances = coin_balances(test_cluster, executor.sender()).await;
+            if balances.len() == executor.num_coins()
+                && balances.iter().all(|balance| *balance >= min_coin_balance)
+            {
+                return balances;
+            }
+            sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .expect("Coins of the sender do not match the pool")
+}
+
+#[sim_test]
+async fn test_more_transactions_than_coins() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let config = ParallelExecutorConfig {
+        num_coins: 4,
+        ..Default::default()
+    };
+    let executor = funded_executor(&test_cluster, 10_000_000_000, config).await;
+    assert_eq!(executor.num_coins(), 4);
+
+    // All transactions are in flight at once, contending for the coins of the pool.
+    let recipient = SuiAddress::random_for_testing_only();
+    let responses = join_all((0..20).map(|_| {
+        executor.execute(
+            pay_sui(recipient, 1),
+            SuiTransactionBlockResponseOptions::new(),
+        )
+    }))
+    .await;
+    for response in responses {
+        assert_success(response);
+    }
+
+    // Pipelined, as many in flight as there are coins.
+    let responses = executor
+        .execute_all(
+            (0..20).map(|_| pay_sui(recipient, 1)),
+            SuiTransactionBlockResponseOptions::new(),
+        )
+        .await;
+    for response in responses {
+        assert_success(response);
+    }
+
+    assert_eq!(coin_balances(&test_cluster, recipient).await, vec![1; 40]);
+    assert_eq!(executor.num_coins(), 4);
+    assert_eq!(wait_for_pool(&test_cluster, &executor).await.len(), 4);
+}
+
+#[sim_test]
+async fn test_rebalance_low_coins() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let config = ParallelExecutorConfig {
+        num_coins: 4,
+        gas_budget: 50_000_000,
+        min_coin_balance: 500_000_000,
+    };
+    let executor = funded_executor(&test_cluster, 4_000_000_000, config).await;
+
+    // The funding coin is split into the pool.
+    assert_eq!(executor.num_coins(), 4);
+    wait_for_pool(&test_cluster, &executor).await;
+
+    // Every payment leaves its coin below the minimum balance, to be merged back into the pool,
+    // which shrinks once there is not enough left for all of its coins.
+    let recipient = SuiAddress::random_for_testing_only();
+    for _ in 0..4 {
+        assert_success(
+            executor
+                .execute(
+                    pay_sui(recipient, 600_000_000),
+                    SuiTransactionBlockResponseOptions::new(),
+                )
+                .await,
+        );
+        wait_for_pool(&test_cluster, &executor).await;
+    }
+    assert!(executor.num_coins() < 4);
+    assert_eq!(
+        coin_balances(&test_cluster, recipient).await,
+        vec![600_000_000; 4]
+    );
+}
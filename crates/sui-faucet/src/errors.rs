@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::QuotaExceeded;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -40,6 +41,9 @@ pub enum FaucetError {
     #[error("Coin amounts sent are incorrect:`{0}`")]
     CoinAmountTransferredIncorrect(String),
 
+    #[error(transparent)]
+    QuotaExceeded(#[from] QuotaExceeded),
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
@@ -6,8 +6,10 @@ use serde::{Deserialize, Serialize};
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
 use uuid::Uuid;
 
+mod quota;
 mod simple_faucet;
 mod write_ahead_log;
+pub use self::quota::{FaucetQuotas, QuotaExceeded, QuotaKind, QuotaReservation};
 pub use self::simple_faucet::SimpleFaucet;
 use clap::Parser;
 use std::{net::Ipv4Addr, path::PathBuf};
@@ -114,6 +116,31 @@ pub struct FaucetConfig {
 
     #[clap(long, action = clap::ArgAction::Set, default_value_t = false)]
     pub batch_enabled: bool,
+
+    /// Maximum amount of MIST sent to a single recipient address within the quota window.
+    /// Unlimited if not set.
+    #[clap(long)]
+    pub address_quota: Option<u64>,
+
+    /// Maximum amount of MIST sent to requests from a single client IP within the quota window.
+    /// Unlimited if not set.
+    #[clap(long)]
+    pub ip_quota: Option<u64>,
+
+    /// Length of the rolling window that quotas apply to.
+    #[clap(long, default_value_t = 86400)]
+    pub quota_window_secs: u64,
+
+    /// Where to persist how much was sent to each address and IP. Defaults to a `.quota` store
+    /// next to the write ahead log.
+    #[clap(long)]
+    pub quota_store: Option<PathBuf>,
+
+    /// Number of proxies in front of the faucet that append the address of their client to the
+    /// `X-Forwarded-For` header. The client IP is the entry appended by the outermost of them, as
+    /// the entries before it are set by the client. The header is ignored if this is 0.
+    #[clap(long, default_value_t = 0)]
+    pub trusted_proxies: usize,
 }
 
 impl Default for FaucetConfig {
@@ -132,6 +159,11 @@ impl Default for FaucetConfig {
             batch_request_size: 500,
             ttl_expiration: 300,
             batch_enabled: false,
+            address_quota: None,
+            ip_quota: None,
+            quota_window_secs: 86400,
+            quota_store: None,
+            trusted_proxies: 0,
         }
     }
 }
@@ -0,0 +1,442 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt;
+use std::net::IpAddr;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::SuiAddress;
+use thiserror::Error;
+use tokio::sync::Mutex;
+use typed_store::rocks::DBMap;
+use typed_store::traits::{TableSummary, TypedStoreDebug};
+use typed_store::{Map, TypedStoreError};
+use typed_store_derive::DBMapUtils;
+
+/// Persistent record of how much the faucet has sent to each recipient address and client IP,
+/// used to enforce rolling-window quotas across restarts.
+#[derive(DBMapUtils)]
+pub struct QuotaStore {
+    pub grants: DBMap<QuotaKey, Vec<Grant>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum QuotaKey {
+    Address(SuiAddress),
+    Ip(IpAddr),
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub struct Grant {
+    pub timestamp_ms: u64,
+    pub amount: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum QuotaKind {
+    Address,
+    Ip,
+}
+
+impl fmt::Display for QuotaKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QuotaKind::Address => write!(f, "address"),
+            QuotaKind::Ip => write!(f, "IP"),
+        }
+    }
+}
+
+/// Returned to clients whose request would take them over one of their quotas.
+#[derive(Error, Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+#[error(
+    "Request exceeds the {kind} quota of {quota} MIST per {window_secs} seconds, try again in {retry_after_secs} seconds"
+)]
+pub struct QuotaExceeded {
+    pub kind: QuotaKind,
+    pub quota: u64,
+    pub window_secs: u64,
+    pub retry_after_secs: u64,
+}
+
+/// A grant charged against the quotas of a request, which can be released if the request fails.
+#[derive(Debug, Clone)]
+pub struct QuotaReservation {
+    keys: Vec<QuotaKey>,
+    grant: Grant,
+}
+
+/// Rolling-window quotas on the amount sent per recipient address and per client IP. A quota of
+/// `None` is unlimited.
+pub struct FaucetQuotas {
+    address_quota: Option<u64>,
+    ip_quota: Option<u64>,
+    window_ms: u64,
+    // Serializes reservations, so that concurrent requests cannot both pass the same check.
+    store: Mutex<QuotaStore>,
+}
+
+impl QuotaStore {
+    pub(crate) fn open(path: &Path) -> Self {
+        Self::open_tables_read_write(
+            path.to_path_buf(),
+            typed_store::rocks::MetricConf::new("faucet_quota_store"),
+            None,
+            None,
+        )
+    }
+}
+
+impl FaucetQuotas {
+    pub(crate) fn new(
+        store: QuotaStore,
+        address_quota: Option<u64>,
+        ip_quota: Option<u64>,
+        window_secs: u64,
+    ) -> Self {
+        Self {
+            address_quota,
+            ip_quota,
+            window_ms: window_secs * 1000,
+            store: Mutex::new(store),
+        }
+    }
+
+    /// Charge `amount` against the quotas of `recipient` and, if known, `ip`. Fails without
+    /// charging anything if either quota would be exceeded.
+    pub async fn reserve(
+        &self,
+        recipient: SuiAddress,
+        ip: Option<IpAddr>,
+        amount: u64,
+    ) -> Result<Result<QuotaReservation, QuotaExceeded>, TypedStoreError> {
+        self.reserve_at(recipient, ip, amount, now_ms()).await
+    }
+
+    async fn reserve_at(
+        &self,
+        recipient: SuiAddress,
+        ip: Option<IpAddr>,
+        amount: u64,
+        now_ms: u64,
+    ) -> Result<Result<QuotaReservation, QuotaExceeded>, TypedStoreError> {
+        let grant = Grant {
+            timestamp_ms: now_ms,
+            amount,
+        };
+        let mut limits = vec![];
+        if let Some(quota) = self.address_quota {
+            limits.push((QuotaKey::Address(recipient), QuotaKind::Address, quota));
+        }
+        if let (Some(ip), Some(quota)) = (ip, self.ip_quota) {
+            limits.push((QuotaKey::Ip(ip), QuotaKind::Ip, quota));
+        }
+        if limits.is_empty() {
+            return Ok(Ok(QuotaReservation {
+                keys: vec![],
+                grant,
+            }));
+        }
+
+        let store = self.store.lock().await;
+        let mut charged = vec![];
+        for (key, kind, quota) in limits {
+            let grants = self.live_grants(&store, &key, now_ms)?;
+            if let Some(retry_after_ms) = self.retry_after(&grants, quota, amount, now_ms) {
+                return Ok(Err(QuotaExceeded {
+                    kind,
+                    quota,
+                    window_secs: self.window_ms / 1000,
+                    retry_after_secs: retry_after_ms.div_ceil(1000),
+                }));
+            }
+            charged.push((key, grants));
+        }
+
+        let mut batch = store.grants.batch();
+        batch.insert_batch(
+            &store.grants,
+            charged.iter().map(|(key, grants)| {
+                let mut grants = grants.clone();
+                grants.push(grant);
+                (*key, grants)
+            }),
+        )?;
+        batch.write()?;
+
+        Ok(Ok(QuotaReservation {
+            keys: charged.into_iter().map(|(key, _)| key).collect(),
+            grant,
+        }))
+    }
+
+    /// Undo `reservation`, for requests that did not end up sending anything.
+    pub async fn release(&self, reservation: QuotaReservation) -> Result<(), TypedStoreError> {
+        if reservation.keys.is_empty() {
+            return Ok(());
+        }
+        let store = self.store.lock().await;
+        for key in reservation.keys {
+            let Some(mut grants) = store.grants.get(&key)? else {
+                continue;
+            };
+            if let Some(position) = grants.iter().position(|g| *g == reservation.grant) {
+                grants.remove(position);
+                store.grants.insert(&key, &grants)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop the grants that fell out of the window, and the keys left without any, so that the
+    /// store only holds the addresses and IPs served within the last window.
+    pub async fn prune(&self) -> Result<(), TypedStoreError> {
+        self.prune_at(now_ms()).await
+    }
+
+    async fn prune_at(&self, now_ms: u64) -> Result<(), TypedStoreError> {
+        let store = self.store.lock().await;
+        let mut expired = vec![];
+        let mut updated = vec![];
+        for entry in store.grants.safe_iter() {
+            let (key, mut grants) = entry?;
+            let len = grants.len();
+            grants.retain(|g| g.timestamp_ms + self.window_ms > now_ms);
+            if grants.is_empty() {
+                expired.push(key);
+            } else if grants.len() < len {
+                updated.push((key, grants));
+            }
+        }
+
+        let mut batch = store.grants.batch();
+        batch.delete_batch(&store.grants, expired)?;
+        batch.insert_batch(&store.grants, updated)?;
+        batch.write()
+    }
+
+    /// Grants of `key` that are still within the window, oldest first.
+    fn live_grants(
+        &self,
+        store: &QuotaStore,
+        key: &QuotaKey,
+        now_ms: u64,
+    ) -> Result<Vec<Grant>, TypedStoreError> {
+        let mut grants = store.grants.get(key)?.unwrap_or_default();
+        grants.retain(|g| g.timestamp_ms + self.window_ms > now_ms);
+        Ok(grants)
+    }
+
+    /// How long until `amount` fits in `quota` given `grants`, or `None` if it fits now.
+    fn retry_after(&self, grants: &[Grant], quota: u64, amount: u64, now_ms: u64) -> Option<u64> {
+        let mut used: u64 = grants.iter().map(|g| g.amount).sum();
+        if used + amount <= quota {
+            return None;
+        }
+        if amount > quota {
+            // Never fits, but there is nothing better to tell the client.
+            return Some(self.window_ms);
+        }
+        for grant in grants {
+            used -= grant.amount;
+            if used + amount <= quota {
+                return Some(grant.timestamp_ms + self.window_ms - now_ms);
+            }
+        }
+        unreachable!("amount fits in quota once every grant expired")
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW_SECS: u64 = 60;
+
+    fn open_quotas(path: &Path, address_quota: Option<u64>, ip_quota: Option<u64>) -> FaucetQuotas {
+        FaucetQuotas::new(QuotaStore::open(path), address_quota, ip_quota, WINDOW_SECS)
+    }
+
+    #[tokio::test]
+    async fn address_quota() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = open_quotas(&tmp.path().join("quota"), Some(100), None);
+        let recipient = SuiAddress::random_for_testing_only();
+
+        quotas
+            .reserve_at(recipient, None, 60, 1_000)
+            .await
+            .unwrap()
+            .unwrap();
+        let exceeded = quotas
+            .reserve_at(recipient, None, 60, 2_000)
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            exceeded,
+            QuotaExceeded {
+                kind: QuotaKind::Address,
+                quota: 100,
+                window_secs: WINDOW_SECS,
+                retry_after_secs: 59,
+            }
+        );
+
+        // Other recipients are not affected
+        quotas
+            .reserve_at(SuiAddress::random_for_testing_only(), None, 60, 2_000)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Once the first grant leaves the window, the request fits
+        quotas
+            .reserve_at(recipient, None, 60, 61_000)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn ip_quota() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = open_quotas(&tmp.path().join("quota"), Some(100), Some(150));
+        let ip = IpAddr::from([10, 0, 0, 1]);
+
+        quotas
+            .reserve_at(SuiAddress::random_for_testing_only(), Some(ip), 100, 1_000)
+            .await
+            .unwrap()
+            .unwrap();
+        let exceeded = quotas
+            .reserve_at(SuiAddress::random_for_testing_only(), Some(ip), 100, 2_000)
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(exceeded.kind, QuotaKind::Ip);
+
+        // Requests without a known IP are only subject to the address quota
+        quotas
+            .reserve_at(SuiAddress::random_for_testing_only(), None, 100, 2_000)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Requests larger than the quota never fit
+        let exceeded = quotas
+            .reserve_at(SuiAddress::random_for_testing_only(), None, 101, 2_000)
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(exceeded.retry_after_secs, WINDOW_SECS);
+    }
+
+    #[tokio::test]
+    async fn release_refunds_quota() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = open_quotas(&tmp.path().join("quota"), Some(100), Some(100));
+        let recipient = SuiAddress::random_for_testing_only();
+        let ip = IpAddr::from([10, 0, 0, 1]);
+
+        let reservation = quotas
+            .reserve_at(recipient, Some(ip), 100, 1_000)
+            .await
+            .unwrap()
+            .unwrap();
+        quotas.release(reservation).await.unwrap();
+
+        quotas
+            .reserve_at(recipient, Some(ip), 100, 2_000)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn prune_expired_grants() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = open_quotas(&tmp.path().join("quota"), Some(100), Some(100));
+        let old = SuiAddress::random_for_testing_only();
+        let recent = SuiAddress::random_for_testing_only();
+        let ip = IpAddr::from([10, 0, 0, 1]);
+
+        for (recipient, now_ms) in [(old, 1_000), (recent, 1_000), (recent, 30_000)] {
+            quotas
+                .reserve_at(recipient, Some(ip), 10, now_ms)
+                .await
+                .unwrap()
+                .unwrap();
+        }
+        quotas.prune_at(61_000).await.unwrap();
+
+        let store = quotas.store.lock().await;
+        let grants = |key| store.grants.get(&key).unwrap();
+        assert_eq!(grants(QuotaKey::Address(old)), None);
+        assert_eq!(
+            grants(QuotaKey::Address(recent)),
+            Some(vec![Grant {
+                timestamp_ms: 30_000,
+                amount: 10
+            }])
+        );
+        assert_eq!(grants(QuotaKey::Ip(ip)).unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn disabled_quotas_are_not_recorded() {
+        let tmp = tempfile::tempdir().unwrap();
+        let quotas = open_quotas(&tmp.path().join("quota"), None, None);
+        let recipient = SuiAddress::random_for_testing_only();
+
+        let reservation = quotas
+            .reserve_at(recipient, Some(IpAddr::from([10, 0, 0, 1])), 100, 1_000)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(reservation.keys.is_empty());
+        quotas.release(reservation).await.unwrap();
+        assert!(quotas
+            .store
+            .lock()
+            .await
+            .grants
+            .unbounded_iter()
+            .next()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn quota_survives_restart() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("quota");
+        let recipient = SuiAddress::random_for_testing_only();
+
+        {
+            let quotas = open_quotas(&path, Some(100), None);
+            quotas
+                .reserve_at(recipient, None, 100, 1_000)
+                .await
+                .unwrap()
+                .unwrap();
+        }
+
+        let quotas = open_quotas(&path, Some(100), None);
+        assert!(quotas
+            .reserve_at(recipient, None, 1, 2_000)
+            .await
+            .unwrap()
+            .is_err());
+    }
+}
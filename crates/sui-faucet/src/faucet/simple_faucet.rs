@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::faucet::quota::{FaucetQuotas, QuotaReservation, QuotaStore};
 use crate::faucet::write_ahead_log;
 use crate::metrics::FaucetMetrics;
 use async_recursion::async_recursion;
@@ -12,6 +13,7 @@ use std::collections::HashMap;
 #[cfg(test)]
 use std::collections::HashSet;
 use std::fmt;
+use std::net::IpAddr;
 use std::path::Path;
 use std::sync::{Arc, Weak};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
@@ -61,6 +63,7 @@ pub struct SimpleFaucet {
     task_id_cache: Mutex<TtlCache<Uuid, BatchSendStatus>>,
     ttl_expiration: u64,
     coin_amount: u64,
+    quotas: FaucetQuotas,
     /// Shuts down the batch transfer task. Used only in testing.
     #[allow(unused)]
     batch_transfer_shutdown: parking_lot::Mutex<Option<oneshot::Sender<()>>>,
@@ -119,6 +122,18 @@ impl SimpleFaucet {
         let metrics = FaucetMetrics::new(prometheus_registry);
 
         let wal = WriteAheadLog::open(wal_path);
+        let quota_store = QuotaStore::open(
+            &config
+                .quota_store
+                .clone()
+                .unwrap_or_else(|| wal_path.with_extension("quota")),
+        );
+        let quotas = FaucetQuotas::new(
+            quota_store,
+            config.address_quota,
+            config.ip_quota,
+            config.quota_window_secs,
+        );
         let mut pending = vec![];
 
         let (producer, consumer) = mpsc::channel(coins.len());
@@ -188,6 +203,7 @@ impl SimpleFaucet {
             task_id_cache: TtlCache::new(config.max_request_per_second as usize * 60 * 10).into(),
             ttl_expiration: config.ttl_expiration,
             coin_amount: config.amount,
+            quotas,
             batch_transfer_shutdown: parking_lot::Mutex::new(Some(batch_transfer_shutdown)),
         };
 
@@ -219,6 +235,23 @@ impl SimpleFaucet {
                 }
             }
         });
+        if config.address_quota.is_some() || config.ip_quota.is_some() {
+            let quotas_clone = Arc::downgrade(&arc_faucet);
+            let prune_interval = Duration::from_secs(config.quota_window_secs.max(1));
+            spawn_monitored_task!(async move {
+                info!("Starting task to prune expired quota grants.");
+                let mut interval = tokio::time::interval(prune_interval);
+                loop {
+                    interval.tick().await;
+                    let Some(faucet) = quotas_clone.upgrade() else {
+                        return;
+                    };
+                    if let Err(e) = faucet.quotas.prune().await {
+                        error!("Failed to prune quota store: {e:?}");
+                    }
+                }
+            });
+        }
         // Retrying all the pending transactions from the WAL, before continuing.  Ignore return
         // values -- if the executions failed, the pending coins will simply remain in the WAL, and
         // not recycled.
@@ -230,6 +263,36 @@ impl SimpleFaucet {
         Ok(arc_faucet)
     }
 
+    /// Charge a request sending `amounts` to `recipient`, made from `ip`, against the address and
+    /// IP quotas. The reservation should be released if the request ends up sending nothing.
+    pub async fn reserve_quota(
+        &self,
+        recipient: SuiAddress,
+        ip: Option<IpAddr>,
+        amounts: &[u64],
+    ) -> Result<QuotaReservation, FaucetError> {
+        let amount = amounts.iter().sum();
+        match self
+            .quotas
+            .reserve(recipient, ip, amount)
+            .await
+            .map_err(FaucetError::internal)?
+        {
+            Ok(reservation) => Ok(reservation),
+            Err(exceeded) => {
+                info!(?recipient, ?ip, "Request exceeds quota: {exceeded}");
+                self.metrics.total_requests_over_quota.inc();
+                Err(exceeded.into())
+            }
+        }
+    }
+
+    pub async fn release_quota(&self, reservation: QuotaReservation) {
+        if let Err(e) = self.quotas.release(reservation).await {
+            error!("Failed to release quota reservation: {e:?}");
+        }
+    }
+
     /// Take the consumer lock and pull a Coin ID from the queue, without checking whether it is
     /// valid or not.
     async fn pop_gas_coin(&self, uuid: Uuid) -> Option<ObjectID> {
@@ -3,8 +3,8 @@
 
 use axum::{
     error_handling::HandleErrorLayer,
-    extract::Path,
-    http::StatusCode,
+    extract::{ConnectInfo, Path},
+    http::{HeaderMap, StatusCode},
     response::IntoResponse,
     routing::{get, post},
     BoxError, Extension, Json, Router,
@@ -22,7 +22,8 @@ use std::{
 use sui_config::{sui_config_dir, SUI_CLIENT_CONFIG};
 use sui_faucet::{
     BatchFaucetResponse, BatchStatusFaucetResponse, Faucet, FaucetConfig, FaucetError,
-    FaucetRequest, FaucetResponse, RequestMetricsLayer, SimpleFaucet,
+    FaucetRequest, FaucetResponse, FixedAmountRequest, QuotaReservation, RequestMetricsLayer,
+    SimpleFaucet,
 };
 use sui_sdk::wallet_context::WalletContext;
 use tower::{limit::RateLimitLayer, ServiceBuilder};
@@ -121,7 +122,7 @@ async fn main() -> Result<(), anyhow::Error> {
     let addr = SocketAddr::new(IpAddr::V4(host_ip), port);
     info!("listening on {}", addr);
     axum::Server::bind(&addr)
-        .serve(app.into_make_service())
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
         .await?;
     Ok(())
 }
@@ -134,6 +135,8 @@ async fn health() -> &'static str {
 /// handler for batch_request_gas requests
 async fn batch_request_gas(
     Extension(state): Extension<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<FaucetRequest>,
 ) -> impl IntoResponse {
     let id = Uuid::new_v4();
@@ -149,16 +152,23 @@ async fn batch_request_gas(
         );
     };
 
+    let amounts = vec![state.config.amount; state.config.num_coins];
+    let reservation = match reserve_quota(&state, &headers, addr, &request, &amounts).await {
+        Ok(reservation) => reservation,
+        Err((status, e)) => return (status, Json(BatchFaucetResponse::from(e))),
+    };
+
     if state.config.batch_enabled {
         let result = spawn_monitored_task!(async move {
-            state
+            let result = state
                 .faucet
-                .batch_send(
-                    id,
-                    request.recipient,
-                    &vec![state.config.amount; state.config.num_coins],
-                )
-                .await
+                .batch_send(id, request.recipient, &amounts)
+                .await;
+            // Nothing will be sent for requests that could not be queued
+            if result.is_err() {
+                state.faucet.release_quota(reservation).await;
+            }
+            result
         })
         .await
         .unwrap();
@@ -180,14 +190,11 @@ async fn batch_request_gas(
         // TODO (jian): remove this feature gate when batch has proven to be baked long enough
         info!(uuid = ?id, "Falling back to v1 implementation");
         let result = spawn_monitored_task!(async move {
-            state
-                .faucet
-                .send(
-                    id,
-                    request.recipient,
-                    &vec![state.config.amount; state.config.num_coins],
-                )
-                .await
+            let result = state.faucet.send(id, request.recipient, &amounts).await;
+            if result.is_err() {
+                state.faucet.release_quota(reservation).await;
+            }
+            result
         })
         .await
         .unwrap();
@@ -239,6 +246,8 @@ async fn request_status(
 /// handler for all the request_gas requests
 async fn request_gas(
     Extension(state): Extension<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<FaucetRequest>,
 ) -> impl IntoResponse {
     // ID for traceability
@@ -246,17 +255,20 @@ async fn request_gas(
     info!(uuid = ?id, "Got new gas request.");
     let result = match payload {
         FaucetRequest::FixedAmountRequest(requests) => {
+            let amounts = vec![state.config.amount; state.config.num_coins];
+            let reservation = match reserve_quota(&state, &headers, addr, &requests, &amounts).await
+            {
+                Ok(reservation) => reservation,
+                Err((status, e)) => return (status, Json(FaucetResponse::from(e))),
+            };
             // We spawn a tokio task for this such that connection drop will not interrupt
             // it and impact the recycling of coins
             spawn_monitored_task!(async move {
-                state
-                    .faucet
-                    .send(
-                        id,
-                        requests.recipient,
-                        &vec![state.config.amount; state.config.num_coins],
-                    )
-                    .await
+                let result = state.faucet.send(id, requests.recipient, &amounts).await;
+                if result.is_err() {
+                    state.faucet.release_quota(reservation).await;
+                }
+                result
             })
             .await
             .unwrap()
@@ -285,6 +297,50 @@ async fn request_gas(
     }
 }
 
+/// Charge the request against the quotas of its recipient and of the client's IP.
+async fn reserve_quota(
+    state: &AppState,
+    headers: &HeaderMap,
+    addr: SocketAddr,
+    request: &FixedAmountRequest,
+    amounts: &[u64],
+) -> Result<QuotaReservation, (StatusCode, FaucetError)> {
+    let ip = client_ip(headers, addr, state.config.trusted_proxies);
+
+    state
+        .faucet
+        .reserve_quota(request.recipient, Some(ip), amounts)
+        .await
+        .map_err(|e| {
+            warn!(recipient = ?request.recipient, ?ip, "Failed to reserve quota: {:?}", e);
+            let status = match e {
+                FaucetError::QuotaExceeded(_) => StatusCode::TOO_MANY_REQUESTS,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (status, e)
+        })
+}
+
+/// The IP of the client, which is the peer address unless there are `trusted_proxies` in front of
+/// the faucet. Each proxy appends the address it got the request from to `X-Forwarded-For`, so
+/// only the last `trusted_proxies` entries can be trusted, and the client IP is the first of them.
+fn client_ip(headers: &HeaderMap, addr: SocketAddr, trusted_proxies: usize) -> IpAddr {
+    if trusted_proxies == 0 {
+        return addr.ip();
+    }
+    let entries: Vec<&str> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .collect();
+    entries
+        .len()
+        .checked_sub(trusted_proxies)
+        .and_then(|i| entries[i].trim().parse().ok())
+        .unwrap_or(addr.ip())
+}
+
 fn create_wallet_context(timeout_secs: u64) -> Result<WalletContext, anyhow::Error> {
     let wallet_conf = sui_config_dir()?.join(SUI_CLIENT_CONFIG);
     info!("Initialize wallet from config path: {:?}", wallet_conf);
@@ -308,3 +364,37 @@ async fn handle_error(error: BoxError) -> impl IntoResponse {
         Cow::from(format!("Unhandled internal error: {}", error)),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_ip() {
+        let peer = SocketAddr::from(([192, 168, 0, 1], 5003));
+        let mut headers = HeaderMap::new();
+        // The first entry is spoofed by the client, the proxy appended the real one.
+        headers.insert("x-forwarded-for", "1.2.3.4, 10.0.0.1".parse().unwrap());
+
+        assert_eq!(client_ip(&headers, peer, 0), peer.ip());
+        assert_eq!(client_ip(&headers, peer, 1), IpAddr::from([10, 0, 0, 1]));
+
+        // Behind two proxies, the client IP is appended by the outer one.
+        headers.insert(
+            "x-forwarded-for",
+            "1.2.3.4, 10.0.0.1, 172.16.0.1".parse().unwrap(),
+        );
+        assert_eq!(client_ip(&headers, peer, 2), IpAddr::from([10, 0, 0, 1]));
+
+        // Entries can be split over several headers.
+        headers.insert("x-forwarded-for", "1.2.3.4".parse().unwrap());
+        headers.append("x-forwarded-for", "10.0.0.1".parse().unwrap());
+        assert_eq!(client_ip(&headers, peer, 1), IpAddr::from([10, 0, 0, 1]));
+
+        // Without enough entries, or with an invalid one, the peer address is used.
+        assert_eq!(client_ip(&headers, peer, 3), peer.ip());
+        headers.insert("x-forwarded-for", "1.2.3.4, unknown".parse().unwrap());
+        assert_eq!(client_ip(&headers, peer, 1), peer.ip());
+        assert_eq!(client_ip(&HeaderMap::new(), peer, 1), peer.ip());
+    }
+}
@@ -28,6 +28,7 @@ pub struct FaucetMetrics {
     pub(crate) total_available_coins: IntGauge,
     pub(crate) total_discarded_coins: IntGauge,
     pub(crate) total_coin_requests_succeeded: IntGauge,
+    pub(crate) total_requests_over_quota: IntCounter,
 }
 
 const LATENCY_SEC_BUCKETS: &[f64] = &[
@@ -112,6 +113,12 @@ impl FaucetMetrics {
                 registry,
             )
             .unwrap(),
+            total_requests_over_quota: register_int_counter_with_registry!(
+                "total_requests_over_quota",
+                "Total number of requests rejected for exceeding an address or IP quota",
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
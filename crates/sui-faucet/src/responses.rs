@@ -10,11 +10,15 @@ use uuid::Uuid;
 pub struct FaucetResponse {
     pub transferred_gas_objects: Vec<CoinInfo>,
     pub error: Option<String>,
+    /// Set when the request was rejected for exceeding a quota.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota_exceeded: Option<QuotaExceeded>,
 }
 
 impl From<FaucetError> for FaucetResponse {
     fn from(e: FaucetError) -> Self {
         Self {
+            quota_exceeded: quota_exceeded(&e),
             error: Some(e.to_string()),
             transferred_gas_objects: vec![],
         }
@@ -26,6 +30,7 @@ impl From<FaucetReceipt> for FaucetResponse {
         Self {
             transferred_gas_objects: v.sent,
             error: None,
+            quota_exceeded: None,
         }
     }
 }
@@ -36,11 +41,15 @@ pub struct BatchFaucetResponse {
     // This string is the Uuid for the req
     pub task: Option<String>,
     pub error: Option<String>,
+    /// Set when the request was rejected for exceeding a quota.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota_exceeded: Option<QuotaExceeded>,
 }
 
 impl From<FaucetError> for BatchFaucetResponse {
     fn from(e: FaucetError) -> Self {
         Self {
+            quota_exceeded: quota_exceeded(&e),
             error: Some(e.to_string()),
             task: None,
         }
@@ -52,6 +61,7 @@ impl From<BatchFaucetReceipt> for BatchFaucetResponse {
         Self {
             task: Some(v.task),
             error: None,
+            quota_exceeded: None,
         }
     }
 }
@@ -61,6 +71,7 @@ impl From<Uuid> for BatchFaucetResponse {
         Self {
             task: Some(v.to_string()),
             error: None,
+            quota_exceeded: None,
         }
     }
 }
@@ -90,3 +101,10 @@ impl From<BatchSendStatus> for BatchStatusFaucetResponse {
         }
     }
 }
+
+fn quota_exceeded(e: &FaucetError) -> Option<QuotaExceeded> {
+    match e {
+        FaucetError::QuotaExceeded(exceeded) => Some(exceeded.clone()),
+        _ => None,
+    }
+}
@@ -24,10 +24,17 @@ pub(crate) struct WatermarkTask {
     cancel: CancellationToken,
     sender: watch::Sender<u64>,
     receiver: watch::Receiver<u64>,
+    checkpoint_sender: watch::Sender<u64>,
+    checkpoint_receiver: watch::Receiver<u64>,
 }
 
 pub(crate) type WatermarkLock = Arc<RwLock<Watermark>>;
 
+/// Receiver that is notified whenever the checkpoint high watermark advances. Subscriptions use
+/// this to learn when there is newly indexed data to send to their subscribers. It holds zero until
+/// the watermark is first read from the database.
+pub(crate) type CheckpointReceiver = watch::Receiver<u64>;
+
 /// Watermark used by GraphQL queries to ensure cross-query consistency and flag epoch-boundary
 /// changes.
 #[derive(Clone, Copy, Default)]
@@ -47,6 +54,7 @@ impl WatermarkTask {
         cancel: CancellationToken,
     ) -> Self {
         let (sender, receiver) = watch::channel(0);
+        let (checkpoint_sender, checkpoint_receiver) = watch::channel(0);
 
         Self {
             watermark: Default::default(),
@@ -56,6 +64,8 @@ impl WatermarkTask {
             cancel,
             sender,
             receiver,
+            checkpoint_sender,
+            checkpoint_receiver,
         }
     }
 
@@ -78,15 +88,21 @@ impl WatermarkTask {
                     };
 
                     // Write the watermark as follows to limit how long we hold the lock
-                    let prev_epoch = {
+                    let (prev_checkpoint, prev_epoch) = {
                         let mut w = self.watermark.write().await;
-                        w.checkpoint = checkpoint;
-                        mem::replace(&mut w.epoch, epoch)
+                        (
+                            mem::replace(&mut w.checkpoint, checkpoint),
+                            mem::replace(&mut w.epoch, epoch),
+                        )
                     };
 
                     if epoch > prev_epoch {
                         self.sender.send(epoch).unwrap();
                     }
+
+                    if checkpoint > prev_checkpoint {
+                        self.checkpoint_sender.send(checkpoint).unwrap();
+                    }
                 }
             }
         }
@@ -100,6 +116,11 @@ impl WatermarkTask {
     pub(crate) fn epoch_receiver(&self) -> watch::Receiver<u64> {
         self.receiver.clone()
     }
+
+    /// Receiver for subscribing to checkpoint watermark changes.
+    pub(crate) fn checkpoint_receiver(&self) -> CheckpointReceiver {
+        self.checkpoint_receiver.clone()
+    }
 }
 
 impl Watermark {
@@ -0,0 +1,391 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::VecDeque;
+use std::future::Future;
+
+use async_graphql::connection::{Connection, CursorType};
+use async_graphql::*;
+use futures::{stream, Stream};
+
+use crate::config::ServiceConfig;
+use crate::data::Db;
+use crate::error::Error;
+use crate::server::watermark_task::CheckpointReceiver;
+use crate::types::checkpoint::{self, Checkpoint, CheckpointCursor};
+use crate::types::cursor::Page;
+use crate::types::event::{Event, EventFilter};
+use crate::types::transaction_block::{TransactionBlock, TransactionBlockFilter};
+
+pub struct Subscription;
+
+/// Subscriptions stream data from the Sui network as it is indexed.
+///
+/// Each subscription starts from the checkpoint that the service had indexed up to when it was
+/// created, and sends every matching value from later checkpoints, in order, as the indexer's
+/// watermark advances.
+#[Subscription]
+impl Subscription {
+    /// Stream newly indexed transaction blocks, optionally filtered to only include transaction
+    /// blocks that match `filter` (the same filters supported by `Query.transactionBlocks`).
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<TransactionBlockFilter>,
+    ) -> Result<impl Stream<Item = Result<TransactionBlock>>> {
+        let watermark = checkpoint_receiver(ctx)?;
+        let db: Db = ctx.data_unchecked::<Db>().clone();
+        let config: ServiceConfig = ctx.data_unchecked::<ServiceConfig>().clone();
+        let filter = filter.unwrap_or_default();
+
+        Ok(watch_checkpoints(
+            watermark,
+            move |after, checkpoint_viewed_at, cursor| {
+                let (db, config, filter) = (db.clone(), config.clone(), filter.clone());
+                async move {
+                    let Some(filter) = filter.intersect(TransactionBlockFilter {
+                        after_checkpoint: Some(after),
+                        ..Default::default()
+                    }) else {
+                        return Ok((vec![], None));
+                    };
+
+                    paginate_page(&config, cursor, |page| {
+                        TransactionBlock::paginate(&db, page, filter, checkpoint_viewed_at)
+                    })
+                    .await
+                }
+            },
+        ))
+    }
+
+    /// Stream newly indexed events, optionally filtered to only include events that match
+    /// `filter` (the same filters supported by `Query.events`).
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<EventFilter>,
+    ) -> Result<impl Stream<Item = Result<Event>>> {
+        let watermark = checkpoint_receiver(ctx)?;
+        let db: Db = ctx.data_unchecked::<Db>().clone();
+        let config: ServiceConfig = ctx.data_unchecked::<ServiceConfig>().clone();
+        let filter = filter.unwrap_or_default();
+
+        Ok(watch_checkpoints(
+            watermark,
+            move |after, checkpoint_viewed_at, cursor| {
+                let (db, config, filter) = (db.clone(), config.clone(), filter.clone());
+                async move {
+                    paginate_page(&config, cursor, |page| {
+                        Event::paginate_after_checkpoint(
+                            &db,
+                            page,
+                            filter,
+                            Some(after),
+                            checkpoint_viewed_at,
+                        )
+                    })
+                    .await
+                }
+            },
+        ))
+    }
+
+    /// Stream checkpoints as they are indexed.
+    async fn checkpoints(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<impl Stream<Item = Result<Checkpoint>>> {
+        let watermark = checkpoint_receiver(ctx)?;
+        let db: Db = ctx.data_unchecked::<Db>().clone();
+        let config: ServiceConfig = ctx.data_unchecked::<ServiceConfig>().clone();
+
+        Ok(watch_checkpoints(
+            watermark,
+            move |after, checkpoint_viewed_at, cursor| {
+                let (db, config) = (db.clone(), config.clone());
+                async move {
+                    let cursor = cursor.unwrap_or_else(|| {
+                        checkpoint::Cursor::new(CheckpointCursor {
+                            checkpoint_viewed_at,
+                            sequence_number: after,
+                        })
+                    });
+
+                    paginate_page(&config, Some(cursor), |page| {
+                        Checkpoint::paginate(&db, page, /* epoch */ None, checkpoint_viewed_at)
+                    })
+                    .await
+                }
+            },
+        ))
+    }
+}
+
+/// The receiver for checkpoint watermark updates, provided to subscriptions by the service.
+fn checkpoint_receiver(ctx: &Context<'_>) -> Result<CheckpointReceiver> {
+    Ok(ctx
+        .data::<CheckpointReceiver>()
+        .map_err(|_| Error::Internal("Subscriptions are not available".to_string()))
+        .extend()?
+        .clone())
+}
+
+/// Create a stream that waits for the checkpoint `watermark` to advance, and each time it does,
+/// fetches the values from checkpoints in the range `(after, checkpoint_viewed_at]`, sending them
+/// on, in order. The first range starts at the first watermark the service has seen, so values
+/// from checkpoints that were indexed before the stream was created are not sent.
+///
+/// Values are fetched a page at a time, by calling `fetch(after, checkpoint_viewed_at, cursor)`,
+/// which returns the values in the page following `cursor` (or the first page of the range, if
+/// there is no cursor), and the cursor to fetch the next page of the range from, if there is one.
+/// The next page is only fetched once the previous one has been sent.
+///
+/// The stream ends when the watermark stops being updated (the service is shutting down), or after
+/// sending the first error returned by `fetch`.
+fn watch_checkpoints<T, C, F, Fut>(
+    mut watermark: CheckpointReceiver,
+    fetch: F,
+) -> impl Stream<Item = Result<T>>
+where
+    F: FnMut(u64, u64, Option<C>) -> Fut,
+    Fut: Future<Output = Result<(Vec<T>, Option<C>)>>,
+{
+    // The watermark starts at zero, until the service reads the real one from the database.
+    let after = Some(*watermark.borrow_and_update()).filter(|checkpoint| *checkpoint > 0);
+    let state = WatchState {
+        watermark,
+        after,
+        next_page: None,
+        buffer: VecDeque::new(),
+        fetch,
+        done: false,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(value) = state.buffer.pop_front() {
+                return Some((Ok(value), state));
+            }
+
+            if state.done {
+                return None;
+            }
+
+            // An error indicates that the sender has been dropped, so no further updates will
+            // arrive.
+            let Some(after) = state.after else {
+                let first = *state.watermark.wait_for(|c| *c > 0).await.ok()?;
+                state.after = Some(first);
+                continue;
+            };
+
+            let (checkpoint_viewed_at, cursor) = match state.next_page.take() {
+                Some((checkpoint_viewed_at, cursor)) => (checkpoint_viewed_at, Some(cursor)),
+                None => {
+                    state.watermark.changed().await.ok()?;
+                    let checkpoint_viewed_at = *state.watermark.borrow_and_update();
+                    if checkpoint_viewed_at <= after {
+                        continue;
+                    }
+                    (checkpoint_viewed_at, None)
+                }
+            };
+
+            match (state.fetch)(after, checkpoint_viewed_at, cursor).await {
+                Ok((values, next)) => {
+                    state.buffer.extend(values);
+                    match next {
+                        Some(cursor) => state.next_page = Some((checkpoint_viewed_at, cursor)),
+                        None => state.after = Some(checkpoint_viewed_at),
+                    }
+                }
+
+                Err(e) => {
+                    state.done = true;
+                    return Some((Err(e), state));
+                }
+            }
+        }
+    })
+}
+
+/// State of the stream created by [watch_checkpoints].
+struct WatchState<T, C, F> {
+    watermark: CheckpointReceiver,
+    /// The checkpoint up to which values have been fetched, once it is known.
+    after: Option<u64>,
+    /// The watermark and cursor of the next page of the range being fetched, if it has more pages.
+    next_page: Option<(u64, C)>,
+    /// Values fetched but not sent yet.
+    buffer: VecDeque<T>,
+    fetch: F,
+    /// Whether the stream is ending, after an error.
+    done: bool,
+}
+
+/// Fetch the page of results returned by `paginate` that starts after the `after` cursor (or from
+/// the beginning, if it is not provided), at the maximum page size permitted by `config`. Returns
+/// the nodes in the page, and the cursor to fetch the next page from, if there are more pages.
+async fn paginate_page<C, T, F, Fut>(
+    config: &ServiceConfig,
+    after: Option<C>,
+    paginate: F,
+) -> Result<(Vec<T>, Option<C>)>
+where
+    C: CursorType,
+    F: FnOnce(Page<C>) -> Fut,
+    Fut: Future<Output = Result<Connection<String, T>, Error>>,
+{
+    let limit = config.limits.max_page_size;
+    let page = Page::from_params(config, Some(limit), after, None, None)?;
+    let conn = paginate(page).await.extend()?;
+
+    let has_next_page = conn.has_next_page;
+    let mut nodes = Vec::with_capacity(conn.edges.len());
+    let mut end_cursor = None;
+    for edge in conn.edges {
+        end_cursor = Some(edge.cursor);
+        nodes.push(edge.node);
+    }
+
+    let (true, Some(cursor)) = (has_next_page, end_cursor) else {
+        return Ok((nodes, None));
+    };
+
+    let next = C::decode_cursor(&cursor)
+        .map_err(|e| Error::Internal(format!("Failed to decode cursor: {e}")))
+        .extend()?;
+    Ok((nodes, Some(next)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use async_graphql::connection::Edge;
+    use futures::StreamExt;
+    use tokio::sync::watch;
+
+    use super::*;
+    use crate::server::builder::schema_builder;
+
+    /// Values of a fake data source: one value per checkpoint, equal to its sequence number.
+    /// Pages hold at most `PAGE_SIZE` values, and cursors are the last checkpoint of a page.
+    const PAGE_SIZE: u64 = 2;
+
+    type Calls = Arc<Mutex<Vec<(u64, u64, Option<u64>)>>>;
+
+    fn fake_fetch(
+        calls: Calls,
+    ) -> impl FnMut(u64, u64, Option<u64>) -> futures::future::Ready<Result<(Vec<u64>, Option<u64>)>>
+    {
+        move |after, checkpoint_viewed_at, cursor| {
+            calls
+                .lock()
+                .unwrap()
+                .push((after, checkpoint_viewed_at, cursor));
+            let start = cursor.unwrap_or(after) + 1;
+            let end = checkpoint_viewed_at.min(start + PAGE_SIZE - 1);
+            let next = (end < checkpoint_viewed_at).then_some(end);
+            futures::future::ready(Ok(((start..=end).collect(), next)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch_checkpoints_waits_for_first_watermark() {
+        let (sender, receiver) = watch::channel(0);
+        let calls = Calls::default();
+        let mut stream = Box::pin(watch_checkpoints(receiver, fake_fetch(calls.clone())));
+
+        // The first real watermark is where the subscription starts, nothing up to it is sent.
+        sender.send(10).unwrap();
+        let pending = tokio::time::timeout(Duration::from_millis(100), stream.next()).await;
+        assert!(pending.is_err());
+
+        sender.send(11).unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap(), 11);
+
+        drop(sender);
+        assert!(stream.next().await.is_none());
+        assert_eq!(*calls.lock().unwrap(), vec![(10, 11, None)]);
+    }
+
+    #[tokio::test]
+    async fn test_watch_checkpoints_pages() {
+        let (sender, receiver) = watch::channel(3);
+        let calls = Calls::default();
+        let stream = watch_checkpoints(receiver, fake_fetch(calls.clone()));
+
+        sender.send(8).unwrap();
+        drop(sender);
+        let values: Vec<_> = stream.map(|v| v.unwrap()).collect().await;
+        assert_eq!(values, vec![4, 5, 6, 7, 8]);
+
+        // One page at a time, each continuing from the previous one's cursor.
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![(3, 8, None), (3, 8, Some(5)), (3, 8, Some(7))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_watch_checkpoints_ends_after_error() {
+        let (sender, receiver) = watch::channel(1);
+        let stream = watch_checkpoints(receiver, |_, _, _: Option<u64>| async {
+            Err::<(Vec<u64>, _), _>(Error::Internal("boom".to_string()).extend())
+        });
+
+        sender.send(2).unwrap();
+        let values: Vec<_> = stream.collect().await;
+        assert_eq!(values.len(), 1);
+        assert!(values[0].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_paginate_page() {
+        let config = ServiceConfig::default();
+        let max_page_size = config.limits.max_page_size;
+
+        let (nodes, next) = paginate_page(&config, Some(4usize), |page: Page<usize>| async move {
+            assert_eq!(page.after(), Some(&4));
+            assert_eq!(page.limit() as u64, max_page_size);
+            let mut conn = Connection::new(true, true);
+            conn.edges
+                .extend([5, 6].map(|i| Edge::new(i.encode_cursor(), i)));
+            Ok(conn)
+        })
+        .await
+        .unwrap();
+        assert_eq!((nodes, next), (vec![5, 6], Some(6)));
+
+        let (nodes, next) = paginate_page(&config, None, |_: Page<usize>| async {
+            let mut conn = Connection::new(false, false);
+            conn.edges.push(Edge::new(0usize.encode_cursor(), 0));
+            Ok(conn)
+        })
+        .await
+        .unwrap();
+        assert_eq!((nodes, next), (vec![0], None));
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions_require_watermark() {
+        let schema = schema_builder().finish();
+        for subscription in [
+            "subscription { checkpoints { sequenceNumber } }",
+            "subscription { transactions { digest } }",
+            "subscription { events { timestamp } }",
+        ] {
+            let response = schema.execute_stream(subscription).next().await.unwrap();
+            assert!(
+                response.errors[0]
+                    .message
+                    .contains("Subscriptions are not available"),
+                "{subscription}: {:?}",
+                response.errors
+            );
+        }
+    }
+}
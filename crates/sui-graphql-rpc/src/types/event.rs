@@ -155,6 +155,19 @@ impl Event {
         page: Page<Cursor>,
         filter: EventFilter,
         checkpoint_viewed_at: u64,
+    ) -> Result<Connection<String, Event>, Error> {
+        Self::paginate_after_checkpoint(db, page, filter, None, checkpoint_viewed_at).await
+    }
+
+    /// Like `paginate`, but if `after_checkpoint` is provided, only events emitted in checkpoints
+    /// strictly after it are considered. This is used by subscriptions to fetch the events from
+    /// checkpoints that have been indexed since they last checked.
+    pub(crate) async fn paginate_after_checkpoint(
+        db: &Db,
+        page: Page<Cursor>,
+        filter: EventFilter,
+        after_checkpoint: Option<u64>,
+        checkpoint_viewed_at: u64,
     ) -> Result<Connection<String, Event>, Error> {
         let cursor_viewed_at = page.validate_cursor_consistency()?;
         let checkpoint_viewed_at = cursor_viewed_at.unwrap_or(checkpoint_viewed_at);
@@ -171,6 +184,11 @@ impl Event {
                         events::dsl::checkpoint_sequence_number.le(checkpoint_viewed_at as i64),
                     );
 
+                    if let Some(after) = after_checkpoint {
+                        query =
+                            query.filter(events::dsl::checkpoint_sequence_number.gt(after as i64));
+                    }
+
                     // The transactions table doesn't have an index on the senders column, so use
                     // `tx_senders`.
                     if let Some(sender) = &filter.sender {
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::errors::IndexerError;
 use crate::indexer_reader::IndexerReader;
 use async_trait::async_trait;
 use diesel::r2d2::R2D2Connection;
@@ -9,11 +10,12 @@ use jsonrpsee::RpcModule;
 use sui_json_rpc::coin_api::{parse_to_struct_tag, parse_to_type_tag};
 use sui_json_rpc::SuiRpcModule;
 use sui_json_rpc_api::{cap_page_limit, CoinReadApiServer};
-use sui_json_rpc_types::{Balance, CoinPage, Page, SuiCoinMetadata};
+use sui_json_rpc_types::{Balance, BalanceHistoryPage, CoinPage, Page, SuiCoinMetadata};
 use sui_open_rpc::Module;
 use sui_types::balance::Supply;
 use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::gas_coin::{GAS, TOTAL_SUPPLY_MIST};
+use sui_types::sui_serde::BigInt;
 
 pub(crate) struct CoinReadApi<T: R2D2Connection + 'static> {
     inner: IndexerReader<T>,
@@ -98,7 +100,15 @@ impl<T: R2D2Connection + 'static> CoinReadApiServer for CoinReadApi<T> {
         &self,
         owner: SuiAddress,
         coin_type: Option<String>,
+        at_checkpoint: Option<BigInt<u64>>,
     ) -> RpcResult<Balance> {
+        if at_checkpoint.is_some() {
+            return Err(IndexerError::NotSupportedError(
+                "Historical balances are not supported.".into(),
+            )
+            .into());
+        }
+
         // Normalize coin type tag and default to Gas
         let coin_type =
             parse_to_type_tag(coin_type)?.to_canonical_string(/* with_prefix */ true);
@@ -113,6 +123,16 @@ impl<T: R2D2Connection + 'static> CoinReadApiServer for CoinReadApi<T> {
         Ok(results.swap_remove(0))
     }
 
+    async fn get_balance_history(
+        &self,
+        _owner: SuiAddress,
+        _coin_type: Option<String>,
+        _cursor: Option<BigInt<u64>>,
+        _limit: Option<usize>,
+    ) -> RpcResult<BalanceHistoryPage> {
+        Err(IndexerError::NotSupportedError("Historical balances are not supported.".into()).into())
+    }
+
     async fn get_all_balances(&self, owner: SuiAddress) -> RpcResult<Vec<Balance>> {
         self.inner
             .get_coin_balances_in_blocking_task(owner, None)
@@ -24,6 +24,7 @@ use sui_types::dynamic_field::{DynamicFieldName, Field};
 use sui_types::error::SuiObjectResponseError;
 use sui_types::event::EventID;
 use sui_types::object::ObjectRead;
+use sui_types::sui_serde::BigInt;
 use sui_types::TypeTag;
 
 use crate::indexer_reader::IndexerReader;
@@ -289,7 +290,12 @@ impl<T: R2D2Connection + 'static> IndexerApiServer for IndexerApi<T> {
         ))
     }
 
-    fn subscribe_event(&self, _sink: SubscriptionSink, _filter: EventFilter) -> SubscriptionResult {
+    fn subscribe_event(
+        &self,
+        _sink: SubscriptionSink,
+        _filter: EventFilter,
+        _cursor: Option<EventID>,
+    ) -> SubscriptionResult {
         Err(SubscriptionEmptyError)
     }
 
@@ -297,6 +303,7 @@ impl<T: R2D2Connection + 'static> IndexerApiServer for IndexerApi<T> {
         &self,
         _sink: SubscriptionSink,
         _filter: TransactionFilter,
+        _start_checkpoint: Option<BigInt<u64>>,
     ) -> SubscriptionResult {
         Err(SubscriptionEmptyError)
     }
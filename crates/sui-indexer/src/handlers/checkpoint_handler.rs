@@ -194,7 +194,7 @@ where
         }
     }
 
-    async fn index_epoch(
+    pub(crate) async fn index_epoch(
         state: Arc<S>,
         data: &CheckpointData,
     ) -> Result<Option<EpochToCommit>, IndexerError> {
@@ -715,7 +715,7 @@ where
     }
 }
 
-async fn get_move_struct_layout_map(
+pub(crate) async fn get_move_struct_layout_map(
     objects: &[Object],
     package_resolver: Arc<Resolver<impl PackageStore>>,
 ) -> Result<HashMap<StructTag, MoveStructLayout>, IndexerError> {
@@ -805,7 +805,7 @@ pub fn get_latest_objects(
     (latest_objects, discarded_versions)
 }
 
-fn try_create_dynamic_field_info(
+pub(crate) fn try_create_dynamic_field_info(
     o: &Object,
     struct_tag_to_move_struct_layout: &HashMap<StructTag, MoveStructLayout>,
     latest_objects: &HashMap<ObjectID, Object>,
@@ -21,6 +21,7 @@ use crate::handlers::checkpoint_handler::new_handlers;
 use crate::handlers::objects_snapshot_processor::{ObjectsSnapshotProcessor, SnapshotLagConfig};
 use crate::indexer_reader::IndexerReader;
 use crate::metrics::IndexerMetrics;
+use crate::restorer::FormalSnapshotRestorer;
 use crate::store::IndexerStore;
 use crate::IndexerConfig;
 
@@ -68,6 +69,22 @@ impl Indexer {
             env!("CARGO_PKG_VERSION")
         );
 
+        let rest_client = sui_rest_api::Client::new(format!("{}/rest", config.rpc_client_url));
+
+        if let Some(epoch) = config.snapshot_epoch {
+            if store
+                .get_latest_checkpoint_sequence_number()
+                .await?
+                .is_none()
+            {
+                FormalSnapshotRestorer::new(store.clone(), rest_client.clone(), config, epoch)
+                    .restore::<T>()
+                    .await?;
+            } else {
+                info!("Database is not empty, skipping restore from formal snapshot");
+            }
+        }
+
         let watermark = store
             .get_latest_checkpoint_sequence_number()
             .await
@@ -87,8 +104,6 @@ impl Indexer {
             .parse::<usize>()
             .unwrap();
 
-        let rest_client = sui_rest_api::Client::new(format!("{}/rest", config.rpc_client_url));
-
         let objects_snapshot_processor = ObjectsSnapshotProcessor::new_with_config(
             rest_client.clone(),
             store.clone(),
@@ -40,6 +40,7 @@ pub mod indexer;
 pub mod indexer_reader;
 pub mod metrics;
 pub mod models;
+pub mod restorer;
 pub mod schema;
 pub mod store;
 pub mod system_package_task;
@@ -91,6 +92,16 @@ pub struct IndexerConfig {
     pub name_service_registry_id: Option<ObjectID>,
     #[clap(long)]
     pub name_service_reverse_registry_id: Option<ObjectID>,
+    /// Bootstrap an empty database from the formal snapshot taken at the end of this epoch, and
+    /// start indexing from the first checkpoint after it.
+    #[clap(long)]
+    pub snapshot_epoch: Option<u64>,
+    /// The bucket to download the formal snapshot from. Defaults to the public bucket of the chain
+    /// the fullnode at `rpc_client_url` is on.
+    #[clap(long, global = true)]
+    pub snapshot_store_url: Option<String>,
+    #[clap(long)]
+    pub snapshot_download_dir: Option<PathBuf>,
 }
 
 impl IndexerConfig {
@@ -147,6 +158,9 @@ impl Default for IndexerConfig {
             name_service_package_address: None,
             name_service_registry_id: None,
             name_service_reverse_registry_id: None,
+            snapshot_epoch: None,
+            snapshot_store_url: None,
+            snapshot_download_dir: None,
         }
     }
 }
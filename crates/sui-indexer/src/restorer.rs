@@ -0,0 +1,682 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bootstraps an empty database from the formal state snapshot taken at the end of an epoch,
+//! instead of replaying every checkpoint up to it.
+//!
+//! The epoch's last checkpoint is looked up on the fullnode, which must not have pruned it. The
+//! live object set of the snapshot is restored into a temporary store, verified against the
+//! root state digest committed to by the epoch's last checkpoint, and bulk loaded into the
+//! `objects` and `objects_snapshot` tables. The next epoch and the epoch's last checkpoint are
+//! persisted last, so that checkpoint ingestion resumes from the first checkpoint after the
+//! snapshot, and an interrupted restore is started over.
+
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use diesel::r2d2::R2D2Connection;
+use fastcrypto::hash::MultisetHash;
+use futures::future::AbortHandle;
+use indicatif::{MultiProgress, ProgressDrawTarget};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::annotated_value::MoveStructLayout;
+use move_core_types::language_storage::StructTag;
+use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
+use sui_core::authority::authority_store_tables::{AuthorityPerpetualTables, LiveObject};
+use sui_package_resolver::{
+    error::Error as PackageResolverError, Package, PackageStore, PackageStoreWithLruCache, Resolver,
+};
+use sui_rest_api::{CheckpointData, Client, NodeInfo};
+use sui_snapshot::reader::StateSnapshotReaderV1;
+use sui_types::accumulator::Accumulator;
+use sui_types::base_types::ObjectID;
+use sui_types::committee::EpochId;
+use sui_types::dynamic_field::{DynamicFieldInfo, DynamicFieldType};
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointCommitment, CheckpointSequenceNumber,
+    ECMHLiveObjectSetDigest,
+};
+use sui_types::object::Object;
+use sui_types::storage::ObjectStore;
+use sui_types::transaction::TransactionDataAPI;
+use tokio::sync::mpsc;
+use tracing::info;
+
+use crate::errors::IndexerError;
+use crate::handlers::checkpoint_handler::{
+    get_move_struct_layout_map, try_create_dynamic_field_info, CheckpointHandler,
+};
+use crate::handlers::{EpochToCommit, TransactionObjectChangesToCommit};
+use crate::store::IndexerStore;
+use crate::types::{IndexedCheckpoint, IndexedObject, IndexedPackage, IndexerResult};
+use crate::IndexerConfig;
+
+const OBJECTS_BATCH_SIZE: usize = 10_000;
+const DOWNLOAD_CONCURRENCY: usize = 50;
+
+pub struct FormalSnapshotRestorer<S> {
+    store: S,
+    client: Client,
+    epoch: EpochId,
+    // Derived from the fullnode's chain if not set.
+    snapshot_store_url: Option<String>,
+    download_dir: Option<PathBuf>,
+}
+
+impl<S> FormalSnapshotRestorer<S>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    pub fn new(store: S, client: Client, config: &IndexerConfig, epoch: EpochId) -> Self {
+        Self {
+            store,
+            client,
+            epoch,
+            snapshot_store_url: config.snapshot_store_url.clone(),
+            download_dir: config.snapshot_download_dir.clone(),
+        }
+    }
+
+    /// Restore the snapshot taken at the end of the epoch, returning the sequence number of the
+    /// first checkpoint after it.
+    pub async fn restore<T: R2D2Connection + 'static>(
+        &self,
+    ) -> IndexerResult<CheckpointSequenceNumber> {
+        let node_info = self.client.get_node_info().await?;
+        let snapshot_store_url = match &self.snapshot_store_url {
+            Some(url) => url.clone(),
+            None => default_snapshot_store_url(&node_info.chain)?,
+        };
+        let summary = end_of_epoch_checkpoint(&self.client, &node_info, self.epoch).await?;
+        let sequence_number = *summary.sequence_number();
+        info!(
+            epoch = self.epoch,
+            sequence_number,
+            %snapshot_store_url,
+            "Restoring formal snapshot at end of epoch"
+        );
+
+        let download_dir = match &self.download_dir {
+            Some(dir) => tempfile::tempdir_in(dir),
+            None => tempfile::tempdir(),
+        }
+        .map_err(|e| IndexerError::GenericError(format!("Cannot create download dir: {e}")))?;
+        let perpetual_db = self
+            .read_snapshot(
+                &snapshot_store_config(&snapshot_store_url),
+                root_state_digest(&summary)?,
+                download_dir.path().to_path_buf(),
+            )
+            .await?;
+        self.persist_live_objects(perpetual_db, sequence_number)
+            .await?;
+
+        let checkpoint = self.client.get_full_checkpoint(sequence_number).await?;
+        let Some(EpochToCommit { new_epoch, .. }) =
+            CheckpointHandler::<S, T>::index_epoch(Arc::new(self.store.clone()), &checkpoint)
+                .await?
+        else {
+            return Err(IndexerError::GenericError(format!(
+                "Checkpoint {sequence_number} is not the last checkpoint of epoch {}",
+                self.epoch
+            )));
+        };
+        self.store
+            .persist_bootstrap_epoch(EpochToCommit {
+                last_epoch: None,
+                new_epoch,
+            })
+            .await?;
+
+        // The checkpoint is persisted last, as the watermark ingestion resumes from.
+        self.store
+            .persist_checkpoints(vec![indexed_checkpoint(&checkpoint)])
+            .await?;
+        info!(
+            epoch = self.epoch,
+            "Restored formal snapshot, resuming from checkpoint {}",
+            sequence_number + 1
+        );
+        Ok(sequence_number + 1)
+    }
+
+    /// Download the snapshot into a store under `dir`, checking that the live object set matches
+    /// the root state digest of the epoch.
+    async fn read_snapshot(
+        &self,
+        snapshot_store_config: &ObjectStoreConfig,
+        root_state_digest: ECMHLiveObjectSetDigest,
+        dir: PathBuf,
+    ) -> IndexerResult<Arc<AuthorityPerpetualTables>> {
+        let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&dir.join("store"), None));
+        let local_store_config = ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(dir.join("snapshot")),
+            ..Default::default()
+        };
+        let mut reader = StateSnapshotReaderV1::new(
+            self.epoch,
+            snapshot_store_config,
+            &local_store_config,
+            usize::MAX,
+            NonZeroUsize::new(DOWNLOAD_CONCURRENCY).unwrap(),
+            MultiProgress::with_draw_target(ProgressDrawTarget::hidden()),
+        )
+        .await?;
+
+        let (sender, mut receiver) = mpsc::channel(DOWNLOAD_CONCURRENCY);
+        let (_abort_handle, abort_registration) = AbortHandle::new_pair();
+        let db = perpetual_db.clone();
+        let read_handle =
+            tokio::spawn(async move { reader.read(&db, abort_registration, Some(sender)).await });
+
+        let mut accumulator = Accumulator::default();
+        let mut num_live_objects = 0;
+        while let Some((partial_accumulator, num_objects)) = receiver.recv().await {
+            accumulator.union(&partial_accumulator);
+            num_live_objects += num_objects;
+        }
+        read_handle.await??;
+
+        let local_digest: ECMHLiveObjectSetDigest = accumulator.digest().into();
+        if local_digest != root_state_digest {
+            return Err(IndexerError::PersistentStorageDataCorruptionError(format!(
+                "Root state digest {} of epoch {} does not match digest {} of the snapshot",
+                root_state_digest.digest, self.epoch, local_digest.digest
+            )));
+        }
+        info!(
+            num_live_objects,
+            "Downloaded and verified formal snapshot of epoch {}", self.epoch
+        );
+        Ok(perpetual_db)
+    }
+
+    /// Persist the restored live objects and packages, as of the epoch's last checkpoint.
+    async fn persist_live_objects(
+        &self,
+        perpetual_db: Arc<AuthorityPerpetualTables>,
+        checkpoint_sequence_number: CheckpointSequenceNumber,
+    ) -> IndexerResult<()> {
+        let package_resolver = Arc::new(Resolver::new(PackageStoreWithLruCache::new(
+            SnapshotPackageStore(perpetual_db.clone()),
+        )));
+
+        // RocksDB iterators cannot be held across awaits, so the live object set is read in
+        // batches on a blocking thread.
+        let (sender, mut receiver) = mpsc::channel(1);
+        let db = perpetual_db.clone();
+        let iter_handle = tokio::task::spawn_blocking(move || {
+            let mut batch = Vec::with_capacity(OBJECTS_BATCH_SIZE);
+            for object in db
+                .iter_live_object_set(false)
+                .filter_map(LiveObject::to_normal)
+            {
+                batch.push(object);
+                if batch.len() == OBJECTS_BATCH_SIZE
+                    && sender.blocking_send(std::mem::take(&mut batch)).is_err()
+                {
+                    return;
+                }
+            }
+            if !batch.is_empty() {
+                let _ = sender.blocking_send(batch);
+            }
+        });
+
+        let mut num_objects = 0;
+        while let Some(objects) = receiver.recv().await {
+            num_objects += objects.len();
+            self.persist_objects_batch(
+                objects,
+                &perpetual_db,
+                package_resolver.clone(),
+                checkpoint_sequence_number,
+            )
+            .await?;
+            info!(num_objects, "Persisted objects from formal snapshot");
+        }
+        iter_handle.await?;
+        Ok(())
+    }
+
+    async fn persist_objects_batch(
+        &self,
+        objects: Vec<Object>,
+        perpetual_db: &AuthorityPerpetualTables,
+        package_resolver: Arc<Resolver<impl PackageStore>>,
+        checkpoint_sequence_number: CheckpointSequenceNumber,
+    ) -> IndexerResult<()> {
+        let packages = objects
+            .iter()
+            .filter_map(|o| {
+                o.data.try_as_package().map(|p| IndexedPackage {
+                    package_id: o.id(),
+                    move_package: p.clone(),
+                    checkpoint_sequence_number,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let move_struct_layout_map = get_move_struct_layout_map(&objects, package_resolver).await?;
+
+        // The objects of dynamic object fields are not necessarily in the same batch.
+        let mut latest_objects = HashMap::new();
+        for object in &objects {
+            let Some(child_id) = dynamic_object_field_id(object, &move_struct_layout_map)? else {
+                continue;
+            };
+            if let Some(child) = perpetual_db
+                .get_object(&child_id)
+                .map_err(|e| IndexerError::GenericError(e.to_string()))?
+            {
+                latest_objects.insert(child_id, child);
+            }
+        }
+
+        let changed_objects = objects
+            .into_iter()
+            .map(|o| {
+                let df_info =
+                    try_create_dynamic_field_info(&o, &move_struct_layout_map, &latest_objects)?;
+                Ok(IndexedObject::from_object(
+                    checkpoint_sequence_number,
+                    o,
+                    df_info,
+                ))
+            })
+            .collect::<IndexerResult<Vec<_>>>()?;
+        let object_changes = vec![TransactionObjectChangesToCommit {
+            changed_objects,
+            deleted_objects: vec![],
+        }];
+
+        self.store.persist_packages(packages).await?;
+        self.store.persist_objects(object_changes.clone()).await?;
+        self.store.backfill_objects_snapshot(object_changes).await
+    }
+}
+
+/// The public bucket holding the formal snapshots of `chain`, as reported by the fullnode.
+fn default_snapshot_store_url(chain: &str) -> IndexerResult<String> {
+    match chain {
+        "mainnet" | "testnet" => Ok(format!("https://formal-snapshot.{chain}.sui.io")),
+        _ => Err(IndexerError::GenericError(format!(
+            "No formal snapshots are published for chain {chain}, set --snapshot-store-url"
+        ))),
+    }
+}
+
+/// The public bucket at `url`, which is read without credentials.
+fn snapshot_store_config(url: &str) -> ObjectStoreConfig {
+    ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::S3),
+        aws_endpoint: Some(url.to_string()),
+        aws_virtual_hosted_style_request: true,
+        object_store_connection_limit: 200,
+        no_sign_request: true,
+        ..Default::default()
+    }
+}
+
+/// The root state digest committed to by the last checkpoint of an epoch.
+fn root_state_digest(
+    summary: &CertifiedCheckpointSummary,
+) -> IndexerResult<ECMHLiveObjectSetDigest> {
+    match summary
+        .end_of_epoch_data
+        .as_ref()
+        .and_then(|data| data.epoch_commitments.last())
+    {
+        Some(CheckpointCommitment::ECMHLiveObjectSetDigest(root_state_digest)) => {
+            Ok(root_state_digest.clone())
+        }
+        _ => Err(IndexerError::GenericError(format!(
+            "End of epoch {} has no root state digest commitment",
+            summary.epoch()
+        ))),
+    }
+}
+
+/// Search the checkpoints still available on the fullnode for the last checkpoint of `epoch`,
+/// which is the first checkpoint followed by one of a later epoch.
+async fn end_of_epoch_checkpoint(
+    client: &Client,
+    node_info: &NodeInfo,
+    epoch: EpochId,
+) -> IndexerResult<CertifiedCheckpointSummary> {
+    if node_info.epoch <= epoch {
+        return Err(IndexerError::GenericError(format!(
+            "Epoch {epoch} has not ended yet, the fullnode is in epoch {}",
+            node_info.epoch
+        )));
+    }
+
+    // The restore also needs the contents of the checkpoint, so it cannot be older than the
+    // lowest checkpoint the fullnode has not pruned.
+    let lowest = client
+        .get_checkpoint_summary(node_info.lowest_available_checkpoint)
+        .await?;
+    if lowest.epoch() > epoch {
+        return Err(IndexerError::GenericError(format!(
+            "The last checkpoint of epoch {epoch} has been pruned by the fullnode, whose lowest \
+             available checkpoint {} is in epoch {}",
+            node_info.lowest_available_checkpoint,
+            lowest.epoch()
+        )));
+    }
+
+    // Checkpoint `high` is always after the epoch.
+    let (mut low, mut high) = (
+        node_info.lowest_available_checkpoint,
+        node_info.checkpoint_height,
+    );
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if client.get_checkpoint_summary(mid).await?.epoch() > epoch {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+
+    let summary = client
+        .get_checkpoint_summary(high.saturating_sub(1))
+        .await?;
+    if summary.epoch() != epoch || summary.end_of_epoch_data.is_none() {
+        return Err(IndexerError::GenericError(format!(
+            "Cannot find the last checkpoint of epoch {epoch}"
+        )));
+    }
+    Ok(summary)
+}
+
+/// The ID of the object a dynamic object field points to, if `object` is a dynamic object field.
+fn dynamic_object_field_id(
+    object: &Object,
+    move_struct_layout_map: &HashMap<StructTag, MoveStructLayout>,
+) -> IndexerResult<Option<ObjectID>> {
+    let Some(move_object) = object.data.try_as_move() else {
+        return Ok(None);
+    };
+    if !move_object.type_().is_dynamic_field() {
+        return Ok(None);
+    }
+    let struct_tag: StructTag = move_object.type_().clone().into();
+    // A missing layout is reported when creating the dynamic field info.
+    let Some(move_struct_layout) = move_struct_layout_map.get(&struct_tag) else {
+        return Ok(None);
+    };
+    let move_struct = move_object.to_move_struct(move_struct_layout)?;
+    let (_, type_, object_id) = DynamicFieldInfo::parse_move_object(&move_struct)?;
+    Ok((type_ == DynamicFieldType::DynamicObject).then_some(object_id))
+}
+
+fn indexed_checkpoint(checkpoint: &CheckpointData) -> IndexedCheckpoint {
+    let successful_tx_num = checkpoint
+        .transactions
+        .iter()
+        .filter(|tx| tx.effects.status().is_ok())
+        .map(|tx| tx.transaction.transaction_data().kind().tx_count())
+        .sum();
+    IndexedCheckpoint::from_sui_checkpoint(
+        &checkpoint.checkpoint_summary,
+        &checkpoint.checkpoint_contents,
+        successful_tx_num,
+    )
+}
+
+/// A package store over the live object set restored from a snapshot.
+struct SnapshotPackageStore(Arc<AuthorityPerpetualTables>);
+
+#[async_trait]
+impl PackageStore for SnapshotPackageStore {
+    async fn fetch(&self, id: AccountAddress) -> Result<Arc<Package>, PackageResolverError> {
+        let object = self
+            .0
+            .get_object(&ObjectID::from(id))
+            .map_err(|e| PackageResolverError::Store {
+                store: "FormalSnapshot",
+                source: Arc::new(e),
+            })?
+            .ok_or(PackageResolverError::PackageNotFound(id))?;
+        Ok(Arc::new(Package::read_from_object(&object)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use simulacrum::Simulacrum;
+    use sui_config::local_ip_utils;
+    use sui_rest_api::RestService;
+
+    use super::*;
+
+    /// Serve the REST API over a chain with `epochs` complete epochs, returning a client for it and
+    /// the sequence number of the last checkpoint of each epoch.
+    async fn start_rest_service(epochs: usize) -> (Client, Vec<CheckpointSequenceNumber>) {
+        let mut sim = Simulacrum::new();
+        let mut end_of_epoch_checkpoints = vec![];
+        for _ in 0..epochs {
+            sim.create_checkpoint();
+            let checkpoint = sim.create_checkpoint();
+            // Advancing the epoch creates one more checkpoint, the last of the epoch.
+            sim.advance_epoch(false);
+            end_of_epoch_checkpoints.push(checkpoint.sequence_number + 1);
+        }
+        sim.create_checkpoint();
+
+        let address: SocketAddr = format!(
+            "127.0.0.1:{}",
+            local_ip_utils::get_available_port("127.0.0.1")
+        )
+        .parse()
+        .unwrap();
+        tokio::spawn(async move {
+            RestService::new_without_version(Arc::new(sim))
+                .start_service(address, Some("/rest".to_owned()))
+                .await;
+        });
+
+        let client = Client::new(format!("http://{address}/rest"));
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while client.get_node_info().await.is_err() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("REST service did not start");
+        (client, end_of_epoch_checkpoints)
+    }
+
+    #[tokio::test]
+    async fn test_end_of_epoch_checkpoint() {
+        let (client, end_of_epoch_checkpoints) = start_rest_service(3).await;
+        let node_info = client.get_node_info().await.unwrap();
+        assert_eq!(node_info.epoch, 3);
+
+        for (epoch, sequence_number) in end_of_epoch_checkpoints.into_iter().enumerate() {
+            let summary = end_of_epoch_checkpoint(&client, &node_info, epoch as EpochId)
+                .await
+                .unwrap();
+            assert_eq!(*summary.sequence_number(), sequence_number);
+            assert_eq!(summary.epoch(), epoch as EpochId);
+            assert!(summary.end_of_epoch_data.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_end_of_epoch_checkpoint_of_current_epoch() {
+        let (client, _) = start_rest_service(1).await;
+        let node_info = client.get_node_info().await.unwrap();
+
+        let err = end_of_epoch_checkpoint(&client, &node_info, node_info.epoch)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("has not ended yet"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_end_of_epoch_checkpoint_pruned() {
+        let (client, end_of_epoch_checkpoints) = start_rest_service(2).await;
+        let mut node_info = client.get_node_info().await.unwrap();
+
+        // The fullnode only has the checkpoints after the end of epoch 0.
+        node_info.lowest_available_checkpoint = end_of_epoch_checkpoints[0] + 1;
+        let err = end_of_epoch_checkpoint(&client, &node_info, 0)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("has been pruned"), "{err}");
+
+        let summary = end_of_epoch_checkpoint(&client, &node_info, 1)
+            .await
+            .unwrap();
+        assert_eq!(*summary.sequence_number(), end_of_epoch_checkpoints[1]);
+    }
+
+    #[cfg(feature = "pg_integration")]
+    #[tokio::test]
+    async fn test_restore_live_objects() {
+        use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
+        use std::collections::BTreeMap;
+        use sui_core::state_accumulator::StateAccumulator;
+        use sui_snapshot::writer::StateSnapshotWriterV1;
+        use sui_storage::FileCompression;
+
+        use crate::db::{
+            get_pool_connection, new_connection_pool_with_config, reset_database,
+            ConnectionPoolConfig,
+        };
+        use crate::models::objects::StoredObject;
+        use crate::schema::{objects, objects_snapshot, packages};
+        use crate::store::PgIndexerStore;
+        use crate::IndexerMetrics;
+
+        const CHECKPOINT: CheckpointSequenceNumber = 7;
+
+        // A small snapshot of the genesis live object set, written to a local store.
+        let sim = Simulacrum::new();
+        let live_objects: BTreeMap<ObjectID, Object> = sim
+            .store()
+            .live_objects()
+            .map(|object| (object.id(), object))
+            .collect();
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(AuthorityPerpetualTables::open(&dir.path().join("db"), None));
+        for object in live_objects.values() {
+            db.insert_object_test_only(object.clone()).unwrap();
+        }
+        let mut accumulator = Accumulator::default();
+        for object in db.iter_live_object_set(false) {
+            StateAccumulator::accumulate_live_object(&mut accumulator, &object);
+        }
+        let root_state_digest: ECMHLiveObjectSetDigest = accumulator.digest().into();
+        let snapshot_store_config = ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(dir.path().join("remote")),
+            ..Default::default()
+        };
+        let staging_store_config = ObjectStoreConfig {
+            object_store: Some(ObjectStoreType::File),
+            directory: Some(dir.path().join("staging")),
+            ..Default::default()
+        };
+        StateSnapshotWriterV1::new(
+            &staging_store_config,
+            &snapshot_store_config,
+            FileCompression::Zstd,
+            NonZeroUsize::new(1).unwrap(),
+        )
+        .await
+        .unwrap()
+        .write_internal(0, false, db, root_state_digest.clone())
+        .await
+        .unwrap();
+
+        let db_url = {
+            let pg_host = std::env::var("POSTGRES_HOST").unwrap_or_else(|_| "localhost".into());
+            let pg_port = std::env::var("POSTGRES_PORT").unwrap_or_else(|_| "32770".into());
+            let pw = std::env::var("POSTGRES_PASSWORD").unwrap_or_else(|_| "postgrespw".into());
+            format!("postgres://postgres:{pw}@{pg_host}:{pg_port}")
+        };
+        let pool = new_connection_pool_with_config::<PgConnection>(
+            &db_url,
+            Some(5),
+            ConnectionPoolConfig::default(),
+        )
+        .unwrap();
+        reset_database(&mut pool.get().unwrap(), true).unwrap();
+        let registry = prometheus::Registry::default();
+        let restorer = FormalSnapshotRestorer {
+            store: PgIndexerStore::new(pool.clone(), IndexerMetrics::new(&registry)),
+            client: Client::new("http://localhost"),
+            epoch: 0,
+            snapshot_store_url: None,
+            download_dir: None,
+        };
+
+        let perpetual_db = restorer
+            .read_snapshot(
+                &snapshot_store_config,
+                root_state_digest,
+                dir.path().join("restore"),
+            )
+            .await
+            .unwrap();
+        restorer
+            .persist_live_objects(perpetual_db, CHECKPOINT)
+            .await
+            .unwrap();
+
+        let mut conn = get_pool_connection(&pool).unwrap();
+        let stored_objects: Vec<StoredObject> = objects::table
+            .order(objects::object_id)
+            .load(&mut conn)
+            .unwrap();
+        assert_eq!(stored_objects.len(), live_objects.len());
+        for (stored, (id, object)) in stored_objects.iter().zip(&live_objects) {
+            let (_, version, digest) = object.compute_object_reference();
+            assert_eq!(stored.object_id, id.to_vec());
+            assert_eq!(stored.object_version, version.value() as i64);
+            assert_eq!(stored.object_digest, digest.inner().to_vec());
+            assert_eq!(stored.checkpoint_sequence_number, CHECKPOINT as i64);
+            assert_eq!(
+                stored.serialized_object,
+                bcs::to_bytes(object).unwrap(),
+                "object {id}"
+            );
+        }
+
+        let num_packages: i64 = packages::table.count().get_result(&mut conn).unwrap();
+        assert_eq!(
+            num_packages as usize,
+            live_objects.values().filter(|o| o.is_package()).count()
+        );
+        let num_snapshot_objects: i64 = objects_snapshot::table
+            .filter(objects_snapshot::checkpoint_sequence_number.eq(CHECKPOINT as i64))
+            .count()
+            .get_result(&mut conn)
+            .unwrap();
+        assert_eq!(num_snapshot_objects as usize, live_objects.len());
+    }
+
+    #[test]
+    fn test_default_snapshot_store_url() {
+        assert_eq!(
+            default_snapshot_store_url("mainnet").unwrap(),
+            "https://formal-snapshot.mainnet.sui.io"
+        );
+        assert_eq!(
+            default_snapshot_store_url("testnet").unwrap(),
+            "https://formal-snapshot.testnet.sui.io"
+        );
+        assert!(default_snapshot_store_url("unknown").is_err());
+    }
+}
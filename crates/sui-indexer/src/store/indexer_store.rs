@@ -70,6 +70,10 @@ pub trait IndexerStore: Any + Clone + Sync + Send + 'static {
 
     async fn advance_epoch(&self, epoch: EpochToCommit) -> Result<(), IndexerError>;
 
+    /// Persist the first epoch of a database bootstrapped from a formal snapshot, starting the
+    /// epoch partitions at its first checkpoint.
+    async fn persist_bootstrap_epoch(&self, epoch: EpochToCommit) -> Result<(), IndexerError>;
+
     async fn get_network_total_transactions_by_end_of_epoch(
         &self,
         epoch: u64,
@@ -993,6 +993,23 @@ impl<T: R2D2Connection + 'static> PgIndexerStore<T> {
         Ok(())
     }
 
+    fn persist_bootstrap_epoch(&self, epoch: EpochToCommit) -> Result<(), IndexerError> {
+        let epoch_partition_data = EpochPartitionData::bootstrap(&epoch.new_epoch);
+        self.persist_epoch(epoch)?;
+        let table_partitions = self.partition_manager.get_table_partitions()?;
+        for (table, (first_partition, last_partition)) in table_partitions {
+            // Nothing before the bootstrapped epoch exists to be pruned.
+            self.partition_manager.advance_and_prune_epoch_partition(
+                table,
+                first_partition,
+                last_partition,
+                &epoch_partition_data,
+                None,
+            )?;
+        }
+        Ok(())
+    }
+
     fn get_network_total_transactions_by_end_of_epoch(
         &self,
         epoch: u64,
@@ -1437,6 +1454,11 @@ impl<T: R2D2Connection> IndexerStore for PgIndexerStore<T> {
             .await
     }
 
+    async fn persist_bootstrap_epoch(&self, epoch: EpochToCommit) -> Result<(), IndexerError> {
+        self.execute_in_blocking_worker(move |this| this.persist_bootstrap_epoch(epoch))
+            .await
+    }
+
     async fn get_network_total_transactions_by_end_of_epoch(
         &self,
         epoch: u64,
@@ -13,6 +13,7 @@ use crate::errors::IndexerError;
 use crate::handlers::EpochToCommit;
 use crate::models::epoch::StoredEpochInfo;
 use crate::store::diesel_macro::*;
+use crate::types::IndexedEpochInfo;
 use downcast::Any;
 
 const GET_PARTITION_SQL: &str = if cfg!(feature = "postgres-feature") {
@@ -75,6 +76,18 @@ impl EpochPartitionData {
             next_epoch_start_cp,
         }
     }
+
+    /// Partition data for a database bootstrapped at the beginning of `epoch`, without the
+    /// checkpoints before it: partition 0 is closed at the epoch's first checkpoint, and the
+    /// epoch's partition starts there.
+    pub fn bootstrap(epoch: &IndexedEpochInfo) -> Self {
+        Self {
+            last_epoch: 0,
+            next_epoch: epoch.epoch,
+            last_epoch_start_cp: 0,
+            next_epoch_start_cp: epoch.first_checkpoint_id,
+        }
+    }
 }
 
 impl<T: R2D2Connection> PgPartitionManager<T> {
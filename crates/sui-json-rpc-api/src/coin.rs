@@ -3,10 +3,11 @@
 
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
-use sui_json_rpc_types::{Balance, CoinPage, SuiCoinMetadata};
+use sui_json_rpc_types::{Balance, BalanceHistoryPage, CoinPage, SuiCoinMetadata};
 use sui_open_rpc_macros::open_rpc;
 use sui_types::balance::Supply;
 use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::sui_serde::BigInt;
 
 #[open_rpc(namespace = "suix", tag = "Coin Query API")]
 #[rpc(server, client, namespace = "suix")]
@@ -45,8 +46,24 @@ pub trait CoinReadApi {
         owner: SuiAddress,
         /// optional type names for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC), default to 0x2::sui::SUI if not specified.
         coin_type: Option<String>,
+        /// optional checkpoint to return the balance as of, instead of the latest balance. Requires the balance history index on the Fullnode.
+        at_checkpoint: Option<BigInt<u64>>,
     ) -> RpcResult<Balance>;
 
+    /// Return the total coin balance for one coin type, owned by the address owner, as of the end of every checkpoint that changed it, in ascending checkpoint order. Requires the balance history index on the Fullnode.
+    #[method(name = "getBalanceHistory")]
+    async fn get_balance_history(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// optional type names for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC), default to 0x2::sui::SUI if not specified.
+        coin_type: Option<String>,
+        /// optional paging cursor, the checkpoint of the last balance of the previous page
+        cursor: Option<BigInt<u64>>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+    ) -> RpcResult<BalanceHistoryPage>;
+
     /// Return the total coin balance for all coin type, owned by the address owner.
     #[method(name = "getAllBalances")]
     async fn get_all_balances(
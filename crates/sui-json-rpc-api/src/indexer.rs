@@ -15,6 +15,7 @@ use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::digests::TransactionDigest;
 use sui_types::dynamic_field::DynamicFieldName;
 use sui_types::event::EventID;
+use sui_types::sui_serde::BigInt;
 
 #[open_rpc(namespace = "suix", tag = "Extended API")]
 #[rpc(server, client, namespace = "suix")]
@@ -71,11 +72,19 @@ pub trait IndexerApi {
         &self,
         /// The filter criteria of the event stream. See [Event filter](https://docs.sui.io/build/event_api#event-filters) documentation for examples.
         filter: EventFilter,
+        /// An optional resumption cursor. If provided, the stream first replays every matching event after the specified cursor, and then continues with new events. Default to only streaming new events if not specified.
+        cursor: Option<EventID>,
     );
 
     /// Subscribe to a stream of Sui transaction effects
     #[subscription(name = "subscribeTransaction", item = SuiTransactionBlockEffects)]
-    fn subscribe_transaction(&self, filter: TransactionFilter);
+    fn subscribe_transaction(
+        &self,
+        /// The filter criteria of the transaction stream.
+        filter: TransactionFilter,
+        /// An optional checkpoint to resume from. If provided, the stream first replays every matching transaction from the specified checkpoint onwards (inclusive), and then continues with new transactions. Default to only streaming new transactions if not specified.
+        start_checkpoint: Option<BigInt<u64>>,
+    );
 
     /// Return the list of dynamic field objects owned by an object.
     #[method(name = "getDynamicFields")]
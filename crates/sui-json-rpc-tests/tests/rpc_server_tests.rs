@@ -409,7 +409,7 @@ async fn test_get_balance() -> Result<(), anyhow::Error> {
     let http_client = cluster.rpc_client();
     let address = cluster.get_address_0();
 
-    let result: Balance = http_client.get_balance(address, None).await?;
+    let result: Balance = http_client.get_balance(address, None, None).await?;
     assert_eq!("0x2::sui::SUI", result.coin_type);
     assert_eq!(
         (DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT as u64 * DEFAULT_GAS_AMOUNT) as u128,
@@ -194,6 +194,19 @@ pub trait StateRead: Send + Sync {
         &self,
         owner: SuiAddress,
     ) -> StateReadResult<Arc<HashMap<TypeTag, TotalBalance>>>;
+    fn get_balance_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> StateReadResult<TotalBalance>;
+    fn get_balance_history(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+        cursor: Option<CheckpointSequenceNumber>,
+        limit: usize,
+    ) -> StateReadResult<Vec<(CheckpointSequenceNumber, TotalBalance)>>;
 
     // read_api
     fn get_verified_checkpoint_by_sequence_number(
@@ -500,6 +513,40 @@ impl StateRead for AuthorityState {
             .await?)
     }
 
+    fn get_balance_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> StateReadResult<TotalBalance> {
+        // Checkpoints are indexed before they are marked as executed.
+        if checkpoint > self.get_latest_checkpoint_sequence_number()? {
+            return Err(UserInputError::VerifiedCheckpointNotFound(checkpoint).into());
+        }
+        Ok(self
+            .indexes
+            .as_ref()
+            .ok_or(SuiError::IndexStoreNotAvailable)?
+            .get_balance_at_checkpoint(owner, coin_type, checkpoint)?)
+    }
+
+    fn get_balance_history(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+        cursor: Option<CheckpointSequenceNumber>,
+        limit: usize,
+    ) -> StateReadResult<Vec<(CheckpointSequenceNumber, TotalBalance)>> {
+        let latest_checkpoint = self.get_latest_checkpoint_sequence_number()?;
+        let mut history = self
+            .indexes
+            .as_ref()
+            .ok_or(SuiError::IndexStoreNotAvailable)?
+            .get_balance_history(owner, coin_type, cursor, limit)?;
+        history.retain(|(checkpoint, _)| *checkpoint <= latest_checkpoint);
+        Ok(history)
+    }
+
     fn get_verified_checkpoint_by_sequence_number(
         &self,
         sequence_number: CheckpointSequenceNumber,
@@ -165,11 +165,12 @@ async fn process_raw_request<L: Logger>(
                 return blocked_response;
             }
         }
+        let method = request.method.to_string();
         let response = process_request(request, api_version, service.call_data()).await;
 
         // handle response tallying
         if let Some(traffic_controller) = &service.traffic_controller {
-            handle_traffic_resp(traffic_controller.clone(), client, &response);
+            handle_traffic_resp(traffic_controller.clone(), client, method, &response);
         }
         response
     } else if let Ok(_batch) = serde_json::from_str::<Vec<&RawValue>>(raw_request) {
@@ -200,6 +201,7 @@ async fn handle_traffic_req(
 fn handle_traffic_resp(
     traffic_controller: Arc<TrafficController>,
     client: Option<IpAddr>,
+    method: String,
     response: &MethodResponse,
 ) {
     let error = response.error_code.map(ErrorCode::from);
@@ -207,6 +209,7 @@ fn handle_traffic_resp(
         direct: client,
         through_fullnode: None,
         error_weight: error.map(normalize).unwrap_or(Weight::zero()),
+        route: Some(method),
         timestamp: SystemTime::now(),
     });
 }
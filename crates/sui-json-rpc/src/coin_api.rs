@@ -17,7 +17,7 @@ use tracing::{debug, info, instrument};
 use mysten_metrics::spawn_monitored_task;
 use sui_core::authority::AuthorityState;
 use sui_json_rpc_api::{cap_page_limit, CoinReadApiOpenRpc, CoinReadApiServer, JsonRpcMetrics};
-use sui_json_rpc_types::Balance;
+use sui_json_rpc_types::{Balance, BalanceHistoryPage, HistoricalBalance, Page};
 use sui_json_rpc_types::{CoinPage, SuiCoinMetadata};
 use sui_open_rpc::Module;
 use sui_storage::key_value_store::TransactionKeyValueStore;
@@ -26,8 +26,10 @@ use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::coin::{CoinMetadata, TreasuryCap};
 use sui_types::effects::TransactionEffectsAPI;
 use sui_types::gas_coin::{GAS, TOTAL_SUPPLY_MIST};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Object;
 use sui_types::parse_sui_struct_tag;
+use sui_types::sui_serde::BigInt;
 
 #[cfg(test)]
 use mockall::automock;
@@ -157,16 +159,25 @@ impl CoinReadApiServer for CoinReadApi {
         &self,
         owner: SuiAddress,
         coin_type: Option<String>,
+        at_checkpoint: Option<BigInt<u64>>,
     ) -> RpcResult<Balance> {
         with_tracing!(async move {
             let coin_type_tag = parse_to_type_tag(coin_type)?;
-            let balance = self
-                .internal
-                .get_balance(owner, coin_type_tag.clone())
-                .await
-                .tap_err(|e| {
-                    debug!(?owner, "Failed to get balance with error: {:?}", e);
-                })?;
+            let balance = match at_checkpoint {
+                Some(checkpoint) => {
+                    self.internal
+                        .get_balance_at_checkpoint(owner, coin_type_tag.clone(), *checkpoint)
+                        .await
+                }
+                None => {
+                    self.internal
+                        .get_balance(owner, coin_type_tag.clone())
+                        .await
+                }
+            }
+            .tap_err(|e| {
+                debug!(?owner, "Failed to get balance with error: {:?}", e);
+            })?;
             Ok(Balance {
                 coin_type: coin_type_tag.to_string(),
                 coin_object_count: balance.num_coins as usize,
@@ -177,6 +188,46 @@ impl CoinReadApiServer for CoinReadApi {
         })
     }
 
+    #[instrument(skip(self))]
+    async fn get_balance_history(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        // exclusive cursor if `Some`, otherwise start from the first indexed checkpoint
+        cursor: Option<BigInt<u64>>,
+        limit: Option<usize>,
+    ) -> RpcResult<BalanceHistoryPage> {
+        with_tracing!(async move {
+            let coin_type_tag = parse_to_type_tag(coin_type)?;
+            let limit = cap_page_limit(limit);
+            let mut history = self
+                .internal
+                .get_balance_history(owner, coin_type_tag.clone(), cursor.map(|c| *c), limit + 1)
+                .await
+                .tap_err(|e| {
+                    debug!(?owner, "Failed to get balance history with error: {:?}", e);
+                })?;
+
+            let has_next_page = history.len() > limit;
+            history.truncate(limit);
+            let next_cursor = history.last().map(|(checkpoint, _)| (*checkpoint).into());
+            let data = history
+                .into_iter()
+                .map(|(checkpoint, balance)| HistoricalBalance {
+                    checkpoint,
+                    coin_type: coin_type_tag.to_string(),
+                    coin_object_count: balance.num_coins as usize,
+                    total_balance: balance.balance as u128,
+                })
+                .collect();
+            Ok(Page {
+                data,
+                next_cursor,
+                has_next_page,
+            })
+        })
+    }
+
     #[instrument(skip(self))]
     async fn get_all_balances(&self, owner: SuiAddress) -> RpcResult<Vec<Balance>> {
         with_tracing!(async move {
@@ -293,6 +344,19 @@ pub trait CoinReadInternal {
         &self,
         owner: SuiAddress,
     ) -> RpcInterimResult<Arc<HashMap<TypeTag, TotalBalance>>>;
+    async fn get_balance_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> RpcInterimResult<TotalBalance>;
+    async fn get_balance_history(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+        cursor: Option<CheckpointSequenceNumber>,
+        limit: usize,
+    ) -> RpcInterimResult<Vec<(CheckpointSequenceNumber, TotalBalance)>>;
     async fn find_package_object(
         &self,
         package_id: &ObjectID,
@@ -353,6 +417,29 @@ impl CoinReadInternal for CoinReadInternalImpl {
         Ok(self.state.get_all_balance(owner).await?)
     }
 
+    async fn get_balance_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> RpcInterimResult<TotalBalance> {
+        Ok(self
+            .state
+            .get_balance_at_checkpoint(owner, coin_type, checkpoint)?)
+    }
+
+    async fn get_balance_history(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+        cursor: Option<CheckpointSequenceNumber>,
+        limit: usize,
+    ) -> RpcInterimResult<Vec<(CheckpointSequenceNumber, TotalBalance)>> {
+        Ok(self
+            .state
+            .get_balance_history(owner, coin_type, cursor, limit)?)
+    }
+
     async fn find_package_object(
         &self,
         package_id: &ObjectID,
@@ -956,7 +1043,7 @@ mod tests {
                     })
                 });
             let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
-            let response = coin_read_api.get_balance(owner, None).await;
+            let response = coin_read_api.get_balance(owner, None, None).await;
 
             assert!(response.is_ok());
             let result = response.unwrap();
@@ -991,7 +1078,7 @@ mod tests {
                 });
             let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
             let response = coin_read_api
-                .get_balance(owner, Some(coin.coin_type.clone()))
+                .get_balance(owner, Some(coin.coin_type.clone()), None)
                 .await;
 
             assert!(response.is_ok());
@@ -1015,7 +1102,7 @@ mod tests {
             let mock_state = MockStateRead::new();
             let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
             let response = coin_read_api
-                .get_balance(owner, Some(coin_type.to_string()))
+                .get_balance(owner, Some(coin_type.to_string()), None)
                 .await;
 
             assert!(response.is_err());
@@ -1040,7 +1127,7 @@ mod tests {
             });
             let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
             let response = coin_read_api
-                .get_balance(owner, Some(coin_type.to_string()))
+                .get_balance(owner, Some(coin_type.to_string()), None)
                 .await;
 
             assert!(response.is_err());
@@ -1065,7 +1152,7 @@ mod tests {
             });
             let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
             let response = coin_read_api
-                .get_balance(owner, Some(coin_type.to_string()))
+                .get_balance(owner, Some(coin_type.to_string()), None)
                 .await;
 
             assert!(response.is_err());
@@ -1079,6 +1166,205 @@ mod tests {
             let expected = expect!["Error executing mock db error"];
             expected.assert_eq(error_object.message());
         }
+
+        #[tokio::test]
+        async fn test_at_checkpoint() {
+            let owner = get_test_owner();
+            let gas_coin = get_test_coin(None, CoinType::Gas);
+            let gas_coin_type_tag = get_test_coin_type_tag(gas_coin.coin_type.clone());
+            let mut mock_state = MockStateRead::new();
+            mock_state
+                .expect_get_balance_at_checkpoint()
+                .with(
+                    predicate::eq(owner),
+                    predicate::eq(gas_coin_type_tag),
+                    predicate::eq(42),
+                )
+                .return_once(move |_, _, _| {
+                    Ok(TotalBalance {
+                        balance: 5,
+                        num_coins: 2,
+                    })
+                });
+            let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
+            let response = coin_read_api
+                .get_balance(owner, None, Some(42.into()))
+                .await;
+
+            assert_eq!(
+                response.unwrap(),
+                Balance {
+                    coin_type: gas_coin.coin_type,
+                    coin_object_count: 2,
+                    total_balance: 5,
+                    locked_balance: Default::default()
+                }
+            );
+        }
+
+        #[tokio::test]
+        async fn test_at_checkpoint_balance_history_disabled() {
+            let owner = get_test_owner();
+            let mut mock_state = MockStateRead::new();
+            mock_state
+                .expect_get_balance_at_checkpoint()
+                .return_once(move |_, _, _| {
+                    Err(SuiError::UnsupportedFeatureError {
+                        error: "Balance history index is not enabled on this node".to_string(),
+                    }
+                    .into())
+                });
+            let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
+            let response = coin_read_api
+                .get_balance(owner, None, Some(42.into()))
+                .await;
+
+            let error_object: ErrorObjectOwned = response.unwrap_err().into();
+            assert_eq!(
+                error_object.code(),
+                jsonrpsee::types::error::INVALID_PARAMS_CODE
+            );
+            let expected = expect![[
+                r#"Use of disabled feature: "Balance history index is not enabled on this node""#
+            ]];
+            expected.assert_eq(error_object.message());
+        }
+    }
+
+    mod get_balance_history_tests {
+        use super::super::*;
+        use super::*;
+        use jsonrpsee::types::ErrorObjectOwned;
+
+        #[tokio::test]
+        async fn test_pagination() {
+            let owner = get_test_owner();
+            let gas_coin = get_test_coin(None, CoinType::Gas);
+            let gas_coin_type_tag = get_test_coin_type_tag(gas_coin.coin_type.clone());
+            let mut mock_state = MockStateRead::new();
+            mock_state
+                .expect_get_balance_history()
+                .with(
+                    predicate::eq(owner),
+                    predicate::eq(gas_coin_type_tag),
+                    predicate::eq(Some(3)),
+                    predicate::eq(3),
+                )
+                .return_once(move |_, _, _, _| {
+                    Ok((4..7)
+                        .map(|checkpoint| {
+                            let balance = TotalBalance {
+                                balance: checkpoint as i128 * 10,
+                                num_coins: 1,
+                            };
+                            (checkpoint, balance)
+                        })
+                        .collect())
+                });
+            let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
+            let response = coin_read_api
+                .get_balance_history(owner, None, Some(3.into()), Some(2))
+                .await;
+
+            let page = response.unwrap();
+            assert!(page.has_next_page);
+            assert_eq!(page.next_cursor, Some(5.into()));
+            assert_eq!(
+                page.data,
+                [4, 5].map(|checkpoint| HistoricalBalance {
+                    checkpoint,
+                    coin_type: gas_coin.coin_type.clone(),
+                    coin_object_count: 1,
+                    total_balance: checkpoint as u128 * 10,
+                })
+            );
+        }
+
+        #[tokio::test]
+        async fn test_last_page() {
+            let owner = get_test_owner();
+            let gas_coin = get_test_coin(None, CoinType::Gas);
+            let mut mock_state = MockStateRead::new();
+            mock_state
+                .expect_get_balance_history()
+                .with(
+                    predicate::always(),
+                    predicate::always(),
+                    predicate::eq(Some(5)),
+                    predicate::eq(3),
+                )
+                .return_once(move |_, _, _, _| {
+                    let balance = TotalBalance {
+                        balance: 60,
+                        num_coins: 1,
+                    };
+                    Ok(vec![(6, balance)])
+                });
+            let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
+            let response = coin_read_api
+                .get_balance_history(owner, None, Some(5.into()), Some(2))
+                .await;
+
+            // The cursor of the last page is its last checkpoint, to poll for later changes from.
+            let page = response.unwrap();
+            assert!(!page.has_next_page);
+            assert_eq!(page.next_cursor, Some(6.into()));
+            assert_eq!(
+                page.data,
+                [HistoricalBalance {
+                    checkpoint: 6,
+                    coin_type: gas_coin.coin_type,
+                    coin_object_count: 1,
+                    total_balance: 60,
+                }]
+            );
+        }
+
+        #[tokio::test]
+        async fn test_no_history() {
+            let owner = get_test_owner();
+            let mut mock_state = MockStateRead::new();
+            mock_state
+                .expect_get_balance_history()
+                .return_once(move |_, _, _, _| Ok(vec![]));
+            let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
+            let response = coin_read_api
+                .get_balance_history(owner, None, None, None)
+                .await;
+
+            let page = response.unwrap();
+            assert!(!page.has_next_page);
+            assert_eq!(page.next_cursor, None);
+            assert!(page.data.is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_balance_history_disabled() {
+            let owner = get_test_owner();
+            let mut mock_state = MockStateRead::new();
+            mock_state
+                .expect_get_balance_history()
+                .return_once(move |_, _, _, _| {
+                    Err(SuiError::UnsupportedFeatureError {
+                        error: "Balance history index is not enabled on this node".to_string(),
+                    }
+                    .into())
+                });
+            let coin_read_api = CoinReadApi::new_for_tests(Arc::new(mock_state), None);
+            let response = coin_read_api
+                .get_balance_history(owner, None, None, None)
+                .await;
+
+            let error_object: ErrorObjectOwned = response.unwrap_err().into();
+            assert_eq!(
+                error_object.code(),
+                jsonrpsee::types::error::INVALID_PARAMS_CODE
+            );
+            let expected = expect![[
+                r#"Use of disabled feature: "Balance history index is not enabled on this node""#
+            ]];
+            expected.assert_eq(error_object.message());
+        }
     }
 
     mod get_all_balances_tests {
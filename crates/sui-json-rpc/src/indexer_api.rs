@@ -1,11 +1,12 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use std::collections::HashSet;
-use std::sync::Arc;
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
 
 use anyhow::bail;
 use async_trait::async_trait;
-use futures::{future, Stream};
+use futures::{future, stream, Stream, StreamExt, TryStream, TryStreamExt};
 use jsonrpsee::{
     core::{error::SubscriptionClosed, RpcResult},
     types::SubscriptionResult,
@@ -16,14 +17,16 @@ use move_core_types::language_storage::TypeTag;
 use mysten_metrics::spawn_monitored_task;
 use serde::Serialize;
 use sui_core::authority::AuthorityState;
+use sui_core::subscription_handler::EVENT_DISPATCH_BUFFER_SIZE;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_api::{
     cap_page_limit, validate_limit, IndexerApiOpenRpc, IndexerApiServer, JsonRpcMetrics,
     ReadApiServer, QUERY_MAX_RESULT_LIMIT,
 };
 use sui_json_rpc_types::{
-    DynamicFieldPage, EventFilter, EventPage, ObjectsPage, Page, SuiObjectDataOptions,
-    SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionBlockResponse,
+    DynamicFieldPage, EffectsWithInput, EventFilter, EventPage, Filter, ObjectsPage, Page,
+    SuiEvent, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery,
+    SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
     SuiTransactionBlockResponseQuery, TransactionBlocksPage, TransactionFilter,
 };
 use sui_open_rpc::Module;
@@ -34,6 +37,8 @@ use sui_types::{
     dynamic_field::{DynamicFieldName, Field},
     error::SuiObjectResponseError,
     event::EventID,
+    messages_checkpoint::CheckpointSequenceNumber,
+    sui_serde::BigInt,
 };
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tracing::{debug, instrument, warn};
@@ -45,17 +50,27 @@ use crate::{
     with_tracing, SuiRpcModule,
 };
 
-pub fn spawn_subscription<S, T>(
+pub fn spawn_subscription<S, T>(sink: SubscriptionSink, rx: S, permit: Option<OwnedSemaphorePermit>)
+where
+    S: Stream<Item = T> + Unpin + Send + 'static,
+    T: Serialize,
+{
+    spawn_try_subscription(sink, rx.map(Ok::<_, std::convert::Infallible>), permit)
+}
+
+/// Like [spawn_subscription], but the subscription is closed with an error if `rx` yields one.
+pub fn spawn_try_subscription<S, T, E>(
     mut sink: SubscriptionSink,
     rx: S,
     permit: Option<OwnedSemaphorePermit>,
 ) where
-    S: Stream<Item = T> + Unpin + Send + 'static,
+    S: TryStream<Ok = T, Error = E> + Unpin + Send + 'static,
     T: Serialize,
+    E: std::fmt::Display,
 {
     spawn_monitored_task!(async move {
         let _permit = permit;
-        match sink.pipe_from_stream(rx).await {
+        match sink.pipe_from_try_stream(rx).await {
             SubscriptionClosed::Success => {
                 debug!("Subscription completed.");
                 sink.close(SubscriptionClosed::Success);
@@ -71,6 +86,207 @@ pub fn spawn_subscription<S, T>(
         };
     });
 }
+
+/// Number of the most recently backfilled items that are remembered, to avoid sending them again
+/// when they also show up at the start of the live stream.
+///
+/// Any item that is both backfilled and sent live was indexed after the live subscription was
+/// registered (or shortly before, while still in the dispatch queue), so it is among the last
+/// items backfilled. There can only be so many of them: once a subscriber's buffer fills up, it is
+/// dropped by the streamer.
+const BACKFILL_DEDUP_WINDOW: usize = 2 * EVENT_DISPATCH_BUFFER_SIZE;
+
+/// Stream every item from `backfill`, followed by the items from `live` that were not already
+/// sent as part of the backfill (identified by `key`).
+///
+/// `live` must be subscribed to before `backfill` starts reading from the store, so that there is
+/// no gap between the two. The stream ends with `live`, which happens if the subscriber falls too
+/// far behind (e.g. because the backfill took too long) -- clients are expected to resubscribe
+/// from the last item they received.
+fn backfill_then_live<T, K>(
+    backfill: impl Stream<Item = anyhow::Result<T>> + Send + 'static,
+    live: impl Stream<Item = T> + Send + 'static,
+    key: fn(&T) -> K,
+) -> impl Stream<Item = anyhow::Result<T>> + Send + 'static
+where
+    T: Send + 'static,
+    K: Eq + Hash + Clone + Send + 'static,
+{
+    let recent = Arc::new(Mutex::new((VecDeque::new(), HashSet::new())));
+
+    let backfill = backfill.inspect_ok({
+        let recent = recent.clone();
+        move |item| {
+            let (order, keys) = &mut *recent.lock().unwrap();
+            if keys.insert(key(item)) {
+                order.push_back(key(item));
+            }
+
+            if order.len() > BACKFILL_DEDUP_WINDOW {
+                if let Some(evicted) = order.pop_front() {
+                    keys.remove(&evicted);
+                }
+            }
+        }
+    });
+
+    let live = live
+        .filter(move |item| {
+            let (_, keys) = &*recent.lock().unwrap();
+            future::ready(!keys.contains(&key(item)))
+        })
+        .map(Ok);
+
+    backfill.chain(live)
+}
+
+/// Replay events after `cursor` that match `filter`, from the store.
+fn event_backfill(
+    state: Arc<dyn StateRead>,
+    kv_store: Arc<TransactionKeyValueStore>,
+    filter: EventFilter,
+    cursor: EventID,
+) -> impl Stream<Item = anyhow::Result<SuiEvent>> + Send + 'static {
+    // Filters that the fullnode can't query by are applied to the full event stream instead.
+    let query = match &filter {
+        EventFilter::All(filters) if filters.is_empty() => filter.clone(),
+        EventFilter::Transaction(_)
+        | EventFilter::MoveModule { .. }
+        | EventFilter::MoveEventType(_)
+        | EventFilter::Sender(_)
+        | EventFilter::TimeRange { .. }
+        | EventFilter::MoveEventModule { .. } => filter.clone(),
+        _ => EventFilter::All(vec![]),
+    };
+
+    let limit = *QUERY_MAX_RESULT_LIMIT;
+    stream::try_unfold(Some(cursor), move |cursor| {
+        let (state, kv_store, query, filter) = (
+            state.clone(),
+            kv_store.clone(),
+            query.clone(),
+            filter.clone(),
+        );
+
+        async move {
+            let Some(cursor) = cursor else {
+                return Ok(None);
+            };
+
+            let events = state
+                .query_events(&kv_store, query, Some(cursor), limit, false)
+                .await?;
+
+            let next_cursor = if events.len() < limit {
+                None
+            } else {
+                events.last().map(|event| event.id)
+            };
+
+            let events = events
+                .into_iter()
+                .filter(move |event| filter.matches(event))
+                .map(Ok);
+
+            anyhow::Ok(Some((stream::iter(events), next_cursor)))
+        }
+    })
+    .try_flatten()
+}
+
+/// Replay transactions after `cursor` (or from the very first transaction, if it is `None`) that
+/// match `filter`, from the store.
+fn transaction_backfill(
+    state: Arc<dyn StateRead>,
+    kv_store: Arc<TransactionKeyValueStore>,
+    filter: TransactionFilter,
+    cursor: Option<TransactionDigest>,
+) -> impl Stream<Item = anyhow::Result<SuiTransactionBlockEffects>> + Send + 'static {
+    // Only use indices that list transactions in execution order, otherwise fall back to filtering
+    // all transactions.
+    let query = match &filter {
+        TransactionFilter::MoveFunction {
+            module: Some(_),
+            function: Some(_),
+            ..
+        }
+        | TransactionFilter::FromAddress(_)
+        | TransactionFilter::ToAddress(_) => Some(filter.clone()),
+        _ => None,
+    };
+
+    let limit = *QUERY_MAX_RESULT_LIMIT;
+    stream::try_unfold(Some(cursor), move |cursor| {
+        let (state, kv_store, query, filter) = (
+            state.clone(),
+            kv_store.clone(),
+            query.clone(),
+            filter.clone(),
+        );
+
+        async move {
+            let Some(cursor) = cursor else {
+                return Ok(None);
+            };
+
+            let mut digests = state
+                .get_transactions(&kv_store, query, cursor, Some(limit), false)
+                .await?;
+
+            let next_cursor = if digests.len() < limit {
+                None
+            } else {
+                digests.last().map(|digest| Some(*digest))
+            };
+
+            let mut seen = HashSet::new();
+            digests.retain(|digest| seen.insert(*digest));
+
+            let (transactions, effects, _) = state.multi_get(&digests, &digests, &[]).await?;
+
+            let mut matching = vec![];
+            for (transaction, effects) in transactions.into_iter().zip(effects) {
+                let (Some(transaction), Some(effects)) = (transaction, effects) else {
+                    continue;
+                };
+
+                let item = EffectsWithInput {
+                    effects: effects.try_into()?,
+                    input: transaction.data().transaction_data().clone(),
+                };
+
+                if filter.matches(&item) {
+                    matching.push(Ok(item.effects));
+                }
+            }
+
+            anyhow::Ok(Some((stream::iter(matching), next_cursor)))
+        }
+    })
+    .try_flatten()
+}
+
+/// The digest of the last transaction before `checkpoint`, to use as an exclusive cursor when
+/// querying transactions from `checkpoint` onwards, or `None` to start from the very first
+/// transaction.
+fn last_transaction_before(
+    state: &dyn StateRead,
+    checkpoint: CheckpointSequenceNumber,
+) -> anyhow::Result<Option<TransactionDigest>> {
+    let Some(previous) = checkpoint.checked_sub(1) else {
+        return Ok(None);
+    };
+
+    let summary = state.get_verified_checkpoint_by_sequence_number(previous)?;
+    let contents = state.get_checkpoint_contents(summary.content_digest)?;
+    let last = contents
+        .iter()
+        .last()
+        .ok_or_else(|| anyhow::anyhow!("Checkpoint {previous} has no transactions"))?;
+
+    Ok(Some(last.transaction))
+}
+
 const DEFAULT_MAX_SUBSCRIPTIONS: usize = 100;
 
 pub struct IndexerApi<R> {
@@ -295,13 +511,35 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
     }
 
     #[instrument(skip(self))]
-    fn subscribe_event(&self, sink: SubscriptionSink, filter: EventFilter) -> SubscriptionResult {
+    fn subscribe_event(
+        &self,
+        sink: SubscriptionSink,
+        filter: EventFilter,
+        cursor: Option<EventID>,
+    ) -> SubscriptionResult {
         let permit = self.acquire_subscribe_permit()?;
-        spawn_subscription(
+        let live = self
+            .state
+            .get_subscription_handler()
+            .subscribe_events(filter.clone());
+
+        let Some(cursor) = cursor else {
+            spawn_subscription(sink, live, Some(permit));
+            return Ok(());
+        };
+
+        let backfill = event_backfill(
+            self.state.clone(),
+            self.transaction_kv_store.clone(),
+            filter,
+            cursor,
+        );
+
+        spawn_try_subscription(
             sink,
-            self.state
-                .get_subscription_handler()
-                .subscribe_events(filter),
+            Box::pin(backfill_then_live(backfill, live, |event: &SuiEvent| {
+                event.id
+            })),
             Some(permit),
         );
         Ok(())
@@ -311,13 +549,34 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
         &self,
         sink: SubscriptionSink,
         filter: TransactionFilter,
+        start_checkpoint: Option<BigInt<u64>>,
     ) -> SubscriptionResult {
         let permit = self.acquire_subscribe_permit()?;
-        spawn_subscription(
+        let live = self
+            .state
+            .get_subscription_handler()
+            .subscribe_transactions(filter.clone());
+
+        let Some(start_checkpoint) = start_checkpoint else {
+            spawn_subscription(sink, live, Some(permit));
+            return Ok(());
+        };
+
+        let cursor = last_transaction_before(self.state.as_ref(), *start_checkpoint)?;
+        let backfill = transaction_backfill(
+            self.state.clone(),
+            self.transaction_kv_store.clone(),
+            filter,
+            cursor,
+        );
+
+        spawn_try_subscription(
             sink,
-            self.state
-                .get_subscription_handler()
-                .subscribe_transactions(filter),
+            Box::pin(backfill_then_live(
+                backfill,
+                live,
+                |effects: &SuiTransactionBlockEffects| *effects.transaction_digest(),
+            )),
             Some(permit),
         );
         Ok(())
@@ -509,3 +768,35 @@ impl<R: ReadApiServer> SuiRpcModule for IndexerApi<R> {
         IndexerApiOpenRpc::module_doc()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_backfill_then_live_skips_backfilled_items() {
+        let backfill = stream::iter([1u64, 2, 3, 4].map(anyhow::Ok));
+        let live = stream::iter([3u64, 4, 5, 6]);
+
+        let items: Vec<_> = backfill_then_live(backfill, live, |i: &u64| *i)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(items, vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_then_live_stops_at_backfill_error() {
+        let backfill = stream::iter([Ok(1u64), Err(anyhow::anyhow!("pruned")), Ok(2)]);
+        let live = stream::iter([3u64]);
+
+        let items: Vec<_> = backfill_then_live(backfill, live, |i: &u64| *i)
+            .take_while(|item| future::ready(item.is_ok()))
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(items, vec![1]);
+    }
+}
@@ -22,6 +22,7 @@ use tower_http::trace::TraceLayer;
 use tracing::info;
 
 pub use balance_changes::*;
+pub use metrics::REQUEST_LOG_TARGET;
 pub use object_changes::*;
 pub use sui_config::node::ServerType;
 use sui_json_rpc_api::{
@@ -4,6 +4,7 @@
 use hyper::body::HttpBody;
 use std::collections::HashSet;
 use std::net::SocketAddr;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use jsonrpsee::server::logger::{HttpRequest, Logger, MethodKind, TransportProtocol};
 use jsonrpsee::types::Params;
@@ -14,8 +15,14 @@ use prometheus::{
 use sui_json_rpc_api::TRANSIENT_ERROR_CODE;
 use sui_json_rpc_api::{CLIENT_SDK_TYPE_HEADER, CLIENT_TARGET_API_VERSION_HEADER};
 use tokio::time::Instant;
+use tracing::{debug, enabled, Level};
 
 const SPAM_LABEL: &str = "SPAM";
+/// Target of the debug events logging every request with its parameters, enabled with e.g.
+/// `RUST_LOG=info,sui_json_rpc::request_log=debug`. Each event's message is a JSON object with the
+/// `timestamp_ms`, `method` and `params` of the request, so that traffic can be captured from the
+/// logs and replayed by `sui-rpc-loadgen`.
+pub const REQUEST_LOG_TARGET: &str = "sui_json_rpc::request_log";
 const LATENCY_SEC_BUCKETS: &[f64] = &[
     0.001, 0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1., 2.5, 5., 10., 20., 30., 60., 90.,
 ];
@@ -195,11 +202,14 @@ impl Logger for MetricsLogger {
     fn on_call(
         &self,
         method_name: &str,
-        _params: Params,
+        params: Params,
         _kind: MethodKind,
         _transport: TransportProtocol,
     ) {
         let method_name = self.check_spam(method_name);
+        if method_name != SPAM_LABEL && enabled!(target: REQUEST_LOG_TARGET, Level::DEBUG) {
+            log_request(method_name, &params);
+        }
         self.metrics
             .inflight_requests_by_route
             .with_label_values(&[method_name])
@@ -269,3 +279,19 @@ impl Logger for MetricsLogger {
             .dec();
     }
 }
+
+fn log_request(method_name: &str, params: &Params) {
+    let timestamp_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64);
+    let params = params
+        .as_str()
+        .and_then(|params| serde_json::from_str::<serde_json::Value>(params).ok())
+        .unwrap_or(serde_json::Value::Null);
+    let request = serde_json::json!({
+        "timestamp_ms": timestamp_ms,
+        "method": method_name,
+        "params": params,
+    });
+    debug!(target: REQUEST_LOG_TARGET, "{request}");
+}
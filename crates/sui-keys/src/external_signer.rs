@@ -0,0 +1,217 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A simple protocol for delegating signing to a separate process, such as a hardware wallet
+//! bridge, so that private keys never need to be read by (or written to disk for) the client.
+//!
+//! Every exchange is a single request, followed by a single response, each encoded as one line of
+//! JSON. Requests are tagged by their `method`:
+//!
+//! ```text
+//! {"method":"keys"}
+//! {"method":"sign","address":"0x...","intent_message":"<base64>"}
+//! {"method":"sign_hashed","address":"0x...","digest":"<base64>"}
+//! ```
+//!
+//! and answered with one of:
+//!
+//! ```text
+//! {"keys":[{"public_key":"<base64 flag || pk>","alias":"my-key"}]}
+//! {"signature":"<base64 flag || sig || pk>"}
+//! {"error":"<message>"}
+//! ```
+//!
+//! For `sign`, the signer is sent the BCS bytes of the intent message, so that it can inspect (or
+//! display) what it is asked to sign, and is responsible for hashing them with Blake2b256 before
+//! signing. For `sign_hashed`, the digest is signed as is.
+
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use anyhow::{anyhow, bail, Context};
+use fastcrypto::encoding::{Base64, Encoding};
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::Signature;
+
+/// How to reach an external signer.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum ExternalSigner {
+    /// Run `program` with `args` for every request, writing the request to its stdin and reading
+    /// the response from its stdout.
+    Command {
+        program: PathBuf,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+    /// Connect to a signer listening on a unix domain socket at `path`, for every request.
+    Socket { path: PathBuf },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum SignerRequest {
+    /// List the keys the signer can sign with.
+    Keys,
+    /// Sign the Blake2b256 hash of the Base64 encoded `intent_message`, with the key of `address`.
+    Sign {
+        address: SuiAddress,
+        intent_message: String,
+    },
+    /// Sign the Base64 encoded `digest`, with the key of `address`.
+    SignHashed { address: SuiAddress, digest: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerResponse {
+    Keys(Vec<SignerKey>),
+    Signature(Signature),
+    Error(String),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SignerKey {
+    /// Base64 encoded `flag || public key`.
+    pub public_key: String,
+    /// The alias to use for the key, if the signer has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+}
+
+impl ExternalSigner {
+    /// Send `request` to the signer, and wait for its response. Error responses are returned as
+    /// errors.
+    pub fn request(&self, request: &SignerRequest) -> Result<SignerResponse, anyhow::Error> {
+        let mut line = serde_json::to_string(request)?;
+        line.push('\n');
+
+        let response = match self {
+            ExternalSigner::Command { program, args } => {
+                let mut child = Command::new(program)
+                    .args(args)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::inherit())
+                    .spawn()
+                    .with_context(|| {
+                        format!("Cannot start external signer {}", program.display())
+                    })?;
+
+                // Closing stdin after the request tells the signer there are no more requests.
+                child
+                    .stdin
+                    .take()
+                    .expect("Signer stdin is piped")
+                    .write_all(line.as_bytes())?;
+
+                let output = child.wait_with_output()?;
+                if !output.status.success() {
+                    bail!("External signer exited with {}", output.status);
+                }
+
+                String::from_utf8(output.stdout)?
+                    .lines()
+                    .next()
+                    .map(str::to_owned)
+                    .unwrap_or_default()
+            }
+
+            #[cfg(unix)]
+            ExternalSigner::Socket { path } => {
+                use std::io::BufReader;
+                use std::os::unix::net::UnixStream;
+
+                let mut stream = UnixStream::connect(path).with_context(|| {
+                    format!("Cannot connect to external signer at {}", path.display())
+                })?;
+                stream.write_all(line.as_bytes())?;
+
+                let mut response = String::new();
+                BufReader::new(stream).read_line(&mut response)?;
+                response
+            }
+
+            #[cfg(not(unix))]
+            ExternalSigner::Socket { .. } => {
+                bail!("External signers over unix sockets are not supported on this platform")
+            }
+        };
+
+        if response.trim().is_empty() {
+            bail!("External signer did not respond");
+        }
+
+        match serde_json::from_str(&response)
+            .with_context(|| format!("Invalid response from external signer: {response}"))?
+        {
+            SignerResponse::Error(e) => Err(anyhow!("External signer error: {e}")),
+            response => Ok(response),
+        }
+    }
+
+    /// The keys the signer can sign with.
+    pub fn keys(&self) -> Result<Vec<SignerKey>, anyhow::Error> {
+        match self.request(&SignerRequest::Keys)? {
+            SignerResponse::Keys(keys) => Ok(keys),
+            response => bail!("Unexpected response from external signer: {response:?}"),
+        }
+    }
+
+    /// Ask the signer to sign `intent_message` (the BCS bytes of an intent message) with the key
+    /// of `address`.
+    pub fn sign(
+        &self,
+        address: &SuiAddress,
+        intent_message: &[u8],
+    ) -> Result<Signature, anyhow::Error> {
+        self.signature(&SignerRequest::Sign {
+            address: *address,
+            intent_message: Base64::encode(intent_message),
+        })
+    }
+
+    /// Ask the signer to sign `digest` with the key of `address`.
+    pub fn sign_hashed(
+        &self,
+        address: &SuiAddress,
+        digest: &[u8],
+    ) -> Result<Signature, anyhow::Error> {
+        self.signature(&SignerRequest::SignHashed {
+            address: *address,
+            digest: Base64::encode(digest),
+        })
+    }
+
+    fn signature(&self, request: &SignerRequest) -> Result<Signature, anyhow::Error> {
+        match self.request(request)? {
+            SignerResponse::Signature(signature) => Ok(signature),
+            response => bail!("Unexpected response from external signer: {response:?}"),
+        }
+    }
+}
+
+/// Read requests from `reader`, one per line, answering each of them on `writer` using `handle`,
+/// until `reader` is exhausted. Meant for implementing signers.
+pub fn serve_requests(
+    reader: impl BufRead,
+    mut writer: impl Write,
+    mut handle: impl FnMut(SignerRequest) -> SignerResponse,
+) -> Result<(), anyhow::Error> {
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str(&line) {
+            Ok(request) => handle(request),
+            Err(e) => SignerResponse::Error(format!("Invalid request: {e}")),
+        };
+
+        writeln!(writer, "{}", serde_json::to_string(&response)?)?;
+        writer.flush()?;
+    }
+    Ok(())
+}
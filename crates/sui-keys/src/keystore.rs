@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::external_signer::ExternalSigner;
 use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
 use crate::random_names::{random_name, random_names};
 use anyhow::{anyhow, bail, ensure, Context};
@@ -17,10 +18,12 @@ use std::fs;
 use std::fs::File;
 use std::io::BufReader;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::get_key_pair_from_rng;
 use sui_types::crypto::{
     enum_dispatch, EncodeDecodeBase64, PublicKey, Signature, SignatureScheme, SuiKeyPair,
+    SuiSignature,
 };
 
 #[derive(Serialize, Deserialize)]
@@ -28,6 +31,7 @@ use sui_types::crypto::{
 pub enum Keystore {
     File(FileBasedKeystore),
     InMem(InMemKeystore),
+    External(ExternalKeystore),
 }
 #[enum_dispatch]
 pub trait AccountKeystore: Send + Sync {
@@ -150,6 +154,11 @@ impl Display for Keystore {
                 writeln!(writer, "Keystore Type : InMem")?;
                 write!(f, "{}", writer)
             }
+            Keystore::External(external) => {
+                writeln!(writer, "Keystore Type : External")?;
+                write!(writer, "Keystore Signer : {:?}", external.signer)?;
+                write!(f, "{}", writer)
+            }
         }
     }
 }
@@ -605,6 +614,269 @@ impl InMemKeystore {
     }
 }
 
+/// A keystore whose private keys are held by an external signer process (for example, a bridge to
+/// a hardware wallet), which is asked to sign on the keystore's behalf. See
+/// [`crate::external_signer`] for the protocol used to talk to the signer.
+///
+/// The keys (and their aliases) are listed by the signer the first time they are needed, rather
+/// than when the keystore is created or loaded, so that loading a config that refers to the
+/// keystore does not start the signer. Keys cannot be added to or exported from this keystore,
+/// and updated aliases are not persisted.
+///
+/// If the signer cannot list its keys, the methods that cannot report errors behave as if the
+/// keystore were empty, and the others return the signer's error.
+pub struct ExternalKeystore {
+    signer: ExternalSigner,
+    keys: OnceLock<ExternalKeys>,
+}
+
+/// The keys listed by an external signer, and their aliases.
+struct ExternalKeys {
+    keys: BTreeMap<SuiAddress, PublicKey>,
+    aliases: BTreeMap<SuiAddress, Alias>,
+}
+
+impl Serialize for ExternalKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.signer.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExternalKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(ExternalKeystore::new(ExternalSigner::deserialize(
+            deserializer,
+        )?))
+    }
+}
+
+impl AccountKeystore for ExternalKeystore {
+    fn sign_hashed(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        self.check_address(address)?;
+        self.signer
+            .sign_hashed(address, msg)
+            .map_err(|e| signature::Error::from_source(e.to_string()))
+    }
+
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        self.check_address(address)?;
+        let intent_msg = IntentMessage::new(intent, msg);
+        let bytes = bcs::to_bytes(&intent_msg).map_err(signature::Error::from_source)?;
+        let sig = self
+            .signer
+            .sign(address, &bytes)
+            .map_err(|e| signature::Error::from_source(e.to_string()))?;
+
+        // Don't hand out a signature that would not be accepted by validators.
+        sig.verify_secure(&intent_msg, *address, sig.scheme())
+            .map_err(|e| {
+                signature::Error::from_source(format!(
+                    "External signer returned an invalid signature for address [{address}]: {e}"
+                ))
+            })?;
+        Ok(sig)
+    }
+
+    fn add_key(
+        &mut self,
+        _alias: Option<String>,
+        _keypair: SuiKeyPair,
+    ) -> Result<(), anyhow::Error> {
+        bail!("Cannot add keys to an external signer keystore, add them to the signer instead")
+    }
+
+    fn aliases(&self) -> Vec<&Alias> {
+        match self.loaded() {
+            Ok(loaded) => loaded.aliases.values().collect(),
+            Err(_) => vec![],
+        }
+    }
+
+    fn addresses_with_alias(&self) -> Vec<(&SuiAddress, &Alias)> {
+        match self.loaded() {
+            Ok(loaded) => loaded.aliases.iter().collect::<Vec<_>>(),
+            Err(_) => vec![],
+        }
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        match self.loaded() {
+            Ok(loaded) => loaded.keys.values().cloned().collect(),
+            Err(_) => vec![],
+        }
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        bail!("The private key for address [{address}] is held by an external signer")
+    }
+
+    /// Get alias of address
+    fn get_alias_by_address(&self, address: &SuiAddress) -> Result<String, anyhow::Error> {
+        match self.loaded()?.aliases.get(address) {
+            Some(alias) => Ok(alias.alias.clone()),
+            None => bail!("Cannot find alias for address {address}"),
+        }
+    }
+
+    /// Get the address by its alias
+    fn get_address_by_alias(&self, alias: String) -> Result<&SuiAddress, anyhow::Error> {
+        self.loaded()?
+            .aliases
+            .iter()
+            .find(|x| x.1.alias == alias)
+            .ok_or_else(|| anyhow!("Cannot resolve alias {alias} to an address"))
+            .map(|x| x.0)
+    }
+
+    /// This function returns an error if the provided alias already exists. If the alias
+    /// has not already been used, then it returns the alias.
+    /// If no alias has been passed, it will generate a new alias.
+    fn create_alias(&self, alias: Option<String>) -> Result<String, anyhow::Error> {
+        self.loaded()?;
+        match alias {
+            Some(a) if self.alias_exists(&a) => {
+                bail!("Alias {a} already exists. Please choose another alias.")
+            }
+            Some(a) => validate_alias(&a),
+            None => Ok(random_name(
+                &self
+                    .alias_names()
+                    .into_iter()
+                    .map(|x| x.to_string())
+                    .collect::<HashSet<_>>(),
+            )),
+        }
+    }
+
+    fn aliases_mut(&mut self) -> Vec<&mut Alias> {
+        if self.loaded().is_err() {
+            return vec![];
+        }
+        match self.keys.get_mut() {
+            Some(loaded) => loaded.aliases.values_mut().collect(),
+            None => vec![],
+        }
+    }
+
+    /// Updates an old alias to the new alias, for as long as this keystore is loaded. If the
+    /// new_alias is None, it will generate a new random alias.
+    fn update_alias(
+        &mut self,
+        old_alias: &str,
+        new_alias: Option<&str>,
+    ) -> Result<String, anyhow::Error> {
+        self.loaded()?;
+        self.update_alias_value(old_alias, new_alias)
+    }
+}
+
+impl ExternalKeystore {
+    /// Create a keystore for the keys held by `signer`. The signer is not contacted until its
+    /// keys are first needed.
+    pub fn new(signer: ExternalSigner) -> Self {
+        Self {
+            signer,
+            keys: OnceLock::new(),
+        }
+    }
+
+    pub fn signer(&self) -> &ExternalSigner {
+        &self.signer
+    }
+
+    /// The signer's keys, asking the signer for them if they have not been listed yet. Failures
+    /// are not remembered, so the signer is asked again next time.
+    fn loaded(&self) -> Result<&ExternalKeys, anyhow::Error> {
+        if let Some(loaded) = self.keys.get() {
+            return Ok(loaded);
+        }
+        let loaded = ExternalKeys::list(&self.signer)?;
+        // Another thread may have listed the keys in the meantime, either result will do.
+        let _ = self.keys.set(loaded);
+        Ok(self.keys.get().expect("Keys were just set"))
+    }
+
+    fn check_address(&self, address: &SuiAddress) -> Result<(), signature::Error> {
+        let loaded = self
+            .loaded()
+            .map_err(|e| signature::Error::from_source(e.to_string()))?;
+        if loaded.keys.contains_key(address) {
+            Ok(())
+        } else {
+            Err(signature::Error::from_source(format!(
+                "Cannot find key for address: [{address}]"
+            )))
+        }
+    }
+}
+
+impl ExternalKeys {
+    /// Ask `signer` which keys it has. Keys the signer does not provide an alias for are given
+    /// random ones.
+    fn list(signer: &ExternalSigner) -> Result<Self, anyhow::Error> {
+        let mut keys = BTreeMap::new();
+        let mut aliases = BTreeMap::new();
+        let mut alias_names = HashSet::new();
+        let mut unnamed = vec![];
+
+        for key in signer.keys()? {
+            let public_key = PublicKey::decode_base64(&key.public_key).map_err(|e| {
+                anyhow!(
+                    "Invalid public key {} from external signer: {e}",
+                    key.public_key
+                )
+            })?;
+            let address = SuiAddress::from(&public_key);
+
+            match key.alias {
+                Some(alias) => {
+                    let alias = validate_alias(&alias)?;
+                    ensure!(
+                        alias_names.insert(alias.clone()),
+                        "Alias {alias} is used for more than one key by the external signer"
+                    );
+                    aliases.insert(
+                        address,
+                        Alias {
+                            alias,
+                            public_key_base64: key.public_key,
+                        },
+                    );
+                }
+                None => unnamed.push((address, key.public_key)),
+            }
+            keys.insert(address, public_key);
+        }
+
+        let names = random_names(alias_names, unnamed.len());
+        for ((address, public_key_base64), alias) in unnamed.into_iter().zip(names) {
+            aliases.insert(
+                address,
+                Alias {
+                    alias,
+                    public_key_base64,
+                },
+            );
+        }
+
+        Ok(Self { keys, aliases })
+    }
+}
+
 fn validate_alias(alias: &str) -> Result<String, anyhow::Error> {
     let re = Regex::new(r"^[A-Za-z][A-Za-z0-9-_\.]*$")
         .map_err(|_| anyhow!("Cannot build the regex needed to validate the alias naming"))?;
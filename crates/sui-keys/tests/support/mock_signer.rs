@@ -0,0 +1,101 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A mock external signer, for testing the external signer keystore. It signs with the keys of a
+//! file based keystore, either answering requests on stdin, or, if `--socket <path>` is given,
+//! answering requests on connections to a unix domain socket at `path`.
+//!
+//! Usage: mock_signer <keystore path> [--socket <path>]
+
+use std::io::{stdin, stdout};
+use std::path::PathBuf;
+
+use anyhow::bail;
+use fastcrypto::encoding::{Base64, Encoding};
+use fastcrypto::hash::HashFunction;
+use sui_keys::external_signer::{serve_requests, SignerKey, SignerRequest, SignerResponse};
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{DefaultHash, Signature};
+
+fn handle(keystore: &FileBasedKeystore, request: SignerRequest) -> SignerResponse {
+    match request {
+        SignerRequest::Keys => SignerResponse::Keys(
+            keystore
+                .addresses_with_alias()
+                .into_iter()
+                .map(|(_, alias)| SignerKey {
+                    public_key: alias.public_key_base64.clone(),
+                    alias: Some(alias.alias.clone()),
+                })
+                .collect(),
+        ),
+
+        SignerRequest::Sign {
+            address,
+            intent_message,
+        } => sign(keystore, &address, &intent_message, |bytes| {
+            let mut hasher = DefaultHash::default();
+            hasher.update(bytes);
+            hasher.finalize().digest.to_vec()
+        }),
+
+        SignerRequest::SignHashed { address, digest } => {
+            sign(keystore, &address, &digest, |bytes| bytes.to_vec())
+        }
+    }
+}
+
+fn sign(
+    keystore: &FileBasedKeystore,
+    address: &SuiAddress,
+    message: &str,
+    digest: impl FnOnce(&[u8]) -> Vec<u8>,
+) -> SignerResponse {
+    let key = match keystore.get_key(address) {
+        Ok(key) => key,
+        Err(e) => return SignerResponse::Error(e.to_string()),
+    };
+
+    match Base64::decode(message) {
+        Ok(bytes) => SignerResponse::Signature(Signature::new_hashed(&digest(&bytes), key)),
+        Err(e) => SignerResponse::Error(format!("Invalid message: {e}")),
+    }
+}
+
+fn main() -> Result<(), anyhow::Error> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (keystore_path, socket) = match args.as_slice() {
+        [keystore] => (PathBuf::from(keystore), None),
+        [keystore, flag, socket] if flag == "--socket" => {
+            (PathBuf::from(keystore), Some(PathBuf::from(socket)))
+        }
+        _ => bail!("Usage: mock_signer <keystore path> [--socket <path>]"),
+    };
+
+    let keystore = FileBasedKeystore::new(&keystore_path)?;
+
+    match socket {
+        None => serve_requests(stdin().lock(), stdout().lock(), |request| {
+            handle(&keystore, request)
+        }),
+
+        #[cfg(unix)]
+        Some(socket) => {
+            use std::io::BufReader;
+            use std::os::unix::net::UnixListener;
+
+            let listener = UnixListener::bind(socket)?;
+            for stream in listener.incoming() {
+                let stream = stream?;
+                serve_requests(BufReader::new(stream.try_clone()?), stream, |request| {
+                    handle(&keystore, request)
+                })?;
+            }
+            Ok(())
+        }
+
+        #[cfg(not(unix))]
+        Some(_) => bail!("Unix sockets are not supported on this platform"),
+    }
+}
@@ -9,8 +9,12 @@ use fastcrypto::traits::EncodeDecodeBase64;
 use sui_keys::key_derive::generate_new_key;
 use tempfile::TempDir;
 
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, InMemKeystore, Keystore};
-use sui_types::crypto::{DefaultHash, SignatureScheme, SuiSignatureInner};
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_keys::external_signer::ExternalSigner;
+use sui_keys::keystore::{
+    AccountKeystore, ExternalKeystore, FileBasedKeystore, InMemKeystore, Keystore,
+};
+use sui_types::crypto::{DefaultHash, SignatureScheme, SuiSignature, SuiSignatureInner};
 use sui_types::{
     base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
     crypto::Ed25519SuiSignature,
@@ -266,3 +270,142 @@ fn get_alias_by_address_test() {
     let address = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
     assert!(keystore.get_alias_by_address(&address.0).is_err())
 }
+
+fn mock_signer_keystore(temp_dir: &TempDir) -> (std::path::PathBuf, FileBasedKeystore) {
+    let keystore_path = temp_dir.path().join("signer.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path).unwrap();
+    keystore
+        .generate_and_add_new_key(
+            SignatureScheme::ED25519,
+            Some("ed25519_key".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+    keystore
+        .generate_and_add_new_key(SignatureScheme::Secp256k1, None, None, None)
+        .unwrap();
+    (keystore_path, keystore)
+}
+
+fn check_external_keystore(signer: ExternalSigner, expected: &FileBasedKeystore) {
+    let keystore = Keystore::from(ExternalKeystore::new(signer));
+    assert!(keystore.to_string().contains("External"));
+
+    // The external keystore lists the signer's keys and aliases, but never has the keys.
+    let mut addresses = keystore.addresses();
+    addresses.sort();
+    let mut expected_addresses = expected.addresses();
+    expected_addresses.sort();
+    assert_eq!(expected_addresses, addresses);
+    for address in &addresses {
+        assert_eq!(
+            expected.get_alias_by_address(address).unwrap(),
+            keystore.get_alias_by_address(address).unwrap()
+        );
+        assert!(keystore.get_key(address).is_err());
+    }
+
+    // Signatures from the signer are valid for the requested address.
+    let msg = b"hello".to_vec();
+    let intent = Intent::personal_message();
+    for address in &addresses {
+        let signature = keystore.sign_secure(address, &msg, intent.clone()).unwrap();
+        signature
+            .verify_secure(
+                &IntentMessage::new(intent.clone(), &msg),
+                *address,
+                signature.scheme(),
+            )
+            .unwrap();
+    }
+
+    // Addresses the signer has no key for cannot sign.
+    let (unknown, _, _, _) = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
+    assert!(keystore.sign_secure(&unknown, &msg, intent).is_err());
+}
+
+#[test]
+fn external_keystore_command_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let (keystore_path, expected) = mock_signer_keystore(&temp_dir);
+
+    let signer = ExternalSigner::Command {
+        program: env!("CARGO_BIN_EXE_mock_signer").into(),
+        args: vec![keystore_path.to_str().unwrap().to_string()],
+    };
+    check_external_keystore(signer, &expected);
+}
+
+#[cfg(unix)]
+#[test]
+fn external_keystore_socket_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let (keystore_path, expected) = mock_signer_keystore(&temp_dir);
+    let socket_path = temp_dir.path().join("signer.sock");
+
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_mock_signer"))
+        .arg(&keystore_path)
+        .arg("--socket")
+        .arg(&socket_path)
+        .spawn()
+        .unwrap();
+
+    // Wait for the signer to start listening.
+    for _ in 0..100 {
+        if socket_path.exists() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    check_external_keystore(ExternalSigner::Socket { path: socket_path }, &expected);
+    child.kill().unwrap();
+}
+
+#[test]
+fn external_keystore_rejects_new_keys_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let (keystore_path, _) = mock_signer_keystore(&temp_dir);
+
+    let signer = ExternalSigner::Command {
+        program: env!("CARGO_BIN_EXE_mock_signer").into(),
+        args: vec![keystore_path.to_str().unwrap().to_string()],
+    };
+    let mut keystore = Keystore::from(ExternalKeystore::new(signer.clone()));
+    assert!(keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None, None)
+        .is_err());
+
+    // The keystore is configured by its signer alone.
+    let serialized = serde_json::to_string(&keystore).unwrap();
+    let deserialized: Keystore = serde_json::from_str(&serialized).unwrap();
+    match deserialized {
+        Keystore::External(external) => assert_eq!(&signer, external.signer()),
+        _ => panic!("Expected an external keystore"),
+    }
+}
+
+#[test]
+fn external_keystore_lists_keys_lazily_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let signer = ExternalSigner::Command {
+        program: temp_dir.path().join("missing_signer"),
+        args: vec![],
+    };
+
+    // Loading the keystore does not start the signer, so it loads even if the signer is missing.
+    let serialized = serde_json::to_string(&Keystore::from(ExternalKeystore::new(signer))).unwrap();
+    let keystore: Keystore = serde_json::from_str(&serialized).unwrap();
+
+    // The signer's failure is reported once its keys are needed.
+    assert!(keystore.addresses().is_empty());
+    let err = keystore
+        .get_address_by_alias("ed25519_key".to_string())
+        .unwrap_err();
+    assert!(err.to_string().contains("Cannot start external signer"));
+    let (address, _, _, _) = generate_new_key(SignatureScheme::ED25519, None, None).unwrap();
+    assert!(keystore
+        .sign_secure(&address, &b"hello".to_vec(), Intent::personal_message())
+        .is_err());
+}
@@ -0,0 +1,12 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod config;
+pub mod light_client;
+pub mod server;
+pub mod store;
+pub mod verifier;
+
+pub use config::Config;
+pub use light_client::LightClient;
+pub use verifier::{CheckpointedTransaction, CommitteeChain};
@@ -0,0 +1,280 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use jsonrpsee::core::Error as RpcError;
+use jsonrpsee::types::error::{CallError, INVALID_PARAMS_CODE};
+use std::fmt;
+use std::sync::RwLock;
+use tokio::sync::{Mutex, OnceCell};
+
+use sui_config::genesis::Genesis;
+use sui_json_rpc_types::SuiTransactionBlockResponseOptions;
+use sui_rest_api::Client;
+use sui_sdk::{error::Error as SdkError, SuiClient, SuiClientBuilder};
+use sui_types::{
+    base_types::ObjectID,
+    committee::{Committee, EpochId},
+    digests::TransactionDigest,
+    messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber},
+    object::Object,
+};
+
+use crate::config::Config;
+use crate::store::CheckpointStore;
+use crate::verifier::{
+    extract_verified_transaction, verify_object_in_effects, CheckpointedTransaction, CommitteeChain,
+};
+
+/// The fullnode does not know the transaction, object or checkpoint that was asked for, or the
+/// transaction is not final yet.
+#[derive(Debug)]
+pub struct NotFound(String);
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for NotFound {}
+
+/// Replace `error`, from a request to the fullnode's REST API, with [`NotFound`] if the fullnode
+/// answered that the resource does not exist.
+fn rest_not_found(error: anyhow::Error, what: impl FnOnce() -> String) -> anyhow::Error {
+    match error
+        .downcast_ref::<reqwest::Error>()
+        .and_then(|e| e.status())
+    {
+        Some(reqwest::StatusCode::NOT_FOUND) => NotFound(what()).into(),
+        _ => error,
+    }
+}
+
+/// A light client for the Sui blockchain.
+///
+/// The client trusts nothing but the genesis committee. It keeps a chain of committees, one per
+/// epoch, each authenticated by the certified end-of-epoch checkpoint of the epoch before it, and
+/// uses it to verify the checkpoints that transactions, their effects and events, and objects are
+/// read from. Verified end-of-epoch checkpoints are cached in the configured checkpoint summary
+/// directory so that they are only downloaded once.
+pub struct LightClient {
+    config: Config,
+    store: CheckpointStore,
+    rest_client: Client,
+    // Only needed to find out which checkpoint a transaction was included in, which the light
+    // client then verifies itself, so it is created on first use.
+    sui_client: OnceCell<SuiClient>,
+    chain: RwLock<CommitteeChain>,
+    // Serializes syncs, which extend the chain and the on-disk cache.
+    sync_lock: Mutex<()>,
+}
+
+impl LightClient {
+    /// Create a light client from `config`, re-verifying the chain of end-of-epoch checkpoints
+    /// cached on disk from the genesis committee. Checkpoints that are in the cached list, but
+    /// whose summaries are missing, are downloaded and verified.
+    pub async fn new(config: Config) -> anyhow::Result<Self> {
+        let genesis_committee = Genesis::load(config.genesis_path())?.committee()?;
+        let store = CheckpointStore::new(config.checkpoint_summary_dir.clone());
+        let rest_client = Client::new(config.rest_url());
+
+        let mut chain = CommitteeChain::new(genesis_committee);
+        for seq in store.read_checkpoint_list()?.checkpoints {
+            if store.has_checkpoint(seq) {
+                chain.append(store.read_checkpoint(seq)?)?;
+            } else {
+                let summary = rest_client.get_checkpoint_summary(seq).await?;
+                chain.append(summary.clone())?;
+                store.write_checkpoint(&summary)?;
+            }
+        }
+
+        Ok(Self {
+            config,
+            store,
+            rest_client,
+            sui_client: OnceCell::new(),
+            chain: RwLock::new(chain),
+            sync_lock: Mutex::new(()),
+        })
+    }
+
+    /// Create a light client that starts from an already verified `chain`, without reading the
+    /// genesis or the cached checkpoints.
+    #[cfg(test)]
+    pub(crate) fn new_for_testing(config: Config, chain: CommitteeChain) -> Self {
+        Self {
+            store: CheckpointStore::new(config.checkpoint_summary_dir.clone()),
+            rest_client: Client::new(config.rest_url()),
+            config,
+            sui_client: OnceCell::new(),
+            chain: RwLock::new(chain),
+            sync_lock: Mutex::new(()),
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// The latest epoch whose committee is known to the light client.
+    pub fn latest_epoch(&self) -> EpochId {
+        self.chain.read().unwrap().latest_epoch()
+    }
+
+    /// The committee of `epoch`, if the light client has synced up to it.
+    pub fn committee(&self, epoch: EpochId) -> Option<Committee> {
+        self.chain.read().unwrap().committee(epoch).cloned()
+    }
+
+    /// The verified end-of-epoch checkpoints, in order, starting from the end of epoch 0.
+    pub fn end_of_epoch_checkpoints(&self) -> Vec<CertifiedCheckpointSummary> {
+        self.chain
+            .read()
+            .unwrap()
+            .end_of_epoch_checkpoints()
+            .to_vec()
+    }
+
+    /// Download and verify the end-of-epoch checkpoints of every epoch since the latest one known
+    /// to the light client, up to the fullnode's current epoch. Returns the latest epoch whose
+    /// committee is known after syncing.
+    pub async fn sync(&self) -> anyhow::Result<EpochId> {
+        let _guard = self.sync_lock.lock().await;
+
+        let latest = self.rest_client.get_latest_checkpoint().await?;
+        loop {
+            let (target_epoch, mut start) = {
+                let chain = self.chain.read().unwrap();
+                let start = chain
+                    .last_end_of_epoch_checkpoint()
+                    .map(|summary| summary.sequence_number + 1)
+                    .unwrap_or(0);
+                (chain.latest_epoch(), start)
+            };
+
+            if target_epoch >= latest.epoch() {
+                return Ok(target_epoch);
+            }
+
+            // Binary search for the last checkpoint of the target epoch
+            let mut end = latest.sequence_number;
+            let mut found_summary = None;
+            while start < end {
+                let mid = (start + end) / 2;
+                let summary = self.rest_client.get_checkpoint_summary(mid).await?;
+
+                if summary.epoch() == target_epoch && summary.end_of_epoch_data.is_some() {
+                    found_summary = Some(summary);
+                    break;
+                }
+
+                if summary.epoch() <= target_epoch {
+                    start = mid + 1;
+                } else {
+                    end = mid;
+                }
+            }
+
+            let summary = found_summary.ok_or_else(|| {
+                anyhow!("Unable to find the end-of-epoch checkpoint for epoch {target_epoch}")
+            })?;
+
+            // Only persist checkpoints once they have been verified by the previous committee.
+            self.chain.write().unwrap().append(summary.clone())?;
+            self.store.write_checkpoint(&summary)?;
+            let mut checkpoints_list = self.store.read_checkpoint_list()?;
+            checkpoints_list.checkpoints.push(summary.sequence_number);
+            self.store.write_checkpoint_list(&checkpoints_list)?;
+        }
+    }
+
+    /// The committee of `epoch`, syncing first if the light client does not know it yet.
+    async fn committee_for_epoch(&self, epoch: EpochId) -> anyhow::Result<Committee> {
+        if let Some(committee) = self.committee(epoch) {
+            return Ok(committee);
+        }
+
+        self.sync().await?;
+        self.committee(epoch)
+            .ok_or_else(|| anyhow!("No committee known for epoch {epoch}. Need to Sync."))
+    }
+
+    async fn sui_client(&self) -> anyhow::Result<&SuiClient> {
+        self.sui_client
+            .get_or_try_init(|| async {
+                Ok(SuiClientBuilder::default()
+                    .build(self.config.full_node_url.as_str())
+                    .await?)
+            })
+            .await
+    }
+
+    /// Find the checkpoint that includes transaction `tid`, according to the fullnode. This is
+    /// not trusted, the checkpoint is verified to include the transaction before it is used.
+    async fn transaction_checkpoint(
+        &self,
+        tid: TransactionDigest,
+    ) -> anyhow::Result<CheckpointSequenceNumber> {
+        let options = SuiTransactionBlockResponseOptions::new();
+        let response = self
+            .sui_client()
+            .await?
+            .read_api()
+            .get_transaction_with_options(tid, options)
+            .await
+            .map_err(|e| match e {
+                // The digest is the only parameter, so it is only invalid if it is unknown.
+                SdkError::RpcError(RpcError::Call(CallError::Custom(object)))
+                    if object.code() == INVALID_PARAMS_CODE =>
+                {
+                    NotFound(format!("Transaction {tid} not found")).into()
+                }
+                e => anyhow::Error::from(e),
+            })?;
+        response
+            .checkpoint
+            .ok_or_else(|| NotFound(format!("Transaction {tid} is not in a checkpoint yet")).into())
+    }
+
+    /// Fetch transaction `tid`, with its effects and events, and verify that it was included in a
+    /// certified checkpoint, meaning that it is final.
+    pub async fn get_verified_transaction(
+        &self,
+        tid: TransactionDigest,
+    ) -> anyhow::Result<CheckpointedTransaction> {
+        let seq = self.transaction_checkpoint(tid).await?;
+
+        // Download the full checkpoint for this sequence number
+        let full_checkpoint = self
+            .rest_client
+            .get_full_checkpoint(seq)
+            .await
+            .map_err(|e| rest_not_found(e, || format!("Checkpoint {seq} not found")))?;
+        let committee = self
+            .committee_for_epoch(full_checkpoint.checkpoint_summary.epoch())
+            .await?;
+
+        extract_verified_transaction(&full_checkpoint, &committee, tid)
+    }
+
+    /// Fetch the latest version of object `id` from the fullnode, and verify that it was written
+    /// by a final transaction. Note that the fullnode could still return an older version of the
+    /// object than the latest one.
+    pub async fn get_verified_object(&self, id: ObjectID) -> anyhow::Result<Object> {
+        let object = self
+            .rest_client
+            .get_object(id)
+            .await
+            .map_err(|e| rest_not_found(e, || format!("Object {id} not found")))?;
+
+        // Need to authenticate this object
+        let verified = self
+            .get_verified_transaction(object.previous_transaction)
+            .await?;
+        verify_object_in_effects(&object, &verified.effects)?;
+
+        Ok(object)
+    }
+}
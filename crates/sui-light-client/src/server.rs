@@ -0,0 +1,323 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A local HTTP API over a [`LightClient`], so that other services can check whether transactions
+//! are final, and read authenticated objects, without trusting a fullnode.
+//!
+//! - `GET /epoch`: the latest epoch whose committee the light client knows.
+//! - `POST /sync`: sync end-of-epoch checkpoints up to the fullnode's current epoch.
+//! - `GET /transactions/:digest`: the transaction, its effects and events, once verified to be
+//!   included in a certified checkpoint. A successful response means the transaction is final.
+//! - `GET /objects/:object_id`: the object, once verified to have been written by a final
+//!   transaction.
+//!
+//! Requests that fail are answered with an error status and a plain text description of the
+//! failure: `400 Bad Request` for malformed digests and object IDs, `404 Not Found` for
+//! transactions, objects and checkpoints that the fullnode does not know, or transactions that are
+//! not final yet, and `500 Internal Server Error` for everything else, including failed
+//! verification.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use sui_types::base_types::ObjectID;
+use sui_types::committee::EpochId;
+use sui_types::digests::TransactionDigest;
+use sui_types::object::Object;
+
+use crate::light_client::{LightClient, NotFound};
+use crate::verifier::CheckpointedTransaction;
+
+pub const EPOCH_PATH: &str = "/epoch";
+pub const SYNC_PATH: &str = "/sync";
+pub const TRANSACTION_PATH: &str = "/transactions/:digest";
+pub const OBJECT_PATH: &str = "/objects/:object_id";
+
+pub fn router(light_client: Arc<LightClient>) -> Router {
+    Router::new()
+        .route(EPOCH_PATH, get(get_epoch))
+        .route(SYNC_PATH, post(sync))
+        .route(TRANSACTION_PATH, get(get_transaction))
+        .route(OBJECT_PATH, get(get_object))
+        .with_state(light_client)
+}
+
+/// Serve the light client's HTTP API on `address` until the server fails.
+pub async fn serve(light_client: Arc<LightClient>, address: SocketAddr) -> anyhow::Result<()> {
+    axum::Server::bind(&address)
+        .serve(router(light_client).into_make_service())
+        .await?;
+    Ok(())
+}
+
+#[derive(serde::Serialize)]
+struct EpochResponse {
+    epoch: EpochId,
+}
+
+async fn get_epoch(State(light_client): State<Arc<LightClient>>) -> Json<EpochResponse> {
+    Json(EpochResponse {
+        epoch: light_client.latest_epoch(),
+    })
+}
+
+async fn sync(State(light_client): State<Arc<LightClient>>) -> Result<Json<EpochResponse>> {
+    let epoch = light_client.sync().await?;
+    Ok(Json(EpochResponse { epoch }))
+}
+
+async fn get_transaction(
+    Path(digest): Path<TransactionDigest>,
+    State(light_client): State<Arc<LightClient>>,
+) -> Result<Json<CheckpointedTransaction>> {
+    Ok(Json(light_client.get_verified_transaction(digest).await?))
+}
+
+async fn get_object(
+    Path(object_id): Path<ObjectID>,
+    State(light_client): State<Arc<LightClient>>,
+) -> Result<Json<Object>> {
+    Ok(Json(light_client.get_verified_object(object_id).await?))
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+struct Error(anyhow::Error);
+
+impl From<anyhow::Error> for Error {
+    fn from(error: anyhow::Error) -> Self {
+        Self(error)
+    }
+}
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        let status = if self.0.is::<NotFound>() {
+            StatusCode::NOT_FOUND
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        };
+        (status, format!("{:#}", self.0)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use axum::body::{Body, Bytes};
+    use axum::http::Request;
+    use serde_json::{json, Value};
+    use sui_json_rpc_types::SuiTransactionBlockResponse;
+    use sui_rest_api::CheckpointData;
+    use sui_types::committee::Committee;
+    use sui_types::messages_checkpoint::CertifiedCheckpointSummary;
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::config::Config;
+    use crate::verifier::CommitteeChain;
+
+    const TRANSACTION: &str = "8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zi6cMVA9t4WhWk";
+    const UNKNOWN_TRANSACTION: &str = "8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zj6cMVA9t4WhWk";
+
+    fn example_path(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("example_config")
+            .join(name)
+    }
+
+    /// A fullnode that only knows about the transactions and objects in the example checkpoint.
+    #[derive(Clone)]
+    struct MockFullNode {
+        checkpoint: Arc<CheckpointData>,
+    }
+
+    impl MockFullNode {
+        fn transaction(&self, digest: TransactionDigest) -> Option<SuiTransactionBlockResponse> {
+            self.checkpoint
+                .transactions
+                .iter()
+                .any(|tx| *tx.transaction.digest() == digest)
+                .then(|| SuiTransactionBlockResponse {
+                    checkpoint: Some(self.checkpoint.checkpoint_summary.sequence_number),
+                    ..SuiTransactionBlockResponse::new(digest)
+                })
+        }
+
+        fn object(&self, id: ObjectID) -> Option<Object> {
+            self.checkpoint
+                .transactions
+                .iter()
+                .flat_map(|tx| &tx.output_objects)
+                .find(|object| object.id() == id)
+                .cloned()
+        }
+    }
+
+    async fn json_rpc(State(node): State<MockFullNode>, Json(request): Json<Value>) -> Json<Value> {
+        let id = request["id"].clone();
+        let result = match request["method"].as_str() {
+            Some("rpc.discover") => Ok(json!({ "info": { "version": "1.0.0" }, "methods": [] })),
+            Some("sui_getTransactionBlock") => {
+                let digest =
+                    TransactionDigest::from_str(request["params"][0].as_str().unwrap()).unwrap();
+                node.transaction(digest)
+                    .map(|response| serde_json::to_value(response).unwrap())
+                    .ok_or("Could not find the referenced transaction")
+            }
+            method => panic!("Unexpected method {method:?}"),
+        };
+        Json(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(message) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": -32602, "message": message },
+            }),
+        })
+    }
+
+    async fn full_checkpoint(
+        Path(seq): Path<u64>,
+        State(node): State<MockFullNode>,
+    ) -> std::result::Result<Vec<u8>, StatusCode> {
+        if seq == node.checkpoint.checkpoint_summary.sequence_number {
+            Ok(bcs::to_bytes(node.checkpoint.as_ref()).unwrap())
+        } else {
+            Err(StatusCode::NOT_FOUND)
+        }
+    }
+
+    async fn object(
+        Path(id): Path<ObjectID>,
+        State(node): State<MockFullNode>,
+    ) -> std::result::Result<Vec<u8>, StatusCode> {
+        node.object(id)
+            .map(|object| bcs::to_bytes(&object).unwrap())
+            .ok_or(StatusCode::NOT_FOUND)
+    }
+
+    /// Start a mock fullnode serving the example checkpoint, and a light client that knows the
+    /// committee that certified it.
+    fn setup(checkpoint_summary_dir: &std::path::Path) -> (MockFullNode, Router) {
+        let checkpoint: CheckpointData =
+            bcs::from_bytes(&fs::read(example_path("20958462.bcs")).unwrap()).unwrap();
+        let end_of_epoch: CertifiedCheckpointSummary =
+            bcs::from_bytes(&fs::read(example_path("20873329.yaml")).unwrap()).unwrap();
+        let committee = Committee::new(
+            end_of_epoch.epoch() + 1,
+            end_of_epoch
+                .end_of_epoch_data
+                .as_ref()
+                .unwrap()
+                .next_epoch_committee
+                .iter()
+                .cloned()
+                .collect(),
+        );
+
+        let node = MockFullNode {
+            checkpoint: Arc::new(checkpoint),
+        };
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let app = Router::new()
+            .route("/", post(json_rpc))
+            .route("/rest/checkpoints/:seq/full", get(full_checkpoint))
+            .route("/rest/objects/:object_id", get(object))
+            .with_state(node.clone());
+        tokio::spawn(
+            axum::Server::from_tcp(listener)
+                .unwrap()
+                .serve(app.into_make_service()),
+        );
+
+        let config = Config {
+            full_node_url: format!("http://{address}"),
+            checkpoint_summary_dir: checkpoint_summary_dir.to_path_buf(),
+            genesis_filename: "genesis.blob".into(),
+        };
+        let light_client = LightClient::new_for_testing(config, CommitteeChain::new(committee));
+        (node, router(Arc::new(light_client)))
+    }
+
+    async fn get(router: &Router, uri: String) -> (StatusCode, Bytes) {
+        let response = router
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        (status, body)
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction() {
+        let dir = tempfile::tempdir().unwrap();
+        let (node, router) = setup(dir.path());
+
+        let (status, body) = get(&router, format!("/transactions/{TRANSACTION}")).await;
+        assert_eq!(status, StatusCode::OK);
+        let verified: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            verified["checkpoint"],
+            json!(node.checkpoint.checkpoint_summary.sequence_number)
+        );
+
+        let (status, _) = get(&router, format!("/transactions/{UNKNOWN_TRANSACTION}")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) = get(&router, "/transactions/not_a_digest".to_string()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_get_object() {
+        let dir = tempfile::tempdir().unwrap();
+        let (node, router) = setup(dir.path());
+        let tid = TransactionDigest::from_str(TRANSACTION).unwrap();
+        let expected = node
+            .checkpoint
+            .transactions
+            .iter()
+            .find(|tx| *tx.transaction.digest() == tid)
+            .unwrap()
+            .output_objects[0]
+            .clone();
+
+        let (status, body) = get(&router, format!("/objects/{}", expected.id())).await;
+        assert_eq!(status, StatusCode::OK);
+        let object: Object = serde_json::from_slice(&body).unwrap();
+        assert_eq!(object, expected);
+
+        let (status, _) = get(&router, format!("/objects/{}", ObjectID::random())).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) = get(&router, "/objects/not_an_id".to_string()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_get_epoch() {
+        let dir = tempfile::tempdir().unwrap();
+        let (node, router) = setup(dir.path());
+
+        let (status, body) = get(&router, EPOCH_PATH.to_string()).await;
+        assert_eq!(status, StatusCode::OK);
+        let epoch: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            epoch["epoch"],
+            json!(node.checkpoint.checkpoint_summary.epoch())
+        );
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber};
+
+const CHECKPOINT_LIST_FILENAME: &str = "checkpoints.yaml";
+
+// The list of checkpoints at the end of each epoch
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct CheckpointsList {
+    // List of end of epoch checkpoints
+    pub checkpoints: Vec<CheckpointSequenceNumber>,
+}
+
+/// The light client's on-disk cache of end-of-epoch checkpoint summaries. Summaries are stored
+/// BCS-encoded, one per file named after their sequence number, next to a YAML list of the
+/// sequence numbers of all end-of-epoch checkpoints the client knows about.
+///
+/// The store does not verify anything: summaries read back from it must be checked against the
+/// committee chain before they are trusted.
+#[derive(Debug, Clone)]
+pub struct CheckpointStore {
+    dir: PathBuf,
+}
+
+impl CheckpointStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn checkpoint_path(&self, seq: CheckpointSequenceNumber) -> PathBuf {
+        self.dir.join(format!("{}.yaml", seq))
+    }
+
+    /// Read the list of end-of-epoch checkpoints, which is empty if none have been synced yet.
+    pub fn read_checkpoint_list(&self) -> anyhow::Result<CheckpointsList> {
+        let checkpoints_path = self.dir.join(CHECKPOINT_LIST_FILENAME);
+        if !checkpoints_path.exists() {
+            return Ok(CheckpointsList::default());
+        }
+        // Read the resulting file and parse the yaml checkpoint list
+        let reader = fs::File::open(checkpoints_path)?;
+        Ok(serde_yaml::from_reader(reader)?)
+    }
+
+    pub fn write_checkpoint_list(&self, checkpoints_list: &CheckpointsList) -> anyhow::Result<()> {
+        // Write the checkpoint list to a file
+        let checkpoints_path = self.dir.join(CHECKPOINT_LIST_FILENAME);
+        let mut writer = fs::File::create(checkpoints_path)?;
+        let bytes = serde_yaml::to_vec(&checkpoints_list)?;
+        writer
+            .write_all(&bytes)
+            .map_err(|_| anyhow!("Unable to serialize checkpoint list"))
+    }
+
+    pub fn has_checkpoint(&self, seq: CheckpointSequenceNumber) -> bool {
+        self.checkpoint_path(seq).exists()
+    }
+
+    pub fn read_checkpoint(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> anyhow::Result<CertifiedCheckpointSummary> {
+        let checkpoint_path = self.checkpoint_path(seq);
+        let mut reader = fs::File::open(&checkpoint_path)?;
+        let metadata = fs::metadata(&checkpoint_path)?;
+        let mut buffer = vec![0; metadata.len() as usize];
+        reader.read_exact(&mut buffer)?;
+        bcs::from_bytes(&buffer).map_err(|_| anyhow!("Unable to parse checkpoint file"))
+    }
+
+    pub fn write_checkpoint(&self, summary: &CertifiedCheckpointSummary) -> anyhow::Result<()> {
+        // Write the checkpoint summary to a file
+        let mut writer = fs::File::create(self.checkpoint_path(summary.sequence_number))?;
+        let bytes = bcs::to_bytes(&summary)
+            .map_err(|_| anyhow!("Unable to serialize checkpoint summary"))?;
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+}
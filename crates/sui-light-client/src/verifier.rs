@@ -0,0 +1,356 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use std::collections::BTreeMap;
+use sui_rest_api::CheckpointData;
+use sui_types::{
+    committee::{Committee, EpochId},
+    digests::TransactionDigest,
+    effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
+    messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber, EndOfEpochData},
+    object::Object,
+    transaction::Transaction,
+};
+
+/// The committees of every epoch the light client has verified, starting from the genesis
+/// committee. The committee of epoch `e + 1` is read from the end-of-epoch checkpoint of epoch
+/// `e`, which is only accepted once its certificate has been checked against the committee of
+/// epoch `e`.
+#[derive(Debug, Clone)]
+pub struct CommitteeChain {
+    committees: BTreeMap<EpochId, Committee>,
+    end_of_epoch_checkpoints: Vec<CertifiedCheckpointSummary>,
+}
+
+impl CommitteeChain {
+    pub fn new(genesis_committee: Committee) -> Self {
+        Self {
+            committees: BTreeMap::from([(genesis_committee.epoch, genesis_committee)]),
+            end_of_epoch_checkpoints: vec![],
+        }
+    }
+
+    pub fn committee(&self, epoch: EpochId) -> Option<&Committee> {
+        self.committees.get(&epoch)
+    }
+
+    pub fn latest_committee(&self) -> &Committee {
+        let (_, committee) = self
+            .committees
+            .last_key_value()
+            .expect("Committee chain always contains the genesis committee");
+        committee
+    }
+
+    pub fn latest_epoch(&self) -> EpochId {
+        self.latest_committee().epoch
+    }
+
+    /// The last end-of-epoch checkpoint that was added to the chain, if any.
+    pub fn last_end_of_epoch_checkpoint(&self) -> Option<&CertifiedCheckpointSummary> {
+        self.end_of_epoch_checkpoints.last()
+    }
+
+    /// The end-of-epoch checkpoints the chain was built from, in order.
+    pub fn end_of_epoch_checkpoints(&self) -> &[CertifiedCheckpointSummary] {
+        &self.end_of_epoch_checkpoints
+    }
+
+    /// Extend the chain by one epoch, using the end-of-epoch checkpoint of the latest epoch in the
+    /// chain. Fails without modifying the chain if the checkpoint is not from that epoch, is not
+    /// an end-of-epoch checkpoint, or is not certified by that epoch's committee.
+    pub fn append(&mut self, summary: CertifiedCheckpointSummary) -> anyhow::Result<&Committee> {
+        let committee = self.latest_committee();
+        anyhow::ensure!(
+            summary.epoch() == committee.epoch,
+            "Expected an end-of-epoch checkpoint for epoch {}, got one for epoch {}",
+            committee.epoch,
+            summary.epoch()
+        );
+
+        summary.verify_authority_signatures(committee)?;
+
+        let Some(EndOfEpochData {
+            next_epoch_committee,
+            ..
+        }) = &summary.end_of_epoch_data
+        else {
+            return Err(anyhow!(
+                "Expected all checkpoints to be end-of-epoch checkpoints"
+            ));
+        };
+
+        let next_epoch = summary.epoch().checked_add(1).unwrap();
+        let next_committee =
+            Committee::new(next_epoch, next_epoch_committee.iter().cloned().collect());
+        self.committees.insert(next_epoch, next_committee);
+        self.end_of_epoch_checkpoints.push(summary);
+        Ok(&self.committees[&next_epoch])
+    }
+}
+
+/// A transaction, along with its effects and events, that was verified to be included in a
+/// certified checkpoint, and is therefore final.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct CheckpointedTransaction {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub epoch: EpochId,
+    pub transaction: Transaction,
+    pub effects: TransactionEffects,
+    pub events: Option<TransactionEvents>,
+}
+
+/// Verify that `checkpoint` is certified by `committee`, that its contents match the summary, and
+/// that it includes transaction `tid`. The transaction, its effects and its events are returned
+/// only if their digests match the ones authenticated by the checkpoint contents.
+pub fn extract_verified_transaction(
+    checkpoint: &CheckpointData,
+    committee: &Committee,
+    tid: TransactionDigest,
+) -> anyhow::Result<CheckpointedTransaction> {
+    let summary = &checkpoint.checkpoint_summary;
+
+    // Verify the checkpoint summary using the committee
+    summary.verify_with_contents(committee, Some(&checkpoint.checkpoint_contents))?;
+
+    // Check the validity of the transaction
+    let contents = &checkpoint.checkpoint_contents;
+    let (matching_tx, _) = checkpoint
+        .transactions
+        .iter()
+        .zip(contents.iter())
+        // Note that we get the digest of the effects to ensure this is
+        // indeed the correct effects that are authenticated in the contents.
+        .find(|(tx, digest)| {
+            tx.effects.execution_digests() == **digest && digest.transaction == tid
+        })
+        .ok_or(anyhow!("Transaction not found in checkpoint contents"))?;
+
+    // Check the transaction itself is the one authenticated in the contents.
+    anyhow::ensure!(
+        *matching_tx.transaction.digest() == tid,
+        "Transaction digest does not match"
+    );
+
+    // Check the events are all correct.
+    let events_digest = matching_tx.events.as_ref().map(|events| events.digest());
+    anyhow::ensure!(
+        events_digest.as_ref() == matching_tx.effects.events_digest(),
+        "Events digest does not match"
+    );
+
+    Ok(CheckpointedTransaction {
+        checkpoint: summary.sequence_number,
+        epoch: summary.epoch(),
+        transaction: matching_tx.transaction.clone(),
+        effects: matching_tx.effects.clone(),
+        events: matching_tx.events.clone(),
+    })
+}
+
+/// Check that `object` (its ID, version and digest) was written by the transaction that produced
+/// `effects`. This authenticates the object's contents at that version, but not that it is still
+/// the latest version of the object.
+pub fn verify_object_in_effects(
+    object: &Object,
+    effects: &TransactionEffects,
+) -> anyhow::Result<()> {
+    let object_ref = object.compute_object_reference();
+    effects
+        .all_changed_objects()
+        .iter()
+        .find(|(changed_ref, _, _)| *changed_ref == object_ref)
+        .ok_or(anyhow!("Object not found"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_types::crypto::AuthorityQuorumSignInfo;
+    use sui_types::message_envelope::Envelope;
+    use sui_types::messages_checkpoint::{CheckpointSummary, FullCheckpointContents};
+
+    use super::*;
+    use std::fs;
+    use std::io::Read;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    async fn read_full_checkpoint(checkpoint_path: &PathBuf) -> anyhow::Result<CheckpointData> {
+        let mut reader = fs::File::open(checkpoint_path.clone())?;
+        let metadata = fs::metadata(checkpoint_path)?;
+        let mut buffer = vec![0; metadata.len() as usize];
+        reader.read_exact(&mut buffer)?;
+        bcs::from_bytes(&buffer).map_err(|_| anyhow!("Unable to parse checkpoint file"))
+    }
+
+    fn read_end_of_epoch_checkpoint() -> CertifiedCheckpointSummary {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("example_config/20873329.yaml");
+
+        let mut reader = fs::File::open(d.clone()).unwrap();
+        let metadata = fs::metadata(&d).unwrap();
+        let mut buffer = vec![0; metadata.len() as usize];
+        reader.read_exact(&mut buffer).unwrap();
+        let checkpoint: Envelope<CheckpointSummary, AuthorityQuorumSignInfo<true>> =
+            bcs::from_bytes(&buffer)
+                .map_err(|_| anyhow!("Unable to parse checkpoint file"))
+                .unwrap();
+        checkpoint
+    }
+
+    async fn read_data() -> (Committee, CheckpointData) {
+        let checkpoint = read_end_of_epoch_checkpoint();
+
+        let prev_committee = checkpoint
+            .end_of_epoch_data
+            .as_ref()
+            .ok_or(anyhow!(
+                "Expected all checkpoints to be end-of-epoch checkpoints"
+            ))
+            .unwrap()
+            .next_epoch_committee
+            .iter()
+            .cloned()
+            .collect();
+
+        // Make a committee object using this
+        let committee = Committee::new(checkpoint.epoch().checked_add(1).unwrap(), prev_committee);
+
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.push("example_config/20958462.bcs");
+
+        let full_checkpoint = read_full_checkpoint(&d).await.unwrap();
+
+        (committee, full_checkpoint)
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_all_good() {
+        let (committee, full_checkpoint) = read_data().await;
+
+        let tid =
+            TransactionDigest::from_str("8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zi6cMVA9t4WhWk").unwrap();
+        let verified = extract_verified_transaction(&full_checkpoint, &committee, tid).unwrap();
+
+        assert_eq!(*verified.transaction.digest(), tid);
+        assert_eq!(verified.effects.transaction_digest(), &tid);
+        assert_eq!(
+            verified.checkpoint,
+            full_checkpoint.checkpoint_summary.sequence_number
+        );
+        assert_eq!(verified.epoch, committee.epoch);
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_bad_committee() {
+        let (mut committee, full_checkpoint) = read_data().await;
+
+        // Change committee
+        committee.epoch += 10;
+
+        assert!(extract_verified_transaction(
+            &full_checkpoint,
+            &committee,
+            TransactionDigest::from_str("8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zi6cMVA9t4WhWk").unwrap(),
+        )
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_no_transaction() {
+        let (committee, full_checkpoint) = read_data().await;
+
+        assert!(extract_verified_transaction(
+            &full_checkpoint,
+            &committee,
+            TransactionDigest::from_str("8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zj6cMVA9t4WhWk").unwrap(),
+        )
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_bad_contents() {
+        let (committee, mut full_checkpoint) = read_data().await;
+
+        // Change contents
+        let random_contents = FullCheckpointContents::random_for_testing();
+        full_checkpoint.checkpoint_contents = random_contents.checkpoint_contents();
+
+        assert!(extract_verified_transaction(
+            &full_checkpoint,
+            &committee,
+            TransactionDigest::from_str("8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zj6cMVA9t4WhWk").unwrap(),
+        )
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_bad_events() {
+        let (committee, mut full_checkpoint) = read_data().await;
+
+        let event = full_checkpoint.transactions[4]
+            .events
+            .as_ref()
+            .unwrap()
+            .data[0]
+            .clone();
+
+        for t in &mut full_checkpoint.transactions {
+            if let Some(events) = &mut t.events {
+                events.data.push(event.clone());
+            }
+        }
+
+        assert!(extract_verified_transaction(
+            &full_checkpoint,
+            &committee,
+            TransactionDigest::from_str("8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zj6cMVA9t4WhWk").unwrap(),
+        )
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_object_in_effects() {
+        let (committee, full_checkpoint) = read_data().await;
+
+        let tid =
+            TransactionDigest::from_str("8RiKBwuAbtu8zNCtz8SrcfHyEUzto6zi6cMVA9t4WhWk").unwrap();
+        let verified = extract_verified_transaction(&full_checkpoint, &committee, tid).unwrap();
+
+        // Every object written by the transaction is authenticated by its effects, but objects
+        // written by other transactions are not.
+        for tx in &full_checkpoint.transactions {
+            for object in &tx.output_objects {
+                assert_eq!(
+                    verify_object_in_effects(object, &verified.effects).is_ok(),
+                    *tx.transaction.digest() == tid,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_committee_chain_rejects_wrong_epoch() {
+        let checkpoint = read_end_of_epoch_checkpoint();
+        let committee = Committee::new(
+            checkpoint.epoch().checked_add(1).unwrap(),
+            checkpoint
+                .end_of_epoch_data
+                .as_ref()
+                .unwrap()
+                .next_epoch_committee
+                .iter()
+                .cloned()
+                .collect(),
+        );
+
+        // The checkpoint ends the epoch before the chain's latest committee, so it cannot be used
+        // to extend the chain.
+        let mut chain = CommitteeChain::new(committee.clone());
+        assert!(chain.append(checkpoint).is_err());
+        assert_eq!(chain.latest_epoch(), committee.epoch);
+        assert!(chain.last_end_of_epoch_checkpoint().is_none());
+    }
+}
@@ -0,0 +1,577 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generates Rust bindings for Move packages: serde structs and enums with the same BCS
+//! representation as the package's datatypes, and helpers adding calls to its public and entry
+//! functions to a `ProgrammableTransactionBuilder`.
+//!
+//! The bindings are generated as the source of a single module, which refers to the `anyhow`,
+//! `move_core_types`, `serde` and `sui_types` crates. Datatypes from dependencies that the package
+//! refers to are generated under its `deps` module, except for those that already have a
+//! counterpart in Rust (strings, options, `UID`, `ID`, `Balance`, `Coin`, ...).
+
+use anyhow::{anyhow, Result};
+use move_binary_format::file_format::Visibility;
+use move_binary_format::CompiledModule;
+use move_core_types::account_address::AccountAddress;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use sui_move_build::CompiledPackage;
+use sui_package_resolver::{
+    DataDef, DatatypeKey, FunctionDef, MoveData, OpenSignatureBody, Package, PackageStore,
+};
+use sui_types::base_types::{SequenceNumber, TX_CONTEXT_MODULE_NAME, TX_CONTEXT_STRUCT_NAME};
+use sui_types::move_package::MovePackage;
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+
+const INDENT: &str = "    ";
+
+const DERIVES: &str =
+    "#[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]";
+
+const ARGUMENT: &str = "::sui_types::transaction::Argument";
+
+/// Generates bindings for packages, resolving the datatypes they refer to from the set of packages
+/// it was created with.
+pub struct BindingsGenerator {
+    /// Packages by runtime ID.
+    packages: BTreeMap<AccountAddress, Package>,
+}
+
+/// Generation state for the bindings of one package.
+struct Bindings<'g> {
+    generator: &'g BindingsGenerator,
+    /// Runtime ID of the package bindings are generated for.
+    root: AccountAddress,
+    /// Datatypes from dependencies that have been referred to so far.
+    deps: BTreeSet<DatatypeKey>,
+}
+
+impl BindingsGenerator {
+    pub fn new(packages: impl IntoIterator<Item = Package>) -> Self {
+        Self {
+            packages: packages
+                .into_iter()
+                .map(|package| (package.runtime_id(), package))
+                .collect(),
+        }
+    }
+
+    /// Create a generator for a locally built package and its dependencies, returning it along
+    /// with the runtime ID of the built package.
+    pub fn from_compiled_package(package: &CompiledPackage) -> Result<(Self, AccountAddress)> {
+        let root = package
+            .get_modules()
+            .next()
+            .ok_or_else(|| anyhow!("Package has no modules"))?;
+        let root = *root.address();
+
+        let mut modules: BTreeMap<AccountAddress, Vec<CompiledModule>> = BTreeMap::new();
+        for module in package.get_modules_and_deps() {
+            modules
+                .entry(*module.address())
+                .or_default()
+                .push(module.clone());
+        }
+
+        let packages = modules
+            .into_values()
+            .map(|modules| {
+                // Every datatype originates from the address of its module, as it would once the
+                // package is first published. The linkage table is not needed for bindings.
+                let package = MovePackage::new_system(SequenceNumber::new(), &modules, []);
+                Ok(Package::read_from_package(&package)?)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((Self::new(packages), root))
+    }
+
+    /// Create a generator for the package with storage ID `storage_id` and its dependencies, as
+    /// fetched from `store`, returning it along with the runtime ID of the package.
+    pub async fn fetch(
+        store: &impl PackageStore,
+        storage_id: AccountAddress,
+    ) -> Result<(Self, AccountAddress)> {
+        let root = store.fetch(storage_id).await?;
+        let mut packages = vec![(*root).clone()];
+        for dep in root.linkage().values() {
+            packages.push((*store.fetch(*dep).await?).clone());
+        }
+        Ok((Self::new(packages), root.runtime_id()))
+    }
+
+    /// Generate the source of the bindings of the package with runtime ID `package`.
+    pub fn generate(&self, package: AccountAddress) -> Result<String> {
+        let root = self.package(package)?;
+        let mut bindings = Bindings {
+            generator: self,
+            root: package,
+            deps: BTreeSet::new(),
+        };
+
+        let mut out = String::new();
+        writeln!(
+            out,
+            "// Generated by `sui move bindgen` for package {}, do not edit.",
+            package.to_hex_literal(),
+        )?;
+        writeln!(
+            out,
+            "#![allow(clippy::all, dead_code, non_camel_case_types, non_snake_case)]"
+        )?;
+
+        for (name, module) in root.modules() {
+            writeln!(out)?;
+            writeln!(out, "pub mod {} {{", escape(name))?;
+            writeln!(out, "{INDENT}pub const MODULE_NAME: &str = \"{name}\";")?;
+
+            for datatype in module.datatypes(None, None) {
+                let Some(def) = module.data_def(datatype)? else {
+                    continue;
+                };
+                writeln!(out)?;
+                bindings.datatype(&mut out, 1, datatype, &def)?;
+            }
+
+            for function in module.functions(None, None) {
+                let Some(def) = module.function_def(function)? else {
+                    continue;
+                };
+                if def.visibility != Visibility::Public && !def.is_entry {
+                    continue;
+                }
+                writeln!(out)?;
+                bindings.function(&mut out, 1, name, function, &def)?;
+            }
+            writeln!(out, "}}")?;
+        }
+
+        // Generating a dependency's datatype can refer to further datatypes, so they are all
+        // gathered before being written out, grouped by package and module.
+        let mut deps: BTreeMap<AccountAddress, BTreeMap<String, String>> = BTreeMap::new();
+        let mut generated = BTreeSet::new();
+        while let Some(key) = bindings
+            .deps
+            .iter()
+            .find(|key| !generated.contains(*key))
+            .cloned()
+        {
+            let def = self.data_def(&key)?;
+            let module_out = deps
+                .entry(key.package)
+                .or_default()
+                .entry(key.module.to_string())
+                .or_default();
+            writeln!(module_out)?;
+            bindings.datatype(module_out, 3, &key.name, &def)?;
+            generated.insert(key);
+        }
+
+        if !deps.is_empty() {
+            writeln!(out)?;
+            writeln!(out, "pub mod deps {{")?;
+            for (i, (package, modules)) in deps.iter().enumerate() {
+                if i > 0 {
+                    writeln!(out)?;
+                }
+                writeln!(out, "{INDENT}pub mod {} {{", package_module(*package))?;
+                for (j, (module, datatypes)) in modules.iter().enumerate() {
+                    if j > 0 {
+                        writeln!(out)?;
+                    }
+                    writeln!(out, "{INDENT}{INDENT}pub mod {} {{", escape(module))?;
+                    // Skip the blank line separating datatypes before the first one.
+                    out.push_str(&datatypes[1..]);
+                    writeln!(out, "{INDENT}{INDENT}}}")?;
+                }
+                writeln!(out, "{INDENT}}}")?;
+            }
+            writeln!(out, "}}")?;
+        }
+
+        Ok(out)
+    }
+
+    fn package(&self, runtime_id: AccountAddress) -> Result<&Package> {
+        self.packages.get(&runtime_id).ok_or_else(|| {
+            anyhow!(
+                "Package {} is not available to generate bindings with",
+                runtime_id.to_hex_literal()
+            )
+        })
+    }
+
+    fn data_def(&self, key: &DatatypeKey) -> Result<DataDef> {
+        self.package(key.package)?
+            .module(&key.module)?
+            .data_def(&key.name)?
+            .ok_or_else(|| {
+                anyhow!(
+                    "Datatype {}::{}::{} not found",
+                    key.package.to_hex_literal(),
+                    key.module,
+                    key.name,
+                )
+            })
+    }
+}
+
+impl<'g> Bindings<'g> {
+    /// Write the struct or enum mirroring datatype `name` with definition `def`, in a module
+    /// nested `depth` levels deep in the bindings.
+    fn datatype(
+        &mut self,
+        out: &mut String,
+        depth: usize,
+        name: &str,
+        def: &DataDef,
+    ) -> Result<()> {
+        let ind = INDENT.repeat(depth);
+        let generics = generics(
+            def.type_params
+                .iter()
+                .enumerate()
+                .filter(|(_, param)| !param.is_phantom)
+                .map(|(ix, _)| format!("T{ix}")),
+        );
+
+        writeln!(out, "{ind}{DERIVES}")?;
+        match &def.data {
+            MoveData::Struct(fields) => {
+                writeln!(out, "{ind}pub struct {}{generics} {{", escape(name))?;
+                for (field, sig) in fields {
+                    let type_ = self.rust_type(sig, depth)?;
+                    writeln!(out, "{ind}{INDENT}pub {}: {type_},", escape(field))?;
+                }
+            }
+            MoveData::Enum(variants) => {
+                writeln!(out, "{ind}pub enum {}{generics} {{", escape(name))?;
+                for variant in variants {
+                    if variant.signatures.is_empty() {
+                        writeln!(out, "{ind}{INDENT}{},", escape(&variant.name))?;
+                        continue;
+                    }
+                    writeln!(out, "{ind}{INDENT}{} {{", escape(&variant.name))?;
+                    for (field, sig) in &variant.signatures {
+                        let type_ = self.rust_type(sig, depth)?;
+                        writeln!(out, "{ind}{INDENT}{INDENT}{}: {type_},", escape(field))?;
+                    }
+                    writeln!(out, "{ind}{INDENT}}},")?;
+                }
+            }
+        }
+        writeln!(out, "{ind}}}")?;
+        Ok(())
+    }
+
+    /// Write a helper adding a call to `module::function` to a programmable transaction. Pure
+    /// parameters are taken as Rust values, other parameters as arguments of the transaction, and
+    /// the `TxContext` is left out.
+    fn function(
+        &mut self,
+        out: &mut String,
+        depth: usize,
+        module: &str,
+        function: &str,
+        def: &FunctionDef,
+    ) -> Result<()> {
+        let ind = INDENT.repeat(depth);
+        let mut params = vec![
+            "builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder".to_string(),
+            "package: ::sui_types::base_types::ObjectID".to_string(),
+        ];
+        if !def.type_params.is_empty() {
+            params.push(format!(
+                "type_arguments: [::sui_types::TypeTag; {}]",
+                def.type_params.len()
+            ));
+        }
+
+        let mut arguments = vec![];
+        for (ix, param) in def.parameters.iter().enumerate() {
+            if param.ref_.is_some() && is_tx_context(&param.body) {
+                continue;
+            }
+            let arg = format!("arg{ix}");
+            if is_pure(&param.body) {
+                params.push(format!("{arg}: {}", self.rust_type(&param.body, depth)?));
+                arguments.push(format!("builder.pure({arg})?"));
+            } else {
+                params.push(format!("{arg}: {ARGUMENT}"));
+                arguments.push(arg);
+            }
+        }
+
+        writeln!(
+            out,
+            "{ind}/// Adds a call to `{module}::{function}` to the transaction, returning its result."
+        )?;
+        writeln!(out, "{ind}pub fn {}(", escape(function))?;
+        for param in params {
+            writeln!(out, "{ind}{INDENT}{param},")?;
+        }
+        writeln!(out, "{ind}) -> ::anyhow::Result<{ARGUMENT}> {{")?;
+        if arguments.is_empty() {
+            writeln!(out, "{ind}{INDENT}let arguments = vec![];")?;
+        } else {
+            writeln!(out, "{ind}{INDENT}let arguments = vec![")?;
+            for argument in arguments {
+                writeln!(out, "{ind}{INDENT}{INDENT}{argument},")?;
+            }
+            writeln!(out, "{ind}{INDENT}];")?;
+        }
+        let type_arguments = if def.type_params.is_empty() {
+            "vec![]"
+        } else {
+            "type_arguments.to_vec()"
+        };
+        writeln!(out, "{ind}{INDENT}Ok(builder.programmable_move_call(")?;
+        writeln!(out, "{ind}{INDENT}{INDENT}package,")?;
+        writeln!(
+            out,
+            "{ind}{INDENT}{INDENT}::sui_types::Identifier::new(MODULE_NAME)?,"
+        )?;
+        writeln!(
+            out,
+            "{ind}{INDENT}{INDENT}::sui_types::Identifier::new(\"{function}\")?,"
+        )?;
+        writeln!(out, "{ind}{INDENT}{INDENT}{type_arguments},")?;
+        writeln!(out, "{ind}{INDENT}{INDENT}arguments,")?;
+        writeln!(out, "{ind}{INDENT}))")?;
+        writeln!(out, "{ind}}}")?;
+        Ok(())
+    }
+
+    /// The Rust type mirroring `sig`, as referred to from a module nested `depth` levels deep in
+    /// the bindings. Datatypes from dependencies are recorded to be generated too.
+    fn rust_type(&mut self, sig: &OpenSignatureBody, depth: usize) -> Result<String> {
+        use OpenSignatureBody as O;
+        Ok(match sig {
+            O::Address => "::sui_types::base_types::SuiAddress".to_string(),
+            O::Bool => "bool".to_string(),
+            O::U8 => "u8".to_string(),
+            O::U16 => "u16".to_string(),
+            O::U32 => "u32".to_string(),
+            O::U64 => "u64".to_string(),
+            O::U128 => "u128".to_string(),
+            O::U256 => "::move_core_types::u256::U256".to_string(),
+            O::Vector(sig) => format!("Vec<{}>", self.rust_type(sig, depth)?),
+            O::TypeParameter(ix) => format!("T{ix}"),
+
+            O::Datatype(key, params) => {
+                if let Some((path, generic)) = well_known(key) {
+                    if !generic {
+                        return Ok(path.to_string());
+                    }
+                    let params = params
+                        .iter()
+                        .map(|param| self.rust_type(param, depth))
+                        .collect::<Result<Vec<_>>>()?;
+                    return Ok(format!("{path}{}", generics(params)));
+                }
+
+                // Phantom type parameters are not part of the Rust type, as they do not affect
+                // its representation.
+                let def = self.generator.data_def(key)?;
+                let params = def
+                    .type_params
+                    .iter()
+                    .zip(params)
+                    .filter(|(param, _)| !param.is_phantom)
+                    .map(|(_, param)| self.rust_type(param, depth))
+                    .collect::<Result<Vec<_>>>()?;
+
+                let root = "super::".repeat(depth);
+                let module = escape(&key.module);
+                let name = escape(&key.name);
+                let generics = generics(params);
+                if key.package == self.root {
+                    format!("{root}{module}::{name}{generics}")
+                } else {
+                    self.deps.insert(key.clone());
+                    let package = package_module(key.package);
+                    format!("{root}deps::{package}::{module}::{name}{generics}")
+                }
+            }
+        })
+    }
+}
+
+/// The Rust counterpart of framework datatypes that have one, and whether it is generic over the
+/// datatype's type parameters.
+fn well_known(key: &DatatypeKey) -> Option<(&'static str, bool)> {
+    let datatype = (&*key.module, &*key.name);
+    if key.package == MOVE_STDLIB_ADDRESS {
+        match datatype {
+            ("ascii", "String") | ("string", "String") | ("type_name", "TypeName") => {
+                Some(("String", false))
+            }
+            ("option", "Option") => Some(("Option", true)),
+            _ => None,
+        }
+    } else if key.package == SUI_FRAMEWORK_ADDRESS {
+        match datatype {
+            ("object", "UID") => Some(("::sui_types::id::UID", false)),
+            ("object", "ID") => Some(("::sui_types::id::ID", false)),
+            ("balance", "Balance") => Some(("::sui_types::balance::Balance", false)),
+            ("balance", "Supply") => Some(("::sui_types::balance::Supply", false)),
+            ("coin", "Coin") => Some(("::sui_types::coin::Coin", false)),
+            ("url", "Url") => Some(("String", false)),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+/// Whether values of type `sig` can be passed to a function as pure inputs.
+fn is_pure(sig: &OpenSignatureBody) -> bool {
+    use OpenSignatureBody as O;
+    match sig {
+        O::Address | O::Bool | O::U8 | O::U16 | O::U32 | O::U64 | O::U128 | O::U256 => true,
+        O::Vector(sig) => is_pure(sig),
+        O::TypeParameter(_) => false,
+        O::Datatype(key, params) => {
+            let datatype = (&*key.module, &*key.name);
+            if key.package == MOVE_STDLIB_ADDRESS {
+                match datatype {
+                    ("ascii", "String") | ("string", "String") => true,
+                    ("option", "Option") => params.iter().all(is_pure),
+                    _ => false,
+                }
+            } else {
+                key.package == SUI_FRAMEWORK_ADDRESS && datatype == ("object", "ID")
+            }
+        }
+    }
+}
+
+fn is_tx_context(sig: &OpenSignatureBody) -> bool {
+    matches!(
+        sig,
+        OpenSignatureBody::Datatype(key, _)
+            if key.package == SUI_FRAMEWORK_ADDRESS
+                && key.module == TX_CONTEXT_MODULE_NAME.as_str()
+                && key.name == TX_CONTEXT_STRUCT_NAME.as_str()
+    )
+}
+
+fn generics(params: impl IntoIterator<Item = String>) -> String {
+    let params: Vec<_> = params.into_iter().collect();
+    if params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", params.join(", "))
+    }
+}
+
+/// Name of the module holding the datatypes of dependency `package` under `deps`.
+fn package_module(package: AccountAddress) -> String {
+    format!("_0x{}", package.short_str_lossless())
+}
+
+/// Move identifiers that are Rust keywords are escaped as raw identifiers, or suffixed with an
+/// underscore for those that cannot be raw.
+fn escape(ident: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let",
+        "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+        "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+        "virtual", "where", "while", "yield",
+    ];
+
+    match ident {
+        "crate" | "self" | "Self" | "super" => format!("{ident}_"),
+        _ if KEYWORDS.contains(&ident) => format!("r#{ident}"),
+        _ => ident.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use sui_move_build::BuildConfig;
+
+    #[test]
+    fn test_generate_bindings() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.extend(["tests", "packages", "example"]);
+        let package = BuildConfig::new_for_testing().build(&path).unwrap();
+
+        let (generator, root) = BindingsGenerator::from_compiled_package(&package).unwrap();
+        let bindings = generator.generate(root).unwrap();
+
+        let expected = [
+            "pub mod example {",
+            "    pub const MODULE_NAME: &str = \"example\";",
+            // Structs, with framework types mapped to their Rust counterpart, and dependencies'
+            // datatypes generated under `deps`.
+            "    pub struct Registry {\n\
+            \x20       pub id: ::sui_types::id::UID,\n\
+            \x20       pub names: super::deps::_0x2::table::Table,\n\
+            \x20       pub count: u64,\n\
+            \x20   }",
+            // Phantom type parameters are dropped.
+            "    pub struct Wrapper<T0> {\n\
+            \x20       pub value: T0,\n\
+            \x20       pub tag: Option<u8>,\n\
+            \x20       pub r#dyn: Vec<String>,\n\
+            \x20   }",
+            "    pub enum Shape {\n\
+            \x20       Circle {\n\
+            \x20           radius: u64,\n\
+            \x20       },\n\
+            \x20       Empty,\n\
+            \x20   }",
+            // Pure parameters are taken by value, and the `TxContext` is left out.
+            "    pub fn register(\n\
+            \x20       builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder,\n\
+            \x20       package: ::sui_types::base_types::ObjectID,\n\
+            \x20       arg0: ::sui_types::transaction::Argument,\n\
+            \x20       arg1: String,\n\
+            \x20   ) -> ::anyhow::Result<::sui_types::transaction::Argument> {\n\
+            \x20       let arguments = vec![\n\
+            \x20           arg0,\n\
+            \x20           builder.pure(arg1)?,\n\
+            \x20       ];",
+            "    pub fn wrap(\n\
+            \x20       builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder,\n\
+            \x20       package: ::sui_types::base_types::ObjectID,\n\
+            \x20       type_arguments: [::sui_types::TypeTag; 2],\n\
+            \x20       arg0: ::sui_types::transaction::Argument,\n\
+            \x20   ) -> ::anyhow::Result<::sui_types::transaction::Argument> {",
+            "            type_arguments.to_vec(),",
+            "    pub fn set_count(",
+            "pub mod deps {\n\
+            \x20   pub mod _0x2 {\n\
+            \x20       pub mod table {\n\
+            \x20           #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]\n\
+            \x20           pub struct Table {\n\
+            \x20               pub id: ::sui_types::id::UID,\n\
+            \x20               pub size: u64,\n\
+            \x20           }\n\
+            \x20       }\n\
+            \x20   }\n\
+            }",
+        ];
+        for snippet in expected {
+            assert!(
+                bindings.contains(snippet),
+                "Missing from bindings:\n{snippet}\n\nBindings:\n{bindings}"
+            );
+        }
+
+        // Private functions cannot be called from a transaction.
+        assert!(!bindings.contains("pub fn private_helper("));
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("counter"), "counter");
+        assert_eq!(escape("dyn"), "r#dyn");
+        assert_eq!(escape("self"), "self_");
+    }
+}
@@ -0,0 +1,117 @@
+// Generated by `sui move bindgen` for package 0x0, do not edit.
+#![allow(clippy::all, dead_code, non_camel_case_types, non_snake_case)]
+
+pub mod example {
+    pub const MODULE_NAME: &str = "example";
+
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    pub struct Registry {
+        pub id: ::sui_types::id::UID,
+        pub names: super::deps::_0x2::table::Table,
+        pub count: u64,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    pub enum Shape {
+        Circle {
+            radius: u64,
+        },
+        Empty,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+    pub struct Wrapper<T0> {
+        pub value: T0,
+        pub tag: Option<u8>,
+        pub r#dyn: Vec<String>,
+    }
+
+    /// Adds a call to `example::create` to the transaction, returning its result.
+    pub fn create(
+        builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder,
+        package: ::sui_types::base_types::ObjectID,
+        arg0: u64,
+    ) -> ::anyhow::Result<::sui_types::transaction::Argument> {
+        let arguments = vec![
+            builder.pure(arg0)?,
+        ];
+        Ok(builder.programmable_move_call(
+            package,
+            ::sui_types::Identifier::new(MODULE_NAME)?,
+            ::sui_types::Identifier::new("create")?,
+            vec![],
+            arguments,
+        ))
+    }
+
+    /// Adds a call to `example::register` to the transaction, returning its result.
+    pub fn register(
+        builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder,
+        package: ::sui_types::base_types::ObjectID,
+        arg0: ::sui_types::transaction::Argument,
+        arg1: String,
+    ) -> ::anyhow::Result<::sui_types::transaction::Argument> {
+        let arguments = vec![
+            arg0,
+            builder.pure(arg1)?,
+        ];
+        Ok(builder.programmable_move_call(
+            package,
+            ::sui_types::Identifier::new(MODULE_NAME)?,
+            ::sui_types::Identifier::new("register")?,
+            vec![],
+            arguments,
+        ))
+    }
+
+    /// Adds a call to `example::set_count` to the transaction, returning its result.
+    pub fn set_count(
+        builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder,
+        package: ::sui_types::base_types::ObjectID,
+        arg0: ::sui_types::transaction::Argument,
+        arg1: u64,
+    ) -> ::anyhow::Result<::sui_types::transaction::Argument> {
+        let arguments = vec![
+            arg0,
+            builder.pure(arg1)?,
+        ];
+        Ok(builder.programmable_move_call(
+            package,
+            ::sui_types::Identifier::new(MODULE_NAME)?,
+            ::sui_types::Identifier::new("set_count")?,
+            vec![],
+            arguments,
+        ))
+    }
+
+    /// Adds a call to `example::wrap` to the transaction, returning its result.
+    pub fn wrap(
+        builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder,
+        package: ::sui_types::base_types::ObjectID,
+        type_arguments: [::sui_types::TypeTag; 2],
+        arg0: ::sui_types::transaction::Argument,
+    ) -> ::anyhow::Result<::sui_types::transaction::Argument> {
+        let arguments = vec![
+            arg0,
+        ];
+        Ok(builder.programmable_move_call(
+            package,
+            ::sui_types::Identifier::new(MODULE_NAME)?,
+            ::sui_types::Identifier::new("wrap")?,
+            type_arguments.to_vec(),
+            arguments,
+        ))
+    }
+}
+
+pub mod deps {
+    pub mod _0x2 {
+        pub mod table {
+            #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+            pub struct Table {
+                pub id: ::sui_types::id::UID,
+                pub size: u64,
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use simulacrum::Simulacrum;
+use sui_move_bindgen::BindingsGenerator;
+use sui_move_build::{BuildConfig, CompiledPackage};
+use sui_types::base_types::ObjectID;
+use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
+use sui_types::object::Owner;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::transaction::{Transaction, TransactionData};
+
+// Bindings generated for the example package, checked in so that they are compiled along with
+// this test.
+#[rustfmt::skip]
+#[path = "bindings/example.rs"]
+mod bindings;
+
+use bindings::example::{Registry, Shape};
+
+fn build_example() -> CompiledPackage {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.extend(["tests", "packages", "example"]);
+    BuildConfig::new_for_testing().build(&path).unwrap()
+}
+
+/// Execute the transaction built by `builder`, paid for and signed by the first account of `sim`.
+fn execute(sim: &mut Simulacrum, builder: ProgrammableTransactionBuilder) -> TransactionEffects {
+    let (sender, key) = sim.keystore().accounts().next().unwrap();
+    let sender = *sender;
+    let gas = sim
+        .store()
+        .owned_objects(sender)
+        .find(|object| object.is_gas_coin())
+        .unwrap();
+
+    let data = TransactionData::new_programmable(
+        sender,
+        vec![gas.compute_object_reference()],
+        builder.finish(),
+        1_000_000_000,
+        sim.reference_gas_price(),
+    );
+    let transaction = Transaction::from_data_and_signer(data, vec![key]);
+    let (effects, error) = sim.execute_transaction(transaction).unwrap();
+    assert!(error.is_none(), "{error:?}");
+    effects
+}
+
+#[test]
+fn test_checked_in_bindings() {
+    let package = build_example();
+    let (generator, root) = BindingsGenerator::from_compiled_package(&package).unwrap();
+    let bindings = generator.generate(root).unwrap();
+
+    // If this fails, regenerate tests/bindings/example.rs with `sui move bindgen`.
+    assert_eq!(bindings, include_str!("bindings/example.rs"));
+}
+
+#[test]
+fn test_round_trip_move_values() {
+    let package = build_example();
+    let mut sim = Simulacrum::new();
+
+    // Publish the example package.
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.publish_immutable(
+        package.get_package_bytes(false),
+        package.get_dependency_original_package_ids(),
+    );
+    let effects = execute(&mut sim, builder);
+    let package_id: ObjectID = effects
+        .created()
+        .into_iter()
+        .find(|(_, owner)| *owner == Owner::Immutable)
+        .map(|((id, _, _), _)| id)
+        .unwrap();
+
+    // Create a registry and shapes, calling into the package through its bindings.
+    let mut builder = ProgrammableTransactionBuilder::new();
+    bindings::example::create(&mut builder, package_id, 42).unwrap();
+    let effects = execute(&mut sim, builder);
+
+    // A struct, as stored by Move.
+    let registry_id = effects
+        .created()
+        .into_iter()
+        .find(|(_, owner)| matches!(owner, Owner::Shared { .. }))
+        .map(|((id, _, _), _)| id)
+        .unwrap();
+    let object = sim.store().get_object(&registry_id).unwrap();
+    let bytes = object.data.try_as_move().unwrap().contents();
+
+    let registry: Registry = bcs::from_bytes(bytes).unwrap();
+    assert_eq!(*registry.id.object_id(), registry_id);
+    assert_eq!(registry.names.size, 0);
+    assert_eq!(registry.count, 42);
+    assert_eq!(bcs::to_bytes(&registry).unwrap(), bytes);
+
+    // Enum variants, as emitted by Move.
+    let events = sim
+        .store()
+        .get_transaction_events_by_tx_digest(effects.transaction_digest())
+        .unwrap();
+    let shapes: Vec<Shape> = events
+        .data
+        .iter()
+        .map(|event| {
+            let shape: Shape = bcs::from_bytes(&event.contents).unwrap();
+            assert_eq!(bcs::to_bytes(&shape).unwrap(), event.contents);
+            shape
+        })
+        .collect();
+    assert_eq!(shapes, vec![Shape::Circle { radius: 42 }, Shape::Empty]);
+}
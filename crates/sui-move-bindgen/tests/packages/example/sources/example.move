@@ -0,0 +1,51 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[allow(unused_field)]
+module example::example {
+    use std::string::String;
+    use sui::event;
+    use sui::table::{Self, Table};
+
+    public struct Registry has key {
+        id: UID,
+        names: Table<address, String>,
+        count: u64,
+    }
+
+    public struct Wrapper<T: store, phantom P> has store {
+        value: T,
+        tag: Option<u8>,
+        dyn: vector<String>,
+    }
+
+    public enum Shape has copy, drop, store {
+        Circle { radius: u64 },
+        Empty,
+    }
+
+    public fun register(registry: &mut Registry, name: String, ctx: &TxContext) {
+        registry.names.add(ctx.sender(), name);
+        registry.count = registry.count + 1;
+    }
+
+    public fun wrap<T: store, P>(value: T): Wrapper<T, P> {
+        Wrapper { value, tag: option::none(), dyn: vector[] }
+    }
+
+    /// Shares a registry and emits shapes, whose bytes the bindings are checked against.
+    entry fun create(count: u64, ctx: &mut TxContext) {
+        transfer::share_object(Registry { id: object::new(ctx), names: table::new(ctx), count });
+        event::emit(Shape::Circle { radius: count });
+        event::emit(Shape::Empty);
+    }
+
+    entry fun set_count(registry: &mut Registry, count: u64) {
+        registry.count = count;
+    }
+
+    #[allow(unused_function)]
+    fun private_helper(ctx: &mut TxContext): Registry {
+        Registry { id: object::new(ctx), names: table::new(ctx), count: 0 }
+    }
+}
@@ -0,0 +1,43 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::manage_package::resolve_lock_file_path;
+use clap::Parser;
+use move_cli::base;
+use move_package::BuildConfig as MoveBuildConfig;
+use std::{fs, path::Path, path::PathBuf};
+use sui_move_bindgen::BindingsGenerator;
+use sui_move_build::BuildConfig;
+
+/// Generate Rust bindings for the package: serde structs and enums with the same BCS
+/// representation as its datatypes, and helpers to call its public and entry functions from a
+/// programmable transaction.
+#[derive(Parser)]
+#[group(id = "sui-move-bindgen")]
+pub struct Bindgen {
+    /// File to write the bindings to, as the source of a Rust module. Bindings are printed to
+    /// stdout if no file is given.
+    #[clap(long, short)]
+    pub output: Option<PathBuf>,
+}
+
+impl Bindgen {
+    pub fn execute(&self, path: Option<&Path>, config: MoveBuildConfig) -> anyhow::Result<()> {
+        let rerooted_path = base::reroot_path(path)?;
+        let config = resolve_lock_file_path(config, Some(&rerooted_path))?;
+        let package = BuildConfig {
+            config,
+            run_bytecode_verifier: false,
+            print_diags_to_stderr: true,
+        }
+        .build(&rerooted_path)?;
+
+        let (generator, root) = BindingsGenerator::from_compiled_package(&package)?;
+        let bindings = generator.generate(root)?;
+        match &self.output {
+            Some(output) => fs::write(output, bindings)?,
+            None => print!("{bindings}"),
+        }
+        Ok(())
+    }
+}
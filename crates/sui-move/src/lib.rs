@@ -8,6 +8,8 @@ use move_package::BuildConfig;
 use std::path::Path;
 use sui_move_build::set_sui_flavor;
 
+#[cfg(feature = "bindgen")]
+pub mod bindgen;
 #[cfg(feature = "build")]
 pub mod build;
 #[cfg(feature = "coverage")]
@@ -22,6 +24,8 @@ pub mod unit_test;
 
 #[derive(Parser)]
 pub enum Command {
+    #[cfg(feature = "bindgen")]
+    Bindgen(bindgen::Bindgen),
     #[cfg(feature = "build")]
     Build(build::Build),
     #[cfg(feature = "coverage")]
@@ -51,6 +55,8 @@ pub fn execute_move_command(
         anyhow::bail!(err_msg);
     }
     match command {
+        #[cfg(feature = "bindgen")]
+        Command::Bindgen(c) => c.execute(package_path, build_config),
         #[cfg(feature = "build")]
         Command::Build(c) => c.execute(package_path, build_config),
         #[cfg(feature = "coverage")]
@@ -12,6 +12,7 @@ use humantime::parse_duration;
 use serde::Deserialize;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::sync::Arc;
+use sui_config::transaction_deny_config::{TransactionDenyConfig, TransactionDenyConfigPatch};
 use sui_types::error::SuiError;
 use telemetry_subscribers::TracingHandle;
 use tracing::info;
@@ -47,6 +48,27 @@ use tracing::info;
 // Reset tracing to the TRACE_FILTER env var.
 //
 //   $ curl -X POST 'http://127.0.0.1:1337/reset-tracing'
+//
+// View the transaction deny config currently in use:
+//
+//   $ curl 'http://127.0.0.1:1337/transaction-deny-config'
+//
+// Replace the transaction deny config (the body uses the same YAML format as the
+// `transaction-deny-config` section of the node config, and JSON is accepted as well):
+//
+//   $ curl -X PUT 'http://127.0.0.1:1337/transaction-deny-config' \
+//       --data-binary 'user-transaction-disabled: true'
+//
+// Patch the transaction deny config, adding and removing deny list entries and setting kill
+// switches, leaving everything else unchanged:
+//
+//   $ curl -X PATCH 'http://127.0.0.1:1337/transaction-deny-config' \
+//       -d '{"add-denied-addresses": ["0x1234..."], "package-publish-disabled": false}'
+//
+// Changes to the transaction deny config take effect immediately, and are persisted in the node's
+// db directory as `transaction_deny_config.yaml`. On restart, the persisted config is merged into
+// the deny config of the node config: everything denied by either of them stays denied. Delete
+// the file to go back to the node config alone.
 
 const LOGGING_ROUTE: &str = "/logging";
 const TRACING_ROUTE: &str = "/enable-tracing";
@@ -56,6 +78,7 @@ const CLEAR_BUFFER_STAKE_ROUTE: &str = "/clear-override-buffer-stake";
 const FORCE_CLOSE_EPOCH: &str = "/force-close-epoch";
 const CAPABILITIES: &str = "/capabilities";
 const NODE_CONFIG: &str = "/node-config";
+const TRANSACTION_DENY_CONFIG: &str = "/transaction-deny-config";
 
 struct AppState {
     node: Arc<SuiNode>,
@@ -74,6 +97,12 @@ pub async fn run_admin_server(node: Arc<SuiNode>, port: u16, tracing_handle: Tra
         .route(LOGGING_ROUTE, get(get_filter))
         .route(CAPABILITIES, get(capabilities))
         .route(NODE_CONFIG, get(node_config))
+        .route(
+            TRANSACTION_DENY_CONFIG,
+            get(get_transaction_deny_config)
+                .put(replace_transaction_deny_config)
+                .patch(patch_transaction_deny_config),
+        )
         .route(LOGGING_ROUTE, post(set_filter))
         .route(
             SET_BUFFER_STAKE_ROUTE,
@@ -288,3 +317,50 @@ async fn force_close_epoch(
         Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
     }
 }
+
+async fn get_transaction_deny_config(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    transaction_deny_config_response(&state.node.transaction_deny_config())
+}
+
+async fn replace_transaction_deny_config(
+    State(state): State<Arc<AppState>>,
+    body: String,
+) -> (StatusCode, String) {
+    let config: TransactionDenyConfig = match serde_yaml::from_str(&body) {
+        Ok(config) => config,
+        Err(err) => return (StatusCode::BAD_REQUEST, format!("invalid config: {err}\n")),
+    };
+
+    match state.node.replace_transaction_deny_config(config).await {
+        Ok(config) => {
+            info!("Transaction deny config replaced");
+            transaction_deny_config_response(&config)
+        }
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+async fn patch_transaction_deny_config(
+    State(state): State<Arc<AppState>>,
+    body: String,
+) -> (StatusCode, String) {
+    let patch: TransactionDenyConfigPatch = match serde_yaml::from_str(&body) {
+        Ok(patch) => patch,
+        Err(err) => return (StatusCode::BAD_REQUEST, format!("invalid patch: {err}\n")),
+    };
+
+    match state.node.patch_transaction_deny_config(&patch).await {
+        Ok(config) => {
+            info!(?patch, "Transaction deny config patched");
+            transaction_deny_config_response(&config)
+        }
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+fn transaction_deny_config_response(config: &TransactionDenyConfig) -> (StatusCode, String) {
+    match serde_yaml::to_string(config) {
+        Ok(config) => (StatusCode::OK, config),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
@@ -60,7 +60,8 @@ use sui_archival::writer::ArchiveWriter;
 use sui_config::node::{DBCheckpointConfig, RunWithRange};
 use sui_config::node_config_metrics::NodeConfigMetrics;
 use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
-use sui_config::{ConsensusConfig, NodeConfig};
+use sui_config::transaction_deny_config::{TransactionDenyConfig, TransactionDenyConfigPatch};
+use sui_config::{Config, ConsensusConfig, NodeConfig};
 use sui_core::authority::authority_per_epoch_store::AuthorityPerEpochStore;
 use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
 use sui_core::authority::epoch_start_configuration::EpochStartConfigTrait;
@@ -114,6 +115,7 @@ use sui_protocol_config::{Chain, ProtocolConfig, SupportedProtocolVersions};
 use sui_snapshot::uploader::StateSnapshotUploader;
 use sui_storage::{
     http_key_value_store::HttpKVStore,
+    indexes::BalanceHistoryStart,
     key_value_store::{FallbackTransactionKVStore, TransactionKeyValueStore},
     key_value_store_metrics::KeyValueStoreMetrics,
 };
@@ -244,6 +246,11 @@ pub struct SuiNode {
     _state_snapshot_uploader_handle: Option<broadcast::Sender<()>>,
     // Channel to allow signaling upstream to shutdown sui-node
     shutdown_channel_tx: broadcast::Sender<Option<RunWithRange>>,
+
+    node_config_metrics: Arc<NodeConfigMetrics>,
+    /// Serializes updates to the transaction deny config, so that the copy persisted on disk
+    /// always matches the one in use.
+    transaction_deny_config_lock: Mutex<()>,
 }
 
 impl fmt::Debug for SuiNode {
@@ -256,6 +263,12 @@ impl fmt::Debug for SuiNode {
 
 static MAX_JWK_KEYS_PER_FETCH: usize = 100;
 
+/// Name of the file in the node's db directory that holds the transaction deny config set through
+/// the admin API. When present, it is merged into the deny config of the node config at startup:
+/// anything denied by either of them is denied, so the admin API cannot lift restrictions of the
+/// node config across restarts. Delete the file to drop the changes made through the admin API.
+const TRANSACTION_DENY_CONFIG_FILE: &str = "transaction_deny_config.yaml";
+
 impl SuiNode {
     pub async fn start(
         config: NodeConfig,
@@ -406,8 +419,20 @@ impl SuiNode {
         custom_rpc_runtime: Option<Handle>,
         software_version: &'static str,
     ) -> Result<Arc<SuiNode>> {
-        NodeConfigMetrics::new(&registry_service.default_registry()).record_metrics(&config);
         let mut config = config.clone();
+        let transaction_deny_config_path = config.db_path().join(TRANSACTION_DENY_CONFIG_FILE);
+        if transaction_deny_config_path.exists() {
+            warn!(
+                "Merging the transaction deny config set through the admin API, from {}, into the \
+                 deny config of the node config. Delete the file to only use the node config.",
+                transaction_deny_config_path.display()
+            );
+            let persisted = TransactionDenyConfig::load(&transaction_deny_config_path)?;
+            config.transaction_deny_config = config.transaction_deny_config.merged(&persisted);
+        }
+
+        let node_config_metrics = NodeConfigMetrics::new(&registry_service.default_registry());
+        node_config_metrics.record_metrics(&config);
         if config.supported_protocol_versions.is_none() {
             info!(
                 "populating config.supported_protocol_versions with default {:?}",
@@ -531,6 +556,18 @@ impl SuiNode {
 
         let index_store = if is_full_node && config.enable_index_processing {
             info!("creating index store");
+            let balance_history = if config.enable_balance_history_index {
+                Some(BalanceHistoryStart {
+                    next_checkpoint: checkpoint_store
+                        .get_highest_executed_checkpoint_seq_number()?
+                        .map_or(0, |seq| seq + 1),
+                    highest_synced_checkpoint: checkpoint_store
+                        .get_highest_synced_checkpoint()?
+                        .map(|checkpoint| *checkpoint.sequence_number()),
+                })
+            } else {
+                None
+            };
             Some(Arc::new(IndexStore::new(
                 config.db_path().join("indexes"),
                 &prometheus_registry,
@@ -538,7 +575,8 @@ impl SuiNode {
                     .protocol_config()
                     .max_move_identifier_len_as_option(),
                 config.remove_deprecated_tables,
-            )))
+                balance_history,
+            )?))
         } else {
             None
         };
@@ -796,6 +834,9 @@ impl SuiNode {
             _state_archive_handle: state_archive_handle,
             _state_snapshot_uploader_handle: state_snapshot_handle,
             shutdown_channel_tx: shutdown_channel,
+
+            node_config_metrics,
+            transaction_deny_config_lock: Mutex::new(()),
         };
 
         info!("SuiNode started!");
@@ -854,6 +895,49 @@ impl SuiNode {
             .set_override_protocol_upgrade_buffer_stake(epoch, buffer_stake_bps)
     }
 
+    pub fn transaction_deny_config(&self) -> Arc<TransactionDenyConfig> {
+        self.state.transaction_deny_config()
+    }
+
+    /// Replace the transaction deny config in use by this node. The new config is persisted in the
+    /// node's db directory, and is merged into the one in the node config after a restart (see
+    /// [TRANSACTION_DENY_CONFIG_FILE]).
+    pub async fn replace_transaction_deny_config(
+        &self,
+        config: TransactionDenyConfig,
+    ) -> Result<Arc<TransactionDenyConfig>> {
+        let _guard = self.transaction_deny_config_lock.lock().await;
+        self.update_transaction_deny_config(config)
+    }
+
+    /// Apply `patch` to the transaction deny config in use by this node, persisting the result
+    /// like [SuiNode::replace_transaction_deny_config].
+    pub async fn patch_transaction_deny_config(
+        &self,
+        patch: &TransactionDenyConfigPatch,
+    ) -> Result<Arc<TransactionDenyConfig>> {
+        let _guard = self.transaction_deny_config_lock.lock().await;
+        let config = self.state.transaction_deny_config().patched(patch);
+        self.update_transaction_deny_config(config)
+    }
+
+    fn update_transaction_deny_config(
+        &self,
+        config: TransactionDenyConfig,
+    ) -> Result<Arc<TransactionDenyConfig>> {
+        // Write to a temporary file and move it into place, so that a failed write never leaves a
+        // partial config behind to be picked up on restart.
+        let path = self.config.db_path().join(TRANSACTION_DENY_CONFIG_FILE);
+        let tmp_path = path.with_extension("yaml.tmp");
+        config.save(&tmp_path)?;
+        std::fs::rename(&tmp_path, &path)?;
+
+        self.node_config_metrics
+            .record_transaction_deny_config_metrics(&config);
+        self.state.set_transaction_deny_config(config);
+        Ok(self.state.transaction_deny_config())
+    }
+
     // Testing-only API to start epoch close process.
     // For production code, please use the non-testing version.
     pub async fn close_epoch_for_testing(&self) -> SuiResult {
@@ -898,6 +982,9 @@ impl SuiNode {
                 &config.db_checkpoint_path(),
                 &config.snapshot_path(),
                 remote_store_config.clone(),
+                config
+                    .state_snapshot_write_config
+                    .full_snapshot_interval_epochs,
                 60,
                 prometheus_registry,
                 checkpoint_store,
@@ -759,6 +759,18 @@ impl Package {
         &self.modules
     }
 
+    /// The ID that this package is associated with at runtime, and that types and functions from
+    /// this package are referred to by.
+    pub fn runtime_id(&self) -> AccountAddress {
+        self.runtime_id
+    }
+
+    /// The package's transitive dependencies as a mapping from their runtime IDs to their storage
+    /// IDs.
+    pub fn linkage(&self) -> &BTreeMap<AccountAddress, AccountAddress> {
+        &self.linkage
+    }
+
     fn data_def(&self, module_name: &str, datatype_name: &str) -> Result<DataDef> {
         let module = self.module(module_name)?;
         let Some(data_def) = module.data_def(datatype_name)? else {
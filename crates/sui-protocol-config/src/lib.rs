@@ -501,6 +501,11 @@ struct FeatureFlags {
     // Set number of leaders per round for Mysticeti commits.
     #[serde(skip_serializing_if = "Option::is_none")]
     mysticeti_num_leaders_per_round: Option<usize>,
+
+    // Zero the Mysticeti reputation score of authorities that equivocated in the scored commits,
+    // so they are not picked as leaders.
+    #[serde(skip_serializing_if = "is_false")]
+    mysticeti_penalize_equivocators: bool,
 }
 
 fn is_false(b: &bool) -> bool {
@@ -1433,6 +1438,10 @@ impl ProtocolConfig {
     pub fn mysticeti_num_leaders_per_round(&self) -> Option<usize> {
         self.feature_flags.mysticeti_num_leaders_per_round
     }
+
+    pub fn mysticeti_penalize_equivocators(&self) -> bool {
+        self.feature_flags.mysticeti_penalize_equivocators
+    }
 }
 
 #[cfg(not(msim))]
@@ -2388,6 +2397,11 @@ impl ProtocolConfig {
                     }
 
                     cfg.feature_flags.mysticeti_num_leaders_per_round = Some(1);
+
+                    // Only penalize equivocating leaders in devnet.
+                    if chain != Chain::Testnet && chain != Chain::Mainnet {
+                        cfg.feature_flags.mysticeti_penalize_equivocators = true;
+                    }
                 }
                 // Use this template when making changes:
                 //
@@ -2566,6 +2580,10 @@ impl ProtocolConfig {
     pub fn set_mysticeti_num_leaders_per_round(&mut self, val: Option<usize>) {
         self.feature_flags.mysticeti_num_leaders_per_round = val;
     }
+
+    pub fn set_mysticeti_penalize_equivocators(&mut self, val: bool) {
+        self.feature_flags.mysticeti_penalize_equivocators = val;
+    }
 }
 
 type OverrideFn = dyn Fn(ProtocolVersion, ProtocolConfig) -> ProtocolConfig + Send;
@@ -1,37 +1,42 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use axum::extract::{Path, State};
+use std::time::Duration;
+
+use axum::{
+    body::{Bytes, StreamBody},
+    extract::{Path, Query, State},
+    http::{header, HeaderValue, StatusCode},
+    response::IntoResponse,
+};
 use sui_sdk2::types::{
     CheckpointData, CheckpointDigest, CheckpointSequenceNumber, SignedCheckpointSummary,
 };
 use sui_types::storage::ReadStore;
 use tap::Pipe;
 
-use crate::{accept::AcceptFormat, response::ResponseContent, Result};
+use crate::{accept::AcceptFormat, response::ResponseContent, RestError, Result, APPLICATION_BCS};
 
 pub const GET_LATEST_CHECKPOINT_PATH: &str = "/checkpoints";
 pub const GET_CHECKPOINT_PATH: &str = "/checkpoints/:checkpoint";
 pub const GET_FULL_CHECKPOINT_PATH: &str = "/checkpoints/:checkpoint/full";
+pub const STREAM_FULL_CHECKPOINTS_PATH: &str = "/streams/checkpoints";
+
+/// Content type of the newline-delimited JSON stream of checkpoints.
+const APPLICATION_JSONL: &str = "application/jsonl";
+
+/// How long to wait before checking again for a new checkpoint, when a stream has caught up with
+/// the latest executed checkpoint. The interval doubles every time there is still no new
+/// checkpoint, up to the maximum, so that idle streams do not keep polling the store.
+const STREAM_POLL_INTERVAL_MIN: Duration = Duration::from_millis(50);
+const STREAM_POLL_INTERVAL_MAX: Duration = Duration::from_secs(1);
 
 pub async fn get_full_checkpoint<S: ReadStore>(
     Path(checkpoint_id): Path<CheckpointId>,
     accept: AcceptFormat,
     State(state): State<S>,
 ) -> Result<ResponseContent<CheckpointData>> {
-    let verified_summary = match checkpoint_id {
-        CheckpointId::SequenceNumber(s) => state.get_checkpoint_by_sequence_number(s),
-        CheckpointId::Digest(d) => state.get_checkpoint_by_digest(&d.into()),
-    }?
-    .ok_or(CheckpointNotFoundError(checkpoint_id))?;
-
-    let checkpoint_contents = state
-        .get_checkpoint_contents_by_digest(&verified_summary.content_digest)?
-        .ok_or(CheckpointNotFoundError(checkpoint_id))?;
-
-    let checkpoint_data = state
-        .get_checkpoint_data(verified_summary, checkpoint_contents)?
-        .into();
+    let checkpoint_data = load_full_checkpoint(&state, checkpoint_id)?;
 
     match accept {
         AcceptFormat::Json => ResponseContent::Json(checkpoint_data),
@@ -73,6 +78,112 @@ pub async fn get_checkpoint<S: ReadStore>(
     .pipe(Ok)
 }
 
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct StreamFullCheckpointsQueryParameters {
+    /// Sequence number of the first checkpoint to send. Defaults to the latest executed
+    /// checkpoint. Clients that are resuming a stream should set this to one more than the last
+    /// checkpoint they received.
+    pub start: Option<CheckpointSequenceNumber>,
+}
+
+/// Stream every full checkpoint from `start` onwards, sending each one as soon as it has been
+/// executed. The stream does not end unless the client disconnects, or a checkpoint fails to load.
+///
+/// With `Accept: application/bcs`, each checkpoint is sent as a frame made up of its BCS-encoded
+/// length as a little-endian `u32`, followed by the BCS-encoded checkpoint itself. Otherwise, each
+/// checkpoint is sent as a single line of JSON.
+pub async fn stream_full_checkpoints<S>(
+    Query(parameters): Query<StreamFullCheckpointsQueryParameters>,
+    accept: AcceptFormat,
+    State(state): State<S>,
+) -> Result<impl IntoResponse>
+where
+    S: ReadStore + Send + Sync + 'static,
+{
+    let latest = state.get_latest_checkpoint_sequence_number()?;
+    let lowest_available = state.get_lowest_available_checkpoint()?;
+    let start = parameters.start.unwrap_or(latest);
+
+    if start < lowest_available {
+        return Err(RestError::new(
+            StatusCode::GONE,
+            format!(
+                "Checkpoint {start} has been pruned, lowest available checkpoint is \
+                 {lowest_available}"
+            ),
+        ));
+    }
+
+    let content_type = match accept {
+        AcceptFormat::Json => APPLICATION_JSONL,
+        AcceptFormat::Bcs => APPLICATION_BCS,
+    };
+
+    let checkpoints =
+        futures::stream::try_unfold((state, start), move |(state, next)| async move {
+            let mut poll_interval = STREAM_POLL_INTERVAL_MIN;
+            while state.get_latest_checkpoint_sequence_number()? < next {
+                tokio::time::sleep(poll_interval).await;
+                poll_interval = (poll_interval * 2).min(STREAM_POLL_INTERVAL_MAX);
+            }
+
+            let checkpoint_data = load_full_checkpoint(&state, CheckpointId::SequenceNumber(next))
+                .map_err(|e| {
+                    anyhow::Error::new(e).context(format!("Failed to load checkpoint {next}"))
+                })?;
+
+            let frame = encode_frame(accept, &checkpoint_data)?;
+            anyhow::Ok(Some((frame, (state, next + 1))))
+        });
+
+    Ok((
+        [(header::CONTENT_TYPE, HeaderValue::from_static(content_type))],
+        StreamBody::new(checkpoints),
+    ))
+}
+
+fn load_full_checkpoint<S: ReadStore>(
+    state: &S,
+    checkpoint_id: CheckpointId,
+) -> Result<CheckpointData> {
+    let verified_summary = match checkpoint_id {
+        CheckpointId::SequenceNumber(s) => state.get_checkpoint_by_sequence_number(s),
+        CheckpointId::Digest(d) => state.get_checkpoint_by_digest(&d.into()),
+    }?
+    .ok_or(CheckpointNotFoundError(checkpoint_id))?;
+
+    let checkpoint_contents = state
+        .get_checkpoint_contents_by_digest(&verified_summary.content_digest)?
+        .ok_or(CheckpointNotFoundError(checkpoint_id))?;
+
+    let checkpoint_data = state
+        .get_checkpoint_data(verified_summary, checkpoint_contents)?
+        .into();
+
+    Ok(checkpoint_data)
+}
+
+fn encode_frame<T: serde::Serialize>(accept: AcceptFormat, value: &T) -> anyhow::Result<Bytes> {
+    let frame = match accept {
+        AcceptFormat::Json => {
+            let mut frame = serde_json::to_vec(value)?;
+            frame.push(b'\n');
+            frame
+        }
+
+        AcceptFormat::Bcs => {
+            let bytes = bcs::to_bytes(value)?;
+            let len = u32::try_from(bytes.len())?;
+            let mut frame = Vec::with_capacity(4 + bytes.len());
+            frame.extend_from_slice(&len.to_le_bytes());
+            frame.extend_from_slice(&bytes);
+            frame
+        }
+    };
+
+    Ok(frame.into())
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum CheckpointId {
     SequenceNumber(CheckpointSequenceNumber),
@@ -133,3 +244,60 @@ impl From<CheckpointNotFoundError> for crate::RestError {
         Self::new(axum::http::StatusCode::NOT_FOUND, value.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+
+    #[test]
+    fn bcs_frames_round_trip() {
+        let values = vec![vec![], vec![1u64, 2, 3], (0..1000).collect::<Vec<u64>>()];
+        let stream: Vec<u8> = values
+            .iter()
+            .flat_map(|value| encode_frame(AcceptFormat::Bcs, value).unwrap())
+            .collect();
+
+        // Frames are decoded however the stream is split into chunks.
+        for chunk_size in [1, 3, 7, stream.len()] {
+            let mut buffer = vec![];
+            let mut decoded = vec![];
+            for chunk in stream.chunks(chunk_size) {
+                buffer.extend_from_slice(chunk);
+                while let Some(value) = Client::next_frame::<Vec<u64>>(&mut buffer).unwrap() {
+                    decoded.push(value);
+                }
+            }
+            assert_eq!(decoded, values);
+            assert!(buffer.is_empty());
+        }
+    }
+
+    #[test]
+    fn incomplete_bcs_frame() {
+        let frame = encode_frame(AcceptFormat::Bcs, &vec![1u64, 2, 3]).unwrap();
+
+        // Neither a partial length nor a partial payload is decoded, or consumed.
+        for len in [0, 2, frame.len() - 1] {
+            let mut buffer = frame[..len].to_vec();
+            assert!(Client::next_frame::<Vec<u64>>(&mut buffer)
+                .unwrap()
+                .is_none());
+            assert_eq!(buffer, &frame[..len]);
+        }
+    }
+
+    #[test]
+    fn oversized_bcs_frame() {
+        // The frame is rejected as soon as its length is known.
+        let len = (crate::client::MAX_FRAME_SIZE + 1) as u32;
+        let mut buffer = len.to_le_bytes().to_vec();
+        assert!(Client::next_frame::<Vec<u64>>(&mut buffer).is_err());
+    }
+
+    #[test]
+    fn json_frames_are_lines() {
+        let frame = encode_frame(AcceptFormat::Json, &vec![1u64, 2, 3]).unwrap();
+        assert_eq!(&frame[..], b"[1,2,3]\n");
+    }
+}
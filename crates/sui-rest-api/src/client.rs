@@ -2,15 +2,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::transactions::ExecuteTransactionQueryParameters;
+use crate::NodeInfo;
 use anyhow::Result;
+use futures::Stream;
+use move_core_types::language_storage::StructTag;
+use std::str::FromStr;
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
 use sui_types::crypto::AuthorityStrongQuorumSignInfo;
+use sui_types::dynamic_field::DynamicFieldType;
 use sui_types::effects::{TransactionEffects, TransactionEvents};
 use sui_types::full_checkpoint_content::CheckpointData;
 use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber};
 use sui_types::object::Object;
 use sui_types::transaction::Transaction;
 use sui_types::TypeTag;
+use tap::Pipe;
+
+/// Largest checkpoint stream frame the client accepts. Checkpoint transactions and effects are
+/// limited to 30 MiB by the protocol, so this leaves ample room for the objects of a full
+/// checkpoint, while bounding how much a misbehaving server can make the client buffer.
+pub const MAX_FRAME_SIZE: usize = 256 * 1024 * 1024;
 
 #[derive(Clone)]
 pub struct Client {
@@ -26,6 +37,19 @@ impl Client {
         }
     }
 
+    pub async fn get_node_info(&self) -> Result<NodeInfo> {
+        let url = self.base_url.clone();
+
+        let response = self
+            .inner
+            .get(url)
+            .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
+            .send()
+            .await?;
+
+        self.bcs(response).await
+    }
+
     pub async fn get_latest_checkpoint(&self) -> Result<CertifiedCheckpointSummary> {
         let url = format!("{}/checkpoints", self.base_url);
 
@@ -58,6 +82,45 @@ impl Client {
         self.bcs(response).await
     }
 
+    /// Stream every full checkpoint from `start_checkpoint` onwards, as they are executed by the
+    /// node. The stream only ends if the connection to the node is closed, or a frame fails to
+    /// decode. To resume after that, open a new stream starting after the last checkpoint
+    /// received.
+    pub async fn stream_full_checkpoints(
+        &self,
+        start_checkpoint: CheckpointSequenceNumber,
+    ) -> Result<impl Stream<Item = Result<CheckpointData>>> {
+        let url = format!("{}/streams/checkpoints", self.base_url);
+
+        let response = self
+            .inner
+            .get(url)
+            .query(&[("start", start_checkpoint)])
+            .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
+            .send()
+            .await?;
+
+        let response = self.check_response(response)?;
+
+        futures::stream::try_unfold(
+            (response, Vec::new()),
+            |(mut response, mut buffer)| async move {
+                loop {
+                    if let Some(checkpoint) = Self::next_frame(&mut buffer)? {
+                        return Ok(Some((checkpoint, (response, buffer))));
+                    }
+
+                    match response.chunk().await? {
+                        Some(chunk) => buffer.extend_from_slice(&chunk),
+                        None if buffer.is_empty() => return Ok(None),
+                        None => return Err(anyhow::anyhow!("checkpoint stream ended mid-frame")),
+                    }
+                }
+            },
+        )
+        .pipe(Ok)
+    }
+
     pub async fn get_checkpoint_summary(
         &self,
         checkpoint_sequence_number: CheckpointSequenceNumber,
@@ -104,6 +167,48 @@ impl Client {
         self.bcs(response).await
     }
 
+    /// List a page of the objects owned by `address`, starting at the object `start` (inclusive)
+    /// or at the beginning if it is not provided.
+    pub async fn list_account_owned_objects(
+        &self,
+        address: SuiAddress,
+        limit: Option<u32>,
+        start: Option<ObjectID>,
+    ) -> Result<Page<AccountOwnedObjectInfo, ObjectID>> {
+        let url = format!("{}/accounts/{address}/objects", self.base_url);
+
+        let response = self
+            .inner
+            .get(url)
+            .query(&PageParameters { limit, start })
+            .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
+            .send()
+            .await?;
+
+        self.bcs_page(response).await
+    }
+
+    /// List a page of the dynamic fields of `parent`, starting at the field `start` (inclusive)
+    /// or at the beginning if it is not provided.
+    pub async fn list_dynamic_fields(
+        &self,
+        parent: ObjectID,
+        limit: Option<u32>,
+        start: Option<ObjectID>,
+    ) -> Result<Page<DynamicFieldInfo, ObjectID>> {
+        let url = format!("{}/objects/{parent}/dynamic-fields", self.base_url);
+
+        let response = self
+            .inner
+            .get(url)
+            .query(&PageParameters { limit, start })
+            .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
+            .send()
+            .await?;
+
+        self.bcs_page(response).await
+    }
+
     pub async fn execute_transaction(
         &self,
         parameters: &ExecuteTransactionQueryParameters,
@@ -134,13 +239,36 @@ impl Client {
         self.bcs(response).await
     }
 
+    /// Fail on unsuccessful responses. The error is a [`reqwest::Error`], so that callers can
+    /// read its status.
     fn check_response(&self, response: reqwest::Response) -> Result<reqwest::Response> {
-        if !response.status().is_success() {
-            let status = response.status();
-            return Err(anyhow::anyhow!("request failed with status {status}"));
+        Ok(response.error_for_status()?)
+    }
+
+    /// Decode the first frame of a checkpoint stream from `buffer`, removing it from the buffer,
+    /// or return `None` if the buffer does not contain a whole frame yet. Each frame is the length
+    /// of its payload as a little-endian `u32`, followed by the BCS-encoded checkpoint. Frames
+    /// larger than [`MAX_FRAME_SIZE`] are rejected before they are buffered.
+    pub(crate) fn next_frame<T: serde::de::DeserializeOwned>(
+        buffer: &mut Vec<u8>,
+    ) -> Result<Option<T>> {
+        let Some(len) = buffer.get(..4) else {
+            return Ok(None);
+        };
+
+        let len = u32::from_le_bytes(len.try_into()?) as usize;
+        if len > MAX_FRAME_SIZE {
+            return Err(anyhow::anyhow!(
+                "checkpoint stream frame of {len} bytes exceeds the limit of {MAX_FRAME_SIZE} bytes"
+            ));
+        }
+        if buffer.len() < 4 + len {
+            return Ok(None);
         }
 
-        Ok(response)
+        let frame: Vec<_> = buffer.drain(..4 + len).collect();
+        let bcs = bcs::from_bytes(&frame[4..])?;
+        Ok(Some(bcs))
     }
 
     #[allow(unused)]
@@ -158,6 +286,58 @@ impl Client {
         let bcs = bcs::from_bytes(&bytes)?;
         Ok(bcs)
     }
+
+    async fn bcs_page<T, C>(&self, response: reqwest::Response) -> Result<Page<T, C>>
+    where
+        T: serde::de::DeserializeOwned,
+        C: FromStr,
+        C::Err: std::error::Error + Send + Sync + 'static,
+    {
+        let next_cursor = response
+            .headers()
+            .get(crate::types::X_SUI_CURSOR)
+            .map(|cursor| C::from_str(cursor.to_str()?).map_err(anyhow::Error::from))
+            .transpose()?;
+
+        let items = self.bcs(response).await?;
+        Ok(Page { items, next_cursor })
+    }
+}
+
+#[derive(serde::Serialize)]
+struct PageParameters {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start: Option<ObjectID>,
+}
+
+/// A page of results from a paginated endpoint.
+#[derive(Debug)]
+pub struct Page<T, C> {
+    pub items: Vec<T>,
+    /// Cursor to pass as `start` to fetch the next page, or `None` if this is the last page.
+    pub next_cursor: Option<C>,
+}
+
+#[derive(PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct AccountOwnedObjectInfo {
+    pub owner: SuiAddress,
+    pub object_id: ObjectID,
+    pub version: SequenceNumber,
+    pub type_: StructTag,
+}
+
+#[derive(PartialEq, Eq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct DynamicFieldInfo {
+    pub parent: ObjectID,
+    pub field_id: ObjectID,
+    pub dynamic_field_type: DynamicFieldType,
+    pub name_type: TypeTag,
+    /// BCS serialized value of the field's name
+    pub name_value: Vec<u8>,
+    /// ObjectId of the child object when `dynamic_field_type == DynamicFieldType::DynamicObject`
+    pub dynamic_object_id: Option<ObjectID>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -5,6 +5,7 @@ use axum::http::StatusCode;
 
 pub type Result<T, E = RestError> = std::result::Result<T, E>;
 
+#[derive(Debug)]
 pub struct RestError {
     status: StatusCode,
     message: Option<String>,
@@ -19,6 +20,17 @@ impl RestError {
     }
 }
 
+impl std::fmt::Display for RestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "{}: {message}", self.status),
+            None => write!(f, "{}", self.status),
+        }
+    }
+}
+
+impl std::error::Error for RestError {}
+
 // Tell axum how to convert `AppError` into a response.
 impl axum::response::IntoResponse for RestError {
     fn into_response(self) -> axum::response::Response {
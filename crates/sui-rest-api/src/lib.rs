@@ -7,6 +7,7 @@ use axum::{
 };
 
 pub mod accept;
+mod accounts;
 mod checkpoints;
 pub mod client;
 mod committee;
@@ -24,6 +25,7 @@ pub mod types;
 
 pub use client::Client;
 pub use error::{RestError, Result};
+pub use info::NodeInfo;
 pub use metrics::RestMetrics;
 use mysten_network::callback::CallbackLayer;
 use reader::StateReader;
@@ -37,6 +39,11 @@ pub const TEXT_PLAIN_UTF_8: &str = "text/plain; charset=utf-8";
 pub const APPLICATION_BCS: &str = "application/bcs";
 pub const APPLICATION_JSON: &str = "application/json";
 
+/// Page size used by paginated endpoints when the request does not specify a limit.
+const DEFAULT_PAGE_SIZE: usize = 50;
+/// Largest page size that paginated endpoints will return.
+const MAX_PAGE_SIZE: usize = 1000;
+
 #[derive(Clone)]
 pub struct RestService {
     reader: StateReader,
@@ -109,6 +116,14 @@ impl RestService {
                 get(system::get_protocol_config),
             )
             .route(system::GET_GAS_INFO_PATH, get(system::get_gas_info))
+            .route(
+                accounts::LIST_ACCOUNT_OWNED_OBJECTS_PATH,
+                get(accounts::list_account_owned_objects),
+            )
+            .route(
+                objects::LIST_DYNAMIC_FIELDS_PATH,
+                get(objects::list_dynamic_fields),
+            )
             .with_state(self.clone())
             .merge(rest_router(store))
             .pipe(|router| {
@@ -165,6 +180,10 @@ where
             checkpoints::GET_LATEST_CHECKPOINT_PATH,
             get(checkpoints::get_latest_checkpoint::<S>),
         )
+        .route(
+            checkpoints::STREAM_FULL_CHECKPOINTS_PATH,
+            get(checkpoints::stream_full_checkpoints::<S>),
+        )
         .route(objects::GET_OBJECT_PATH, get(objects::get_object::<S>))
         .route(
             objects::GET_OBJECT_WITH_VERSION_PATH,
@@ -181,3 +200,39 @@ fn execution_router(executor: Arc<dyn TransactionExecutor>) -> Router {
         )
         .with_state(executor)
 }
+
+#[cfg(test)]
+mod test_utils {
+    use std::time::Duration;
+
+    use simulacrum::Simulacrum;
+
+    use super::*;
+
+    /// Serve the REST API over the state of `sim` on a local port, returning its base url.
+    pub(crate) async fn start_test_service(mut sim: Simulacrum) -> String {
+        // The info headers of every response are taken from the latest checkpoint.
+        sim.create_checkpoint();
+
+        let address = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        tokio::spawn(async move {
+            RestService::new_without_version(Arc::new(sim))
+                .start_service(address, Some("/rest".to_owned()))
+                .await;
+        });
+
+        let url = format!("http://{address}/rest");
+        let client = Client::new(&url);
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while client.get_node_info().await.is_err() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("REST service did not start");
+        url
+    }
+}
@@ -1,10 +1,16 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{accept::AcceptFormat, response::ResponseContent, Result};
-use axum::extract::{Path, State};
-use sui_sdk2::types::{Object, ObjectId, Version};
-use sui_types::storage::ReadStore;
+use crate::{
+    accept::AcceptFormat,
+    reader::StateReader,
+    response::{PageCursor, ResponseContent, ResponsePage},
+    Result,
+};
+use axum::extract::{Path, Query, State};
+use sui_sdk2::types::{Object, ObjectId, TypeTag, Version};
+use sui_types::storage::{DynamicFieldIndexInfo, DynamicFieldKey, ReadStore};
+use sui_types::sui_sdk2_conversions::type_tag_core_to_sdk;
 use tap::Pipe;
 
 pub const GET_OBJECT_PATH: &str = "/objects/:object_id";
@@ -45,6 +51,106 @@ pub async fn get_object_with_version<S: ReadStore>(
     .pipe(Ok)
 }
 
+pub const LIST_DYNAMIC_FIELDS_PATH: &str = "/objects/:object_id/dynamic-fields";
+
+pub async fn list_dynamic_fields(
+    Path(parent): Path<ObjectId>,
+    Query(parameters): Query<ListDynamicFieldsQueryParameters>,
+    accept: AcceptFormat,
+    State(state): State<StateReader>,
+) -> Result<ResponsePage<DynamicFieldInfo, ObjectId>> {
+    let limit = parameters.limit();
+    let start = parameters.start.map(Into::into);
+
+    let mut dynamic_fields = state
+        .inner()
+        .dynamic_field_iter(parent.into(), start)?
+        .map(DynamicFieldInfo::from)
+        .take(limit + 1)
+        .collect::<Vec<_>>();
+
+    // An extra entry was requested to find out whether there is another page, and where it
+    // starts.
+    let cursor = if dynamic_fields.len() > limit {
+        dynamic_fields.pop().map(|field| field.field_id)
+    } else {
+        None
+    };
+
+    let dynamic_fields = match accept {
+        AcceptFormat::Json => ResponseContent::Json(dynamic_fields),
+        AcceptFormat::Bcs => ResponseContent::Bcs(dynamic_fields),
+    };
+
+    Ok((PageCursor(cursor), dynamic_fields))
+}
+
+/// Query parameters for the list dynamic fields endpoint
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct ListDynamicFieldsQueryParameters {
+    /// Maximum number of dynamic fields to return. Defaults to 50, and is capped at 1000.
+    pub limit: Option<u32>,
+    /// Field ID to start listing from (inclusive), as returned in the `x-sui-cursor` header of
+    /// the previous page.
+    pub start: Option<ObjectId>,
+}
+
+impl ListDynamicFieldsQueryParameters {
+    pub fn limit(&self) -> usize {
+        self.limit
+            .map(|limit| (limit as usize).clamp(1, crate::MAX_PAGE_SIZE))
+            .unwrap_or(crate::DEFAULT_PAGE_SIZE)
+    }
+}
+
+/// Summary of a dynamic field of an object
+#[serde_with::serde_as]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DynamicFieldInfo {
+    pub parent: ObjectId,
+    pub field_id: ObjectId,
+    pub dynamic_field_type: DynamicFieldType,
+    pub name_type: TypeTag,
+    /// BCS serialized value of the field's name
+    #[serde_as(as = "sui_types::sui_serde::Readable<fastcrypto::encoding::Base64, _>")]
+    pub name_value: Vec<u8>,
+    /// ObjectId of the child object when `dynamic_field_type == DynamicFieldType::Object`
+    pub dynamic_object_id: Option<ObjectId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DynamicFieldType {
+    Field,
+    Object,
+}
+
+impl From<(DynamicFieldKey, DynamicFieldIndexInfo)> for DynamicFieldInfo {
+    fn from(value: (DynamicFieldKey, DynamicFieldIndexInfo)) -> Self {
+        let DynamicFieldKey { parent, field_id } = value.0;
+        let DynamicFieldIndexInfo {
+            dynamic_field_type,
+            name_type,
+            name_value,
+            dynamic_object_id,
+        } = value.1;
+
+        let dynamic_field_type = match dynamic_field_type {
+            sui_types::dynamic_field::DynamicFieldType::DynamicField => DynamicFieldType::Field,
+            sui_types::dynamic_field::DynamicFieldType::DynamicObject => DynamicFieldType::Object,
+        };
+
+        Self {
+            parent: parent.into(),
+            field_id: field_id.into(),
+            dynamic_field_type,
+            name_type: type_tag_core_to_sdk(name_type),
+            name_value,
+            dynamic_object_id: dynamic_object_id.map(Into::into),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ObjectNotFoundError {
     object_id: ObjectId,
@@ -86,3 +192,132 @@ impl From<ObjectNotFoundError> for crate::RestError {
         Self::new(axum::http::StatusCode::NOT_FOUND, value.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::ident_str;
+    use simulacrum::Simulacrum;
+    use sui_types::base_types::ObjectID;
+    use sui_types::dynamic_field::DynamicFieldType;
+    use sui_types::effects::TransactionEffectsAPI;
+    use sui_types::object::Owner;
+    use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+    use sui_types::transaction::{Transaction, TransactionData};
+    use sui_types::{TypeTag, SUI_FRAMEWORK_PACKAGE_ID};
+
+    use crate::test_utils::start_test_service;
+    use crate::Client;
+
+    /// Create a `Bag` holding `len` entries, from `i` to `i * 10`, returning the bag's ID.
+    fn create_bag(sim: &mut Simulacrum, len: u64) -> ObjectID {
+        let (sender, key) = sim.keystore().accounts().next().unwrap();
+        let sender = *sender;
+        let gas = sim
+            .store()
+            .owned_objects(sender)
+            .find(|object| object.is_gas_coin())
+            .unwrap();
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let bag = builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            ident_str!("bag").to_owned(),
+            ident_str!("new").to_owned(),
+            vec![],
+            vec![],
+        );
+        for i in 0..len {
+            let name = builder.pure(i).unwrap();
+            let value = builder.pure(i * 10).unwrap();
+            builder.programmable_move_call(
+                SUI_FRAMEWORK_PACKAGE_ID,
+                ident_str!("bag").to_owned(),
+                ident_str!("add").to_owned(),
+                vec![TypeTag::U64, TypeTag::U64],
+                vec![bag, name, value],
+            );
+        }
+        builder.transfer_arg(sender, bag);
+
+        let data = TransactionData::new_programmable(
+            sender,
+            vec![gas.compute_object_reference()],
+            builder.finish(),
+            1_000_000_000,
+            sim.reference_gas_price(),
+        );
+        let transaction = Transaction::from_data_and_signer(data, vec![key]);
+        let (effects, error) = sim.execute_transaction(transaction).unwrap();
+        assert!(error.is_none(), "{error:?}");
+
+        effects
+            .created()
+            .into_iter()
+            .find(|(_, owner)| *owner == Owner::AddressOwner(sender))
+            .map(|((id, _, _), _)| id)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_list_dynamic_fields() {
+        let mut sim = Simulacrum::new();
+        let bag = create_bag(&mut sim, 5);
+        let client = Client::new(start_test_service(sim).await);
+
+        let all = client.list_dynamic_fields(bag, None, None).await.unwrap();
+        assert_eq!(all.items.len(), 5);
+        assert_eq!(all.next_cursor, None);
+        let mut names = vec![];
+        for field in &all.items {
+            assert_eq!(field.parent, bag);
+            assert_eq!(field.dynamic_field_type, DynamicFieldType::DynamicField);
+            assert_eq!(field.name_type, TypeTag::U64);
+            assert_eq!(field.dynamic_object_id, None);
+            names.push(bcs::from_bytes::<u64>(&field.name_value).unwrap());
+        }
+        names.sort();
+        assert_eq!(names, [0, 1, 2, 3, 4]);
+
+        // Paging through the fields two at a time visits each of them once, in order.
+        let mut paged = vec![];
+        let mut start = None;
+        loop {
+            let page = client
+                .list_dynamic_fields(bag, Some(2), start)
+                .await
+                .unwrap();
+            assert!(page.items.len() <= 2);
+            paged.extend(page.items);
+            start = page.next_cursor;
+            if start.is_none() {
+                break;
+            }
+        }
+        assert_eq!(paged, all.items);
+
+        // The parent of dynamic fields need not be an object (it can be the UID of a wrapped
+        // value), so a parent without fields has a single, empty page rather than being not found.
+        let unknown = client
+            .list_dynamic_fields(ObjectID::random(), None, None)
+            .await
+            .unwrap();
+        assert!(unknown.items.is_empty());
+        assert_eq!(unknown.next_cursor, None);
+
+        // Unknown objects themselves are not found.
+        let error = client.get_object(ObjectID::random()).await.unwrap_err();
+        let status = error
+            .downcast_ref::<reqwest::Error>()
+            .and_then(|e| e.status());
+        assert_eq!(status, Some(reqwest::StatusCode::NOT_FOUND));
+    }
+
+    #[tokio::test]
+    async fn test_list_dynamic_fields_invalid_parent() {
+        let url = start_test_service(Simulacrum::new()).await;
+        let response = reqwest::get(format!("{url}/objects/not-an-id/dynamic-fields"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+    }
+}
@@ -4,14 +4,14 @@
 use axum::{
     extract::State,
     http::HeaderMap,
-    response::{IntoResponse, Response},
+    response::{IntoResponse, IntoResponseParts, Response, ResponseParts},
 };
 use reqwest::StatusCode;
 
 use crate::{
     content_type::ContentType,
     types::{
-        X_SUI_CHAIN, X_SUI_CHAIN_ID, X_SUI_CHECKPOINT_HEIGHT, X_SUI_EPOCH,
+        X_SUI_CHAIN, X_SUI_CHAIN_ID, X_SUI_CHECKPOINT_HEIGHT, X_SUI_CURSOR, X_SUI_EPOCH,
         X_SUI_LOWEST_AVAILABLE_CHECKPOINT, X_SUI_LOWEST_AVAILABLE_CHECKPOINT_OBJECTS,
         X_SUI_TIMESTAMP_MS,
     },
@@ -125,6 +125,30 @@ where
     }
 }
 
+/// Cursor for the next page of a paginated response, sent in the `x-sui-cursor` header. The
+/// header is omitted when there are no further pages.
+pub struct PageCursor<C>(pub Option<C>);
+
+impl<C: std::fmt::Display> IntoResponseParts for PageCursor<C> {
+    type Error = (StatusCode, String);
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        if let Some(cursor) = self.0 {
+            let value = cursor.to_string().try_into().map_err(
+                |e: axum::http::header::InvalidHeaderValue| {
+                    (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+                },
+            )?;
+            res.headers_mut().insert(X_SUI_CURSOR, value);
+        }
+
+        Ok(res)
+    }
+}
+
+/// A single page of results from a paginated endpoint.
+pub type ResponsePage<T, C> = (PageCursor<C>, ResponseContent<Vec<T>>);
+
 pub async fn append_info_headers(
     State(state): State<RestService>,
     response: Response,
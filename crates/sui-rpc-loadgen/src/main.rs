@@ -3,6 +3,8 @@
 
 mod load_test;
 mod payload;
+mod replay;
+mod trace;
 
 use anyhow::Result;
 use clap::Parser;
@@ -20,6 +22,8 @@ use crate::payload::{
     load_addresses_from_file, load_digests_from_file, load_objects_from_file, Command,
     RpcCommandProcessor, SignerInfo,
 };
+use crate::replay::{replay_trace, ReplayConfig};
+use crate::trace::{read_trace, record_trace};
 
 #[derive(Parser)]
 #[clap(
@@ -133,6 +137,44 @@ pub enum ClapCommand {
         #[clap(flatten)]
         common: CommonOptions,
     },
+    /// Capture the requests logged by fullnodes running with
+    /// `RUST_LOG=info,sui_json_rpc::request_log=debug` into a trace file
+    #[clap(name = "record")]
+    Record {
+        /// Fullnode log files, in the plain or JSON log format
+        #[clap(long, num_args(1..), required = true)]
+        log_files: Vec<PathBuf>,
+
+        #[clap(long)]
+        output: PathBuf,
+
+        /// Only record requests to these methods
+        #[clap(long, num_args(1..))]
+        methods: Vec<String>,
+
+        /// Also record requests to methods that write to the network, such as
+        /// `sui_executeTransactionBlock`, when no methods are given
+        #[clap(long)]
+        include_writes: bool,
+    },
+    /// Replay a trace against every url, comparing responses to those of the first url
+    #[clap(name = "replay")]
+    Replay {
+        #[clap(long)]
+        trace: PathBuf,
+
+        /// Multiple of the recorded request rate to replay at
+        #[clap(long, default_value_t = 1.0)]
+        speedup: f64,
+
+        /// Write responses that differ from those of the first url to this file
+        #[clap(long)]
+        diff_output: Option<PathBuf>,
+
+        /// Maximum number of requests in flight
+        #[clap(long, default_value_t = 256)]
+        max_concurrency: usize,
+    },
 }
 
 fn get_keypair() -> Result<SignerInfo> {
@@ -189,6 +231,33 @@ async fn main() -> Result<(), Box<dyn Error>> {
     info!("Running Load Gen with following urls {:?}", opts.urls);
 
     let (command, common, need_keystore) = match opts.command {
+        ClapCommand::Record {
+            log_files,
+            output,
+            methods,
+            include_writes,
+        } => {
+            let recorded = record_trace(&log_files, &output, &methods, include_writes)?;
+            println!("Recorded {recorded} requests to {}", output.display());
+            return Ok(());
+        }
+        ClapCommand::Replay {
+            trace,
+            speedup,
+            diff_output,
+            max_concurrency,
+        } => {
+            let entries = read_trace(&trace)?;
+            info!("Replaying requests from {}", trace.display());
+            let config = ReplayConfig {
+                speedup,
+                diff_output,
+                max_concurrency,
+            };
+            let report = replay_trace(entries, &opts.urls, config).await?;
+            println!("{report}");
+            return Ok(());
+        }
         ClapCommand::DryRun { common } => (Command::new_dry_run(), common, false),
         ClapCommand::PaySui { common } => (Command::new_pay_sui(), common, true),
         ClapCommand::GetCheckpoints {
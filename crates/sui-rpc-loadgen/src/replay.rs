@@ -0,0 +1,385 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Result};
+use futures::future::join_all;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::{JoinError, JoinSet};
+use tokio::time::sleep_until;
+use tracing::{info, warn};
+
+use crate::trace::TraceEntry;
+
+// Differences reported per mismatching response, the rest are only counted.
+const MAX_DIFFERENCES: usize = 10;
+
+pub struct ReplayConfig {
+    /// Multiple of the recorded request rate to replay at.
+    pub speedup: f64,
+    /// Where to write mismatching responses, as one JSON object per line.
+    pub diff_output: Option<PathBuf>,
+    /// Maximum number of requests in flight, past which replay falls behind the recorded rate.
+    pub max_concurrency: usize,
+}
+
+/// A response of an endpoint that differs from the response of the reference endpoint.
+#[derive(Debug, Serialize)]
+pub struct Mismatch {
+    pub method: String,
+    pub params: Value,
+    pub url: String,
+    pub differences: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct MethodStats {
+    pub requests: usize,
+    /// Requests that failed, either to reach the endpoint or with a JSON-RPC error.
+    pub errors: usize,
+    /// Responses that differ from the reference endpoint's.
+    pub mismatches: usize,
+    pub total_latency: Duration,
+}
+
+#[derive(Debug)]
+pub struct ReplayReport {
+    pub urls: Vec<String>,
+    /// Stats per method, per endpoint in the order of `urls`.
+    pub stats: BTreeMap<String, Vec<MethodStats>>,
+    pub elapsed: Duration,
+}
+
+type Responses = Vec<(Result<Value>, Duration)>;
+
+/// Replays `entries` against every endpoint in `urls`, preserving the recorded spacing between
+/// requests divided by `config.speedup`, with at most `config.max_concurrency` requests in flight.
+/// Responses of every endpoint are compared to those of the first one. Entries are only read as
+/// they become due, and replay stops at the first entry that fails to be read.
+pub async fn replay_trace(
+    entries: impl IntoIterator<Item = Result<TraceEntry>>,
+    urls: &[String],
+    config: ReplayConfig,
+) -> Result<ReplayReport> {
+    if config.speedup <= 0.0 || !config.speedup.is_finite() {
+        bail!("Speed-up must be a positive number, got {}", config.speedup);
+    }
+    if config.max_concurrency == 0 {
+        bail!("Max concurrency must be positive");
+    }
+    let client = reqwest::Client::new();
+    let urls = Arc::new(urls.to_vec());
+    let mut comparison = Comparison {
+        urls: urls.clone(),
+        stats: BTreeMap::new(),
+        diff_output: config
+            .diff_output
+            .as_ref()
+            .map(|path| File::create(path).map(BufWriter::new))
+            .transpose()?,
+    };
+
+    let start = Instant::now();
+    let mut first_timestamp_ms = None;
+    let semaphore = Arc::new(Semaphore::new(config.max_concurrency));
+    let mut requests = JoinSet::new();
+    for entry in entries {
+        let entry = entry?;
+        let offset_ms = entry
+            .timestamp_ms
+            .saturating_sub(*first_timestamp_ms.get_or_insert(entry.timestamp_ms));
+        let offset = Duration::from_secs_f64(offset_ms as f64 / 1000.0 / config.speedup);
+        let ready = async {
+            sleep_until((start + offset).into()).await;
+            semaphore.clone().acquire_owned().await
+        };
+        tokio::pin!(ready);
+
+        // Compare the responses of earlier requests while waiting for this one to be due.
+        let permit = loop {
+            tokio::select! {
+                permit = &mut ready => break permit?,
+                Some(result) = requests.join_next(), if !requests.is_empty() => {
+                    comparison.add(result)?;
+                }
+            }
+        };
+
+        let client = client.clone();
+        let urls = urls.clone();
+        requests.spawn(async move {
+            let responses = join_all(urls.iter().map(|url| async {
+                let request_start = Instant::now();
+                let response = send(&client, url, &entry).await;
+                (response, request_start.elapsed())
+            }))
+            .await;
+            drop(permit);
+            (entry, responses)
+        });
+    }
+    while let Some(result) = requests.join_next().await {
+        comparison.add(result)?;
+    }
+    if let Some(output) = &mut comparison.diff_output {
+        output.flush()?;
+    }
+
+    let report = ReplayReport {
+        urls: urls.to_vec(),
+        stats: comparison.stats,
+        elapsed: start.elapsed(),
+    };
+    info!("Replay finished in {:?}", report.elapsed);
+    Ok(report)
+}
+
+/// Accumulates the stats and mismatches of replayed requests.
+struct Comparison {
+    urls: Arc<Vec<String>>,
+    stats: BTreeMap<String, Vec<MethodStats>>,
+    diff_output: Option<BufWriter<File>>,
+}
+
+impl Comparison {
+    fn add(&mut self, result: Result<(TraceEntry, Responses), JoinError>) -> Result<()> {
+        let (entry, responses) = result?;
+        let method_stats = self
+            .stats
+            .entry(entry.method.clone())
+            .or_insert_with(|| vec![MethodStats::default(); self.urls.len()]);
+        let reference = &responses[0].0;
+        for (i, (response, latency)) in responses.iter().enumerate() {
+            let stats = &mut method_stats[i];
+            stats.requests += 1;
+            stats.total_latency += *latency;
+            if !matches!(response, Ok(response) if response.get("error").is_none()) {
+                stats.errors += 1;
+            }
+            if i == 0 {
+                continue;
+            }
+
+            let differences = diff_responses(reference, response);
+            if differences.is_empty() {
+                continue;
+            }
+            stats.mismatches += 1;
+            let mismatch = Mismatch {
+                method: entry.method.clone(),
+                params: entry.params.clone(),
+                url: self.urls[i].clone(),
+                differences,
+            };
+            warn!(?mismatch, "Response differs from {}", self.urls[0]);
+            if let Some(output) = &mut self.diff_output {
+                serde_json::to_writer(&mut *output, &mismatch)?;
+                writeln!(output)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Sends `entry` to `url`, returning the JSON-RPC response.
+async fn send(client: &reqwest::Client, url: &str, entry: &TraceEntry) -> Result<Value> {
+    let mut request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": entry.method,
+    });
+    if !entry.params.is_null() {
+        request["params"] = entry.params.clone();
+    }
+    Ok(client
+        .post(url)
+        .json(&request)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?)
+}
+
+/// Differences between the results of two responses, or between the codes of their errors.
+fn diff_responses(expected: &Result<Value>, actual: &Result<Value>) -> Vec<String> {
+    fn outcome(response: &Result<Value>) -> Value {
+        match response {
+            Ok(response) => match response.get("error") {
+                Some(error) => json!({ "error": error.get("code") }),
+                None => json!({ "result": response.get("result") }),
+            },
+            Err(e) => json!({ "failure": e.to_string() }),
+        }
+    }
+
+    let mut differences = vec![];
+    diff_values("", &outcome(expected), &outcome(actual), &mut differences);
+    differences
+}
+
+fn diff_values(path: &str, expected: &Value, actual: &Value, differences: &mut Vec<String>) {
+    if differences.len() >= MAX_DIFFERENCES {
+        return;
+    }
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            let missing = Value::Null;
+            let keys = expected
+                .keys()
+                .chain(actual.keys().filter(|key| !expected.contains_key(*key)));
+            for key in keys {
+                diff_values(
+                    &format!("{path}.{key}"),
+                    expected.get(key).unwrap_or(&missing),
+                    actual.get(key).unwrap_or(&missing),
+                    differences,
+                );
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) if expected.len() == actual.len() => {
+            for (i, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+                diff_values(&format!("{path}[{i}]"), expected, actual, differences);
+            }
+        }
+        _ if expected != actual => {
+            differences.push(format!("{path}: expected {expected}, got {actual}"));
+        }
+        _ => (),
+    }
+}
+
+impl fmt::Display for ReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Replayed trace in {:?}", self.elapsed)?;
+        for (i, url) in self.urls.iter().enumerate() {
+            writeln!(f, "{url}:")?;
+            for (method, stats) in &self.stats {
+                let stats = &stats[i];
+                let mean_latency = stats.total_latency / stats.requests.max(1) as u32;
+                write!(
+                    f,
+                    "  {method}: {} requests, {} errors, mean latency {mean_latency:?}",
+                    stats.requests, stats.errors
+                )?;
+                if i > 0 {
+                    write!(f, ", {} mismatches", stats.mismatches)?;
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::post;
+    use axum::Router;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Serves a JSON-RPC endpoint that takes a while to answer, returning its url and the maximum
+    /// number of requests it had in flight at once.
+    fn slow_server() -> (String, Arc<AtomicUsize>) {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let app = Router::new().route(
+            "/",
+            post({
+                let max_in_flight = max_in_flight.clone();
+                move || {
+                    let in_flight = in_flight.clone();
+                    let max_in_flight = max_in_flight.clone();
+                    async move {
+                        let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                        max_in_flight.fetch_max(current, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(20)).await;
+                        in_flight.fetch_sub(1, Ordering::SeqCst);
+                        axum::Json(json!({"jsonrpc": "2.0", "id": 1, "result": "0x1"}))
+                    }
+                }
+            }),
+        );
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(
+            axum::Server::from_tcp(listener)
+                .unwrap()
+                .serve(app.into_make_service()),
+        );
+        (url, max_in_flight)
+    }
+
+    #[tokio::test]
+    async fn test_replay_bounds_requests_in_flight() {
+        let (url, max_in_flight) = slow_server();
+        let entries = (0..20).map(|_| {
+            anyhow::Ok(TraceEntry {
+                timestamp_ms: 1700000000000,
+                method: "sui_getChainIdentifier".to_string(),
+                params: Value::Null,
+            })
+        });
+        let config = ReplayConfig {
+            speedup: 1.0,
+            diff_output: None,
+            max_concurrency: 3,
+        };
+
+        let report = replay_trace(entries, &[url], config).await.unwrap();
+        let stats = &report.stats["sui_getChainIdentifier"][0];
+        assert_eq!(stats.requests, 20);
+        assert_eq!(stats.errors, 0);
+        assert!(max_in_flight.load(Ordering::SeqCst) <= 3);
+    }
+
+    #[test]
+    fn test_diff_responses() {
+        let expected = Ok(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {"data": [{"balance": "10"}, {"balance": "20"}], "hasNextPage": false},
+        }));
+
+        let same = Ok(json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "result": {"hasNextPage": false, "data": [{"balance": "10"}, {"balance": "20"}]},
+        }));
+        assert!(diff_responses(&expected, &same).is_empty());
+
+        let different = Ok(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {"data": [{"balance": "10"}, {"balance": "21"}], "nextCursor": "0x1"},
+        }));
+        assert_eq!(
+            diff_responses(&expected, &different),
+            [
+                r#".result.data[1].balance: expected "20", got "21""#,
+                ".result.hasNextPage: expected false, got null",
+                r#".result.nextCursor: expected null, got "0x1""#,
+            ]
+        );
+
+        let error = Ok(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": {"code": -32602, "message": "Invalid params"},
+        }));
+        assert_eq!(
+            diff_responses(&expected, &error).len(),
+            2,
+            "Missing result and unexpected error"
+        );
+    }
+}
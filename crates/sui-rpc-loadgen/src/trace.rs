@@ -0,0 +1,230 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Lines, Write};
+use std::path::{Path, PathBuf};
+use sui_json_rpc::REQUEST_LOG_TARGET;
+
+/// Methods that write to the network, which are only recorded when asked for, since replaying them
+/// submits transactions again.
+const WRITE_METHODS: &[&str] = &["sui_executeTransactionBlock"];
+
+/// A JSON-RPC request captured from the request log of a fullnode. A trace file holds one request
+/// per line, ordered by timestamp.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceEntry {
+    pub timestamp_ms: u64,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// Extracts the requests logged to `sui_json_rpc::request_log` from `log_files`, in either the
+/// plain or the JSON log format, and writes them to `output` as a trace. Only requests to
+/// `methods` are kept, if any are given, and otherwise requests to `WRITE_METHODS` are dropped
+/// unless `include_writes` is set. Returns the number of requests written.
+///
+/// Each log file is expected to be in timestamp order, as logged. Log files can be rotated or come
+/// from several fullnodes, so they are merged by timestamp as they are read, without holding the
+/// requests in memory.
+pub fn record_trace(
+    log_files: &[PathBuf],
+    output: &Path,
+    methods: &[String],
+    include_writes: bool,
+) -> Result<usize> {
+    let keep = |entry: &TraceEntry| {
+        if methods.is_empty() {
+            include_writes || !WRITE_METHODS.contains(&entry.method.as_str())
+        } else {
+            methods.contains(&entry.method)
+        }
+    };
+
+    let mut logs = log_files
+        .iter()
+        .map(|log_file| {
+            let file = File::open(log_file)
+                .with_context(|| format!("Failed to open log file {}", log_file.display()))?;
+            Ok(BufReader::new(file).lines())
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // The next request of each log file, and the log files ordered by the timestamp of their next
+    // request.
+    let mut heads = Vec::with_capacity(logs.len());
+    let mut order = BinaryHeap::new();
+    for (i, log) in logs.iter_mut().enumerate() {
+        let head = next_request(log, keep)?;
+        if let Some(entry) = &head {
+            order.push(Reverse((entry.timestamp_ms, i)));
+        }
+        heads.push(head);
+    }
+
+    let mut writer = BufWriter::new(
+        File::create(output)
+            .with_context(|| format!("Failed to create trace file {}", output.display()))?,
+    );
+    let mut recorded = 0;
+    while let Some(Reverse((_, i))) = order.pop() {
+        let entry = heads[i]
+            .take()
+            .expect("Log files in the merge have a next request");
+        serde_json::to_writer(&mut writer, &entry)?;
+        writeln!(writer)?;
+        recorded += 1;
+
+        heads[i] = next_request(&mut logs[i], keep)?;
+        if let Some(entry) = &heads[i] {
+            order.push(Reverse((entry.timestamp_ms, i)));
+        }
+    }
+    writer.flush()?;
+    Ok(recorded)
+}
+
+/// Reads the requests of the trace at `path` one at a time, so that traces larger than memory can
+/// be replayed.
+pub fn read_trace(path: &Path) -> Result<impl Iterator<Item = Result<TraceEntry>>> {
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open trace file {}", path.display()))?;
+    Ok(BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(line) if line.trim().is_empty()))
+        .map(|(i, line)| {
+            serde_json::from_str(&line?)
+                .with_context(|| format!("Invalid request on line {} of the trace", i + 1))
+        }))
+}
+
+/// The next request in `log` that is kept by `keep`, if any.
+fn next_request(
+    log: &mut Lines<BufReader<File>>,
+    keep: impl Fn(&TraceEntry) -> bool,
+) -> Result<Option<TraceEntry>> {
+    for line in log {
+        if let Some(entry) = parse_log_line(&line?) {
+            if keep(&entry) {
+                return Ok(Some(entry));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Parses a request out of a line of a fullnode log, skipping other events.
+fn parse_log_line(line: &str) -> Option<TraceEntry> {
+    let line = line.trim();
+    if line.starts_with('{') {
+        // JSON log format, the request is the message of the event.
+        let event: Value = serde_json::from_str(line).ok()?;
+        if event.get("target")?.as_str()? != REQUEST_LOG_TARGET {
+            return None;
+        }
+        let fields = event.get("fields").unwrap_or(&event);
+        serde_json::from_str(fields.get("message")?.as_str()?).ok()
+    } else {
+        // Plain log format, the request follows the target of the event.
+        let start = line.find(REQUEST_LOG_TARGET)? + REQUEST_LOG_TARGET.len();
+        let message = line[start..].trim_start_matches(':').trim();
+        serde_json::from_str(message).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_log_lines() {
+        let expected = TraceEntry {
+            timestamp_ms: 1700000000000,
+            method: "sui_getObject".to_string(),
+            params: json!(["0x5", {"showContent": true}]),
+        };
+
+        let plain = r#"2023-11-14T22:13:20.000000Z DEBUG sui_json_rpc::request_log: {"method":"sui_getObject","params":["0x5",{"showContent":true}],"timestamp_ms":1700000000000}"#;
+        assert_eq!(parse_log_line(plain), Some(expected.clone()));
+
+        let json = json!({
+            "timestamp": "2023-11-14T22:13:20.000000Z",
+            "level": "DEBUG",
+            "fields": {
+                "message": r#"{"method":"sui_getObject","params":["0x5",{"showContent":true}],"timestamp_ms":1700000000000}"#,
+            },
+            "target": "sui_json_rpc::request_log",
+        });
+        assert_eq!(parse_log_line(&json.to_string()), Some(expected));
+
+        let other = r#"2023-11-14T22:13:20.000000Z  INFO sui_json_rpc: RPC took longer than threshold to complete."#;
+        assert_eq!(parse_log_line(other), None);
+    }
+
+    fn load_trace(path: &Path) -> Vec<TraceEntry> {
+        read_trace(path)
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_record_and_load_trace() {
+        let dir = tempfile::tempdir().unwrap();
+        // Requests of two fullnodes, interleaved in time.
+        let log_files = [dir.path().join("a.log"), dir.path().join("b.log")];
+        std::fs::write(
+            &log_files[0],
+            [
+                r#"2023-11-14T22:13:20.000000Z DEBUG sui_json_rpc::request_log: {"method":"sui_getObject","params":["0x5"],"timestamp_ms":1}"#,
+                r#"2023-11-14T22:13:22.000000Z DEBUG sui_json_rpc::request_log: {"method":"sui_getChainIdentifier","params":null,"timestamp_ms":3}"#,
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+        std::fs::write(
+            &log_files[1],
+            [
+                r#"2023-11-14T22:13:21.000000Z DEBUG sui_json_rpc::request_log: {"method":"suix_getBalance","params":["0x1"],"timestamp_ms":2}"#,
+                r#"2023-11-14T22:13:23.000000Z DEBUG sui_json_rpc::request_log: {"method":"sui_executeTransactionBlock","params":["AAA=",["sig"]],"timestamp_ms":4}"#,
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        let trace = dir.path().join("trace.jsonl");
+        // Transactions are not executed again, unless asked for.
+        assert_eq!(record_trace(&log_files, &trace, &[], false).unwrap(), 3);
+        let methods = load_trace(&trace)
+            .into_iter()
+            .map(|entry| entry.method)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            methods,
+            ["sui_getObject", "suix_getBalance", "sui_getChainIdentifier"]
+        );
+
+        assert_eq!(record_trace(&log_files, &trace, &[], true).unwrap(), 4);
+        let timestamps = load_trace(&trace)
+            .into_iter()
+            .map(|entry| entry.timestamp_ms)
+            .collect::<Vec<_>>();
+        assert_eq!(timestamps, [1, 2, 3, 4]);
+        assert_eq!(load_trace(&trace)[3].method, "sui_executeTransactionBlock");
+
+        let methods = ["suix_getBalance".to_string()];
+        assert_eq!(
+            record_trace(&log_files, &trace, &methods, false).unwrap(),
+            1
+        );
+        assert_eq!(load_trace(&trace)[0].params, json!(["0x1"]));
+    }
+}
@@ -21,13 +21,13 @@ use sui_json_rpc_api::{
 };
 use sui_json_rpc_types::CheckpointPage;
 use sui_json_rpc_types::{
-    Balance, Checkpoint, CheckpointId, Coin, CoinPage, DelegatedStake, DevInspectResults,
-    DryRunTransactionBlockResponse, DynamicFieldPage, EventFilter, EventPage, ObjectsPage,
-    ProtocolConfigResponse, SuiCoinMetadata, SuiCommittee, SuiEvent, SuiGetPastObjectRequest,
-    SuiMoveNormalizedModule, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery,
-    SuiPastObjectResponse, SuiTransactionBlockEffects, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery, TransactionBlocksPage,
-    TransactionFilter,
+    Balance, BalanceHistoryPage, Checkpoint, CheckpointId, Coin, CoinPage, DelegatedStake,
+    DevInspectResults, DryRunTransactionBlockResponse, DynamicFieldPage, EventFilter, EventPage,
+    ObjectsPage, ProtocolConfigResponse, SuiCoinMetadata, SuiCommittee, SuiEvent,
+    SuiGetPastObjectRequest, SuiMoveNormalizedModule, SuiObjectDataOptions, SuiObjectResponse,
+    SuiObjectResponseQuery, SuiPastObjectResponse, SuiTransactionBlockEffects,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    SuiTransactionBlockResponseQuery, TransactionBlocksPage, TransactionFilter,
 };
 use sui_types::balance::Supply;
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
@@ -81,11 +81,10 @@ impl ReadApi {
         cursor: Option<ObjectID>,
         limit: Option<usize>,
     ) -> SuiRpcResult<ObjectsPage> {
-        Ok(self
-            .api
-            .http
-            .get_owned_objects(address, query, cursor, limit)
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_owned_objects(address, query.clone(), cursor, limit))
+            .await
     }
 
     /// Return a paginated response with the dynamic fields owned by the given [ObjectID], or an error upon failure.
@@ -134,11 +133,10 @@ impl ReadApi {
         cursor: Option<ObjectID>,
         limit: Option<usize>,
     ) -> SuiRpcResult<DynamicFieldPage> {
-        Ok(self
-            .api
-            .http
-            .get_dynamic_fields(object_id, cursor, limit)
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_dynamic_fields(object_id, cursor, limit))
+            .await
     }
 
     /// Return the dynamic field object information for a specified object.
@@ -147,11 +145,10 @@ impl ReadApi {
         parent_object_id: ObjectID,
         name: DynamicFieldName,
     ) -> SuiRpcResult<SuiObjectResponse> {
-        Ok(self
-            .api
-            .http
-            .get_dynamic_field_object(parent_object_id, name)
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_dynamic_field_object(parent_object_id, name.clone()))
+            .await
     }
 
     /// Return a parsed past object for the provided [ObjectID] and version, or an error upon failure.
@@ -212,11 +209,10 @@ impl ReadApi {
         version: SequenceNumber,
         options: SuiObjectDataOptions,
     ) -> SuiRpcResult<SuiPastObjectResponse> {
-        Ok(self
-            .api
-            .http
-            .try_get_past_object(object_id, version, Some(options))
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.try_get_past_object(object_id, version, Some(options.clone())))
+            .await
     }
 
     /// Return a list of [SuiPastObjectResponse] objects, or an error upon failure.
@@ -293,11 +289,12 @@ impl ReadApi {
         past_objects: Vec<SuiGetPastObjectRequest>,
         options: SuiObjectDataOptions,
     ) -> SuiRpcResult<Vec<SuiPastObjectResponse>> {
-        Ok(self
-            .api
-            .http
-            .try_multi_get_past_objects(past_objects, Some(options))
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| {
+                http.try_multi_get_past_objects(past_objects.clone(), Some(options.clone()))
+            })
+            .await
     }
 
     /// Return a [SuiObjectResponse] based on the provided [ObjectID] and [SuiObjectDataOptions], or an error upon failure.
@@ -351,7 +348,10 @@ impl ReadApi {
         object_id: ObjectID,
         options: SuiObjectDataOptions,
     ) -> SuiRpcResult<SuiObjectResponse> {
-        Ok(self.api.http.get_object(object_id, Some(options)).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_object(object_id, Some(options.clone())))
+            .await
     }
 
     /// Return a list of [SuiObjectResponse] from the given vector of [ObjectID]s and [SuiObjectDataOptions], or an error upon failure.
@@ -403,11 +403,10 @@ impl ReadApi {
         object_ids: Vec<ObjectID>,
         options: SuiObjectDataOptions,
     ) -> SuiRpcResult<Vec<SuiObjectResponse>> {
-        Ok(self
-            .api
-            .http
-            .multi_get_objects(object_ids, Some(options))
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.multi_get_objects(object_ids.clone(), Some(options.clone())))
+            .await
     }
 
     /// Return An object's bcs content [`Vec<u8>`] based on the provided [ObjectID], or an error upon failure.
@@ -446,7 +445,11 @@ impl ReadApi {
     /// }
     /// ```
     pub async fn get_total_transaction_blocks(&self) -> SuiRpcResult<u64> {
-        Ok(*self.api.http.get_total_transaction_blocks().await?)
+        Ok(*self
+            .api
+            .endpoints
+            .read(|http| http.get_total_transaction_blocks())
+            .await?)
     }
 
     /// Return a transaction and its effects in a [SuiTransactionBlockResponse] based on its
@@ -456,11 +459,10 @@ impl ReadApi {
         digest: TransactionDigest,
         options: SuiTransactionBlockResponseOptions,
     ) -> SuiRpcResult<SuiTransactionBlockResponse> {
-        Ok(self
-            .api
-            .http
-            .get_transaction_block(digest, Some(options))
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_transaction_block(digest, Some(options.clone())))
+            .await
     }
     /// Return a list of [SuiTransactionBlockResponse] based on the given vector of [TransactionDigest], or an error upon failure.
     ///
@@ -471,11 +473,10 @@ impl ReadApi {
         digests: Vec<TransactionDigest>,
         options: SuiTransactionBlockResponseOptions,
     ) -> SuiRpcResult<Vec<SuiTransactionBlockResponse>> {
-        Ok(self
-            .api
-            .http
-            .multi_get_transaction_blocks(digests, Some(options))
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.multi_get_transaction_blocks(digests.clone(), Some(options.clone())))
+            .await
     }
 
     /// Return the [SuiCommittee] information for the provided `epoch`, or an error upon failure.
@@ -503,7 +504,10 @@ impl ReadApi {
         &self,
         epoch: Option<BigInt<u64>>,
     ) -> SuiRpcResult<SuiCommittee> {
-        Ok(self.api.http.get_committee_info(epoch).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_committee_info(epoch))
+            .await
     }
 
     /// Return a paginated response with all transaction blocks information, or an error upon failure.
@@ -514,16 +518,20 @@ impl ReadApi {
         limit: Option<usize>,
         descending_order: bool,
     ) -> SuiRpcResult<TransactionBlocksPage> {
-        Ok(self
-            .api
-            .http
-            .query_transaction_blocks(query, cursor, limit, Some(descending_order))
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| {
+                http.query_transaction_blocks(query.clone(), cursor, limit, Some(descending_order))
+            })
+            .await
     }
 
     /// Return the first four bytes of the chain's genesis checkpoint digest, or an error upon failure.
     pub async fn get_chain_identifier(&self) -> SuiRpcResult<String> {
-        Ok(self.api.http.get_chain_identifier().await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_chain_identifier())
+            .await
     }
 
     /// Return a checkpoint, or an error upon failure.
@@ -531,7 +539,10 @@ impl ReadApi {
     /// A Sui checkpoint is a sequence of transaction sets that a quorum of validators
     /// agree upon as having been executed within the Sui system.
     pub async fn get_checkpoint(&self, id: CheckpointId) -> SuiRpcResult<Checkpoint> {
-        Ok(self.api.http.get_checkpoint(id).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_checkpoint(id))
+            .await
     }
 
     /// Return a paginated list of checkpoints, or an error upon failure.
@@ -541,11 +552,10 @@ impl ReadApi {
         limit: Option<usize>,
         descending_order: bool,
     ) -> SuiRpcResult<CheckpointPage> {
-        Ok(self
-            .api
-            .http
-            .get_checkpoints(cursor, limit, descending_order)
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_checkpoints(cursor, limit, descending_order))
+            .await
     }
 
     /// Return the sequence number of the latest checkpoint that has been executed, or an error upon failure.
@@ -554,8 +564,8 @@ impl ReadApi {
     ) -> SuiRpcResult<CheckpointSequenceNumber> {
         Ok(*self
             .api
-            .http
-            .get_latest_checkpoint_sequence_number()
+            .endpoints
+            .read(|http| http.get_latest_checkpoint_sequence_number())
             .await?)
     }
 
@@ -605,7 +615,29 @@ impl ReadApi {
             ));
         };
         let subscription: Subscription<SuiTransactionBlockEffects> =
-            c.subscribe_transaction(filter).await?;
+            c.subscribe_transaction(filter, None).await?;
+        Ok(subscription.map(|item| Ok(item?)))
+    }
+
+    /// Subscribe to a stream of transactions, starting with the transactions in
+    /// `start_checkpoint` and every checkpoint after it, before continuing with new transactions
+    /// as they are executed. Transactions from `start_checkpoint` may be sent again if they were
+    /// already received before resubscribing.
+    ///
+    /// This is only available through WebSockets.
+    pub async fn subscribe_transaction_from(
+        &self,
+        filter: TransactionFilter,
+        start_checkpoint: CheckpointSequenceNumber,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiTransactionBlockEffects>>> {
+        let Some(c) = &self.api.ws else {
+            return Err(Error::Subscription(
+                "Subscription only supported by WebSocket client.".to_string(),
+            ));
+        };
+        let subscription: Subscription<SuiTransactionBlockEffects> = c
+            .subscribe_transaction(filter, Some(start_checkpoint.into()))
+            .await?;
         Ok(subscription.map(|item| Ok(item?)))
     }
 
@@ -614,17 +646,20 @@ impl ReadApi {
         &self,
         package: ObjectID,
     ) -> SuiRpcResult<BTreeMap<String, SuiMoveNormalizedModule>> {
-        Ok(self
-            .api
-            .http
-            .get_normalized_move_modules_by_package(package)
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_normalized_move_modules_by_package(package))
+            .await
     }
 
     // TODO(devx): we can probably cache this given an epoch
     /// Return the reference gas price, or an error upon failure.
     pub async fn get_reference_gas_price(&self) -> SuiRpcResult<u64> {
-        Ok(*self.api.http.get_reference_gas_price().await?)
+        Ok(*self
+            .api
+            .endpoints
+            .read(|http| http.get_reference_gas_price())
+            .await?)
     }
 
     /// Dry run a transaction block given the provided transaction data. Returns an error upon failure.
@@ -636,11 +671,11 @@ impl ReadApi {
         &self,
         tx: TransactionData,
     ) -> SuiRpcResult<DryRunTransactionBlockResponse> {
-        Ok(self
-            .api
-            .http
-            .dry_run_transaction_block(Base64::from_bytes(&bcs::to_bytes(&tx)?))
-            .await?)
+        let tx_bytes = Base64::from_bytes(&bcs::to_bytes(&tx)?);
+        self.api
+            .endpoints
+            .read(|http| http.dry_run_transaction_block(tx_bytes.clone()))
+            .await
     }
 
     /// Return the inspection of the transaction block, or an error upon failure.
@@ -673,17 +708,19 @@ impl ReadApi {
         epoch: Option<BigInt<u64>>,
         additional_args: Option<DevInspectArgs>,
     ) -> SuiRpcResult<DevInspectResults> {
-        Ok(self
-            .api
-            .http
-            .dev_inspect_transaction_block(
-                sender_address,
-                Base64::from_bytes(&bcs::to_bytes(&tx)?),
-                gas_price,
-                epoch,
-                additional_args,
-            )
-            .await?)
+        let tx_bytes = Base64::from_bytes(&bcs::to_bytes(&tx)?);
+        self.api
+            .endpoints
+            .read(|http| {
+                http.dev_inspect_transaction_block(
+                    sender_address,
+                    tx_bytes.clone(),
+                    gas_price,
+                    epoch,
+                    additional_args.clone(),
+                )
+            })
+            .await
     }
 
     /// Return the protocol config, or an error upon failure.
@@ -691,7 +728,10 @@ impl ReadApi {
         &self,
         version: Option<BigInt<u64>>,
     ) -> SuiRpcResult<ProtocolConfigResponse> {
-        Ok(self.api.http.get_protocol_config(version).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_protocol_config(version))
+            .await
     }
 
     pub async fn try_get_object_before_version(
@@ -699,11 +739,10 @@ impl ReadApi {
         object_id: ObjectID,
         version: SequenceNumber,
     ) -> SuiRpcResult<SuiPastObjectResponse> {
-        Ok(self
-            .api
-            .http
-            .try_get_object_before_version(object_id, version)
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.try_get_object_before_version(object_id, version))
+            .await
     }
 }
 
@@ -748,11 +787,10 @@ impl CoinReadApi {
         cursor: Option<ObjectID>,
         limit: Option<usize>,
     ) -> SuiRpcResult<CoinPage> {
-        Ok(self
-            .api
-            .http
-            .get_coins(owner, coin_type, cursor, limit)
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_coins(owner, coin_type.clone(), cursor, limit))
+            .await
     }
     /// Return a paginated response with all the coins for the given address, or an error upon failure.
     ///
@@ -782,7 +820,10 @@ impl CoinReadApi {
         cursor: Option<ObjectID>,
         limit: Option<usize>,
     ) -> SuiRpcResult<CoinPage> {
-        Ok(self.api.http.get_all_coins(owner, cursor, limit).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_all_coins(owner, cursor, limit))
+            .await
     }
 
     /// Return the coins for the given address as a stream.
@@ -922,7 +963,45 @@ impl CoinReadApi {
         owner: SuiAddress,
         coin_type: Option<String>,
     ) -> SuiRpcResult<Balance> {
-        Ok(self.api.http.get_balance(owner, coin_type).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_balance(owner, coin_type.clone(), None))
+            .await
+    }
+
+    /// Return the balance for the given coin type owned by address as of the end of the given
+    /// checkpoint, or an error upon failure.
+    ///
+    /// This requires the Fullnode to maintain the balance history index. By default, if
+    /// `coin_type` is set to `None`, it will use the default `Coin<SUI>`.
+    pub async fn get_balance_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiRpcResult<Balance> {
+        self.api
+            .endpoints
+            .read(|http| http.get_balance(owner, coin_type.clone(), Some(checkpoint.into())))
+            .await
+    }
+
+    /// Return a paginated list of the balances for the given coin type owned by address, as of
+    /// the end of every checkpoint that changed it, or an error upon failure.
+    ///
+    /// This requires the Fullnode to maintain the balance history index. By default, if
+    /// `coin_type` is set to `None`, it will use the default `Coin<SUI>`.
+    pub async fn get_balance_history(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<BigInt<u64>>,
+        limit: Option<usize>,
+    ) -> SuiRpcResult<BalanceHistoryPage> {
+        self.api
+            .endpoints
+            .read(|http| http.get_balance_history(owner, coin_type.clone(), cursor, limit))
+            .await
     }
 
     /// Return a list of balances for each coin type owned by the given address,
@@ -949,7 +1028,10 @@ impl CoinReadApi {
     /// }
     /// ```
     pub async fn get_all_balances(&self, owner: SuiAddress) -> SuiRpcResult<Vec<Balance>> {
-        Ok(self.api.http.get_all_balances(owner).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_all_balances(owner))
+            .await
     }
 
     /// Return the coin metadata (name, symbol, description, decimals, etc.) for a given coin type,
@@ -973,7 +1055,10 @@ impl CoinReadApi {
         &self,
         coin_type: String,
     ) -> SuiRpcResult<Option<SuiCoinMetadata>> {
-        Ok(self.api.http.get_coin_metadata(coin_type).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_coin_metadata(coin_type.clone()))
+            .await
     }
 
     /// Return the total supply for a given coin type, or an error upon failure.
@@ -994,7 +1079,10 @@ impl CoinReadApi {
     /// }
     /// ```
     pub async fn get_total_supply(&self, coin_type: String) -> SuiRpcResult<Supply> {
-        Ok(self.api.http.get_total_supply(coin_type).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_total_supply(coin_type.clone()))
+            .await
     }
 }
 
@@ -1044,7 +1132,29 @@ impl EventApi {
     ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiEvent>>> {
         match &self.api.ws {
             Some(c) => {
-                let subscription: Subscription<SuiEvent> = c.subscribe_event(filter).await?;
+                let subscription: Subscription<SuiEvent> = c.subscribe_event(filter, None).await?;
+                Ok(subscription.map(|item| Ok(item?)))
+            }
+            _ => Err(Error::Subscription(
+                "Subscription only supported by WebSocket client.".to_string(),
+            )),
+        }
+    }
+
+    /// Return a stream of events that resumes after `cursor`: the events after `cursor` that
+    /// match `filter` are replayed first, followed by new events as they are emitted.
+    ///
+    /// Subscription is only possible via WebSockets.
+    /// For a list of possible event filters, see [EventFilter].
+    pub async fn subscribe_event_after(
+        &self,
+        filter: EventFilter,
+        cursor: EventID,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiEvent>>> {
+        match &self.api.ws {
+            Some(c) => {
+                let subscription: Subscription<SuiEvent> =
+                    c.subscribe_event(filter, Some(cursor)).await?;
                 Ok(subscription.map(|item| Ok(item?)))
             }
             _ => Err(Error::Subscription(
@@ -1055,7 +1165,10 @@ impl EventApi {
 
     /// Return a list of events for the given transaction digest, or an error upon failure.
     pub async fn get_events(&self, digest: TransactionDigest) -> SuiRpcResult<Vec<SuiEvent>> {
-        Ok(self.api.http.get_events(digest).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_events(digest))
+            .await
     }
 
     /// Return a paginated response with events for the given event filter, or an error upon failure.
@@ -1069,11 +1182,10 @@ impl EventApi {
         limit: Option<usize>,
         descending_order: bool,
     ) -> SuiRpcResult<EventPage> {
-        Ok(self
-            .api
-            .http
-            .query_events(query, cursor, limit, Some(descending_order))
-            .await?)
+        self.api
+            .endpoints
+            .read(|http| http.query_events(query.clone(), cursor, limit, Some(descending_order)))
+            .await
     }
 
     /// Return a stream of events for the given event filter.
@@ -1138,12 +1250,12 @@ impl QuorumDriverApi {
         while retry_count < WAIT_FOR_LOCAL_EXECUTION_RETRY_COUNT {
             let response: SuiTransactionBlockResponse = self
                 .api
-                .http
+                .endpoints
                 .execute_transaction_block(
                     tx_bytes.clone(),
                     signatures.clone(),
-                    Some(options.clone()),
-                    Some(request_type.clone()),
+                    options.clone(),
+                    request_type.clone(),
                 )
                 .await?;
 
@@ -1182,7 +1294,7 @@ impl GovernanceApi {
 
     /// Return a list of [DelegatedStake] objects for the given address, or an error upon failure.
     pub async fn get_stakes(&self, owner: SuiAddress) -> SuiRpcResult<Vec<DelegatedStake>> {
-        Ok(self.api.http.get_stakes(owner).await?)
+        self.api.endpoints.read(|http| http.get_stakes(owner)).await
     }
 
     /// Return the [SuiCommittee] information for the given `epoch`, or an error upon failure.
@@ -1208,7 +1320,10 @@ impl GovernanceApi {
         &self,
         epoch: Option<BigInt<u64>>,
     ) -> SuiRpcResult<SuiCommittee> {
-        Ok(self.api.http.get_committee_info(epoch).await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_committee_info(epoch))
+            .await
     }
 
     /// Return the latest SUI system state object on-chain, or an error upon failure.
@@ -1217,11 +1332,18 @@ impl GovernanceApi {
     /// the protocol version, the reference gas price, the total stake, active validators,
     /// and much more. See the [SuiSystemStateSummary] for all the available fields.
     pub async fn get_latest_sui_system_state(&self) -> SuiRpcResult<SuiSystemStateSummary> {
-        Ok(self.api.http.get_latest_sui_system_state().await?)
+        self.api
+            .endpoints
+            .read(|http| http.get_latest_sui_system_state())
+            .await
     }
 
     /// Return the reference gas price for the network, or an error upon failure.
     pub async fn get_reference_gas_price(&self) -> SuiRpcResult<u64> {
-        Ok(*self.api.http.get_reference_gas_price().await?)
+        Ok(*self
+            .api
+            .endpoints
+            .read(|http| http.get_reference_gas_price())
+            .await?)
     }
 }
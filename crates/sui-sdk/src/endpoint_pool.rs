@@ -0,0 +1,620 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Spreads the requests of a [SuiClient](crate::SuiClient) over several fullnodes.
+//!
+//! Every endpoint is health checked periodically by fetching its latest checkpoint and timing the
+//! request. Reads are balanced in round-robin order over the healthy endpoints that are caught up
+//! with the most advanced one and are not much slower than the fastest one. A read that fails to
+//! reach its endpoint is retried on the next one, the others only being used as a last resort.
+//! Errors returned by a fullnode are not retried, as the other fullnodes would return the same.
+//!
+//! Reads observe the last transaction the client executed with local execution confirmed: they
+//! only go to the endpoint that executed it, and to the endpoints that have since executed it too.
+
+use std::cmp::Reverse;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use fastcrypto::encoding::Base64;
+use futures::future::{join_all, BoxFuture};
+use jsonrpsee::core::Error as JsonRpseeError;
+use jsonrpsee::http_client::HttpClient;
+use sui_json_rpc_api::{ReadApiClient, WriteApiClient};
+use sui_json_rpc_types::{SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions};
+use sui_types::base_types::TransactionDigest;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use tracing::{debug, warn};
+
+use crate::error::{Error, SuiRpcResult};
+
+/// Caught-up endpoints slower than this multiple of the latency of the fastest one only serve
+/// reads that failed on the faster ones.
+const MAX_LATENCY_RATIO: u32 = 3;
+
+/// Health of an endpoint, as of its last health check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointHealth {
+    pub url: String,
+    /// Whether the endpoint answered its last health check and every request since.
+    pub healthy: bool,
+    /// Latest checkpoint executed by the endpoint.
+    pub latest_checkpoint: Option<CheckpointSequenceNumber>,
+    /// Time taken by the endpoint to answer its last health check.
+    pub latency: Option<Duration>,
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    http: HttpClient,
+    health: Mutex<EndpointHealth>,
+}
+
+/// The last transaction executed through the pool.
+#[derive(Debug)]
+struct LastWrite {
+    digest: TransactionDigest,
+    /// Checkpoint of the transaction, once known.
+    checkpoint: Option<CheckpointSequenceNumber>,
+    /// Whether each endpoint is known to have executed the transaction.
+    executed: Vec<bool>,
+}
+
+#[derive(Debug)]
+pub(crate) struct EndpointPool {
+    endpoints: Vec<Endpoint>,
+    max_checkpoint_lag: u64,
+    /// Rotates the order in which reads go to equally good endpoints.
+    next: AtomicUsize,
+    last_write: Mutex<Option<LastWrite>>,
+}
+
+impl EndpointPool {
+    /// Creates a pool of the given `(url, client)` endpoints, which must not be empty.
+    pub(crate) fn new(endpoints: Vec<(String, HttpClient)>, max_checkpoint_lag: u64) -> Self {
+        assert!(!endpoints.is_empty(), "An endpoint pool needs an endpoint");
+        let endpoints = endpoints
+            .into_iter()
+            .map(|(url, http)| Endpoint {
+                http,
+                health: Mutex::new(EndpointHealth {
+                    url,
+                    healthy: true,
+                    latest_checkpoint: None,
+                    latency: None,
+                }),
+            })
+            .collect();
+        Self {
+            endpoints,
+            max_checkpoint_lag,
+            next: AtomicUsize::new(0),
+            last_write: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    /// The client of the first endpoint the pool was created with.
+    pub(crate) fn first(&self) -> &HttpClient {
+        &self.endpoints[0].http
+    }
+
+    /// The clients of all endpoints, from the one a read would go to first.
+    pub(crate) fn clients(&self) -> impl Iterator<Item = &HttpClient> {
+        self.candidates()
+            .into_iter()
+            .map(move |i| &self.endpoints[i].http)
+    }
+
+    pub(crate) fn health(&self) -> Vec<EndpointHealth> {
+        self.endpoints
+            .iter()
+            .map(|endpoint| endpoint.health.lock().unwrap().clone())
+            .collect()
+    }
+
+    /// Updates the health of every endpoint with its latest checkpoint.
+    pub(crate) async fn check_health(&self) {
+        join_all(self.endpoints.iter().map(|endpoint| async move {
+            let start = Instant::now();
+            let result = endpoint.http.get_latest_checkpoint_sequence_number().await;
+            let mut health = endpoint.health.lock().unwrap();
+            match result {
+                Ok(checkpoint) => {
+                    if !health.healthy {
+                        debug!(url = %health.url, "Endpoint is healthy again");
+                    }
+                    health.healthy = true;
+                    health.latest_checkpoint = Some(*checkpoint);
+                    health.latency = Some(start.elapsed());
+                }
+                Err(e) => {
+                    if health.healthy {
+                        warn!(url = %health.url, "Endpoint failed its health check: {e}");
+                    }
+                    health.healthy = false;
+                }
+            }
+        }))
+        .await;
+    }
+
+    /// Health checks the endpoints every `interval`, until the pool is dropped.
+    pub(crate) fn spawn_health_checks(pool: &Arc<Self>, interval: Duration) {
+        let pool = Arc::downgrade(pool);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // The first tick completes immediately, and the pool was checked when built.
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(pool) = pool.upgrade() else {
+                    return;
+                };
+                pool.check_health().await;
+            }
+        });
+    }
+
+    /// Sends a read `request` to the best endpoint that has executed the last transaction
+    /// executed through the pool, trying the next one whenever the request fails to reach its
+    /// endpoint.
+    pub(crate) async fn read<T>(
+        &self,
+        request: impl for<'a> Fn(&'a HttpClient) -> BoxFuture<'a, Result<T, JsonRpseeError>>,
+    ) -> SuiRpcResult<T> {
+        let mut last_error = None;
+        for i in self.candidates() {
+            if !self.has_executed_last_write(i).await {
+                continue;
+            }
+            let endpoint = &self.endpoints[i];
+            match request(&endpoint.http).await {
+                Err(e) if is_unreachable(&e) => {
+                    endpoint.mark_unhealthy(&e);
+                    last_error = Some(e);
+                }
+                result => {
+                    endpoint.mark_healthy();
+                    return Ok(result?);
+                }
+            }
+        }
+        Err(match last_error {
+            Some(e) => e.into(),
+            // Unreachable, as the endpoint that executed the last transaction is always tried.
+            None => Error::DataError("No endpoint has executed the last transaction".into()),
+        })
+    }
+
+    /// Executes a transaction on the best endpoint. The request is not retried, as it may have
+    /// reached the endpoint. If the endpoint confirms local execution, subsequent reads observe
+    /// the transaction.
+    pub(crate) async fn execute_transaction_block(
+        &self,
+        tx_bytes: Base64,
+        signatures: Vec<Base64>,
+        options: SuiTransactionBlockResponseOptions,
+        request_type: ExecuteTransactionRequestType,
+    ) -> SuiRpcResult<SuiTransactionBlockResponse> {
+        let i = self.candidates()[0];
+        let endpoint = &self.endpoints[i];
+        let response = match endpoint
+            .http
+            .execute_transaction_block(tx_bytes, signatures, Some(options), Some(request_type))
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                if is_unreachable(&e) {
+                    endpoint.mark_unhealthy(&e);
+                }
+                return Err(e.into());
+            }
+        };
+
+        if response.confirmed_local_execution == Some(true) {
+            let mut executed = vec![false; self.len()];
+            executed[i] = true;
+            *self.last_write.lock().unwrap() = Some(LastWrite {
+                digest: response.digest,
+                checkpoint: response.checkpoint,
+                executed,
+            });
+        }
+        Ok(response)
+    }
+
+    /// Indices of the endpoints in the order reads should try them.
+    fn candidates(&self) -> Vec<usize> {
+        let rotation = self.next.fetch_add(1, Ordering::Relaxed);
+        candidate_order(&self.health(), self.max_checkpoint_lag, rotation)
+    }
+
+    /// Whether endpoint `i` has executed the last transaction executed through the pool, asking
+    /// it if that is not known yet.
+    async fn has_executed_last_write(&self, i: usize) -> bool {
+        let (digest, checkpoint) = match &*self.last_write.lock().unwrap() {
+            Some(last_write) if !last_write.executed[i] => {
+                (last_write.digest, last_write.checkpoint)
+            }
+            _ => return true,
+        };
+
+        let endpoint = &self.endpoints[i];
+        let latest_checkpoint = endpoint.health.lock().unwrap().latest_checkpoint;
+        let executed = match checkpoint {
+            Some(checkpoint) => latest_checkpoint.is_some_and(|latest| latest >= checkpoint),
+            None => match endpoint.http.get_transaction_block(digest, None).await {
+                // Until it is checkpointed, a fullnode only knows of a transaction it executed.
+                Ok(response) => match response.checkpoint {
+                    None => true,
+                    Some(checkpoint) => {
+                        if let Some(last_write) = &mut *self.last_write.lock().unwrap() {
+                            if last_write.digest == digest {
+                                last_write.checkpoint = Some(checkpoint);
+                            }
+                        }
+                        latest_checkpoint.is_some_and(|latest| latest >= checkpoint)
+                    }
+                },
+                Err(e) => {
+                    if is_unreachable(&e) {
+                        endpoint.mark_unhealthy(&e);
+                    }
+                    false
+                }
+            },
+        };
+
+        if executed {
+            if let Some(last_write) = &mut *self.last_write.lock().unwrap() {
+                if last_write.digest == digest {
+                    last_write.executed[i] = true;
+                }
+            }
+        }
+        executed
+    }
+}
+
+impl Endpoint {
+    fn mark_unhealthy(&self, error: &JsonRpseeError) {
+        let mut health = self.health.lock().unwrap();
+        if health.healthy {
+            warn!(url = %health.url, "Endpoint is unreachable: {error}");
+        }
+        health.healthy = false;
+    }
+
+    fn mark_healthy(&self) {
+        self.health.lock().unwrap().healthy = true;
+    }
+}
+
+/// Whether the request failed before a fullnode could process it, in which case another fullnode
+/// may succeed.
+fn is_unreachable(error: &JsonRpseeError) -> bool {
+    matches!(
+        error,
+        JsonRpseeError::Transport(_)
+            | JsonRpseeError::RequestTimeout
+            | JsonRpseeError::RestartNeeded(_)
+    )
+}
+
+/// Orders endpoints for a read: first the healthy ones at most `max_checkpoint_lag` checkpoints
+/// behind the most advanced healthy one, in round-robin order among the fast ones, then the slow
+/// ones by latency, then the lagging ones and finally the unhealthy ones, the most advanced first.
+fn candidate_order(
+    health: &[EndpointHealth],
+    max_checkpoint_lag: u64,
+    rotation: usize,
+) -> Vec<usize> {
+    let highest_checkpoint = health
+        .iter()
+        .filter(|h| h.healthy)
+        .filter_map(|h| h.latest_checkpoint)
+        .max()
+        .unwrap_or_default();
+    let (caught_up, mut others): (Vec<usize>, Vec<usize>) = (0..health.len()).partition(|i| {
+        health[*i].healthy
+            && health[*i].latest_checkpoint.map_or(true, |checkpoint| {
+                checkpoint + max_checkpoint_lag >= highest_checkpoint
+            })
+    });
+
+    let fastest = caught_up.iter().filter_map(|i| health[*i].latency).min();
+    let (mut fast, mut slow): (Vec<usize>, Vec<usize>) =
+        caught_up
+            .into_iter()
+            .partition(|i| match (health[*i].latency, fastest) {
+                (Some(latency), Some(fastest)) => latency <= fastest * MAX_LATENCY_RATIO,
+                _ => true,
+            });
+    if !fast.is_empty() {
+        let len = fast.len();
+        fast.rotate_left(rotation % len);
+    }
+    slow.sort_by_key(|i| health[*i].latency);
+    others.sort_by_key(|i| (!health[*i].healthy, Reverse(health[*i].latest_checkpoint)));
+
+    fast.extend(slow);
+    fast.extend(others);
+    fast
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use jsonrpsee::http_client::HttpClientBuilder;
+    use jsonrpsee::server::{ServerBuilder, ServerHandle};
+    use jsonrpsee::types::error::CallError;
+    use jsonrpsee::RpcModule;
+
+    use super::*;
+
+    /// A fullnode serving the few methods the pool needs, with transactions executed through it
+    /// known to it immediately.
+    #[derive(Default)]
+    struct MockNode {
+        /// Transactions the node has executed, and their checkpoint once known.
+        transactions: Mutex<HashMap<TransactionDigest, Option<CheckpointSequenceNumber>>>,
+        /// Number of reads the node served.
+        reads: AtomicUsize,
+    }
+
+    impl MockNode {
+        fn has_executed(&self, digest: &TransactionDigest) -> bool {
+            self.transactions.lock().unwrap().contains_key(digest)
+        }
+    }
+
+    async fn start_mock_node(node: Arc<MockNode>) -> (String, ServerHandle) {
+        let mut module = RpcModule::new(node);
+        module
+            .register_method("sui_getChainIdentifier", |_, node| {
+                node.reads.fetch_add(1, Ordering::SeqCst);
+                Ok("4c78adac".to_string())
+            })
+            .unwrap();
+        module
+            .register_method("sui_getTransactionBlock", |params, node| {
+                let digest: TransactionDigest = params.sequence().next()?;
+                match node.transactions.lock().unwrap().get(&digest) {
+                    Some(checkpoint) => Ok(SuiTransactionBlockResponse {
+                        checkpoint: *checkpoint,
+                        ..SuiTransactionBlockResponse::new(digest)
+                    }),
+                    None => Err(CallError::InvalidParams(anyhow::anyhow!(
+                        "Could not find the referenced transaction {digest}"
+                    ))
+                    .into()),
+                }
+            })
+            .unwrap();
+        module
+            .register_method("sui_executeTransactionBlock", |_, node| {
+                let digest = TransactionDigest::random();
+                node.transactions.lock().unwrap().insert(digest, None);
+                Ok(SuiTransactionBlockResponse {
+                    confirmed_local_execution: Some(true),
+                    ..SuiTransactionBlockResponse::new(digest)
+                })
+            })
+            .unwrap();
+
+        let server = ServerBuilder::default().build("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", server.local_addr().unwrap());
+        (url, server.start(module).unwrap())
+    }
+
+    /// Start `count` mock nodes, and a pool of them. Health checks are left to the tests, so that
+    /// reads are spread over every endpoint in turn.
+    async fn start_pool(count: usize) -> (EndpointPool, Vec<Arc<MockNode>>, Vec<ServerHandle>) {
+        let mut nodes = vec![];
+        let mut handles = vec![];
+        let mut endpoints = vec![];
+        for _ in 0..count {
+            let node = Arc::new(MockNode::default());
+            let (url, handle) = start_mock_node(node.clone()).await;
+            let http = HttpClientBuilder::default().build(&url).unwrap();
+            nodes.push(node);
+            handles.push(handle);
+            endpoints.push((url, http));
+        }
+        (EndpointPool::new(endpoints, 10), nodes, handles)
+    }
+
+    async fn stop(handle: ServerHandle) {
+        handle.stop().unwrap();
+        handle.stopped().await;
+    }
+
+    async fn read(pool: &EndpointPool) -> SuiRpcResult<String> {
+        pool.read(|http| http.get_chain_identifier()).await
+    }
+
+    async fn execute(pool: &EndpointPool) -> TransactionDigest {
+        pool.execute_transaction_block(
+            Base64::from_bytes(&[]),
+            vec![],
+            SuiTransactionBlockResponseOptions::new(),
+            ExecuteTransactionRequestType::WaitForLocalExecution,
+        )
+        .await
+        .unwrap()
+        .digest
+    }
+
+    #[tokio::test]
+    async fn test_read_fails_over_unreachable_endpoints() {
+        let (pool, nodes, mut handles) = start_pool(3).await;
+        stop(handles.remove(0)).await;
+
+        // Whichever endpoint the reads go to first, they all succeed on a reachable one.
+        for _ in 0..6 {
+            read(&pool).await.unwrap();
+        }
+        assert_eq!(nodes[0].reads.load(Ordering::SeqCst), 0);
+        assert_eq!(
+            nodes[1].reads.load(Ordering::SeqCst) + nodes[2].reads.load(Ordering::SeqCst),
+            6
+        );
+        let health = pool.health();
+        assert!(!health[0].healthy);
+        assert!(health[1].healthy && health[2].healthy);
+
+        // Once no endpoint can be reached, the read fails with the last transport error.
+        for handle in handles {
+            stop(handle).await;
+        }
+        assert!(matches!(
+            read(&pool).await,
+            Err(Error::RpcError(JsonRpseeError::Transport(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reads_observe_last_write() {
+        let (pool, nodes, _handles) = start_pool(3).await;
+        let digest = execute(&pool).await;
+        let writer = nodes
+            .iter()
+            .position(|node| node.has_executed(&digest))
+            .unwrap();
+
+        // Only the endpoint that executed the transaction serves reads.
+        for _ in 0..6 {
+            read(&pool).await.unwrap();
+        }
+        for (i, node) in nodes.iter().enumerate() {
+            let expected = if i == writer { 6 } else { 0 };
+            assert_eq!(node.reads.load(Ordering::SeqCst), expected);
+        }
+
+        // Endpoints serve reads again once they have executed the transaction too.
+        let other = (writer + 1) % nodes.len();
+        nodes[other]
+            .transactions
+            .lock()
+            .unwrap()
+            .insert(digest, None);
+        for _ in 0..6 {
+            read(&pool).await.unwrap();
+        }
+        assert!(nodes[other].reads.load(Ordering::SeqCst) > 0);
+        let third = (writer + 2) % nodes.len();
+        assert_eq!(nodes[third].reads.load(Ordering::SeqCst), 0);
+
+        // An endpoint that has the transaction in a checkpoint only serves reads once it has
+        // executed that checkpoint.
+        for endpoint in &pool.endpoints {
+            endpoint.health.lock().unwrap().latest_checkpoint = Some(100);
+        }
+        nodes[third]
+            .transactions
+            .lock()
+            .unwrap()
+            .insert(digest, Some(101));
+        for _ in 0..6 {
+            read(&pool).await.unwrap();
+        }
+        assert_eq!(nodes[third].reads.load(Ordering::SeqCst), 0);
+
+        pool.endpoints[third]
+            .health
+            .lock()
+            .unwrap()
+            .latest_checkpoint = Some(101);
+        for _ in 0..6 {
+            read(&pool).await.unwrap();
+        }
+        assert!(nodes[third].reads.load(Ordering::SeqCst) > 0);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_endpoint_marked_unhealthy_checking_last_write() {
+        let (pool, nodes, mut handles) = start_pool(2).await;
+        let digest = execute(&pool).await;
+        let writer = nodes
+            .iter()
+            .position(|node| node.has_executed(&digest))
+            .unwrap();
+        let other = 1 - writer;
+
+        // Finding out whether the other endpoint executed the transaction fails to reach it.
+        stop(handles.remove(other)).await;
+        for _ in 0..2 {
+            read(&pool).await.unwrap();
+        }
+        assert_eq!(nodes[writer].reads.load(Ordering::SeqCst), 2);
+        assert!(!pool.health()[other].healthy);
+        assert!(pool.health()[writer].healthy);
+    }
+
+    fn health(healthy: bool, latest_checkpoint: u64, latency_ms: u64) -> EndpointHealth {
+        EndpointHealth {
+            url: String::new(),
+            healthy,
+            latest_checkpoint: Some(latest_checkpoint),
+            latency: Some(Duration::from_millis(latency_ms)),
+        }
+    }
+
+    #[test]
+    fn test_candidate_order() {
+        let all_good = [
+            health(true, 100, 10),
+            health(true, 98, 12),
+            health(true, 100, 11),
+        ];
+        assert_eq!(candidate_order(&all_good, 10, 0), [0, 1, 2]);
+        assert_eq!(candidate_order(&all_good, 10, 1), [1, 2, 0]);
+        assert_eq!(candidate_order(&all_good, 10, 5), [2, 0, 1]);
+
+        let lagging = [
+            health(true, 50, 10),
+            health(true, 100, 10),
+            health(true, 80, 10),
+        ];
+        assert_eq!(candidate_order(&lagging, 10, 0), [1, 2, 0]);
+        assert_eq!(candidate_order(&lagging, 50, 1), [1, 2, 0]);
+
+        let slow = [
+            health(true, 100, 50),
+            health(true, 100, 10),
+            health(true, 100, 40),
+        ];
+        assert_eq!(candidate_order(&slow, 10, 0), [1, 2, 0]);
+        assert_eq!(candidate_order(&slow, 10, 1), [1, 2, 0]);
+
+        // Unhealthy endpoints don't count towards the most advanced checkpoint.
+        let unhealthy = [
+            health(false, 200, 10),
+            health(true, 100, 10),
+            health(true, 50, 10),
+        ];
+        assert_eq!(candidate_order(&unhealthy, 10, 0), [1, 2, 0]);
+    }
+
+    #[test]
+    fn test_candidate_order_before_health_checks() {
+        let unknown = EndpointHealth {
+            url: String::new(),
+            healthy: true,
+            latest_checkpoint: None,
+            latency: None,
+        };
+        let endpoints = [unknown.clone(), unknown];
+        assert_eq!(candidate_order(&endpoints, 10, 0), [0, 1]);
+        assert_eq!(candidate_order(&endpoints, 10, 1), [1, 0]);
+    }
+}
@@ -65,6 +65,14 @@
 //! }
 //! ```
 //!
+//! ## Multiple fullnodes
+//!
+//! A [SuiClient] can spread its reads over several fullnodes with
+//! [SuiClientBuilder::build_with_endpoints]. Reads go to the healthy fullnodes
+//! that are caught up with the others, and are retried on another fullnode
+//! when one is unreachable. Reads always observe the transactions executed
+//! through the client (see [endpoint_pool]).
+//!
 //! ## Examples
 //!
 //! For detailed examples, please check the APIs docs and the examples folder
@@ -98,11 +106,14 @@ pub use sui_types as types;
 use sui_types::base_types::{ObjectID, ObjectInfo, SuiAddress};
 
 use crate::apis::{CoinReadApi, EventApi, GovernanceApi, QuorumDriverApi, ReadApi};
+use crate::endpoint_pool::{EndpointHealth, EndpointPool};
 use crate::error::{Error, SuiRpcResult};
 
 pub mod apis;
+pub mod endpoint_pool;
 pub mod error;
 pub mod json_rpc_error;
+pub mod parallel_executor;
 pub mod sui_client_config;
 pub mod wallet_context;
 
@@ -121,6 +132,11 @@ pub const SUI_TESTNET_URL: &str = "https://fullnode.testnet.sui.io:443";
 /// value of your choice to prevent the inactive WS subscription being
 /// disconnected due to proxy timeout.
 ///
+/// When built with several endpoints, they are health checked every 10 seconds
+/// and the ones more than 10 checkpoints behind the others are avoided. These
+/// can be adjusted using the `health_check_interval` and the `max_checkpoint_lag`
+/// functions.
+///
 /// # Examples
 ///
 /// ```rust,no_run
@@ -141,6 +157,8 @@ pub struct SuiClientBuilder {
     ws_url: Option<String>,
     ws_ping_interval: Option<Duration>,
     basic_auth: Option<(String, String)>,
+    health_check_interval: Duration,
+    max_checkpoint_lag: u64,
 }
 
 impl Default for SuiClientBuilder {
@@ -151,6 +169,8 @@ impl Default for SuiClientBuilder {
             ws_url: None,
             ws_ping_interval: None,
             basic_auth: None,
+            health_check_interval: Duration::from_secs(10),
+            max_checkpoint_lag: 10,
         }
     }
 }
@@ -186,6 +206,18 @@ impl SuiClientBuilder {
         self
     }
 
+    /// Set the interval between health checks of the endpoints, when there are several
+    pub fn health_check_interval(mut self, interval: Duration) -> Self {
+        self.health_check_interval = interval;
+        self
+    }
+
+    /// Set how many checkpoints an endpoint can be behind the others and still serve reads
+    pub fn max_checkpoint_lag(mut self, max_checkpoint_lag: u64) -> Self {
+        self.max_checkpoint_lag = max_checkpoint_lag;
+        self
+    }
+
     /// Returns a [SuiClient] object connected to the Sui network running at the URI provided.
     ///
     /// # Examples
@@ -204,6 +236,35 @@ impl SuiClientBuilder {
     /// }
     /// ```
     pub async fn build(self, http: impl AsRef<str>) -> SuiRpcResult<SuiClient> {
+        self.build_with_endpoints([http]).await
+    }
+
+    /// Returns a [SuiClient] object spreading its reads over the fullnodes
+    /// running at the URIs provided, see [endpoint_pool]. Transactions are
+    /// executed on a single fullnode, and subscriptions go to the WebSocket URL,
+    /// if any.
+    ///
+    /// Fails if none of the fullnodes is reachable.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use sui_sdk::SuiClientBuilder;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), anyhow::Error> {
+    ///     let sui = SuiClientBuilder::default()
+    ///         .build_with_endpoints(["http://127.0.0.1:9000", "http://127.0.0.1:9001"])
+    ///         .await?;
+    ///
+    ///     println!("{:?}", sui.endpoint_health());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn build_with_endpoints(
+        self,
+        urls: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> SuiRpcResult<SuiClient> {
         let client_version = env!("CARGO_PKG_VERSION");
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -242,16 +303,42 @@ impl SuiClientBuilder {
             None
         };
 
-        let http = HttpClientBuilder::default()
-            .max_request_body_size(2 << 30)
-            .max_concurrent_requests(self.max_concurrent_requests)
-            .set_headers(headers.clone())
-            .request_timeout(self.request_timeout)
-            .build(http)?;
+        let endpoints = urls
+            .into_iter()
+            .map(|url| {
+                let http = HttpClientBuilder::default()
+                    .max_request_body_size(2 << 30)
+                    .max_concurrent_requests(self.max_concurrent_requests)
+                    .set_headers(headers.clone())
+                    .request_timeout(self.request_timeout)
+                    .build(url.as_ref())?;
+                Ok((url.as_ref().to_string(), http))
+            })
+            .collect::<SuiRpcResult<Vec<_>>>()?;
+        if endpoints.is_empty() {
+            return Err(Error::DataError("No endpoint provided".into()));
+        }
+
+        let endpoints = Arc::new(EndpointPool::new(endpoints, self.max_checkpoint_lag));
+        if endpoints.len() > 1 {
+            endpoints.check_health().await;
+            EndpointPool::spawn_health_checks(&endpoints, self.health_check_interval);
+        }
 
-        let info = Self::get_server_info(&http, &ws).await?;
+        let mut info = None;
+        for http in endpoints.clients() {
+            info = Some(Self::get_server_info(http, &ws).await);
+            if matches!(info, Some(Ok(_))) {
+                break;
+            }
+        }
+        let info = info.expect("The endpoint pool is not empty")?;
 
-        let rpc = RpcClient { http, ws, info };
+        let rpc = RpcClient {
+            endpoints,
+            ws,
+            info,
+        };
         let api = Arc::new(rpc);
         let read_api = Arc::new(ReadApi::new(api.clone()));
         let quorum_driver_api = QuorumDriverApi::new(api.clone());
@@ -435,7 +522,7 @@ pub struct SuiClient {
 }
 
 pub(crate) struct RpcClient {
-    http: HttpClient,
+    endpoints: Arc<EndpointPool>,
     ws: Option<WsClient>,
     info: ServerInfo,
 }
@@ -445,7 +532,7 @@ impl Debug for RpcClient {
         write!(
             f,
             "RPC client. Http: {:?}, Websocket: {:?}",
-            self.http, self.ws
+            self.endpoints, self.ws
         )
     }
 }
@@ -519,9 +606,15 @@ impl SuiClient {
         &self.transaction_builder
     }
 
-    /// Returns a reference to the underlying http client.
+    /// Returns a reference to the underlying http client, of the first endpoint
+    /// if there are several.
     pub fn http(&self) -> &HttpClient {
-        &self.api.http
+        self.api.endpoints.first()
+    }
+
+    /// Returns the health of every endpoint, in the order they were provided.
+    pub fn endpoint_health(&self) -> Vec<EndpointHealth> {
+        self.api.endpoints.health()
     }
 
     /// Returns a reference to the underlying WebSocket client, if any.
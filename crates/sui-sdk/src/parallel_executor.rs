@@ -0,0 +1,744 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Executes many independent transactions concurrently from a single address.
+//!
+//! Transactions that share a gas coin cannot be in flight at the same time: the second one either
+//! uses an outdated version of the coin, or equivocates on it and locks it until the end of the
+//! epoch. The [ParallelExecutor] avoids both by owning a pool of gas coins, giving each
+//! transaction in flight its own coin, and tracking the latest version of every coin from the
+//! effects of the transactions that used it. Coins running low are merged and split again, so
+//! that the pool keeps its size for as long as the address has enough SUI.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+
+use futures::stream::{self, StreamExt};
+use jsonrpsee::types::error::CallError;
+use sui_json_rpc_types::{
+    Coin, SuiObjectDataOptions, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+};
+use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress};
+use sui_types::committee::EpochId;
+use sui_types::crypto::SuiKeyPair;
+use sui_types::digests::TransactionDigest;
+use sui_types::gas_coin::GasCoin;
+use sui_types::object::Owner;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use sui_types::transaction::{
+    Argument, Command, ProgrammableTransaction, Transaction, TransactionData,
+};
+use tokio::sync::{Mutex as AsyncMutex, Semaphore};
+use tracing::{debug, warn};
+
+use crate::error::{Error, SuiRpcResult};
+use crate::json_rpc_error::TRANSACTION_EXECUTION_CLIENT_ERROR_CODE;
+use crate::SuiClient;
+
+/// Maximum number of coins merged by a single rebalancing transaction.
+const MAX_MERGED_COINS: usize = 256;
+
+#[derive(Debug, Clone)]
+pub struct ParallelExecutorConfig {
+    /// Number of coins in the pool, which bounds the number of transactions in flight.
+    pub num_coins: usize,
+    /// Gas budget of every transaction, including the ones rebalancing the pool.
+    pub gas_budget: u64,
+    /// Coins left with less than this balance are merged back into the pool. Must be at least the
+    /// gas budget.
+    pub min_coin_balance: u64,
+}
+
+impl Default for ParallelExecutorConfig {
+    fn default() -> Self {
+        Self {
+            num_coins: 32,
+            gas_budget: 50_000_000,
+            min_coin_balance: 100_000_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PooledCoin {
+    object_ref: ObjectRef,
+    balance: u64,
+}
+
+impl PooledCoin {
+    fn id(&self) -> ObjectID {
+        self.object_ref.0
+    }
+}
+
+impl From<Coin> for PooledCoin {
+    fn from(coin: Coin) -> Self {
+        Self {
+            object_ref: coin.object_ref(),
+            balance: coin.balance,
+        }
+    }
+}
+
+#[derive(Default)]
+struct GasPool {
+    /// Coins ready to pay for a transaction, with one permit of `ParallelExecutor::available`
+    /// each.
+    idle: VecDeque<PooledCoin>,
+    /// Coins below the minimum balance, waiting to be merged.
+    low: Vec<PooledCoin>,
+    /// Coins possibly locked by a transaction that failed to finalize, which cannot be used until
+    /// the end of the epoch they were locked in.
+    locked: Vec<(EpochId, PooledCoin)>,
+    in_flight: usize,
+    epoch: EpochId,
+}
+
+/// Pipelines independent transactions of one sender, each paid for by its own coin from a pool
+/// of gas coins.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use sui_sdk::parallel_executor::{ParallelExecutor, ParallelExecutorConfig};
+/// use sui_sdk::rpc_types::SuiTransactionBlockResponseOptions;
+/// use sui_sdk::SuiClientBuilder;
+/// use sui_types::crypto::{get_key_pair, SuiKeyPair};
+/// use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), anyhow::Error> {
+///     let sui = SuiClientBuilder::default().build_localnet().await?;
+///     let keypair = SuiKeyPair::Ed25519(get_key_pair().1);
+///     let recipient = (&keypair.public()).into();
+///     let executor = ParallelExecutor::new(sui, keypair, ParallelExecutorConfig::default()).await?;
+///
+///     let transactions = (0..100).map(|_| {
+///         let mut builder = ProgrammableTransactionBuilder::new();
+///         builder.pay_sui(vec![recipient], vec![1]).unwrap();
+///         builder.finish()
+///     });
+///     let responses = executor
+///         .execute_all(transactions, SuiTransactionBlockResponseOptions::new())
+///         .await;
+///     Ok(())
+/// }
+/// ```
+pub struct ParallelExecutor {
+    client: SuiClient,
+    keypair: SuiKeyPair,
+    sender: SuiAddress,
+    config: ParallelExecutorConfig,
+    gas_price: u64,
+    pool: Mutex<GasPool>,
+    available: Semaphore,
+    rebalancing: AsyncMutex<()>,
+}
+
+impl ParallelExecutor {
+    /// Create an executor for the address of `keypair`, which takes over all its SUI coins. No
+    /// other client should use them while the executor is running.
+    pub async fn new(
+        client: SuiClient,
+        keypair: SuiKeyPair,
+        config: ParallelExecutorConfig,
+    ) -> SuiRpcResult<Self> {
+        if config.num_coins == 0 || config.min_coin_balance < config.gas_budget {
+            return Err(Error::DataError(format!(
+                "Invalid parallel executor config: {config:?}"
+            )));
+        }
+        let sender = (&keypair.public()).into();
+        let gas_price = client.read_api().get_reference_gas_price().await?;
+        let epoch = client
+            .governance_api()
+            .get_latest_sui_system_state()
+            .await?
+            .epoch;
+        let coins: Vec<Coin> = client
+            .coin_read_api()
+            .get_coins_stream(sender, None)
+            .collect()
+            .await;
+
+        let mut pool = GasPool {
+            epoch,
+            ..Default::default()
+        };
+        for coin in coins.into_iter().map(PooledCoin::from) {
+            if coin.balance < config.min_coin_balance {
+                pool.low.push(coin);
+            } else {
+                pool.idle.push_back(coin);
+            }
+        }
+        let executor = Self {
+            client,
+            keypair,
+            sender,
+            config,
+            gas_price,
+            available: Semaphore::new(pool.idle.len()),
+            pool: Mutex::new(pool),
+            rebalancing: AsyncMutex::new(()),
+        };
+        executor.rebalance().await?;
+        Ok(executor)
+    }
+
+    pub fn sender(&self) -> SuiAddress {
+        self.sender
+    }
+
+    pub fn config(&self) -> &ParallelExecutorConfig {
+        &self.config
+    }
+
+    /// Number of coins in the pool, including the ones in flight or waiting to be merged.
+    pub fn num_coins(&self) -> usize {
+        let pool = self.pool.lock().unwrap();
+        pool.idle.len() + pool.low.len() + pool.locked.len() + pool.in_flight
+    }
+
+    /// Execute `pt` as soon as a gas coin is available. The effects are always part of the
+    /// response, on top of what `options` asks for.
+    ///
+    /// Fails with [Error::InsufficientFund] once the pool is exhausted, with no coin left to pay
+    /// for transactions and none in flight or locked to come back. This is final: the executor
+    /// only uses the coins it took over, so a new one must be created after funding the address.
+    pub async fn execute(
+        &self,
+        pt: ProgrammableTransaction,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> SuiRpcResult<SuiTransactionBlockResponse> {
+        let coin = self.check_out().await?;
+        // The balance of the coin is only known from the effects if the transaction does not
+        // spend it, otherwise it is read back once the transaction is executed locally.
+        let spends_gas = uses_gas_coin(&pt);
+        let request_type = if spends_gas {
+            ExecuteTransactionRequestType::WaitForLocalExecution
+        } else {
+            ExecuteTransactionRequestType::WaitForEffectsCert
+        };
+        let data = TransactionData::new_programmable(
+            self.sender,
+            vec![coin.object_ref],
+            pt,
+            self.config.gas_budget,
+            self.gas_price,
+        );
+        let tx = Transaction::from_data_and_signer(data, vec![&self.keypair]);
+        let result = self
+            .client
+            .quorum_driver_api()
+            .execute_transaction_block(tx, options.with_effects(), Some(request_type))
+            .await;
+
+        match &result {
+            Ok(SuiTransactionBlockResponse {
+                effects: Some(effects),
+                ..
+            }) => {
+                let coin = self.coin_after(coin, effects, spends_gas).await;
+                self.observe_epoch(effects.executed_epoch());
+                self.check_in(coin, true);
+            }
+            Ok(_) => self.recover(coin, None).await,
+            Err(e) => self.recover(coin, Some(e)).await,
+        }
+        if self.pool.lock().unwrap().needs_rebalance(&self.config) {
+            // Another task rebalancing the pool is enough.
+            if let Ok(_guard) = self.rebalancing.try_lock() {
+                if let Err(e) = self.rebalance_locked().await {
+                    warn!("Failed to rebalance gas pool: {e}");
+                }
+            }
+        }
+        result
+    }
+
+    /// Execute all of `pts` concurrently, with as many transactions in flight as there are coins
+    /// in the pool. Responses are in the order of `pts`.
+    pub async fn execute_all(
+        &self,
+        pts: impl IntoIterator<Item = ProgrammableTransaction>,
+        options: SuiTransactionBlockResponseOptions,
+    ) -> Vec<SuiRpcResult<SuiTransactionBlockResponse>> {
+        stream::iter(pts)
+            .map(|pt| self.execute(pt, options.clone()))
+            .buffered(self.config.num_coins)
+            .collect()
+            .await
+    }
+
+    /// Merge the idle coins, the coins running low and the coins unlocked by a new epoch, then
+    /// split them into as many coins as the pool is missing. Transactions in flight keep their
+    /// coins.
+    pub async fn rebalance(&self) -> SuiRpcResult<()> {
+        let _guard = self.rebalancing.lock().await;
+        self.rebalance_locked().await
+    }
+
+    async fn rebalance_locked(&self) -> SuiRpcResult<()> {
+        let epoch = self
+            .client
+            .governance_api()
+            .get_latest_sui_system_state()
+            .await?
+            .epoch;
+        let (mut coins, in_flight) = {
+            let mut pool = self.pool.lock().unwrap();
+            pool.epoch = pool.epoch.max(epoch);
+            let mut coins = std::mem::take(&mut pool.low);
+            // Permits already handed to waiting transactions keep their coins.
+            let idle = self.available.available_permits();
+            if let Ok(permits) = self.available.try_acquire_many(idle as u32) {
+                permits.forget();
+                coins.extend(pool.idle.drain(..idle));
+            }
+            (coins, pool.in_flight)
+        };
+        let unlocked = {
+            let mut pool = self.pool.lock().unwrap();
+            let (unlocked, locked) = std::mem::take(&mut pool.locked)
+                .into_iter()
+                .partition::<Vec<_>, _>(|(locked_epoch, _)| *locked_epoch < epoch);
+            pool.locked = locked;
+            unlocked
+        };
+        for (locked_epoch, coin) in unlocked {
+            // The locking transaction might have been executed after all.
+            match self.fetch_coin(coin.id()).await {
+                Ok(Some(coin)) => coins.push(coin),
+                Ok(None) => (),
+                Err(e) => {
+                    warn!("Failed to read back locked coin {}: {e}", coin.id());
+                    self.pool.lock().unwrap().locked.push((locked_epoch, coin));
+                }
+            }
+        }
+        if coins.len() > MAX_MERGED_COINS {
+            // Leave the smallest for a later rebalance.
+            coins.sort_by_key(|coin| std::cmp::Reverse(coin.balance));
+            for coin in coins.split_off(MAX_MERGED_COINS) {
+                self.check_in(coin, false);
+            }
+        }
+
+        let total: u64 = coins.iter().map(|coin| coin.balance).sum();
+        let target = self.config.num_coins.saturating_sub(in_flight).max(1);
+        let amounts = split_amounts(total, target, &self.config);
+        if coins.is_empty() || (coins.len() == 1 && amounts.is_empty()) {
+            for coin in coins {
+                self.check_in(coin, false);
+            }
+            return self.check_not_exhausted();
+        }
+        if total < self.config.gas_budget {
+            // Not enough to pay for merging them, keep them for transactions in flight to return.
+            self.pool.lock().unwrap().low.extend(coins);
+            self.check_not_exhausted()?;
+            return Err(Error::InsufficientFund {
+                address: self.sender,
+                amount: self.config.gas_budget as u128,
+            });
+        }
+
+        // Paying with all the coins merges them into the first one, which is then split.
+        let pt = {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            if !amounts.is_empty() {
+                builder
+                    .pay_sui(vec![self.sender; amounts.len()], amounts.clone())
+                    .map_err(|e| Error::DataError(e.to_string()))?;
+            }
+            builder.finish()
+        };
+        let data = TransactionData::new_programmable(
+            self.sender,
+            coins.iter().map(|coin| coin.object_ref).collect(),
+            pt,
+            self.config.gas_budget,
+            self.gas_price,
+        );
+        let tx = Transaction::from_data_and_signer(data, vec![&self.keypair]);
+        debug!(
+            "Rebalancing {} coins with {total} MIST into {} coins",
+            coins.len(),
+            amounts.len() + 1
+        );
+        let result = self
+            .client
+            .quorum_driver_api()
+            .execute_transaction_block(
+                tx,
+                SuiTransactionBlockResponseOptions::new().with_effects(),
+                Some(ExecuteTransactionRequestType::WaitForEffectsCert),
+            )
+            .await;
+
+        match result {
+            Ok(SuiTransactionBlockResponse {
+                effects: Some(effects),
+                ..
+            }) if effects.status().is_ok() => {
+                let gas_coin = PooledCoin {
+                    object_ref: effects.gas_object().reference.to_object_ref(),
+                    balance: apply_gas_cost(total, &effects) - amounts.iter().sum::<u64>(),
+                };
+                self.observe_epoch(effects.executed_epoch());
+                self.check_in(gas_coin, false);
+                let created = effects.created().iter().filter(|created| {
+                    matches!(created.owner, Owner::AddressOwner(owner) if owner == self.sender)
+                });
+                for (created, balance) in created.zip(amounts) {
+                    let coin = PooledCoin {
+                        object_ref: created.reference.to_object_ref(),
+                        balance,
+                    };
+                    self.check_in(coin, false);
+                }
+                Ok(())
+            }
+            result => {
+                warn!("Rebalancing transaction failed: {result:?}");
+                // Coins the transaction did not touch are still valid, others are read back.
+                // Coins that cannot be read back are kept aside until the next rebalance.
+                for coin in coins {
+                    match self.fetch_coin(coin.id()).await {
+                        Ok(Some(coin)) => self.check_in(coin, false),
+                        Ok(None) => (),
+                        Err(e) => {
+                            warn!("Failed to read back coin {}: {e}", coin.id());
+                            let mut pool = self.pool.lock().unwrap();
+                            let epoch = pool.epoch;
+                            pool.locked.push((epoch, coin));
+                        }
+                    }
+                }
+                result.map(|_| ())
+            }
+        }
+    }
+
+    async fn check_out(&self) -> SuiRpcResult<PooledCoin> {
+        let permit = self
+            .available
+            .acquire()
+            .await
+            .map_err(|_| Error::InsufficientFund {
+                address: self.sender,
+                amount: self.config.gas_budget as u128,
+            })?;
+        permit.forget();
+        let mut pool = self.pool.lock().unwrap();
+        pool.in_flight += 1;
+        Ok(pool
+            .idle
+            .pop_front()
+            .expect("Every permit has an idle coin"))
+    }
+
+    /// Return `coin` to the pool, after a transaction used it if it was `checked_out`.
+    fn check_in(&self, coin: PooledCoin, checked_out: bool) {
+        let mut pool = self.pool.lock().unwrap();
+        if checked_out {
+            pool.in_flight -= 1;
+        }
+        if coin.balance < self.config.min_coin_balance {
+            pool.low.push(coin);
+        } else {
+            pool.idle.push_back(coin);
+            self.available.add_permits(1);
+        }
+    }
+
+    fn observe_epoch(&self, epoch: EpochId) {
+        let mut pool = self.pool.lock().unwrap();
+        pool.epoch = pool.epoch.max(epoch);
+    }
+
+    /// Return `coin` to the pool after a transaction using it failed to execute with `error`, or
+    /// executed without returning its effects.
+    async fn recover(&self, coin: PooledCoin, error: Option<&Error>) {
+        let fetched = self.fetch_coin(coin.id()).await;
+        let mut pool = self.pool.lock().unwrap();
+        pool.in_flight -= 1;
+        match fetched {
+            Ok(Some(fetched)) if fetched.object_ref.1 > coin.object_ref.1 => {
+                // Either executed, or the pool was out of date.
+                drop(pool);
+                self.check_in(fetched, false);
+            }
+            Ok(Some(fetched)) if !error.is_some_and(is_lock_error) => {
+                drop(pool);
+                self.check_in(fetched, false);
+            }
+            // The coin was deleted or sent away.
+            Ok(None) => (),
+            // The coin is locked, or its state is unknown.
+            _ => {
+                let epoch = pool.epoch;
+                pool.locked.push((epoch, coin));
+            }
+        }
+    }
+
+    /// `coin` after the transaction with `effects` used it for gas.
+    async fn coin_after(
+        &self,
+        coin: PooledCoin,
+        effects: &SuiTransactionBlockEffects,
+        spends_gas: bool,
+    ) -> PooledCoin {
+        let object_ref = effects.gas_object().reference.to_object_ref();
+        if spends_gas {
+            match self.fetch_coin(coin.id()).await {
+                Ok(Some(fetched)) => return fetched,
+                // Deleted, or unknown until the next rebalance.
+                Ok(None) | Err(_) => {
+                    return PooledCoin {
+                        object_ref,
+                        balance: 0,
+                    }
+                }
+            }
+        }
+        PooledCoin {
+            object_ref,
+            balance: apply_gas_cost(coin.balance, effects),
+        }
+    }
+
+    /// Latest version of coin `id`, if the sender still owns it.
+    async fn fetch_coin(&self, id: ObjectID) -> SuiRpcResult<Option<PooledCoin>> {
+        let response = self
+            .client
+            .read_api()
+            .get_object_with_options(id, SuiObjectDataOptions::new().with_content().with_owner())
+            .await?;
+        let Some(object) = response.data else {
+            return Ok(None);
+        };
+        if object.owner != Some(Owner::AddressOwner(self.sender)) {
+            return Ok(None);
+        }
+        let coin = GasCoin::try_from(&object).map_err(|e| Error::DataError(e.to_string()))?;
+        Ok(Some(PooledCoin {
+            object_ref: object.object_ref(),
+            balance: coin.value(),
+        }))
+    }
+
+    /// Fail transactions waiting for a coin if none will ever be available. Closing `available`
+    /// also fails every later `check_out`, as coins are never added back to an exhausted pool.
+    fn check_not_exhausted(&self) -> SuiRpcResult<()> {
+        let pool = self.pool.lock().unwrap();
+        if pool.idle.is_empty() && pool.in_flight == 0 && pool.locked.is_empty() {
+            self.available.close();
+            return Err(Error::InsufficientFund {
+                address: self.sender,
+                amount: self.config.gas_budget as u128,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl GasPool {
+    fn needs_rebalance(&self, config: &ParallelExecutorConfig) -> bool {
+        // Wait for enough low coins to be worth merging, unless the pool is drained.
+        self.low.len() >= (config.num_coins / 4).max(1)
+            || (self.idle.is_empty() && !self.low.is_empty())
+    }
+}
+
+/// Amounts of the coins split from a coin of `total` MIST, after paying for the split, so that
+/// with the remainder there are at most `target` coins of at least the minimum balance.
+fn split_amounts(total: u64, target: usize, config: &ParallelExecutorConfig) -> Vec<u64> {
+    let available = total.saturating_sub(config.gas_budget);
+    let per_coin = available / target as u64;
+    if per_coin >= config.min_coin_balance {
+        vec![per_coin; target - 1]
+    } else {
+        let count = available / config.min_coin_balance;
+        vec![config.min_coin_balance; count.saturating_sub(1) as usize]
+    }
+}
+
+fn apply_gas_cost(balance: u64, effects: &SuiTransactionBlockEffects) -> u64 {
+    let net_gas_usage = effects.gas_cost_summary().net_gas_usage();
+    if net_gas_usage >= 0 {
+        balance.saturating_sub(net_gas_usage as u64)
+    } else {
+        balance + net_gas_usage.unsigned_abs()
+    }
+}
+
+fn uses_gas_coin(pt: &ProgrammableTransaction) -> bool {
+    pt.commands.iter().any(|command| {
+        let (first, rest): (Option<&Argument>, &[Argument]) = match command {
+            Command::MoveCall(call) => (None, &call.arguments),
+            Command::TransferObjects(objects, recipient) => (Some(recipient), objects),
+            Command::SplitCoins(coin, amounts) => (Some(coin), amounts),
+            Command::MergeCoins(target, coins) => (Some(target), coins),
+            Command::MakeMoveVec(_, elements) => (None, elements),
+            Command::Upgrade(_, _, _, ticket) => (Some(ticket), &[]),
+            Command::Publish(_, _) => (None, &[]),
+        };
+        first
+            .into_iter()
+            .chain(rest)
+            .any(|arg| *arg == Argument::GasCoin)
+    })
+}
+
+/// Whether `error` reports that an input object was locked by a conflicting transaction, which
+/// lasts until the end of the epoch, or that the transaction used an outdated version of it.
+fn is_lock_error(error: &Error) -> bool {
+    let (code, message, data) = match error {
+        Error::RpcError(jsonrpsee::core::Error::Call(CallError::Custom(object))) => (
+            object.code(),
+            object.message(),
+            object
+                .data()
+                .and_then(|data| serde_json::from_str::<ConflictingTransactions>(data.get()).ok()),
+        ),
+        Error::JsonRpcError(error) => (
+            error.code,
+            error.message.as_str(),
+            error
+                .data
+                .clone()
+                .and_then(|data| serde_json::from_value::<ConflictingTransactions>(data).ok()),
+        ),
+        _ => return false,
+    };
+    if code != TRANSACTION_EXECUTION_CLIENT_ERROR_CODE {
+        return false;
+    }
+    // `QuorumDriverError::ObjectsDoubleUsed` lists the conflicting transactions as its data.
+    if data.is_some_and(|conflicts| !conflicts.is_empty()) {
+        return true;
+    }
+    // `QuorumDriverError::NonRecoverableTransactionError` only carries the rendered input errors.
+    [OBJECT_LOCK_CONFLICT, OBJECT_VERSION_UNAVAILABLE]
+        .iter()
+        .any(|rendered| message.contains(rendered))
+}
+
+/// Data of a `QuorumDriverError::ObjectsDoubleUsed` error: the conflicting transactions, and the
+/// objects they locked.
+type ConflictingTransactions = BTreeMap<TransactionDigest, Vec<ObjectRef>>;
+
+/// Part of the message of `SuiError::ObjectLockConflict`.
+const OBJECT_LOCK_CONFLICT: &str = "already locked by a different transaction";
+/// Part of the message of `UserInputError::ObjectVersionUnavailableForConsumption`.
+const OBJECT_VERSION_UNAVAILABLE: &str = "is not available for consumption";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_amounts() {
+        let config = ParallelExecutorConfig {
+            num_coins: 4,
+            gas_budget: 10,
+            min_coin_balance: 100,
+        };
+        // Evenly split, the remainder stays with the merged coin.
+        assert_eq!(split_amounts(1010, 4, &config), vec![250; 3]);
+        // Not enough for the target, as many coins of the minimum balance as possible.
+        assert_eq!(split_amounts(310, 4, &config), vec![100; 2]);
+        assert_eq!(split_amounts(150, 4, &config), Vec::<u64>::new());
+        assert_eq!(split_amounts(5, 4, &config), Vec::<u64>::new());
+        // Only the merged coin is wanted.
+        assert_eq!(split_amounts(1010, 1, &config), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn test_uses_gas_coin() {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.pay_sui(vec![SuiAddress::ZERO], vec![1]).unwrap();
+        assert!(uses_gas_coin(&builder.finish()));
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .transfer_object(SuiAddress::ZERO, random_object_ref())
+            .unwrap();
+        assert!(!uses_gas_coin(&builder.finish()));
+    }
+
+    #[test]
+    fn test_is_lock_error() {
+        use jsonrpsee::types::error::{ErrorObject, INTERNAL_ERROR_CODE};
+
+        fn call_error(code: i32, message: &str, data: Option<serde_json::Value>) -> Error {
+            jsonrpsee::core::Error::Call(CallError::Custom(ErrorObject::owned(
+                code,
+                message.to_string(),
+                data,
+            )))
+            .into()
+        }
+
+        let object_ref = random_object_ref();
+        let conflicts: ConflictingTransactions =
+            BTreeMap::from([(TransactionDigest::random(), vec![object_ref])]);
+        let double_used = call_error(
+            TRANSACTION_EXECUTION_CLIENT_ERROR_CODE,
+            "Failed to sign transaction by a quorum of validators because of locked objects.",
+            Some(serde_json::to_value(&conflicts).unwrap()),
+        );
+        assert!(is_lock_error(&double_used));
+
+        let lock_conflict = sui_types::error::SuiError::ObjectLockConflict {
+            obj_ref: object_ref,
+            pending_transaction: TransactionDigest::random(),
+        };
+        let non_recoverable = call_error(
+            TRANSACTION_EXECUTION_CLIENT_ERROR_CODE,
+            &format!("Transaction execution failed due to issues with transaction inputs, please review the errors and try again: {lock_conflict}."),
+            None,
+        );
+        assert!(is_lock_error(&non_recoverable));
+
+        let version_unavailable =
+            sui_types::error::UserInputError::ObjectVersionUnavailableForConsumption {
+                provided_obj_ref: object_ref,
+                current_version: 10.into(),
+            };
+        let non_recoverable = call_error(
+            TRANSACTION_EXECUTION_CLIENT_ERROR_CODE,
+            &version_unavailable.to_string(),
+            None,
+        );
+        assert!(is_lock_error(&non_recoverable));
+
+        // Mentions of blocks or clocks, or other error codes, are not lock errors.
+        for message in ["Could not find block 10", "Clock object is not mutable"] {
+            assert!(!is_lock_error(&call_error(
+                TRANSACTION_EXECUTION_CLIENT_ERROR_CODE,
+                message,
+                None
+            )));
+        }
+        assert!(!is_lock_error(&call_error(
+            INTERNAL_ERROR_CODE,
+            &lock_conflict.to_string(),
+            Some(serde_json::to_value(conflicts).unwrap()),
+        )));
+        assert!(!is_lock_error(&Error::DataError(lock_conflict.to_string())));
+    }
+
+    fn random_object_ref() -> ObjectRef {
+        (
+            ObjectID::random(),
+            Default::default(),
+            sui_types::digests::ObjectDigest::random(),
+        )
+    }
+}
@@ -7,7 +7,7 @@ mod tests;
 
 pub mod reader;
 pub mod uploader;
-mod writer;
+pub mod writer;
 
 use anyhow::Result;
 use fastcrypto::hash::MultisetHash;
@@ -55,6 +55,15 @@ use tokio::time::Instant;
 /// as well. Finally, the MANIFEST file contains per file metadata of every file in the snapshot directory.
 /// current one reaches the max size i.e. 64MB. Partitions allow a single hash bucket to be consumed
 /// in parallel. Partition files are compressed with the zstd compression format.
+///
+/// A snapshot can also be taken as a delta against the snapshot of an earlier (base) epoch, in which
+/// case its *.obj and *.ref files only hold the objects added or mutated since the base epoch, and
+/// the references of objects deleted since then are written to *.del files, in the same format as
+/// REFERENCE files. The MANIFEST of a delta snapshot records its base epoch, which may itself be a
+/// delta, so restoring it means restoring the chain of snapshots down to the closest full snapshot.
+/// The references in the REFERENCE and *.del files of a bucket are sorted by object id across its
+/// partitions, so that the live object set of a chain can be merged with the live object set of a
+/// later epoch by reading the files of each bucket once, without downloading any object file.
 /// State Snapshot Directory Layout
 ///  - snapshot/
 ///     - epoch_0/
@@ -72,6 +81,11 @@ use tokio::time::Instant;
 ///     - epoch_1/
 ///       - 1_1.obj
 ///       - ...
+///     - epoch_2/ (delta against epoch_1)
+///       - 1_1.obj
+///       - 1_1.ref
+///       - 1_1.del
+///       - MANIFEST
 /// Object File Disk Format
 ///┌──────────────────────────────┐
 ///│  magic(0x00B7EC75) <4 byte>  │
@@ -142,6 +156,7 @@ const FILE_METADATA_BYTES: usize =
 pub enum FileType {
     Object = 0,
     Reference,
+    Deleted,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
@@ -162,6 +177,9 @@ impl FileMetadata {
             FileType::Reference => {
                 dir_path.child(&*format!("{}_{}.ref", self.bucket_num, self.part_num))
             }
+            FileType::Deleted => {
+                dir_path.child(&*format!("{}_{}.del", self.bucket_num, self.part_num))
+            }
         }
     }
     pub fn local_file_path(&self, root_path: &std::path::Path, dir_path: &Path) -> Result<PathBuf> {
@@ -177,30 +195,53 @@ pub struct ManifestV1 {
     pub epoch: u64,
 }
 
+/// Manifest of a snapshot that may be a delta against the snapshot of `base_epoch`. Full snapshots
+/// are still written as `ManifestV1`, so that they remain readable by older readers.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct ManifestV2 {
+    pub snapshot_version: u8,
+    pub address_length: u64,
+    pub file_metadata: Vec<FileMetadata>,
+    pub epoch: u64,
+    pub base_epoch: Option<u64>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub enum Manifest {
     V1(ManifestV1),
+    V2(ManifestV2),
 }
 
 impl Manifest {
     pub fn snapshot_version(&self) -> u8 {
         match self {
             Self::V1(manifest) => manifest.snapshot_version,
+            Self::V2(manifest) => manifest.snapshot_version,
         }
     }
     pub fn address_length(&self) -> u64 {
         match self {
             Self::V1(manifest) => manifest.address_length,
+            Self::V2(manifest) => manifest.address_length,
         }
     }
     pub fn file_metadata(&self) -> &Vec<FileMetadata> {
         match self {
             Self::V1(manifest) => &manifest.file_metadata,
+            Self::V2(manifest) => &manifest.file_metadata,
         }
     }
     pub fn epoch(&self) -> u64 {
         match self {
             Self::V1(manifest) => manifest.epoch,
+            Self::V2(manifest) => manifest.epoch,
+        }
+    }
+    /// The epoch of the snapshot this one is a delta against, or `None` for a full snapshot.
+    pub fn base_epoch(&self) -> Option<u64> {
+        match self {
+            Self::V1(_) => None,
+            Self::V2(manifest) => manifest.base_epoch,
         }
     }
 }
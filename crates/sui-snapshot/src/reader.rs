@@ -15,7 +15,8 @@ use futures::{StreamExt, TryStreamExt};
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use integer_encoding::VarIntReader;
 use object_store::path::Path;
-use std::collections::BTreeMap;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashSet};
 use std::fs;
 use std::fs::File;
 use std::io::{BufReader, Read, Seek, SeekFrom};
@@ -40,56 +41,147 @@ use tracing::{error, info};
 
 pub type SnapshotChecksums = (DigestByBucketAndPartition, Accumulator);
 pub type DigestByBucketAndPartition = BTreeMap<u32, BTreeMap<u32, [u8; 32]>>;
-pub struct StateSnapshotReaderV1 {
-    epoch: u64,
-    local_staging_dir_root: PathBuf,
-    remote_object_store: Arc<dyn ObjectStoreGetExt>,
-    local_object_store: Arc<dyn ObjectStorePutExt>,
-    ref_files: BTreeMap<u32, BTreeMap<u32, FileMetadata>>,
-    object_files: BTreeMap<u32, BTreeMap<u32, FileMetadata>>,
-    indirect_objects_threshold: usize,
-    m: MultiProgress,
-    concurrency: usize,
+pub type DigestByEpochBucketAndPartition = BTreeMap<u64, DigestByBucketAndPartition>;
+
+/// The files of one snapshot in a chain of snapshots, made of a full snapshot followed by the
+/// snapshots which are (transitively) deltas against it.
+#[derive(Clone, Debug)]
+pub(crate) struct SnapshotLayer {
+    pub(crate) epoch: u64,
+    pub(crate) base_epoch: Option<u64>,
+    pub(crate) ref_files: BTreeMap<u32, BTreeMap<u32, FileMetadata>>,
+    pub(crate) object_files: BTreeMap<u32, BTreeMap<u32, FileMetadata>>,
+    pub(crate) deleted_files: BTreeMap<u32, BTreeMap<u32, FileMetadata>>,
+    /// Objects which a later snapshot in the chain mutated or deleted. Their copies in this
+    /// snapshot are not part of the live object set at the end of the chain.
+    pub(crate) superseded: Arc<HashSet<ObjectID>>,
 }
 
-impl StateSnapshotReaderV1 {
-    pub async fn new(
-        epoch: u64,
-        remote_store_config: &ObjectStoreConfig,
-        local_store_config: &ObjectStoreConfig,
-        indirect_objects_threshold: usize,
-        download_concurrency: NonZeroUsize,
-        m: MultiProgress,
-    ) -> Result<Self> {
-        let epoch_dir = format!("epoch_{}", epoch);
-        let remote_object_store = if remote_store_config.no_sign_request {
-            remote_store_config.make_http()?
+impl SnapshotLayer {
+    fn new(manifest: &Manifest) -> Self {
+        let mut layer = SnapshotLayer {
+            epoch: manifest.epoch(),
+            base_epoch: manifest.base_epoch(),
+            ref_files: BTreeMap::new(),
+            object_files: BTreeMap::new(),
+            deleted_files: BTreeMap::new(),
+            superseded: Arc::new(HashSet::new()),
+        };
+        for file_metadata in manifest.file_metadata() {
+            let files = match file_metadata.file_type {
+                FileType::Object => &mut layer.object_files,
+                FileType::Reference => &mut layer.ref_files,
+                FileType::Deleted => &mut layer.deleted_files,
+            };
+            files
+                .entry(file_metadata.bucket_num)
+                .or_insert_with(BTreeMap::new)
+                .insert(file_metadata.part_num, file_metadata.clone());
+        }
+        layer
+    }
+
+    pub(crate) fn epoch_dir(&self) -> Path {
+        Path::from(format!("epoch_{}", self.epoch))
+    }
+
+    pub(crate) fn is_live(&self, object_id: &ObjectID) -> bool {
+        !self.superseded.contains(object_id)
+    }
+
+    /// Paths of the reference and deleted object files, which are needed locally before objects
+    /// can be restored.
+    fn ref_and_deleted_file_paths(&self) -> Vec<Path> {
+        let epoch_dir = self.epoch_dir();
+        self.ref_files
+            .values()
+            .chain(self.deleted_files.values())
+            .flat_map(|entry| entry.values())
+            .map(|file_metadata| file_metadata.file_path(&epoch_dir))
+            .collect()
+    }
+
+    /// The references in the reference files (or with `deleted`, the deleted object files) of
+    /// `bucket`, in object id order.
+    fn sorted_refs(
+        &self,
+        bucket: u32,
+        deleted: bool,
+        local_staging_dir_root: &std::path::Path,
+    ) -> SortedRefIter {
+        let files = if deleted {
+            &self.deleted_files
         } else {
-            remote_store_config.make().map(Arc::new)?
+            &self.ref_files
         };
-        let local_object_store: Arc<dyn ObjectStorePutExt> =
-            local_store_config.make().map(Arc::new)?;
-        let local_staging_dir_root = local_store_config
-            .directory
-            .as_ref()
-            .context("No directory specified")?
-            .clone();
+        let part_files: Vec<_> = files
+            .get(&bucket)
+            .map(|parts| parts.values().cloned().collect())
+            .unwrap_or_default();
+        SortedRefIter {
+            part_files: part_files.into_iter(),
+            current: None,
+            root_path: local_staging_dir_root.to_path_buf(),
+            epoch_dir: self.epoch_dir(),
+            last_object_id: None,
+        }
+    }
+
+    /// Ids of all objects this snapshot added, mutated or deleted. Only meaningful for deltas.
+    fn touched_object_ids(
+        &self,
+        local_staging_dir_root: &std::path::Path,
+    ) -> Result<Vec<ObjectID>> {
+        let mut object_ids = vec![];
+        for file_metadata in self
+            .ref_files
+            .values()
+            .chain(self.deleted_files.values())
+            .flat_map(|entry| entry.values())
+        {
+            let ref_iter = ObjectRefIter::new(
+                file_metadata,
+                local_staging_dir_root.to_path_buf(),
+                self.epoch_dir(),
+            )?;
+            object_ids.extend(ref_iter.map(|object_ref| object_ref.0));
+        }
+        Ok(object_ids)
+    }
+}
+
+/// Downloads the MANIFEST of the snapshot for `epoch`, and of every snapshot it is (transitively) a
+/// delta against, along with their reference and deleted object files. Object files are left in the
+/// remote store. Returns the chain of snapshots, starting from the full snapshot it is based on and
+/// ending with `epoch`.
+pub(crate) async fn download_snapshot_chain<S: ObjectStoreGetExt, D: ObjectStorePutExt>(
+    epoch: u64,
+    remote_object_store: &S,
+    local_object_store: &D,
+    local_staging_dir_root: &std::path::Path,
+    download_concurrency: NonZeroUsize,
+    m: &MultiProgress,
+) -> Result<Vec<SnapshotLayer>> {
+    let mut layers = vec![];
+    let mut next_epoch = Some(epoch);
+    while let Some(epoch) = next_epoch {
+        let epoch_dir = format!("epoch_{}", epoch);
         let local_epoch_dir_path = local_staging_dir_root.join(&epoch_dir);
         if local_epoch_dir_path.exists() {
             fs::remove_dir_all(&local_epoch_dir_path)?;
         }
         fs::create_dir_all(&local_epoch_dir_path)?;
         // Download MANIFEST first
-        let manifest_file_path = Path::from(epoch_dir.clone()).child("MANIFEST");
+        let manifest_file_path = Path::from(epoch_dir).child("MANIFEST");
         copy_file(
             &manifest_file_path,
             &manifest_file_path,
-            &remote_object_store,
-            &local_object_store,
+            remote_object_store,
+            local_object_store,
         )
         .await?;
-        let manifest = Self::read_manifest(path_to_filesystem(
-            local_staging_dir_root.clone(),
+        let manifest = StateSnapshotReaderV1::read_manifest(path_to_filesystem(
+            local_staging_dir_root.to_path_buf(),
             &manifest_file_path,
         )?)?;
         let snapshot_version = manifest.snapshot_version();
@@ -105,61 +197,257 @@ impl StateSnapshotReaderV1 {
         if manifest.epoch() != epoch {
             return Err(anyhow!("Download manifest is not for epoch: {}", epoch,));
         }
-        let mut object_files = BTreeMap::new();
-        let mut ref_files = BTreeMap::new();
-        for file_metadata in manifest.file_metadata() {
-            match file_metadata.file_type {
-                FileType::Object => {
-                    let entry = object_files
-                        .entry(file_metadata.bucket_num)
-                        .or_insert_with(BTreeMap::new);
-                    entry.insert(file_metadata.part_num, file_metadata.clone());
+        next_epoch = manifest.base_epoch();
+        if let Some(base_epoch) = next_epoch {
+            if base_epoch >= epoch {
+                return Err(anyhow!(
+                    "Snapshot for epoch: {} is a delta against later epoch: {}",
+                    epoch,
+                    base_epoch
+                ));
+            }
+        }
+        layers.push(SnapshotLayer::new(&manifest));
+    }
+    layers.reverse();
+
+    let files: Vec<Path> = layers
+        .iter()
+        .flat_map(|layer| layer.ref_and_deleted_file_paths())
+        .collect();
+    let progress_bar = m.add(
+        ProgressBar::new(files.len() as u64).with_style(
+            ProgressStyle::with_template(
+                "[{elapsed_precise}] {wide_bar} {pos} out of {len} .ref files done ({msg})",
+            )
+            .unwrap(),
+        ),
+    );
+    copy_files(
+        &files,
+        &files,
+        remote_object_store,
+        local_object_store,
+        download_concurrency,
+        Some(progress_bar.clone()),
+    )
+    .await?;
+    progress_bar.finish_with_message("ref files download complete");
+    Ok(layers)
+}
+
+/// Marks the objects of every snapshot in the chain which are superseded by the objects touched in
+/// the deltas after it.
+fn mark_superseded_objects(
+    layers: &mut [SnapshotLayer],
+    local_staging_dir_root: &std::path::Path,
+) -> Result<()> {
+    let mut superseded = HashSet::new();
+    for layer in layers.iter_mut().rev() {
+        layer.superseded = Arc::new(superseded.clone());
+        if layer.base_epoch.is_some() {
+            superseded.extend(layer.touched_object_ids(local_staging_dir_root)?);
+        }
+    }
+    Ok(())
+}
+
+/// The live object set at the end of a chain of snapshots whose reference and deleted object files
+/// were downloaded by `download_snapshot_chain`, in object id order.
+pub(crate) fn live_object_refs(
+    layers: &[SnapshotLayer],
+    local_staging_dir_root: &std::path::Path,
+) -> LiveObjectRefIter {
+    let mut streams = vec![];
+    for (layer_num, layer) in layers.iter().enumerate() {
+        let buckets: BTreeSet<u32> = layer
+            .ref_files
+            .keys()
+            .chain(layer.deleted_files.keys())
+            .copied()
+            .collect();
+        for bucket in buckets {
+            for deleted in [false, true] {
+                streams.push((
+                    layer_num,
+                    deleted,
+                    layer.sorted_refs(bucket, deleted, local_staging_dir_root),
+                ));
+            }
+        }
+    }
+    LiveObjectRefIter {
+        streams,
+        heap: BinaryHeap::new(),
+        error: None,
+        started: false,
+    }
+}
+
+/// Merges the sorted reference and deleted object files of every bucket of every snapshot in a
+/// chain, yielding the references of the objects live at the end of the chain in object id order.
+/// Only the next reference of each file is held in memory.
+pub(crate) struct LiveObjectRefIter {
+    /// The snapshot each file belongs to, whether it holds deleted objects, and its references
+    streams: Vec<(usize, bool, SortedRefIter)>,
+    /// The next reference of every file which is not exhausted, along with its index in `streams`
+    heap: BinaryHeap<Reverse<(ObjectRef, usize)>>,
+    error: Option<anyhow::Error>,
+    started: bool,
+}
+
+impl LiveObjectRefIter {
+    fn advance(&mut self, stream: usize) {
+        match self.streams[stream].2.next() {
+            Some(Ok(object_ref)) => self.heap.push(Reverse((object_ref, stream))),
+            Some(Err(err)) => {
+                self.error.get_or_insert(err);
+            }
+            None => {}
+        }
+    }
+
+    fn next_ref(&mut self) -> Result<Option<ObjectRef>> {
+        if !self.started {
+            self.started = true;
+            for stream in 0..self.streams.len() {
+                self.advance(stream);
+            }
+        }
+        loop {
+            if let Some(err) = self.error.take() {
+                return Err(err);
+            }
+            let Some(Reverse((object_ref, stream))) = self.heap.pop() else {
+                return Ok(None);
+            };
+            // The latest snapshot to touch an object decides whether and how it is live
+            let (mut latest_layer, mut deleted) = (self.streams[stream].0, self.streams[stream].1);
+            let mut latest_ref = object_ref;
+            self.advance(stream);
+            while let Some(Reverse((next_ref, _))) = self.heap.peek() {
+                if next_ref.0 != object_ref.0 {
+                    break;
                 }
-                FileType::Reference => {
-                    let entry = ref_files
-                        .entry(file_metadata.bucket_num)
-                        .or_insert_with(BTreeMap::new);
-                    entry.insert(file_metadata.part_num, file_metadata.clone());
+                let Some(Reverse((next_ref, next_stream))) = self.heap.pop() else {
+                    break;
+                };
+                let (layer, next_deleted) =
+                    (self.streams[next_stream].0, self.streams[next_stream].1);
+                if layer > latest_layer || (layer == latest_layer && next_deleted) {
+                    (latest_layer, deleted, latest_ref) = (layer, next_deleted, next_ref);
                 }
+                self.advance(next_stream);
+            }
+            if !deleted {
+                return Ok(Some(latest_ref));
             }
         }
-        let epoch_dir_path = Path::from(epoch_dir);
-        let files: Vec<Path> = ref_files
-            .values()
-            .flat_map(|entry| {
-                let files: Vec<_> = entry
-                    .values()
-                    .map(|file_metadata| file_metadata.file_path(&epoch_dir_path))
-                    .collect();
-                files
-            })
-            .collect();
+    }
+}
 
-        let progress_bar = m.add(
-            ProgressBar::new(files.len() as u64).with_style(
-                ProgressStyle::with_template(
-                    "[{elapsed_precise}] {wide_bar} {pos} out of {len} .ref files done ({msg})",
-                )
-                .unwrap(),
-            ),
-        );
-        copy_files(
-            &files,
-            &files,
+impl Iterator for LiveObjectRefIter {
+    type Item = Result<ObjectRef>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_ref().transpose()
+    }
+}
+
+/// Iterates over the references in the part files of one bucket, checking that they are sorted by
+/// object id.
+pub(crate) struct SortedRefIter {
+    part_files: std::vec::IntoIter<FileMetadata>,
+    current: Option<ObjectRefIter>,
+    root_path: PathBuf,
+    epoch_dir: Path,
+    last_object_id: Option<ObjectID>,
+}
+
+impl SortedRefIter {
+    fn next_ref(&mut self) -> Result<Option<ObjectRef>> {
+        loop {
+            if let Some(object_ref) = self.current.as_mut().and_then(|refs| refs.next()) {
+                if self
+                    .last_object_id
+                    .is_some_and(|last_object_id| last_object_id >= object_ref.0)
+                {
+                    return Err(anyhow!(
+                        "Object references in {} are not sorted by object id",
+                        self.epoch_dir
+                    ));
+                }
+                self.last_object_id = Some(object_ref.0);
+                return Ok(Some(object_ref));
+            }
+            let Some(file_metadata) = self.part_files.next() else {
+                return Ok(None);
+            };
+            self.current = Some(ObjectRefIter::new(
+                &file_metadata,
+                self.root_path.clone(),
+                self.epoch_dir.clone(),
+            )?);
+        }
+    }
+}
+
+impl Iterator for SortedRefIter {
+    type Item = Result<ObjectRef>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_ref().transpose()
+    }
+}
+
+/// StateSnapshotReaderV1 restores the live object set of an epoch from its snapshot. If that
+/// snapshot is a delta, the chain of snapshots it is based on is restored along with it.
+pub struct StateSnapshotReaderV1 {
+    epoch: u64,
+    local_staging_dir_root: PathBuf,
+    remote_object_store: Arc<dyn ObjectStoreGetExt>,
+    local_object_store: Arc<dyn ObjectStorePutExt>,
+    layers: Vec<SnapshotLayer>,
+    indirect_objects_threshold: usize,
+    m: MultiProgress,
+    concurrency: usize,
+}
+
+impl StateSnapshotReaderV1 {
+    pub async fn new(
+        epoch: u64,
+        remote_store_config: &ObjectStoreConfig,
+        local_store_config: &ObjectStoreConfig,
+        indirect_objects_threshold: usize,
+        download_concurrency: NonZeroUsize,
+        m: MultiProgress,
+    ) -> Result<Self> {
+        let remote_object_store = if remote_store_config.no_sign_request {
+            remote_store_config.make_http()?
+        } else {
+            remote_store_config.make().map(Arc::new)?
+        };
+        let local_object_store: Arc<dyn ObjectStorePutExt> =
+            local_store_config.make().map(Arc::new)?;
+        let local_staging_dir_root = local_store_config
+            .directory
+            .as_ref()
+            .context("No directory specified")?
+            .clone();
+        let mut layers = download_snapshot_chain(
+            epoch,
             &remote_object_store,
             &local_object_store,
+            &local_staging_dir_root,
             download_concurrency,
-            Some(progress_bar.clone()),
+            &m,
         )
         .await?;
-        progress_bar.finish_with_message("ref files download complete");
+        mark_superseded_objects(&mut layers, &local_staging_dir_root)?;
         Ok(StateSnapshotReaderV1 {
             epoch,
             local_staging_dir_root,
             remote_object_store,
             local_object_store,
-            ref_files,
-            object_files,
+            layers,
             indirect_objects_threshold,
             m,
             concurrency: download_concurrency.get(),
@@ -177,12 +465,14 @@ impl StateSnapshotReaderV1 {
         // per *.obj file against this. We do this so during restore we can pre fetch object
         // references and start building state accumulator and fail early if the state root hash
         // doesn't match but we still need to ensure that objects match references exactly.
-        let sha3_digests: Arc<Mutex<DigestByBucketAndPartition>> =
+        // Objects superseded by a later delta are left out of both.
+        let sha3_digests: Arc<Mutex<DigestByEpochBucketAndPartition>> =
             Arc::new(Mutex::new(BTreeMap::new()));
 
         let num_part_files = self
-            .ref_files
-            .values()
+            .layers
+            .iter()
+            .flat_map(|layer| layer.ref_files.values())
             .map(|part_files| part_files.len())
             .sum::<usize>();
 
@@ -197,30 +487,42 @@ impl StateSnapshotReaderV1 {
             ),
         );
 
-        for (bucket, part_files) in self.ref_files.clone().iter() {
-            for (part, _part_file) in part_files.iter() {
-                let mut sha3_digests = sha3_digests.lock().await;
-                let ref_iter = self.ref_iter(*bucket, *part)?;
-                let mut hasher = Sha3_256::default();
-                let mut empty = true;
-                self.object_files
-                    .get(bucket)
-                    .context(format!("No bucket exists for: {bucket}"))?
-                    .get(part)
-                    .context(format!("No part exists for bucket: {bucket}, part: {part}"))?;
-                for object_ref in ref_iter {
-                    hasher.update(object_ref.2.inner());
-                    empty = false;
-                }
-                if !empty {
-                    sha3_digests
-                        .entry(*bucket)
-                        .or_insert(BTreeMap::new())
-                        .entry(*part)
-                        .or_insert(hasher.finalize().digest);
+        for layer in self.layers.iter() {
+            for (bucket, part_files) in layer.ref_files.iter() {
+                for (part, file_metadata) in part_files.iter() {
+                    let mut sha3_digests = sha3_digests.lock().await;
+                    let ref_iter = ObjectRefIter::new(
+                        file_metadata,
+                        self.local_staging_dir_root.clone(),
+                        layer.epoch_dir(),
+                    )?;
+                    let mut hasher = Sha3_256::default();
+                    let mut empty = true;
+                    layer
+                        .object_files
+                        .get(bucket)
+                        .context(format!("No bucket exists for: {bucket}"))?
+                        .get(part)
+                        .context(format!("No part exists for bucket: {bucket}, part: {part}"))?;
+                    for object_ref in ref_iter.filter(|object_ref| layer.is_live(&object_ref.0)) {
+                        hasher.update(object_ref.2.inner());
+                        empty = false;
+                    }
+                    if !empty {
+                        sha3_digests
+                            .entry(layer.epoch)
+                            .or_insert(BTreeMap::new())
+                            .entry(*bucket)
+                            .or_insert(BTreeMap::new())
+                            .entry(*part)
+                            .or_insert(hasher.finalize().digest);
+                    }
+                    checksum_progress_bar.inc(1);
+                    checksum_progress_bar.set_message(format!(
+                        "Epoch: {}, Bucket: {}, Part: {}",
+                        layer.epoch, bucket, part
+                    ));
                 }
-                checksum_progress_bar.inc(1);
-                checksum_progress_bar.set_message(format!("Bucket: {}, Part: {}", bucket, part));
             }
         }
         checksum_progress_bar.finish_with_message("Checksumming complete");
@@ -274,54 +576,47 @@ impl StateSnapshotReaderV1 {
         });
 
         // spawn accumualation task
-        let ref_files = self.ref_files.clone();
-        let epoch_dir = self.epoch_dir();
+        let layers = self.layers.clone();
         let local_staging_dir_root = self.local_staging_dir_root.clone();
         tokio::task::spawn(async move {
-            let local_staging_dir_root_clone = local_staging_dir_root.clone();
-            let epoch_dir_clone = epoch_dir.clone();
-            for (bucket, part_files) in ref_files.clone().iter() {
-                futures::stream::iter(part_files.iter())
-                    .map(|(part, _part_files)| {
-                        // TODO depending on concurrency limit here, we may be
-                        // materializing too many refs into memory at once.
-                        // This is only done because ObjectRefIter is not Send
-                        let obj_digests = {
-                            let file_metadata = ref_files
-                                .get(bucket)
-                                .expect("No ref files found for bucket: {bucket_num}")
-                                .get(part)
-                                .expect(
-                                    "No ref files found for bucket: {bucket_num}, part: {part_num}",
-                                );
-                            ObjectRefIter::new(
+            for layer in layers.iter() {
+                let local_staging_dir_root_clone = local_staging_dir_root.clone();
+                let epoch_dir_clone = layer.epoch_dir();
+                for (_bucket, part_files) in layer.ref_files.iter() {
+                    futures::stream::iter(part_files.iter())
+                        .map(|(_part, file_metadata)| {
+                            // TODO depending on concurrency limit here, we may be
+                            // materializing too many refs into memory at once.
+                            // This is only done because ObjectRefIter is not Send
+                            let obj_digests = ObjectRefIter::new(
                                 file_metadata,
                                 local_staging_dir_root_clone.clone(),
                                 epoch_dir_clone.clone(),
                             )
                             .expect("Failed to create object ref iter")
-                        }
-                        .map(|obj_ref| obj_ref.2)
-                        .collect::<Vec<ObjectDigest>>();
-                        let sender_clone = sender.clone();
-                        tokio::spawn(async move {
-                            let mut partial_acc = Accumulator::default();
-                            let num_objects = obj_digests.len();
-                            partial_acc.insert_all(obj_digests);
-                            sender_clone
-                                .send((partial_acc, num_objects as u64))
-                                .await
-                                .expect("Unable to send accumulator from snapshot reader");
+                            .filter(|obj_ref| layer.is_live(&obj_ref.0))
+                            .map(|obj_ref| obj_ref.2)
+                            .collect::<Vec<ObjectDigest>>();
+                            let sender_clone = sender.clone();
+                            tokio::spawn(async move {
+                                let mut partial_acc = Accumulator::default();
+                                let num_objects = obj_digests.len();
+                                partial_acc.insert_all(obj_digests);
+                                sender_clone
+                                    .send((partial_acc, num_objects as u64))
+                                    .await
+                                    .expect("Unable to send accumulator from snapshot reader");
+                            })
                         })
-                    })
-                    .boxed()
-                    .buffer_unordered(concurrency)
-                    .for_each(|result| {
-                        result.expect("Failed to generate partial accumulator");
-                        accum_counter.fetch_add(1, Ordering::Relaxed);
-                        futures::future::ready(())
-                    })
-                    .await;
+                        .boxed()
+                        .buffer_unordered(concurrency)
+                        .for_each(|result| {
+                            result.expect("Failed to generate partial accumulator");
+                            accum_counter.fetch_add(1, Ordering::Relaxed);
+                            futures::future::ready(())
+                        })
+                        .await;
+                }
             }
             accum_progress_bar.finish_with_message("Accumulation complete");
         })
@@ -331,23 +626,34 @@ impl StateSnapshotReaderV1 {
         &self,
         perpetual_db: &AuthorityPerpetualTables,
         abort_registration: AbortRegistration,
-        sha3_digests: Arc<Mutex<DigestByBucketAndPartition>>,
+        sha3_digests: Arc<Mutex<DigestByEpochBucketAndPartition>>,
     ) -> Result<(), anyhow::Error> {
-        let epoch_dir = self.epoch_dir();
         let concurrency = self.concurrency;
         let threshold = self.indirect_objects_threshold;
         let remote_object_store = self.remote_object_store.clone();
-        let input_files: Vec<_> = self
-            .object_files
-            .iter()
-            .flat_map(|(bucket, parts)| {
-                parts
-                    .clone()
-                    .into_iter()
-                    .map(|entry| (bucket, entry))
-                    .collect::<Vec<_>>()
-            })
-            .collect();
+        // Object files without a digest hold no live objects, and need not be downloaded at all
+        let input_files: Vec<_> = {
+            let sha3_digests = sha3_digests.lock().await;
+            self.layers
+                .iter()
+                .flat_map(|layer| {
+                    layer.object_files.iter().flat_map(move |(bucket, parts)| {
+                        parts.iter().map(move |(part_num, file_metadata)| {
+                            (layer, bucket, part_num, file_metadata)
+                        })
+                    })
+                })
+                .filter_map(|(layer, bucket, part_num, file_metadata)| {
+                    let sha3_digest = sha3_digests.get(&layer.epoch)?.get(bucket)?.get(part_num)?;
+                    Some((
+                        layer.epoch_dir(),
+                        layer.superseded.clone(),
+                        file_metadata.clone(),
+                        *sha3_digest,
+                    ))
+                })
+                .collect()
+        };
         let obj_progress_bar = self.m.add(
             ProgressBar::new(input_files.len() as u64).with_style(
                 ProgressStyle::with_template(
@@ -362,12 +668,10 @@ impl StateSnapshotReaderV1 {
 
         let ret = Abortable::new(
             async move {
-                futures::stream::iter(input_files.iter())
-                    .map(|(bucket, (part_num, file_metadata))| {
-                        let epoch_dir = epoch_dir.clone();
+                futures::stream::iter(input_files.into_iter())
+                    .map(|(epoch_dir, superseded, file_metadata, sha3_digest)| {
                         let file_path = file_metadata.file_path(&epoch_dir);
                         let remote_object_store = remote_object_store.clone();
-                        let sha3_digests_cloned = sha3_digests.clone();
                         async move {
                             // Download object file with retries
                             let max_timeout = Duration::from_secs(30);
@@ -401,31 +705,28 @@ impl StateSnapshotReaderV1 {
                                     }
                                 }
                             };
-
-                            let sha3_digest = sha3_digests_cloned.lock().await;
-                            let bucket_map = sha3_digest
-                                .get(bucket)
-                                .expect("Bucket not in digest map")
-                                .clone();
-                            let sha3_digest = *bucket_map
-                                .get(part_num)
-                                .expect("sha3 digest not in bucket map");
-                            Ok::<(Bytes, FileMetadata, [u8; 32]), anyhow::Error>((
+                            Ok::<
+                                (Bytes, FileMetadata, [u8; 32], Arc<HashSet<ObjectID>>),
+                                anyhow::Error,
+                            >((
                                 bytes,
-                                (*file_metadata).clone(),
+                                file_metadata,
                                 sha3_digest,
+                                superseded,
                             ))
                         }
                     })
                     .boxed()
                     .buffer_unordered(concurrency)
-                    .try_for_each(|(bytes, file_metadata, sha3_digest)| {
+                    .try_for_each(|(bytes, file_metadata, sha3_digest, superseded)| {
                         let bytes_len = bytes.len();
                         let result: Result<(), anyhow::Error> =
                             LiveObjectIter::new(&file_metadata, bytes).map(|obj_iter| {
                                 AuthorityStore::bulk_insert_live_objects(
                                     perpetual_db,
-                                    obj_iter,
+                                    obj_iter.filter(|object| {
+                                        !superseded.contains(&object.object_reference().0)
+                                    }),
                                     threshold,
                                     &sha3_digest,
                                 )
@@ -452,6 +753,7 @@ impl StateSnapshotReaderV1 {
 
     pub fn ref_iter(&self, bucket_num: u32, part_num: u32) -> Result<ObjectRefIter> {
         let file_metadata = self
+            .target_layer()
             .ref_files
             .get(&bucket_num)
             .context(format!("No ref files found for bucket: {bucket_num}"))?
@@ -466,15 +768,22 @@ impl StateSnapshotReaderV1 {
         )
     }
 
+    /// The snapshot of the epoch being restored, which comes last in the chain.
+    fn target_layer(&self) -> &SnapshotLayer {
+        self.layers
+            .last()
+            .expect("Snapshot chain always includes the target epoch")
+    }
+
     fn buckets(&self) -> Result<Vec<u32>> {
-        Ok(self.ref_files.keys().copied().collect())
+        Ok(self.target_layer().ref_files.keys().copied().collect())
     }
 
     fn epoch_dir(&self) -> Path {
         Path::from(format!("epoch_{}", self.epoch))
     }
 
-    fn read_manifest(path: PathBuf) -> anyhow::Result<Manifest> {
+    pub(crate) fn read_manifest(path: PathBuf) -> anyhow::Result<Manifest> {
         let manifest_file = File::open(path)?;
         let manifest_file_size = manifest_file.metadata()?.len() as usize;
         let mut manifest_reader = BufReader::new(manifest_file);
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::reader::StateSnapshotReaderV1;
+use crate::reader::{download_snapshot_chain, live_object_refs, StateSnapshotReaderV1};
 use crate::writer::StateSnapshotWriterV1;
 use crate::FileCompression;
 use fastcrypto::hash::MultisetHash;
@@ -15,7 +15,7 @@ use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
 use sui_core::state_accumulator::StateAccumulator;
 use sui_protocol_config::ProtocolConfig;
 use sui_types::accumulator::Accumulator;
-use sui_types::base_types::ObjectID;
+use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
 use sui_types::messages_checkpoint::ECMHLiveObjectSetDigest;
 use sui_types::object::Object;
 use tempfile::tempdir;
@@ -38,6 +38,16 @@ pub fn insert_keys(
     Ok(())
 }
 
+fn insert_objects(
+    db: &AuthorityPerpetualTables,
+    objects: impl IntoIterator<Item = Object>,
+) -> Result<(), anyhow::Error> {
+    for object in objects {
+        db.insert_object_test_only(object)?;
+    }
+    Ok(())
+}
+
 fn compare_live_objects(
     db1: &AuthorityPerpetualTables,
     db2: &AuthorityPerpetualTables,
@@ -181,3 +191,140 @@ async fn test_snapshot_empty_db() -> Result<(), anyhow::Error> {
     )?;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_snapshot_delta() -> Result<(), anyhow::Error> {
+    let restored_db_path = temp_dir();
+    let local = temp_dir().join("local_dir");
+    let remote = temp_dir().join("remote_dir");
+    let restored_local = temp_dir().join("local_dir_restore");
+    let local_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(local),
+        ..Default::default()
+    };
+    let remote_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(remote),
+        ..Default::default()
+    };
+    let ids = ObjectID::in_range(ObjectID::ZERO, 1200)?;
+    let immutable = |range: std::ops::Range<usize>| {
+        ids[range]
+            .iter()
+            .map(|id| Object::immutable_with_id_for_testing(*id))
+            .collect::<Vec<_>>()
+    };
+    let mutated = |range: std::ops::Range<usize>, version: u64| {
+        ids[range]
+            .iter()
+            .map(|id| {
+                Object::with_id_owner_version_for_testing(
+                    *id,
+                    SequenceNumber::from_u64(version),
+                    SuiAddress::ZERO,
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Epoch 0: full snapshot of 1000 objects
+    let db_0 = Arc::new(AuthorityPerpetualTables::open(&temp_dir(), None));
+    insert_objects(&db_0, immutable(0..1000))?;
+    // Epoch 1: 50 objects deleted, 50 mutated and 100 added
+    let db_1 = Arc::new(AuthorityPerpetualTables::open(&temp_dir(), None));
+    insert_objects(&db_1, mutated(50..100, 5))?;
+    insert_objects(&db_1, immutable(100..1100))?;
+    // Epoch 2: some objects of epoch 0 and epoch 1 deleted or mutated again, and 100 added
+    let db_2 = Arc::new(AuthorityPerpetualTables::open(&temp_dir(), None));
+    insert_objects(&db_2, mutated(50..75, 7))?;
+    insert_objects(&db_2, mutated(75..100, 5))?;
+    insert_objects(&db_2, immutable(150..1000))?;
+    insert_objects(&db_2, immutable(1050..1200))?;
+
+    for (epoch, db) in [(0, &db_0), (1, &db_1), (2, &db_2)] {
+        let snapshot_writer = StateSnapshotWriterV1::new(
+            &local_store_config,
+            &remote_store_config,
+            FileCompression::Zstd,
+            NonZeroUsize::new(1).unwrap(),
+        )
+        .await?;
+        let root_accumulator =
+            ECMHLiveObjectSetDigest::from(accumulate_live_object_set(db, true).digest());
+        if epoch == 0 {
+            snapshot_writer
+                .write_internal(epoch, true, db.clone(), root_accumulator)
+                .await?;
+        } else {
+            snapshot_writer
+                .write_delta_internal(epoch, epoch - 1, true, db.clone(), root_accumulator)
+                .await?;
+        }
+    }
+
+    // The live object set at the end of the chain, merged from its ref and deleted object files
+    let merged_local = temp_dir().join("local_dir_merge");
+    let merged_store_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(merged_local.clone()),
+        ..Default::default()
+    };
+    let layers = download_snapshot_chain(
+        2,
+        &remote_store_config.make()?,
+        &merged_store_config.make()?,
+        &merged_local,
+        NonZeroUsize::new(1).unwrap(),
+        &MultiProgress::new(),
+    )
+    .await?;
+    assert_eq!(
+        layers.iter().map(|layer| layer.epoch).collect::<Vec<_>>(),
+        vec![0, 1, 2]
+    );
+    assert!(layers[1..]
+        .iter()
+        .all(|layer| layer.deleted_files.contains_key(&1)));
+    let merged_object_refs =
+        live_object_refs(&layers, &merged_local).collect::<anyhow::Result<Vec<_>>>()?;
+    let expected_object_refs: Vec<_> = db_2
+        .iter_live_object_set(true)
+        .map(|object| object.object_reference())
+        .collect();
+    assert_eq!(merged_object_refs, expected_object_refs);
+
+    let local_store_restore_config = ObjectStoreConfig {
+        object_store: Some(ObjectStoreType::File),
+        directory: Some(restored_local),
+        ..Default::default()
+    };
+    let mut snapshot_reader = StateSnapshotReaderV1::new(
+        2,
+        &remote_store_config,
+        &local_store_restore_config,
+        usize::MAX,
+        NonZeroUsize::new(1).unwrap(),
+        MultiProgress::new(),
+    )
+    .await?;
+    let restored_perpetual_db = AuthorityPerpetualTables::open(&restored_db_path, None);
+    let (_abort_handle, abort_registration) = AbortHandle::new_pair();
+    let (sender, mut receiver) = tokio::sync::mpsc::channel(100);
+    snapshot_reader
+        .read(&restored_perpetual_db, abort_registration, Some(sender))
+        .await?;
+    let mut restored_accumulator = Accumulator::default();
+    let mut num_live_objects = 0;
+    while let Some((partial_acc, num_objects)) = receiver.recv().await {
+        restored_accumulator.union(&partial_acc);
+        num_live_objects += num_objects;
+    }
+    assert_eq!(num_live_objects, 1050);
+    assert_eq!(
+        ECMHLiveObjectSetDigest::from(restored_accumulator.digest()),
+        ECMHLiveObjectSetDigest::from(accumulate_live_object_set(&db_2, true).digest())
+    );
+    compare_live_objects(&db_2, &restored_perpetual_db, true)?;
+    Ok(())
+}
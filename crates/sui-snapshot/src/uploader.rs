@@ -4,6 +4,7 @@
 use crate::writer::StateSnapshotWriterV1;
 use anyhow::Result;
 use bytes::Bytes;
+use object_store::path::Path;
 use object_store::DynObjectStore;
 use prometheus::{
     register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
@@ -18,7 +19,7 @@ use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
 use sui_core::checkpoints::CheckpointStore;
 use sui_core::db_checkpoint_handler::{STATE_SNAPSHOT_COMPLETED_MARKER, SUCCESS_MARKER};
 use sui_storage::object_store::util::{
-    find_all_dirs_with_epoch_prefix, find_missing_epochs_dirs, path_to_filesystem, put,
+    exists, find_all_dirs_with_epoch_prefix, find_missing_epochs_dirs, path_to_filesystem, put,
     run_manifest_update_loop,
 };
 use sui_storage::FileCompression;
@@ -63,6 +64,9 @@ pub struct StateSnapshotUploader {
     staging_store: Arc<DynObjectStore>,
     /// Remote store i.e. S3, GCS, etc where state snapshots are uploaded to
     snapshot_store: Arc<DynObjectStore>,
+    /// Number of epochs between full state snapshots, with delta snapshots against the last full
+    /// one in between. Every snapshot is a full one if not set
+    full_snapshot_interval_epochs: Option<u64>,
     /// Time interval to check for presence of new db checkpoint
     interval: Duration,
     metrics: Arc<StateSnapshotUploaderMetrics>,
@@ -73,6 +77,7 @@ impl StateSnapshotUploader {
         db_checkpoint_path: &std::path::Path,
         staging_path: &std::path::Path,
         snapshot_store_config: ObjectStoreConfig,
+        full_snapshot_interval_epochs: Option<u64>,
         interval_s: u64,
         registry: &Registry,
         checkpoint_store: Arc<CheckpointStore>,
@@ -94,6 +99,7 @@ impl StateSnapshotUploader {
             staging_path: staging_path.to_path_buf(),
             staging_store: staging_store_config.make()?,
             snapshot_store: snapshot_store_config.make()?,
+            full_snapshot_interval_epochs,
             interval: Duration::from_secs(interval_s),
             metrics: StateSnapshotUploaderMetrics::new(registry),
         }))
@@ -139,9 +145,19 @@ impl StateSnapshotUploader {
                     .last()
                     .expect("Expected at least one commitment")
                     .clone();
-                state_snapshot_writer
-                    .write(*epoch, db, state_hash_commitment)
-                    .await?;
+                if let Some(base_epoch) = self.delta_base_epoch(*epoch).await {
+                    info!(
+                        "Writing state snapshot for epoch: {} as a delta against epoch: {}",
+                        *epoch, base_epoch
+                    );
+                    state_snapshot_writer
+                        .write_delta(*epoch, base_epoch, db, state_hash_commitment)
+                        .await?;
+                } else {
+                    state_snapshot_writer
+                        .write(*epoch, db, state_hash_commitment)
+                        .await?;
+                }
                 info!("State snapshot creation successful for epoch: {}", *epoch);
                 // Drop marker in the output directory that upload completed successfully
                 let bytes = Bytes::from_static(b"success");
@@ -202,6 +218,23 @@ impl StateSnapshotUploader {
         Ok(())
     }
 
+    /// The epoch the state snapshot of `epoch` should be a delta against, if any. That is the last
+    /// epoch due a full snapshot, so that restoring a delta never takes more than two snapshots,
+    /// unless `epoch` is itself due a full snapshot, or the base epoch's snapshot is missing.
+    async fn delta_base_epoch(&self, epoch: u64) -> Option<u64> {
+        let interval = self
+            .full_snapshot_interval_epochs
+            .filter(|interval| *interval > 1)?;
+        if epoch % interval == 0 {
+            return None;
+        }
+        let base_epoch = epoch - epoch % interval;
+        let success_marker = Path::from(format!("epoch_{}", base_epoch)).child(SUCCESS_MARKER);
+        exists(&self.snapshot_store, &success_marker)
+            .await
+            .then_some(base_epoch)
+    }
+
     async fn get_missing_epochs(&self) -> Result<Vec<u64>> {
         let missing_epochs = find_missing_epochs_dirs(&self.snapshot_store, SUCCESS_MARKER).await?;
         Ok(missing_epochs.to_vec())
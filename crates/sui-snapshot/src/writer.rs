@@ -2,23 +2,26 @@
 // SPDX-License-Identifier: Apache-2.0
 #![allow(dead_code)]
 
+use crate::reader::{download_snapshot_chain, live_object_refs, SnapshotLayer};
 use crate::{
     compute_sha3_checksum, create_file_metadata, FileCompression, FileMetadata, FileType, Manifest,
-    ManifestV1, FILE_MAX_BYTES, MAGIC_BYTES, MANIFEST_FILE_MAGIC, OBJECT_FILE_MAGIC,
+    ManifestV1, ManifestV2, FILE_MAX_BYTES, MAGIC_BYTES, MANIFEST_FILE_MAGIC, OBJECT_FILE_MAGIC,
     OBJECT_REF_BYTES, REFERENCE_FILE_MAGIC, SEQUENCE_NUM_BYTES,
 };
 use anyhow::{anyhow, Context, Result};
 use byteorder::{BigEndian, ByteOrder};
 use fastcrypto::hash::MultisetHash;
 use futures::StreamExt;
+use indicatif::{MultiProgress, ProgressDrawTarget};
 use integer_encoding::VarInt;
 use object_store::path::Path;
 use object_store::DynObjectStore;
-use std::collections::hash_map::Entry::Vacant;
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::fs;
 use std::fs::{File, OpenOptions};
 use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::iter::Peekable;
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -40,7 +43,8 @@ use tokio::task::JoinHandle;
 use tokio_stream::wrappers::ReceiverStream;
 use tracing::debug;
 
-/// LiveObjectSetWriterV1 writes live object set. It creates multiple *.obj files and *.ref file
+/// LiveObjectSetWriterV1 writes live object set. It creates multiple *.obj files and *.ref file,
+/// and for a delta snapshot, *.del files
 struct LiveObjectSetWriterV1 {
     dir_path: PathBuf,
     bucket_num: u32,
@@ -48,6 +52,9 @@ struct LiveObjectSetWriterV1 {
     wbuf: BufWriter<File>,
     ref_wbuf: BufWriter<File>,
     n: usize,
+    current_deleted_part_num: u32,
+    deleted_wbuf: Option<BufWriter<File>>,
+    deleted_n: usize,
     files: Vec<FileMetadata>,
     sender: Option<Sender<FileMetadata>>,
     file_compression: FileCompression,
@@ -70,6 +77,9 @@ impl LiveObjectSetWriterV1 {
             wbuf: BufWriter::new(obj_file),
             ref_wbuf: BufWriter::new(ref_file),
             n,
+            current_deleted_part_num: 0,
+            deleted_wbuf: None,
+            deleted_n: 0,
             files: vec![],
             sender: Some(sender),
             file_compression,
@@ -81,9 +91,23 @@ impl LiveObjectSetWriterV1 {
         self.write_object_ref(&object_reference)?;
         Ok(())
     }
+    /// Records the deletion of an object since the base epoch of a delta snapshot.
+    pub fn delete(&mut self, object_ref: &ObjectRef) -> Result<()> {
+        if self.deleted_wbuf.is_none() || self.deleted_n + OBJECT_REF_BYTES > FILE_MAX_BYTES {
+            self.cut_deleted_file()?;
+        }
+        let deleted_wbuf = self
+            .deleted_wbuf
+            .as_mut()
+            .context("Unexpected missing deleted object file")?;
+        write_object_ref(deleted_wbuf, object_ref)?;
+        self.deleted_n += OBJECT_REF_BYTES;
+        Ok(())
+    }
     pub fn done(mut self) -> Result<Vec<FileMetadata>> {
         self.finalize()?;
         self.finalize_ref()?;
+        self.finalize_deleted()?;
         self.sender = None;
         Ok(self.files.clone())
     }
@@ -115,6 +139,20 @@ impl LiveObjectSetWriterV1 {
         f.seek(SeekFrom::Start(n as u64))?;
         Ok(f)
     }
+    fn deleted_file(dir_path: PathBuf, bucket_num: u32, part_num: u32) -> Result<(usize, File)> {
+        let deleted_path = dir_path.join(format!("{bucket_num}_{part_num}.del"));
+        let deleted_tmp_path = dir_path.join(format!("{bucket_num}_{part_num}.del.tmp"));
+        let mut f = File::create(deleted_tmp_path.clone())?;
+        f.rewind()?;
+        let mut metab = [0u8; MAGIC_BYTES];
+        BigEndian::write_u32(&mut metab, REFERENCE_FILE_MAGIC);
+        let n = f.write(&metab)?;
+        drop(f);
+        fs::rename(deleted_tmp_path, deleted_path.clone())?;
+        let mut f = OpenOptions::new().append(true).open(deleted_path)?;
+        f.seek(SeekFrom::Start(n as u64))?;
+        Ok((n, f))
+    }
     fn finalize(&mut self) -> Result<()> {
         self.wbuf.flush()?;
         self.wbuf.get_ref().sync_data()?;
@@ -157,6 +195,32 @@ impl LiveObjectSetWriterV1 {
         }
         Ok(())
     }
+    fn finalize_deleted(&mut self) -> Result<()> {
+        let Some(mut deleted_wbuf) = self.deleted_wbuf.take() else {
+            return Ok(());
+        };
+        deleted_wbuf.flush()?;
+        deleted_wbuf.get_ref().sync_data()?;
+        let off = deleted_wbuf.get_ref().stream_position()?;
+        deleted_wbuf.get_ref().set_len(off)?;
+        drop(deleted_wbuf);
+        let file_path = self.dir_path.join(format!(
+            "{}_{}.del",
+            self.bucket_num, self.current_deleted_part_num
+        ));
+        let file_metadata = create_file_metadata(
+            &file_path,
+            self.file_compression,
+            FileType::Deleted,
+            self.bucket_num,
+            self.current_deleted_part_num,
+        )?;
+        self.files.push(file_metadata.clone());
+        if let Some(sender) = &self.sender {
+            sender.blocking_send(file_metadata)?;
+        }
+        Ok(())
+    }
     fn cut(&mut self) -> Result<()> {
         self.finalize()?;
         let (n, f) = Self::object_file(
@@ -178,6 +242,18 @@ impl LiveObjectSetWriterV1 {
         self.ref_wbuf = BufWriter::new(f);
         Ok(())
     }
+    fn cut_deleted_file(&mut self) -> Result<()> {
+        self.finalize_deleted()?;
+        self.current_deleted_part_num += 1;
+        let (n, f) = Self::deleted_file(
+            self.dir_path.clone(),
+            self.bucket_num,
+            self.current_deleted_part_num,
+        )?;
+        self.deleted_n = n;
+        self.deleted_wbuf = Some(BufWriter::new(f));
+        Ok(())
+    }
     fn write_object(&mut self, object: &LiveObject) -> Result<()> {
         let blob = Blob::encode(object, BlobEncoding::Bcs)?;
         let mut blob_size = blob.data.len().required_space();
@@ -193,19 +269,37 @@ impl LiveObjectSetWriterV1 {
         Ok(())
     }
     fn write_object_ref(&mut self, object_ref: &ObjectRef) -> Result<()> {
-        let mut buf = [0u8; OBJECT_REF_BYTES];
-        buf[0..ObjectID::LENGTH].copy_from_slice(object_ref.0.as_ref());
-        BigEndian::write_u64(
-            &mut buf[ObjectID::LENGTH..OBJECT_REF_BYTES],
-            object_ref.1.value(),
-        );
-        buf[ObjectID::LENGTH + SEQUENCE_NUM_BYTES..OBJECT_REF_BYTES]
-            .copy_from_slice(object_ref.2.as_ref());
-        self.ref_wbuf.write_all(&buf)?;
-        Ok(())
+        write_object_ref(&mut self.ref_wbuf, object_ref)
     }
 }
 
+fn write_object_ref(writer: &mut impl Write, object_ref: &ObjectRef) -> Result<()> {
+    let mut buf = [0u8; OBJECT_REF_BYTES];
+    buf[0..ObjectID::LENGTH].copy_from_slice(object_ref.0.as_ref());
+    BigEndian::write_u64(
+        &mut buf[ObjectID::LENGTH..OBJECT_REF_BYTES],
+        object_ref.1.value(),
+    );
+    buf[ObjectID::LENGTH + SEQUENCE_NUM_BYTES..OBJECT_REF_BYTES]
+        .copy_from_slice(object_ref.2.as_ref());
+    writer.write_all(&buf)?;
+    Ok(())
+}
+
+/// Pops the next reference of the base live object set if its object id is at most `object_id`, or
+/// if `object_id` is `None`.
+fn next_base_object_ref(
+    base_object_refs: &mut Peekable<impl Iterator<Item = Result<ObjectRef>>>,
+    object_id: Option<&ObjectID>,
+) -> Result<Option<ObjectRef>> {
+    base_object_refs
+        .next_if(|base_object_ref| match (base_object_ref, object_id) {
+            (Ok(base_object_ref), Some(object_id)) => base_object_ref.0 <= *object_id,
+            _ => true,
+        })
+        .transpose()
+}
+
 /// StateSnapshotWriterV1 writes snapshot files to a local staging dir and simultaneously uploads them
 /// to a remote object store
 pub struct StateSnapshotWriterV1 {
@@ -261,7 +355,38 @@ impl StateSnapshotWriterV1 {
         perpetual_db: Arc<AuthorityPerpetualTables>,
         root_state_hash: ECMHLiveObjectSetDigest,
     ) -> Result<()> {
-        let system_state_object = get_sui_system_state(&perpetual_db)?;
+        let include_wrapped_tombstone = Self::include_wrapped_tombstone(&perpetual_db)?;
+        self.write_internal(
+            epoch,
+            include_wrapped_tombstone,
+            perpetual_db,
+            root_state_hash,
+        )
+        .await
+    }
+
+    /// Writes the snapshot of `epoch` as a delta against the snapshot of `base_epoch`, which must
+    /// already be in the remote store, holding only the objects added, mutated or deleted since.
+    pub async fn write_delta(
+        self,
+        epoch: u64,
+        base_epoch: u64,
+        perpetual_db: Arc<AuthorityPerpetualTables>,
+        root_state_hash: ECMHLiveObjectSetDigest,
+    ) -> Result<()> {
+        let include_wrapped_tombstone = Self::include_wrapped_tombstone(&perpetual_db)?;
+        self.write_delta_internal(
+            epoch,
+            base_epoch,
+            include_wrapped_tombstone,
+            perpetual_db,
+            root_state_hash,
+        )
+        .await
+    }
+
+    fn include_wrapped_tombstone(perpetual_db: &AuthorityPerpetualTables) -> Result<bool> {
+        let system_state_object = get_sui_system_state(perpetual_db)?;
 
         let protocol_version = system_state_object.protocol_version();
         let chain_identifier = CHAIN_IDENTIFIER
@@ -271,9 +396,21 @@ impl StateSnapshotWriterV1 {
             ProtocolVersion::new(protocol_version),
             chain_identifier.chain(),
         );
-        let include_wrapped_tombstone = !protocol_config.simplified_unwrap_then_delete();
-        self.write_internal(
+        Ok(!protocol_config.simplified_unwrap_then_delete())
+    }
+
+    /// Like `write`, but with whether wrapped object tombstones are part of the live object set
+    /// given, rather than derived from the protocol config of the epoch.
+    pub async fn write_internal(
+        self,
+        epoch: u64,
+        include_wrapped_tombstone: bool,
+        perpetual_db: Arc<AuthorityPerpetualTables>,
+        root_state_hash: ECMHLiveObjectSetDigest,
+    ) -> Result<()> {
+        self.write_snapshot(
             epoch,
+            None,
             include_wrapped_tombstone,
             perpetual_db,
             root_state_hash,
@@ -281,9 +418,46 @@ impl StateSnapshotWriterV1 {
         .await
     }
 
-    pub(crate) async fn write_internal(
+    pub(crate) async fn write_delta_internal(
+        self,
+        epoch: u64,
+        base_epoch: u64,
+        include_wrapped_tombstone: bool,
+        perpetual_db: Arc<AuthorityPerpetualTables>,
+        root_state_hash: ECMHLiveObjectSetDigest,
+    ) -> Result<()> {
+        if base_epoch >= epoch {
+            return Err(anyhow!(
+                "Base epoch: {} of delta snapshot is not before epoch: {}",
+                base_epoch,
+                epoch
+            ));
+        }
+        let base_layers = self.download_base_refs(base_epoch).await.context(format!(
+            "Failed to read base state snapshot for epoch: {}",
+            &base_epoch
+        ))?;
+        let local_staging_dir = self.local_staging_dir.clone();
+        let base_epoch_dirs: Vec<_> = base_layers.iter().map(|layer| layer.epoch_dir()).collect();
+        let result = self
+            .write_snapshot(
+                epoch,
+                Some((base_epoch, base_layers)),
+                include_wrapped_tombstone,
+                perpetual_db,
+                root_state_hash,
+            )
+            .await;
+        for epoch_dir in base_epoch_dirs {
+            fs::remove_dir_all(path_to_filesystem(local_staging_dir.clone(), &epoch_dir)?)?;
+        }
+        result
+    }
+
+    async fn write_snapshot(
         mut self,
         epoch: u64,
+        base: Option<(u64, Vec<SnapshotLayer>)>,
         include_wrapped_tombstone: bool,
         perpetual_db: Arc<AuthorityPerpetualTables>,
         root_state_hash: ECMHLiveObjectSetDigest,
@@ -300,6 +474,7 @@ impl StateSnapshotWriterV1 {
         let write_handler = tokio::task::spawn_blocking(move || {
             self.write_live_object_set(
                 epoch,
+                base,
                 perpetual_db,
                 sender,
                 Self::bucket_func,
@@ -366,9 +541,13 @@ impl StateSnapshotWriterV1 {
         Ok(join_handle)
     }
 
+    /// Writes the live object set, or if `base` is given, only the part of it which differs from
+    /// the live object set at the end of the chain of snapshots of the base epoch. Both are
+    /// iterated in object id order, so that they can be merged without holding either in memory.
     fn write_live_object_set<F>(
         &mut self,
         epoch: u64,
+        base: Option<(u64, Vec<SnapshotLayer>)>,
         perpetual_db: Arc<AuthorityPerpetualTables>,
         sender: Sender<FileMetadata>,
         bucket_func: F,
@@ -376,27 +555,58 @@ impl StateSnapshotWriterV1 {
         root_state_hash: ECMHLiveObjectSetDigest,
     ) -> Result<()>
     where
-        F: Fn(&LiveObject) -> u32,
+        F: Fn(&ObjectID) -> u32,
     {
         let mut object_writers: HashMap<u32, LiveObjectSetWriterV1> = HashMap::new();
         let local_staging_dir_path =
             path_to_filesystem(self.local_staging_dir.clone(), &self.epoch_dir(epoch))?;
+        let (base_epoch, base_object_refs) = match &base {
+            Some((base_epoch, base_layers)) => (
+                Some(*base_epoch),
+                Some(live_object_refs(base_layers, &self.local_staging_dir)),
+            ),
+            None => (None, None),
+        };
+        let mut base_object_refs = base_object_refs.into_iter().flatten().peekable();
         let mut acc = Accumulator::default();
         for object in perpetual_db.iter_live_object_set(include_wrapped_tombstone) {
             StateAccumulator::accumulate_live_object(&mut acc, &object);
-            let bucket_num = bucket_func(&object);
-            if let Vacant(entry) = object_writers.entry(bucket_num) {
-                entry.insert(LiveObjectSetWriterV1::new(
-                    local_staging_dir_path.clone(),
-                    bucket_num,
-                    self.file_compression,
-                    sender.clone(),
-                )?);
+            let object_ref = object.object_reference();
+            let mut unchanged = false;
+            while let Some(base_object_ref) =
+                next_base_object_ref(&mut base_object_refs, Some(&object_ref.0))?
+            {
+                if base_object_ref.0 == object_ref.0 {
+                    unchanged = base_object_ref == object_ref;
+                } else {
+                    // Base objects which precede the next live object were deleted since
+                    self.bucket_writer(
+                        &mut object_writers,
+                        bucket_func(&base_object_ref.0),
+                        &local_staging_dir_path,
+                        &sender,
+                    )?
+                    .delete(&base_object_ref)?;
+                }
+            }
+            if !unchanged {
+                self.bucket_writer(
+                    &mut object_writers,
+                    bucket_func(&object_ref.0),
+                    &local_staging_dir_path,
+                    &sender,
+                )?
+                .write(&object)?;
             }
-            let writer = object_writers
-                .get_mut(&bucket_num)
-                .context("Unexpected missing bucket writer")?;
-            writer.write(&object)?;
+        }
+        while let Some(base_object_ref) = next_base_object_ref(&mut base_object_refs, None)? {
+            self.bucket_writer(
+                &mut object_writers,
+                bucket_func(&base_object_ref.0),
+                &local_staging_dir_path,
+                &sender,
+            )?
+            .delete(&base_object_ref)?;
         }
         assert_eq!(
             ECMHLiveObjectSetDigest::from(acc.digest()),
@@ -407,19 +617,51 @@ impl StateSnapshotWriterV1 {
         for (_, writer) in object_writers.into_iter() {
             files.extend(writer.done()?);
         }
-        self.write_manifest(epoch, files)?;
+        self.write_manifest(epoch, base_epoch, files)?;
         Ok(())
     }
 
-    fn write_manifest(&mut self, epoch: u64, file_metadata: Vec<FileMetadata>) -> Result<()> {
+    fn bucket_writer<'a>(
+        &self,
+        object_writers: &'a mut HashMap<u32, LiveObjectSetWriterV1>,
+        bucket_num: u32,
+        dir_path: &std::path::Path,
+        sender: &Sender<FileMetadata>,
+    ) -> Result<&'a mut LiveObjectSetWriterV1> {
+        match object_writers.entry(bucket_num) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => Ok(entry.insert(LiveObjectSetWriterV1::new(
+                dir_path.to_path_buf(),
+                bucket_num,
+                self.file_compression,
+                sender.clone(),
+            )?)),
+        }
+    }
+
+    fn write_manifest(
+        &mut self,
+        epoch: u64,
+        base_epoch: Option<u64>,
+        file_metadata: Vec<FileMetadata>,
+    ) -> Result<()> {
         let (f, manifest_file_path) = self.manifest_file(epoch)?;
         let mut wbuf = BufWriter::new(f);
-        let manifest: Manifest = Manifest::V1(ManifestV1 {
-            snapshot_version: 1,
-            address_length: ObjectID::LENGTH as u64,
-            file_metadata,
-            epoch,
-        });
+        let manifest: Manifest = match base_epoch {
+            None => Manifest::V1(ManifestV1 {
+                snapshot_version: 1,
+                address_length: ObjectID::LENGTH as u64,
+                file_metadata,
+                epoch,
+            }),
+            Some(_) => Manifest::V2(ManifestV2 {
+                snapshot_version: 1,
+                address_length: ObjectID::LENGTH as u64,
+                file_metadata,
+                epoch,
+                base_epoch,
+            }),
+        };
         let serialized_manifest = bcs::to_bytes(&manifest)?;
         wbuf.write_all(&serialized_manifest)?;
         wbuf.flush()?;
@@ -456,7 +698,21 @@ impl StateSnapshotWriterV1 {
         Ok((f, manifest_file_path))
     }
 
-    fn bucket_func(_object: &LiveObject) -> u32 {
+    /// Downloads the reference and deleted object files of the snapshot of `base_epoch`, along with
+    /// those of the snapshots it is based on, to the local staging dir.
+    async fn download_base_refs(&self, base_epoch: u64) -> Result<Vec<SnapshotLayer>> {
+        download_snapshot_chain(
+            base_epoch,
+            &self.remote_object_store,
+            &self.local_staging_store,
+            &self.local_staging_dir,
+            NonZeroUsize::new(self.concurrency).unwrap(),
+            &MultiProgress::with_draw_target(ProgressDrawTarget::hidden()),
+        )
+        .await
+    }
+
+    fn bucket_func(_object_id: &ObjectID) -> u32 {
         // TODO: Use the hash bucketing function used for accumulator tree if there is one
         1u32
     }
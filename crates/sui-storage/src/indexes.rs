@@ -13,9 +13,10 @@ use std::sync::Arc;
 use itertools::Itertools;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
+use parking_lot::Mutex;
 use prometheus::{register_int_counter_with_registry, IntCounter, Registry};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use tokio::sync::OwnedMutexGuard;
+use tokio::sync::{watch, OwnedMutexGuard};
 use typed_store::TypedStoreError;
 
 use crate::mutex_table::MutexTable;
@@ -29,13 +30,15 @@ use sui_types::digests::TransactionEventsDigest;
 use sui_types::dynamic_field::{self, DynamicFieldInfo};
 use sui_types::effects::TransactionEvents;
 use sui_types::error::{SuiError, SuiResult, UserInputError};
+use sui_types::full_checkpoint_content::{CheckpointData, CheckpointTransaction};
 use sui_types::inner_temporary_store::TxCoins;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::{Object, Owner};
 use sui_types::parse_sui_struct_tag;
 use tokio::task::spawn_blocking;
-use tracing::{debug, trace};
+use tracing::{debug, error, info, trace, warn};
 use typed_store::rocks::{
-    default_db_options, read_size_from_env, DBBatch, DBMap, DBOptions, MetricConf,
+    be_fix_int_ser, default_db_options, read_size_from_env, DBBatch, DBMap, DBOptions, MetricConf,
 };
 use typed_store::traits::Map;
 use typed_store::traits::{TableSummary, TypedStoreDebug};
@@ -43,6 +46,7 @@ use typed_store_derive::DBMapUtils;
 
 type OwnerIndexKey = (SuiAddress, ObjectID);
 type CoinIndexKey = (SuiAddress, String, ObjectID);
+type BalanceHistoryKey = (SuiAddress, String, CheckpointSequenceNumber);
 type DynamicFieldKey = (ObjectID, ObjectID);
 type EventId = (TxSequenceNumber, usize);
 type EventIndex = (TransactionEventsDigest, TransactionDigest, u64);
@@ -54,8 +58,9 @@ pub const MAX_GET_OWNED_OBJECT_SIZE: usize = 256;
 const ENV_VAR_COIN_INDEX_BLOCK_CACHE_SIZE_MB: &str = "COIN_INDEX_BLOCK_CACHE_MB";
 const ENV_VAR_DISABLE_INDEX_CACHE: &str = "DISABLE_INDEX_CACHE";
 const ENV_VAR_INVALIDATE_INSTEAD_OF_UPDATE: &str = "INVALIDATE_INSTEAD_OF_UPDATE";
+const BALANCE_HISTORY_SEED_BATCH_SIZE: usize = 10_000;
 
-#[derive(Default, Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Default, Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct TotalBalance {
     pub balance: i128,
     pub num_coins: i64,
@@ -214,6 +219,26 @@ pub struct IndexStoreTables {
     event_by_sender: DBMap<(SuiAddress, EventId), EventIndex>,
     #[default_options_override_fn = "index_table_default_config"]
     event_by_time: DBMap<(u64, EventId), EventIndex>,
+
+    /// Total balance and coin count of an owner in a coin type as of the end of every checkpoint
+    /// that changed them, and of the checkpoint before the index was enabled. Only populated when
+    /// the balance history index is enabled.
+    #[default_options_override_fn = "index_table_default_config"]
+    balance_history: DBMap<BalanceHistoryKey, TotalBalance>,
+
+    /// Changes to the total balance and coin count of an owner in a coin type in every checkpoint
+    /// indexed before its balance was seeded into `balance_history`.
+    #[default_options_override_fn = "index_table_default_config"]
+    balance_history_pending: DBMap<BalanceHistoryKey, TotalBalance>,
+
+    /// The first checkpoint indexed into `balance_history`.
+    balance_history_start: DBMap<(), CheckpointSequenceNumber>,
+
+    /// How `balance_history` was seeded, once it is.
+    balance_history_seed: DBMap<(), BalanceHistorySeed>,
+
+    /// The last checkpoint indexed into `balance_history`.
+    balance_history_indexed: DBMap<(), CheckpointSequenceNumber>,
 }
 
 impl IndexStoreTables {
@@ -228,11 +253,53 @@ impl IndexStoreTables {
 
 pub struct IndexStore {
     next_sequence_number: AtomicU64,
-    tables: IndexStoreTables,
+    tables: Arc<IndexStoreTables>,
     caches: IndexStoreCaches,
     metrics: Arc<IndexStoreMetrics>,
     max_type_length: u64,
     remove_deprecated_tables: bool,
+    balance_history: Option<BalanceHistory>,
+}
+
+/// Where the node is in the checkpoint stream when the index store is opened, to start the
+/// balance history index from.
+#[derive(Copy, Clone, Debug)]
+pub struct BalanceHistoryStart {
+    /// The next checkpoint the node will execute.
+    pub next_checkpoint: CheckpointSequenceNumber,
+    /// The highest checkpoint the node has synced. It may have executed the transactions of the
+    /// checkpoints up to it ahead of their checkpoint.
+    pub highest_synced_checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+struct BalanceHistory {
+    /// The first checkpoint indexed, balances are known from the end of the checkpoint before it.
+    start: CheckpointSequenceNumber,
+    /// The next checkpoint to index. Balances are indexed cumulatively, so checkpoints, which can
+    /// be finalized concurrently, are indexed in order.
+    next: watch::Sender<CheckpointSequenceNumber>,
+    seed: BalanceHistorySeed,
+    /// Seeding runs in the background, and is shared with it.
+    progress: Arc<Mutex<SeedProgress>>,
+}
+
+/// The coin index the balance history index is seeded from, as of the end of the checkpoint
+/// before the first indexed one.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct BalanceHistorySeed {
+    /// Transactions indexed before this sequence number are part of the seed, so their balance
+    /// changes are not indexed again with their checkpoint.
+    next_tx_sequence: TxSequenceNumber,
+    /// The first checkpoint whose balances are known. The seed includes transactions the node
+    /// executed ahead of their checkpoint, which can be any synced one.
+    first_checkpoint: CheckpointSequenceNumber,
+}
+
+enum SeedProgress {
+    /// Balances of owners and coin types up to, and including, this one are seeded. Changes to
+    /// later ones go to `balance_history_pending` until they are.
+    Seeding(Option<(SuiAddress, String)>),
+    Done,
 }
 
 // These functions are used to initialize the DB tables
@@ -272,18 +339,23 @@ fn coin_index_table_default_config() -> DBOptions {
 }
 
 impl IndexStore {
+    /// Opens the index store at `path`. The balance history index is only maintained if
+    /// `balance_history` is set, and is seeded from the coin index in the background if needed.
     pub fn new(
         path: PathBuf,
         registry: &Registry,
         max_type_length: Option<u64>,
         remove_deprecated_tables: bool,
-    ) -> Self {
-        let tables = IndexStoreTables::open_tables_read_write_with_deprecation_option(
-            path,
-            MetricConf::new("index"),
-            None,
-            None,
-            remove_deprecated_tables,
+        balance_history: Option<BalanceHistoryStart>,
+    ) -> SuiResult<Self> {
+        let tables = Arc::new(
+            IndexStoreTables::open_tables_read_write_with_deprecation_option(
+                path.clone(),
+                MetricConf::new("index"),
+                None,
+                None,
+                remove_deprecated_tables,
+            ),
         );
         let metrics = IndexStoreMetrics::new(registry);
         let caches = IndexStoreCaches {
@@ -297,17 +369,247 @@ impl IndexStore {
             .skip_to_last()
             .next()
             .map(|(seq, _)| seq + 1)
-            .unwrap_or(0)
-            .into();
+            .unwrap_or(0);
+        let balance_history = balance_history
+            .map(|start| {
+                Self::open_balance_history(
+                    &tables,
+                    &path.with_extension("balance_history_seed"),
+                    start,
+                    next_sequence_number,
+                )
+            })
+            .transpose()?;
 
-        Self {
+        Ok(Self {
             tables,
-            next_sequence_number,
+            next_sequence_number: next_sequence_number.into(),
             caches,
             metrics: Arc::new(metrics),
             max_type_length: max_type_length.unwrap_or(128),
             remove_deprecated_tables,
+            balance_history,
+        })
+    }
+
+    /// Opens the balance history index, restarting it at the next checkpoint if it was never
+    /// enabled, was not done seeding, or if checkpoints were executed while it was disabled.
+    fn open_balance_history(
+        tables: &Arc<IndexStoreTables>,
+        seed_path: &Path,
+        start: BalanceHistoryStart,
+        next_tx_sequence: TxSequenceNumber,
+    ) -> SuiResult<BalanceHistory> {
+        let next = start.next_checkpoint;
+        if let (Some(start), Some(seed)) = (
+            tables.balance_history_start.get(&())?,
+            tables.balance_history_seed.get(&())?,
+        ) {
+            let indexed = tables.balance_history_indexed.get(&())?;
+            let next_indexed = indexed.map_or(start, |indexed| indexed + 1);
+            if next_indexed >= next {
+                return Ok(BalanceHistory {
+                    start,
+                    next: watch::channel(next).0,
+                    seed,
+                    progress: Arc::new(Mutex::new(SeedProgress::Done)),
+                });
+            }
+            warn!(
+                "Balance history index is missing checkpoints {next_indexed} to {}, restarting it \
+                 from checkpoint {next}",
+                next - 1,
+            );
+        }
+
+        tables.balance_history.unsafe_clear()?;
+        tables.balance_history_pending.unsafe_clear()?;
+        let mut batch = tables.balance_history_start.batch();
+        batch.insert_batch(&tables.balance_history_start, [((), next)])?;
+        batch.delete_batch(&tables.balance_history_seed, [()])?;
+        batch.delete_batch(&tables.balance_history_indexed, [()])?;
+        batch.write()?;
+
+        let Some(seed_checkpoint) = next.checked_sub(1) else {
+            // Nothing was executed, so there is nothing to seed.
+            let seed = BalanceHistorySeed {
+                next_tx_sequence: 0,
+                first_checkpoint: 0,
+            };
+            tables.balance_history_seed.insert(&(), &seed)?;
+            return Ok(BalanceHistory {
+                start: next,
+                next: watch::channel(next).0,
+                seed,
+                progress: Arc::new(Mutex::new(SeedProgress::Done)),
+            });
+        };
+
+        // Seed from a copy of the coin index as of now, whose transactions are the ones indexed
+        // so far, while later ones are indexed with their checkpoint.
+        if seed_path.exists() {
+            std::fs::remove_dir_all(seed_path).map_err(|e| SuiError::FileIOError(e.to_string()))?;
+        }
+        tables.coin_index.checkpoint_db(seed_path)?;
+        let seed = BalanceHistorySeed {
+            next_tx_sequence,
+            first_checkpoint: max(
+                seed_checkpoint,
+                start.highest_synced_checkpoint.unwrap_or(0),
+            ),
+        };
+        let progress = Arc::new(Mutex::new(SeedProgress::Seeding(None)));
+
+        let (tables, seed_path, seeding) = (tables.clone(), seed_path.to_owned(), progress.clone());
+        std::thread::Builder::new()
+            .name("balance-history-seed".to_string())
+            .spawn(move || {
+                match Self::seed_balance_history(
+                    &tables,
+                    &seed_path,
+                    seed_checkpoint,
+                    seed,
+                    &seeding,
+                ) {
+                    Ok(()) => info!("Seeded balance history index at checkpoint {seed_checkpoint}"),
+                    Err(e) => error!("Failed to seed balance history index: {e}"),
+                }
+            })
+            .map_err(|e| SuiError::FileIOError(e.to_string()))?;
+
+        Ok(BalanceHistory {
+            start: next,
+            next: watch::channel(next).0,
+            seed,
+            progress,
+        })
+    }
+
+    /// Seeds the balance history index with the balances in the copy of the coin index at
+    /// `seed_path`, as of the end of `seed_checkpoint`, folding in the changes of the checkpoints
+    /// indexed meanwhile.
+    fn seed_balance_history(
+        tables: &IndexStoreTables,
+        seed_path: &Path,
+        seed_checkpoint: CheckpointSequenceNumber,
+        seed: BalanceHistorySeed,
+        progress: &Mutex<SeedProgress>,
+    ) -> SuiResult {
+        info!("Seeding balance history index at checkpoint {seed_checkpoint}");
+        let copy = IndexStoreTables::open_tables_read_write(
+            seed_path.to_owned(),
+            MetricConf::new("index_seed"),
+            None,
+            None,
+        );
+        let balances = copy
+            .coin_index
+            .unbounded_iter()
+            .group_by(|((owner, coin_type, _), _)| (*owner, coin_type.clone()));
+        for chunk in &balances
+            .into_iter()
+            .map(|(group, coins)| {
+                let mut balance = TotalBalance::default();
+                for (_, coin) in coins {
+                    balance.balance += coin.balance as i128;
+                    balance.num_coins += 1;
+                }
+                (group, balance)
+            })
+            .chunks(BALANCE_HISTORY_SEED_BATCH_SIZE)
+        {
+            Self::fold_balance_history_seed(
+                tables,
+                seed_checkpoint,
+                chunk.collect(),
+                None,
+                progress,
+            )?;
+        }
+        Self::fold_balance_history_seed(tables, seed_checkpoint, vec![], Some(seed), progress)?;
+
+        drop(copy);
+        std::fs::remove_dir_all(seed_path).map_err(|e| SuiError::FileIOError(e.to_string()))
+    }
+
+    /// Writes the seed balances of a chunk of owners and coin types, following the last seeded
+    /// ones, along with the balances of every checkpoint indexed meanwhile. The last chunk is
+    /// followed by the `seed` record, and covers every owner and coin type after it.
+    fn fold_balance_history_seed(
+        tables: &IndexStoreTables,
+        seed_checkpoint: CheckpointSequenceNumber,
+        seeds: Vec<((SuiAddress, String), TotalBalance)>,
+        seed: Option<BalanceHistorySeed>,
+        progress: &Mutex<SeedProgress>,
+    ) -> SuiResult {
+        let last = match seed {
+            Some(_) => None,
+            None => seeds.last().map(|(group, _)| group.clone()),
+        };
+        let last_key = last
+            .as_ref()
+            .map(|(owner, coin_type)| be_fix_int_ser(&(owner, coin_type)))
+            .transpose()?;
+
+        // Checkpoints are indexed with the lock held, so none is missed.
+        let mut progress = progress.lock();
+        let SeedProgress::Seeding(seeded) = &*progress else {
+            return Ok(());
+        };
+        let pending = match seeded {
+            Some((owner, coin_type)) => tables
+                .balance_history_pending
+                .unbounded_iter()
+                .skip_to(&(*owner, coin_type.clone(), CheckpointSequenceNumber::MAX))?,
+            None => tables.balance_history_pending.unbounded_iter(),
+        };
+        let mut pending_keys = vec![];
+        let mut changes: HashMap<(SuiAddress, String), Vec<_>> = HashMap::new();
+        for ((owner, coin_type, checkpoint), change) in pending {
+            if let Some(last_key) = &last_key {
+                if be_fix_int_ser(&(owner, &coin_type))? > *last_key {
+                    break;
+                }
+            }
+            pending_keys.push((owner, coin_type.clone(), checkpoint));
+            changes
+                .entry((owner, coin_type))
+                .or_default()
+                .push((checkpoint, change));
+        }
+
+        let mut balances = vec![];
+        let mut seeds: HashMap<_, _> = seeds.into_iter().collect();
+        for ((owner, coin_type), changes) in changes {
+            let seeded = seeds.remove(&(owner, coin_type.clone()));
+            if let Some(seeded) = seeded {
+                balances.push(((owner, coin_type.clone(), seed_checkpoint), seeded));
+            }
+            let mut balance = seeded.unwrap_or_default();
+            for (checkpoint, change) in changes {
+                balance.balance += change.balance;
+                balance.num_coins += change.num_coins;
+                balances.push(((owner, coin_type.clone(), checkpoint), balance));
+            }
+        }
+        balances.extend(
+            seeds.into_iter().map(|((owner, coin_type), balance)| {
+                ((owner, coin_type, seed_checkpoint), balance)
+            }),
+        );
+
+        let mut batch = tables.balance_history.batch();
+        batch.insert_batch(&tables.balance_history, balances)?;
+        batch.delete_batch(&tables.balance_history_pending, pending_keys)?;
+        if let Some(seed) = seed {
+            batch.insert_batch(&tables.balance_history_seed, [((), seed)])?;
         }
+        batch.write()?;
+        *progress = match seed {
+            Some(_) => SeedProgress::Done,
+            None => SeedProgress::Seeding(last),
+        };
+        Ok(())
     }
 
     pub fn tables(&self) -> &IndexStoreTables {
@@ -1430,6 +1732,210 @@ impl IndexStore {
             .await
     }
 
+    pub fn is_balance_history_enabled(&self) -> bool {
+        self.balance_history.is_some()
+    }
+
+    /// Records the balances of every owner and coin type changed by the transactions of a
+    /// checkpoint, once the checkpoints before it are indexed. Indexing a checkpoint again is a
+    /// no-op.
+    pub async fn index_balance_history(&self, checkpoint_data: &CheckpointData) -> SuiResult {
+        let Some(history) = &self.balance_history else {
+            return Ok(());
+        };
+        let checkpoint = checkpoint_data.checkpoint_summary.sequence_number;
+        if checkpoint < history.start {
+            return Ok(());
+        }
+        history
+            .next
+            .subscribe()
+            .wait_for(|next| *next >= checkpoint)
+            .await
+            .expect("Balance history sender is owned by the index store");
+
+        self.index_balance_changes(checkpoint, &checkpoint_data.transactions)?;
+        history.next.send_if_modified(|next| {
+            let modified = *next <= checkpoint;
+            *next = max(*next, checkpoint + 1);
+            modified
+        });
+        Ok(())
+    }
+
+    fn index_balance_changes(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+        transactions: &[CheckpointTransaction],
+    ) -> SuiResult {
+        let Some(history) = &self.balance_history else {
+            return Ok(());
+        };
+        // Transactions indexed before the seed was taken are already part of it.
+        let sequence_numbers = self
+            .tables
+            .transactions_seq
+            .multi_get(transactions.iter().map(|tx| *tx.transaction.digest()))?;
+
+        let mut changes: HashMap<(SuiAddress, String), TotalBalance> = HashMap::new();
+        for (transaction, sequence) in transactions.iter().zip(sequence_numbers) {
+            if sequence.is_some_and(|sequence| sequence < history.seed.next_tx_sequence) {
+                continue;
+            }
+            // Input objects hold the coins before the transaction modified, wrapped or deleted
+            // them, and output objects the coins after it created, modified or unwrapped them.
+            let coins = transaction
+                .input_objects
+                .iter()
+                .map(|object| (object, -1))
+                .chain(transaction.output_objects.iter().map(|object| (object, 1)));
+            for (object, sign) in coins {
+                let Owner::AddressOwner(owner) = object.owner else {
+                    continue;
+                };
+                let (Some(coin_type), Some(coin)) =
+                    (object.coin_type_maybe(), object.as_coin_maybe())
+                else {
+                    continue;
+                };
+                let change = changes.entry((owner, coin_type.to_string())).or_default();
+                change.balance += sign as i128 * coin.value() as i128;
+                change.num_coins += sign;
+            }
+        }
+
+        // Owners and coin types that are not seeded yet get their changes folded in once they are.
+        let progress = history.progress.lock();
+        let seeding = match &*progress {
+            SeedProgress::Seeding(seeded) => Some(
+                seeded
+                    .as_ref()
+                    .map(|(owner, coin_type)| be_fix_int_ser(&(owner, coin_type)))
+                    .transpose()?,
+            ),
+            SeedProgress::Done => None,
+        };
+        let mut balances = vec![];
+        let mut pending = vec![];
+        for ((owner, coin_type), change) in changes {
+            if change == TotalBalance::default() {
+                continue;
+            }
+            if let Some(seeded) = &seeding {
+                let key = be_fix_int_ser(&(owner, &coin_type))?;
+                if seeded.as_ref().map_or(true, |seeded| key > *seeded) {
+                    pending.push(((owner, coin_type, checkpoint), change));
+                    continue;
+                }
+            }
+            // Balances as of the previous checkpoints, rather than the ones this checkpoint may
+            // have been indexed with before.
+            let mut balance = match checkpoint.checked_sub(1) {
+                Some(previous) => self
+                    .balance_at(owner, &coin_type, previous)?
+                    .unwrap_or_default(),
+                None => TotalBalance::default(),
+            };
+            balance.balance += change.balance;
+            balance.num_coins += change.num_coins;
+            balances.push(((owner, coin_type, checkpoint), balance));
+        }
+
+        let mut batch = self.tables.balance_history.batch();
+        batch.insert_batch(&self.tables.balance_history, balances)?;
+        batch.insert_batch(&self.tables.balance_history_pending, pending)?;
+        batch.insert_batch(&self.tables.balance_history_indexed, [((), checkpoint)])?;
+        batch.write()?;
+        Ok(())
+    }
+
+    /// Returns the balance of `owner` in `coin_type` as of the end of `checkpoint`, from the
+    /// balance history index.
+    pub fn get_balance_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiResult<TotalBalance> {
+        let first = self.first_balance_history_checkpoint()?;
+        if checkpoint < first {
+            return Err(UserInputError::Unsupported(format!(
+                "Balances before checkpoint {first} are not indexed on this node"
+            ))
+            .into());
+        }
+        Ok(self
+            .balance_at(owner, &coin_type.to_string(), checkpoint)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the balance of `owner` in `coin_type` as of the end of every checkpoint that changed
+    /// it, after the `cursor` checkpoint if any, in ascending checkpoint order. The first entry is
+    /// the balance as of the first indexed checkpoint. At most `limit` entries are returned.
+    pub fn get_balance_history(
+        &self,
+        owner: SuiAddress,
+        coin_type: TypeTag,
+        cursor: Option<CheckpointSequenceNumber>,
+        limit: usize,
+    ) -> SuiResult<Vec<(CheckpointSequenceNumber, TotalBalance)>> {
+        let first = self.first_balance_history_checkpoint()?;
+        let coin_type = coin_type.to_string();
+        let mut history = vec![];
+        if cursor.map_or(true, |cursor| cursor < first) {
+            if let Some(balance) = self.balance_at(owner, &coin_type, first)? {
+                history.push((first, balance));
+            }
+        }
+        let from = cursor.map_or(first, |cursor| max(first, cursor)) + 1;
+        history.extend(
+            self.tables
+                .balance_history
+                .unbounded_iter()
+                .skip_to(&(owner, coin_type.clone(), from))?
+                .take_while(|((addr, type_, _), _)| *addr == owner && *type_ == coin_type)
+                .take(limit.saturating_sub(history.len()))
+                .map(|((_, _, checkpoint), balance)| (checkpoint, balance)),
+        );
+        history.truncate(limit);
+        Ok(history)
+    }
+
+    /// The first checkpoint whose balances are indexed: the one the index was seeded at, unless
+    /// transactions of later checkpoints were executed ahead of them when it was.
+    fn first_balance_history_checkpoint(&self) -> SuiResult<CheckpointSequenceNumber> {
+        let Some(history) = &self.balance_history else {
+            return Err(SuiError::UnsupportedFeatureError {
+                error: "Balance history index is not enabled on this node".to_string(),
+            });
+        };
+        match &*history.progress.lock() {
+            SeedProgress::Seeding(_) => Err(SuiError::UnsupportedFeatureError {
+                error: "Balance history index is still being seeded on this node".to_string(),
+            }),
+            SeedProgress::Done => Ok(history.seed.first_checkpoint),
+        }
+    }
+
+    /// The latest indexed balance of `owner` in `coin_type` at or before `checkpoint`, if any.
+    fn balance_at(
+        &self,
+        owner: SuiAddress,
+        coin_type: &str,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiResult<Option<TotalBalance>> {
+        let key = (owner, coin_type.to_string(), checkpoint);
+        Ok(self
+            .tables
+            .balance_history
+            .unbounded_iter()
+            .skip_prior_to(&key)?
+            .reverse()
+            .next()
+            .filter(|((addr, type_, _), _)| *addr == owner && type_ == coin_type)
+            .map(|(_, balance)| balance))
+    }
+
     /// Read balance for a `SuiAddress` and `CoinType` from the backend database
     pub fn get_balance_from_db(
         metrics: Arc<IndexStoreMetrics>,
@@ -1579,18 +2085,26 @@ impl IndexStore {
 
 #[cfg(test)]
 mod tests {
-    use crate::indexes::ObjectIndexChanges;
+    use crate::indexes::{
+        BalanceHistoryStart, CoinInfo, ObjectIndexChanges, SeedProgress, TotalBalance,
+    };
     use crate::IndexStore;
     use move_core_types::account_address::AccountAddress;
     use prometheus::Registry;
     use std::collections::BTreeMap;
     use std::env::temp_dir;
-    use sui_types::base_types::{ObjectInfo, ObjectType, SuiAddress};
+    use std::path::Path;
+    use std::time::Duration;
+    use sui_types::base_types::{random_object_ref, ObjectInfo, ObjectType, SuiAddress};
+    use sui_types::crypto::{get_key_pair, AccountKeyPair};
     use sui_types::digests::TransactionDigest;
-    use sui_types::effects::TransactionEvents;
+    use sui_types::effects::{TestEffectsBuilder, TransactionEvents};
+    use sui_types::full_checkpoint_content::CheckpointTransaction;
     use sui_types::gas_coin::GAS;
+    use sui_types::messages_checkpoint::CheckpointSequenceNumber;
     use sui_types::object;
-    use sui_types::object::Owner;
+    use sui_types::object::{Object, Owner};
+    use sui_types::transaction::{Transaction, TransactionData};
 
     #[tokio::test]
     async fn test_index_cache() -> anyhow::Result<()> {
@@ -1601,7 +2115,8 @@ mod tests {
         // and verified from both db and cache.
         // This tests make sure we are invalidating entries in the cache and always reading latest
         // balance.
-        let index_store = IndexStore::new(temp_dir(), &Registry::default(), Some(128), false);
+        let index_store =
+            IndexStore::new(temp_dir(), &Registry::default(), Some(128), false, None).unwrap();
         let address: SuiAddress = AccountAddress::random().into();
         let mut written_objects = BTreeMap::new();
         let mut object_map = BTreeMap::new();
@@ -1716,4 +2231,258 @@ mod tests {
 
         Ok(())
     }
+
+    fn coin_transaction(
+        input_objects: Vec<Object>,
+        output_objects: Vec<Object>,
+    ) -> CheckpointTransaction {
+        let (sender, key): (_, AccountKeyPair) = get_key_pair();
+        let transaction = Transaction::from_data_and_signer(
+            TransactionData::new_transfer(
+                sender,
+                random_object_ref(),
+                sender,
+                random_object_ref(),
+                100_000_000_000,
+                100,
+            ),
+            vec![&key],
+        );
+        let effects = TestEffectsBuilder::new(transaction.data()).build();
+        CheckpointTransaction {
+            transaction,
+            effects,
+            events: None,
+            input_objects,
+            output_objects,
+        }
+    }
+
+    #[test]
+    fn test_balance_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let index_store = IndexStore::new(
+            dir.path().join("history"),
+            &Registry::default(),
+            None,
+            false,
+            Some(BalanceHistoryStart {
+                next_checkpoint: 0,
+                highest_synced_checkpoint: None,
+            }),
+        )
+        .unwrap();
+        let alice: SuiAddress = AccountAddress::random().into();
+        let bob: SuiAddress = AccountAddress::random().into();
+        let gas = GAS::type_tag();
+
+        let coin1 = Object::new_gas_with_balance_and_owner_for_testing(100, alice);
+        let coin2 = Object::new_gas_with_balance_and_owner_for_testing(50, alice);
+        let genesis = coin_transaction(vec![], vec![coin1.clone(), coin2.clone()]);
+
+        // Alice sends 40 to Bob out of her first coin.
+        let split = coin_transaction(
+            vec![coin1.clone()],
+            vec![
+                Object::with_id_owner_gas_for_testing(coin1.id(), alice, 60),
+                Object::new_gas_with_balance_and_owner_for_testing(40, bob),
+            ],
+        );
+        // Alice transfers her second coin to Bob.
+        let transfer = coin_transaction(
+            vec![coin2.clone()],
+            vec![Object::with_id_owner_gas_for_testing(coin2.id(), bob, 50)],
+        );
+
+        // Checkpoints can be indexed more than once.
+        index_store.index_balance_changes(0, &[genesis]).unwrap();
+        index_store
+            .index_balance_changes(2, &[transfer.clone()])
+            .unwrap();
+        index_store.index_balance_changes(3, &[split]).unwrap();
+        index_store.index_balance_changes(2, &[transfer]).unwrap();
+
+        let balance = |owner, checkpoint| {
+            index_store
+                .get_balance_at_checkpoint(owner, gas.clone(), checkpoint)
+                .unwrap()
+        };
+        let total = |balance, num_coins| TotalBalance { balance, num_coins };
+        assert_eq!(balance(alice, 0), total(150, 2));
+        assert_eq!(balance(alice, 1), total(150, 2));
+        assert_eq!(balance(alice, 2), total(100, 1));
+        assert_eq!(balance(alice, 3), total(60, 1));
+        assert_eq!(balance(bob, 1), total(0, 0));
+        assert_eq!(balance(bob, 3), total(90, 2));
+
+        let history = index_store
+            .get_balance_history(alice, gas.clone(), None, 10)
+            .unwrap();
+        assert_eq!(
+            history,
+            [(0, total(150, 2)), (2, total(100, 1)), (3, total(60, 1))]
+        );
+        let history = index_store
+            .get_balance_history(alice, gas.clone(), Some(0), 1)
+            .unwrap();
+        assert_eq!(history, [(2, total(100, 1))]);
+
+        let index_store = IndexStore::new(
+            dir.path().join("no_history"),
+            &Registry::default(),
+            None,
+            false,
+            None,
+        )
+        .unwrap();
+        assert!(index_store
+            .get_balance_at_checkpoint(alice, gas, 0)
+            .is_err());
+    }
+
+    /// Opens the index store at `path` with the balance history index, as a node that synced the
+    /// checkpoints up to `next`, and executed the ones before it.
+    fn open_balance_history(path: &Path, next: CheckpointSequenceNumber) -> IndexStore {
+        let start = BalanceHistoryStart {
+            next_checkpoint: next,
+            highest_synced_checkpoint: Some(next),
+        };
+        IndexStore::new(
+            path.to_owned(),
+            &Registry::default(),
+            None,
+            false,
+            Some(start),
+        )
+        .unwrap()
+    }
+
+    fn wait_for_balance_history_seed(index_store: &IndexStore) {
+        let progress = &index_store.balance_history.as_ref().unwrap().progress;
+        for _ in 0..1000 {
+            if let SeedProgress::Done = &*progress.lock() {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        panic!("Balance history index was not seeded");
+    }
+
+    #[test]
+    fn test_balance_history_seeded_from_coin_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("indexes");
+        let alice: SuiAddress = AccountAddress::random().into();
+        let bob: SuiAddress = AccountAddress::random().into();
+        let gas = GAS::type_tag();
+        let coin1 = Object::new_gas_with_balance_and_owner_for_testing(100, alice);
+        let coin2 = Object::new_gas_with_balance_and_owner_for_testing(50, alice);
+        let coin3 = Object::new_gas_with_balance_and_owner_for_testing(10, bob);
+
+        // Alice transfers her second coin to Bob in checkpoint 11, and Bob his coin to Alice in
+        // checkpoint 12.
+        let transfer = coin_transaction(
+            vec![coin2.clone()],
+            vec![Object::with_id_owner_gas_for_testing(coin2.id(), bob, 50)],
+        );
+        let transfer_back = coin_transaction(
+            vec![coin3.clone()],
+            vec![Object::with_id_owner_gas_for_testing(coin3.id(), alice, 10)],
+        );
+
+        // A node that ran without the balance history index up to checkpoint 9, and executed the
+        // transfer ahead of its checkpoint.
+        {
+            let index_store =
+                IndexStore::new(path.clone(), &Registry::default(), None, false, None).unwrap();
+            let coin2 = Object::with_id_owner_gas_for_testing(coin2.id(), bob, 50);
+            index_store
+                .tables
+                .coin_index
+                .multi_insert([&coin1, &coin2, &coin3].map(|coin| {
+                    (
+                        (
+                            coin.owner.get_owner_address().unwrap(),
+                            GAS::type_tag().to_string(),
+                            coin.id(),
+                        ),
+                        CoinInfo::from_object(coin).unwrap(),
+                    )
+                }))
+                .unwrap();
+            let digest = *transfer.transaction.digest();
+            index_store
+                .tables
+                .transaction_order
+                .insert(&0, &digest)
+                .unwrap();
+            index_store
+                .tables
+                .transactions_seq
+                .insert(&digest, &0)
+                .unwrap();
+        }
+
+        // Checkpoints are indexed while the index is seeded, and the transfer is not counted twice.
+        let start = BalanceHistoryStart {
+            next_checkpoint: 10,
+            highest_synced_checkpoint: Some(11),
+        };
+        let index_store =
+            IndexStore::new(path.clone(), &Registry::default(), None, false, Some(start)).unwrap();
+        index_store.index_balance_changes(11, &[transfer]).unwrap();
+        index_store
+            .index_balance_changes(12, &[transfer_back])
+            .unwrap();
+        wait_for_balance_history_seed(&index_store);
+
+        let balance = |owner, checkpoint| {
+            index_store
+                .get_balance_at_checkpoint(owner, gas.clone(), checkpoint)
+                .unwrap()
+        };
+        let total = |balance, num_coins| TotalBalance { balance, num_coins };
+        assert_eq!(balance(alice, 11), total(100, 1));
+        assert_eq!(balance(bob, 11), total(60, 2));
+        assert_eq!(balance(alice, 12), total(110, 2));
+        assert_eq!(balance(bob, 12), total(50, 1));
+
+        // The seed includes the transfer, so balances before its checkpoint are not known.
+        let error = index_store
+            .get_balance_at_checkpoint(alice, gas.clone(), 10)
+            .unwrap_err();
+        assert!(error.to_string().contains("not indexed"), "{error}");
+
+        let history = index_store
+            .get_balance_history(bob, gas.clone(), None, 10)
+            .unwrap();
+        assert_eq!(history, [(11, total(60, 2)), (12, total(50, 1))]);
+        let history = index_store
+            .get_balance_history(bob, gas.clone(), Some(11), 10)
+            .unwrap();
+        assert_eq!(history, [(12, total(50, 1))]);
+        drop(index_store);
+
+        // Restarting after checkpoint 12 keeps the history, but checkpoints executed while the
+        // index was disabled leave a gap, and restart it.
+        let index_store = open_balance_history(&path, 13);
+        assert_eq!(
+            index_store
+                .get_balance_at_checkpoint(alice, gas.clone(), 11)
+                .unwrap(),
+            total(100, 1)
+        );
+        drop(index_store);
+        let index_store = open_balance_history(&path, 20);
+        wait_for_balance_history_seed(&index_store);
+        assert!(index_store
+            .get_balance_at_checkpoint(alice, gas.clone(), 12)
+            .is_err());
+        assert_eq!(
+            index_store
+                .get_balance_at_checkpoint(alice, gas, 20)
+                .unwrap(),
+            total(100, 1)
+        );
+    }
 }
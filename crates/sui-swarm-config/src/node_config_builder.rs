@@ -198,6 +198,7 @@ impl ValidatorConfigBuilder {
                 .unwrap(),
             consensus_config: Some(consensus_config),
             remove_deprecated_tables: false,
+            enable_balance_history_index: false,
             enable_index_processing: default_enable_index_processing(),
             genesis: sui_config::node::Genesis::new(genesis),
             grpc_load_shed: None,
@@ -466,6 +467,7 @@ impl FullnodeConfigBuilder {
             json_rpc_address: self.json_rpc_address.unwrap_or(json_rpc_address),
             consensus_config: None,
             remove_deprecated_tables: false,
+            enable_balance_history_index: false,
             enable_index_processing: default_enable_index_processing(),
             genesis: self.genesis.unwrap_or(sui_config::node::Genesis::new(
                 network_config.genesis.clone(),
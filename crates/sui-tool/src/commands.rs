@@ -43,6 +43,14 @@ pub enum Verbosity {
     Verbose,
 }
 
+#[derive(Parser, Clone, ValueEnum)]
+pub enum DagExportFormat {
+    Json,
+    Dot,
+    /// The text DSL of the consensus test DAG parser
+    Dsl,
+}
+
 #[derive(Parser)]
 pub enum ToolCommand {
     /// Inspect if a specific object is or all gas objects owned by an address are locked by validators
@@ -131,6 +139,24 @@ pub enum ToolCommand {
         cmd: Option<DbToolCommand>,
     },
 
+    /// Export a range of rounds of the DAG from a consensus DB, for debugging. The export includes
+    /// both signed blocks of every equivocation recorded in the range.
+    #[command(name = "export-consensus-dag")]
+    ExportConsensusDag {
+        /// Path of the consensus DB of an epoch. It is opened read-only.
+        #[arg(long = "db-path")]
+        db_path: String,
+        #[arg(long = "start-round", default_value_t = 0)]
+        start_round: u32,
+        #[arg(long = "end-round")]
+        end_round: u32,
+        #[arg(value_enum, long = "format", default_value = "json")]
+        format: DagExportFormat,
+        /// File to write the export to, instead of stdout
+        #[arg(long = "output")]
+        output: Option<PathBuf>,
+    },
+
     /// Tool to verify the archive store
     #[command(name = "verify-archive")]
     VerifyArchive {
@@ -632,6 +658,24 @@ impl ToolCommand {
                     None => print_db_all_tables(path)?,
                 }
             }
+            ToolCommand::ExportConsensusDag {
+                db_path,
+                start_round,
+                end_round,
+                format,
+                output,
+            } => {
+                let export = consensus_core::export_dag(&db_path, start_round, end_round)?;
+                let rendered = match format {
+                    DagExportFormat::Json => serde_json::to_string_pretty(&export)?,
+                    DagExportFormat::Dot => export.to_dot(),
+                    DagExportFormat::Dsl => export.to_dag_dsl().map_err(|e| anyhow::anyhow!(e))?,
+                };
+                match output {
+                    Some(path) => std::fs::write(path, rendered)?,
+                    None => println!("{rendered}"),
+                }
+            }
             ToolCommand::DumpPackages {
                 db_url,
                 output_dir,
@@ -123,8 +123,9 @@ impl TransactionBuilder {
     }
 
     /// Construct the transaction data from a transaction kind, and other parameters.
-    /// If the gas_payment list is empty, it will pick the first gas coin that has at least
-    /// the required gas budget that is not in the input coins.
+    /// If the gas_payment list is empty, it will pick the first gas coin of the gas owner (the
+    /// sponsor if there is one, the sender otherwise) that has at least the required gas budget
+    /// that is not in the input coins.
     pub async fn tx_data(
         &self,
         sender: SuiAddress,
@@ -134,6 +135,7 @@ impl TransactionBuilder {
         gas_payment: Vec<ObjectID>,
         gas_sponsor: Option<SuiAddress>,
     ) -> Result<TransactionData, anyhow::Error> {
+        let gas_owner = gas_sponsor.unwrap_or(sender);
         let gas_payment = if gas_payment.is_empty() {
             let input_objs = kind
                 .input_objects()?
@@ -144,7 +146,7 @@ impl TransactionBuilder {
                 })
                 .collect();
             vec![
-                self.select_gas(sender, None, gas_budget, input_objs, gas_price)
+                self.select_gas(gas_owner, None, gas_budget, input_objs, gas_price)
                     .await?,
             ]
         } else {
@@ -156,7 +158,7 @@ impl TransactionBuilder {
             gas_payment,
             gas_budget,
             gas_price,
-            gas_sponsor.unwrap_or(sender),
+            gas_owner,
         ))
     }
 
@@ -291,6 +291,15 @@ impl SimulatorStore for PersistedStore {
             ))
     }
 
+    fn live_objects(&self) -> Box<dyn Iterator<Item = Object> + '_> {
+        Box::new(
+            self.read_write
+                .live_objects
+                .unbounded_iter()
+                .flat_map(|(id, version)| self.get_object_at_version(&id, version)),
+        )
+    }
+
     fn insert_checkpoint(&mut self, checkpoint: VerifiedCheckpoint) {
         self.read_write
             .checkpoint_digest_to_sequence_number
@@ -729,6 +738,33 @@ impl RestStateReader for PersistedStoreInnerReadOnlyWrapper {
             .digest())
         .into())
     }
+
+    fn account_owned_objects_info_iter(
+        &self,
+        _owner: SuiAddress,
+        _cursor: Option<ObjectID>,
+    ) -> sui_types::storage::error::Result<
+        Box<dyn Iterator<Item = sui_types::storage::AccountOwnedObjectInfo> + '_>,
+    > {
+        todo!()
+    }
+
+    fn dynamic_field_iter(
+        &self,
+        _parent: ObjectID,
+        _cursor: Option<ObjectID>,
+    ) -> sui_types::storage::error::Result<
+        Box<
+            dyn Iterator<
+                    Item = (
+                        sui_types::storage::DynamicFieldKey,
+                        sui_types::storage::DynamicFieldIndexInfo,
+                    ),
+                > + '_,
+        >,
+    > {
+        todo!()
+    }
 }
 
 impl PersistedStoreInnerReadOnlyWrapper {
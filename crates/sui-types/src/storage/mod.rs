@@ -22,6 +22,9 @@ use itertools::Itertools;
 use move_binary_format::CompiledModule;
 use move_core_types::language_storage::ModuleId;
 pub use object_store_trait::ObjectStore;
+pub use read_store::AccountOwnedObjectInfo;
+pub use read_store::DynamicFieldIndexInfo;
+pub use read_store::DynamicFieldKey;
 pub use read_store::ReadStore;
 pub use read_store::RestStateReader;
 use serde::{Deserialize, Serialize};
@@ -3,18 +3,21 @@
 
 use super::error::Result;
 use super::ObjectStore;
-use crate::base_types::EpochId;
+use crate::base_types::{EpochId, MoveObjectType, ObjectID, SequenceNumber, SuiAddress};
 use crate::committee::Committee;
 use crate::digests::{
     ChainIdentifier, CheckpointContentsDigest, CheckpointDigest, TransactionDigest,
     TransactionEventsDigest,
 };
+use crate::dynamic_field::DynamicFieldType;
 use crate::effects::{TransactionEffects, TransactionEvents};
 use crate::full_checkpoint_content::CheckpointData;
 use crate::messages_checkpoint::{
     CheckpointContents, CheckpointSequenceNumber, FullCheckpointContents, VerifiedCheckpoint,
 };
 use crate::transaction::VerifiedTransaction;
+use move_core_types::language_storage::TypeTag;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 pub trait ReadStore: ObjectStore {
@@ -654,4 +657,59 @@ pub trait RestStateReader: ObjectStore + ReadStore + Send + Sync {
     fn get_lowest_available_checkpoint_objects(&self) -> Result<CheckpointSequenceNumber>;
 
     fn get_chain_identifier(&self) -> Result<ChainIdentifier>;
+
+    /// Iterate over the objects owned by `owner`, in ascending order of `ObjectID`, starting at
+    /// (and including) `cursor` if one is provided.
+    fn account_owned_objects_info_iter(
+        &self,
+        owner: SuiAddress,
+        cursor: Option<ObjectID>,
+    ) -> Result<Box<dyn Iterator<Item = AccountOwnedObjectInfo> + '_>>;
+
+    /// Iterate over the dynamic fields of `parent`, in ascending order of field `ObjectID`,
+    /// starting at (and including) `cursor` if one is provided.
+    fn dynamic_field_iter(
+        &self,
+        parent: ObjectID,
+        cursor: Option<ObjectID>,
+    ) -> Result<Box<dyn Iterator<Item = (DynamicFieldKey, DynamicFieldIndexInfo)> + '_>>;
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AccountOwnedObjectInfo {
+    pub owner: SuiAddress,
+    pub object_id: ObjectID,
+    pub version: SequenceNumber,
+    pub type_: MoveObjectType,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct DynamicFieldKey {
+    pub parent: ObjectID,
+    pub field_id: ObjectID,
+}
+
+impl DynamicFieldKey {
+    pub fn new<P: Into<ObjectID>>(parent: P, field_id: ObjectID) -> Self {
+        Self {
+            parent: parent.into(),
+            field_id,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct DynamicFieldIndexInfo {
+    // field_id of this dynamic field is a part of the Key
+    pub dynamic_field_type: DynamicFieldType,
+    pub name_type: TypeTag,
+    pub name_value: Vec<u8>,
+    // TODO do we want to also store the type of the value? We can get this for free for
+    // DynamicFields, but for DynamicObjects it would require a lookup in the DB on init, or
+    // scanning the transaction's output objects for the coorisponding Object to retreive its type
+    // information.
+    //
+    // pub value_type: TypeTag,
+    /// ObjectId of the child object when `dynamic_field_type == DynamicFieldType::DynamicObject`
+    pub dynamic_object_id: Option<ObjectID>,
 }
@@ -3,6 +3,7 @@
 
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 // These values set to loosely attempt to limit
@@ -160,6 +161,86 @@ fn default_sketch_tolerance() -> f64 {
     DEFAULT_SKETCH_TOLERANCE
 }
 
+#[serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TokenBucketConfig {
+    /// Maximum number of tallies a direct client can burst before it is throttled.
+    #[serde(default = "default_client_capacity")]
+    pub client_capacity: u64,
+    /// Rate, in tallies per second, at which a direct client's bucket refills.
+    #[serde(default = "default_client_refill_rate")]
+    pub client_refill_rate: f64,
+    /// Maximum number of tallies a proxied client can burst before it is throttled.
+    #[serde(default = "default_proxied_client_capacity")]
+    pub proxied_client_capacity: u64,
+    /// Rate, in tallies per second, at which a proxied client's bucket refills.
+    #[serde(default = "default_proxied_client_refill_rate")]
+    pub proxied_client_refill_rate: f64,
+    /// Upper bound on the number of buckets tracked at once. Once reached, buckets that have
+    /// refilled completely are evicted, and if that is not enough, new clients go untracked
+    /// until space frees up.
+    #[serde(default = "default_max_tracked_clients")]
+    pub max_tracked_clients: usize,
+}
+
+impl Default for TokenBucketConfig {
+    fn default() -> Self {
+        Self {
+            client_capacity: default_client_capacity(),
+            client_refill_rate: default_client_refill_rate(),
+            proxied_client_capacity: default_proxied_client_capacity(),
+            proxied_client_refill_rate: default_proxied_client_refill_rate(),
+            max_tracked_clients: default_max_tracked_clients(),
+        }
+    }
+}
+
+fn default_client_capacity() -> u64 {
+    // As with the frequency threshold, direct clients may be fullnodes
+    // proxying traffic for many clients, so default to a generous limit
+    100_000
+}
+
+fn default_client_refill_rate() -> f64 {
+    10_000.0
+}
+
+fn default_proxied_client_capacity() -> u64 {
+    20
+}
+
+fn default_proxied_client_refill_rate() -> f64 {
+    5.0
+}
+
+fn default_max_tracked_clients() -> usize {
+    DEFAULT_SKETCH_CAPACITY
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CompositeConfig {
+    pub policies: Vec<CompositePolicyEntry>,
+}
+
+/// A policy that is part of a composite policy, along with the weights with which
+/// tallies from each route are sampled into it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CompositePolicyEntry {
+    pub policy_type: PolicyType,
+    /// Sampling weight for tallies from a given route (the gRPC handler name on
+    /// validators, e.g. `transaction`, or the method name on JSON-RPC servers).
+    /// A weight of 0.0 excludes the route from this policy entirely.
+    #[serde(default)]
+    pub route_weights: HashMap<String, Weight>,
+    /// Sampling weight for tallies from routes not listed in `route_weights`,
+    /// and from tallies that carry no route.
+    #[serde(default = "Weight::one")]
+    pub default_weight: Weight,
+}
+
 // Serializable representation of policy types, used in config
 // in order to easily change in tests or to killswitch
 #[derive(Clone, Serialize, Deserialize, Debug, Default)]
@@ -173,6 +254,16 @@ pub enum PolicyType {
     /// with granularity of `update_interval_secs`
     FreqThreshold(FreqThresholdConfig),
 
+    /// Shapes traffic by giving each client a bucket of `capacity` tokens, refilled at
+    /// `refill_rate` tokens per second, with every tally taking a token. A client that
+    /// runs out of tokens is only blocked until its bucket refills enough for the next
+    /// tally, rather than for the configured blocklist TTL.
+    TokenBucket(TokenBucketConfig),
+
+    /// Combines several policies, blocking a client when any of them would. Each policy
+    /// only sees a sample of tallies, as weighted by the route the tally came from.
+    Composite(CompositeConfig),
+
     /* Below this point are test policies, and thus should not be used in production */
     ///
     /// Simple policy that adds connection_ip to blocklist when the same connection_ip
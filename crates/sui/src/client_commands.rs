@@ -598,6 +598,12 @@ pub struct Opts {
     /// `sui client execute-combined-signed-tx --signed-tx-bytes <SIGNED_TX_BYTES>`.
     #[arg(long, required = false)]
     pub serialize_signed_transaction: bool,
+    /// Address of the sponsor paying for gas. When set, the gas coins must be owned by the sponsor
+    /// and the transaction needs to be signed by both the sender and the sponsor. If the sponsor's
+    /// key is not in the keystore, serialize the transaction and have the sponsor co-sign it with
+    /// `sui keytool co-sign`.
+    #[arg(long)]
+    pub gas_sponsor: Option<SuiAddress>,
 }
 
 /// Global options with gas
@@ -619,6 +625,7 @@ impl Opts {
             dry_run: false,
             serialize_unsigned_transaction: false,
             serialize_signed_transaction: false,
+            gas_sponsor: None,
         }
     }
     /// Uses the passed gas_budget for the gas budget variable, sets dry run to true,
@@ -629,6 +636,7 @@ impl Opts {
             dry_run: true,
             serialize_unsigned_transaction: false,
             serialize_signed_transaction: false,
+            gas_sponsor: None,
         }
     }
 }
@@ -2627,11 +2635,18 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
     gas: Option<ObjectID>,
     opts: Opts,
 ) -> Result<SuiClientCommandResult, anyhow::Error> {
-    let (dry_run, gas_budget, serialize_unsigned_transaction, serialize_signed_transaction) = (
+    let (
+        dry_run,
+        gas_budget,
+        serialize_unsigned_transaction,
+        serialize_signed_transaction,
+        gas_sponsor,
+    ) = (
         opts.dry_run,
         opts.gas_budget,
         opts.serialize_unsigned_transaction,
         opts.serialize_signed_transaction,
+        opts.gas_sponsor,
     );
     ensure!(
         !serialize_unsigned_transaction || !serialize_signed_transaction,
@@ -2657,7 +2672,7 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
             gas_budget,
             gas_price,
             gas.clone(),
-            None,
+            gas_sponsor,
         )
         .await;
     }
@@ -2671,7 +2686,7 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
                 tx_kind.clone(),
                 gas_price,
                 gas.clone(),
-                None,
+                gas_sponsor,
             )
             .await?
         }
@@ -2685,7 +2700,7 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
             gas_budget,
             gas_price,
             gas.unwrap_or_default(),
-            None,
+            gas_sponsor,
         )
         .await?;
 
@@ -2694,12 +2709,31 @@ pub(crate) async fn dry_run_or_execute_or_serialize(
             tx_data,
         ))
     } else {
-        let signature = context.config.keystore.sign_secure(
-            &tx_data.sender(),
-            &tx_data,
-            Intent::sui_transaction(),
-        )?;
-        let sender_signed_data = SenderSignedData::new_from_sender_signature(tx_data, signature);
+        let keystore = &context.config.keystore;
+        let mut signatures: Vec<GenericSignature> = vec![keystore
+            .sign_secure(&tx_data.sender(), &tx_data, Intent::sui_transaction())?
+            .into()];
+        // The sponsor co-signs here if their key is available, otherwise they have to do it
+        // separately from the serialized transaction.
+        let gas_owner = tx_data.gas_owner();
+        if gas_owner != tx_data.sender() {
+            if keystore.addresses().contains(&gas_owner) {
+                signatures.push(
+                    keystore
+                        .sign_secure(&gas_owner, &tx_data, Intent::sui_transaction())?
+                        .into(),
+                );
+            } else {
+                ensure!(
+                    serialize_signed_transaction,
+                    "The key of gas sponsor {gas_owner} is not in the keystore. Use \
+                    --serialize-signed-transaction and have the sponsor co-sign the transaction \
+                    with `sui keytool co-sign`, or use --serialize-unsigned-transaction and \
+                    collect both signatures with `sui keytool sign`."
+                );
+            }
+        }
+        let sender_signed_data = SenderSignedData::new(tx_data, signatures);
         if serialize_signed_transaction {
             Ok(SuiClientCommandResult::SerializedSignedTransaction(
                 sender_signed_data,
@@ -9,7 +9,9 @@ use move_command_line_common::{
 };
 use move_core_types::runtime_value::MoveValue;
 use sui_types::{
-    base_types::{ObjectID, RESOLVED_ASCII_STR, RESOLVED_STD_OPTION, RESOLVED_UTF8_STR},
+    base_types::{
+        ObjectID, SuiAddress, RESOLVED_ASCII_STR, RESOLVED_STD_OPTION, RESOLVED_UTF8_STR,
+    },
     Identifier, TypeTag,
 };
 
@@ -33,6 +35,7 @@ pub const WARN_SHADOWS: &str = "warn-shadows";
 pub const GAS_BUDGET: &str = "gas-budget";
 pub const SUMMARY: &str = "summary";
 pub const GAS_COIN: &str = "gas-coin";
+pub const GAS_SPONSOR: &str = "gas-sponsor";
 pub const JSON: &str = "json";
 pub const DRY_RUN: &str = "dry-run";
 pub const SERIALIZE_UNSIGNED: &str = "serialize-unsigned-transaction";
@@ -72,6 +75,7 @@ pub const COMMANDS: &[&str] = &[
     GAS_BUDGET,
     SUMMARY,
     GAS_COIN,
+    GAS_SPONSOR,
     JSON,
     DRY_RUN,
     SERIALIZE_UNSIGNED,
@@ -108,7 +112,8 @@ pub struct ProgramMetadata {
     pub summary_set: bool,
     pub serialize_unsigned_set: bool,
     pub serialize_signed_set: bool,
-    pub gas_object_id: Option<Spanned<ObjectID>>,
+    pub gas_object_ids: Vec<Spanned<ObjectID>>,
+    pub gas_sponsor: Option<Spanned<SuiAddress>>,
     pub json_set: bool,
     pub dry_run_set: bool,
     pub gas_budget: Option<Spanned<u64>>,
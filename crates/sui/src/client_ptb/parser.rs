@@ -8,7 +8,10 @@ use move_command_line_common::{
     parser::{parse_u128, parse_u16, parse_u256, parse_u32, parse_u64, parse_u8},
     types::{ParsedFqName, ParsedModuleId, ParsedStructType, ParsedType},
 };
-use sui_types::{base_types::ObjectID, Identifier};
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    Identifier,
+};
 
 use crate::{
     client_ptb::{
@@ -41,7 +44,8 @@ struct ProgramParsingState {
     serialize_signed_set: bool,
     json_set: bool,
     dry_run_set: bool,
-    gas_object_id: Option<Spanned<ObjectID>>,
+    gas_object_ids: Vec<Spanned<ObjectID>>,
+    gas_sponsor: Option<Spanned<SuiAddress>>,
     gas_budget: Option<Spanned<u64>>,
 }
 
@@ -63,7 +67,8 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 serialize_signed_set: false,
                 json_set: false,
                 dry_run_set: false,
-                gas_object_id: None,
+                gas_object_ids: Vec::new(),
+                gas_sponsor: None,
                 gas_budget: None,
             },
         })
@@ -114,7 +119,22 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 L(T::Command, A::WARN_SHADOWS) => flag!(warn_shadows_set),
                 L(T::Command, A::GAS_COIN) => {
                     let specifier = try_!(self.parse_gas_specifier());
-                    self.state.gas_object_id = Some(specifier);
+                    self.state.gas_object_ids.push(specifier);
+                }
+                L(T::Command, A::GAS_SPONSOR) => {
+                    let sponsor = try_!(self.parse_gas_sponsor()).widen_span(sp);
+                    if let Some(other) = self.state.gas_sponsor.replace(sponsor) {
+                        self.state.errors.extend([
+                            err!(
+                                other.span,
+                                "Multiple gas sponsors found. Gas sponsor first set here.",
+                            ),
+                            err!(sponsor.span => help: {
+                                "PTBs can have at most one gas sponsor."
+                            },"Sponsor set again here."),
+                        ]);
+                        self.fast_forward_to_next_command();
+                    }
                 }
                 L(T::Command, A::GAS_BUDGET) => {
                     let budget = try_!(self.parse_gas_budget()).widen_span(sp);
@@ -204,7 +224,8 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                     summary_set: self.state.summary_set,
                     serialize_unsigned_set: self.state.serialize_unsigned_set,
                     serialize_signed_set: self.state.serialize_signed_set,
-                    gas_object_id: self.state.gas_object_id,
+                    gas_object_ids: self.state.gas_object_ids,
+                    gas_sponsor: self.state.gas_sponsor,
                     json_set: self.state.json_set,
                     dry_run_set: self.state.dry_run_set,
                     gas_budget: self.state.gas_budget,
@@ -374,6 +395,14 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
             .parse_address_literal()?
             .map(|a| ObjectID::from(a.into_inner())))
     }
+
+    /// Parse a gas sponsor.
+    /// The expected format is: `--gas-sponsor <address>`
+    fn parse_gas_sponsor(&mut self) -> PTBResult<Spanned<SuiAddress>> {
+        Ok(self
+            .parse_address_literal()?
+            .map(|a| SuiAddress::from(a.into_inner())))
+    }
 }
 
 /// Methods for parsing arguments and types in commands
@@ -943,6 +972,7 @@ mod tests {
             "--assign a some(1)",
             // Gas-coin
             "--gas-coin @0x1",
+            "--gas-coin @0x1 --gas-coin @0x2 --gas-sponsor @0x3",
             "--summary",
             "--json",
             "--preview",
@@ -1009,6 +1039,9 @@ mod tests {
             "--gas-coin",
             "--gas-coin @0x1 @0x2",
             "--gas-coin 1",
+            // Gas-sponsor
+            "--gas-sponsor 0x1",
+            "--gas-sponsor @0x1 --gas-sponsor @0x2",
         ];
         let mut parsed = Vec::new();
         for input in inputs {
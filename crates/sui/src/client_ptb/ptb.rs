@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    client_commands::{dry_run_or_execute_or_serialize, Opts, OptsWithGas, SuiClientCommandResult},
+    client_commands::{dry_run_or_execute_or_serialize, Opts, SuiClientCommandResult},
     client_ptb::{
         ast::{ParsedProgram, Program},
         builder::PTBBuilder,
@@ -47,11 +47,15 @@ pub struct Summary {
 }
 
 impl PTB {
-    /// Parses and executes the PTB with the sender as the current active address
-    pub async fn execute(self, context: &mut WalletContext) -> Result<(), Error> {
+    /// Parses and executes the PTB with the sender as the current active address, printing the
+    /// outcome. The executed or serialized transaction is also returned.
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<SuiClientCommandResult, Error> {
         if self.args.is_empty() {
             ptb_description().print_help().unwrap();
-            return Ok(());
+            return Ok(SuiClientCommandResult::NoOutput);
         }
         let source_string = to_source_string(self.args.clone());
 
@@ -59,8 +63,14 @@ impl PTB {
         let tokens = self.args.iter().map(|s| s.as_str());
         for sp!(_, lexeme) in Lexer::new(tokens.clone()).into_iter().flatten() {
             match lexeme {
-                Lexeme(Token::Command, "help") => return Ok(ptb_description().print_long_help()?),
-                Lexeme(Token::Flag, "h") => return Ok(ptb_description().print_help()?),
+                Lexeme(Token::Command, "help") => {
+                    ptb_description().print_long_help()?;
+                    return Ok(SuiClientCommandResult::NoOutput);
+                }
+                Lexeme(Token::Flag, "h") => {
+                    ptb_description().print_help()?;
+                    return Ok(SuiClientCommandResult::NoOutput);
+                }
                 lexeme if lexeme.is_terminal() => break,
                 _ => continue,
             }
@@ -96,7 +106,7 @@ impl PTB {
                     program_metadata: &program_metadata
                 }
             );
-            return Ok(());
+            return Ok(SuiClientCommandResult::NoOutput);
         }
 
         let client = context.get_client().await?;
@@ -126,15 +136,21 @@ impl PTB {
         };
 
         // get all the metadata needed for executing the PTB: sender, gas, signing tx
-        let gas = program_metadata.gas_object_id.map(|x| x.value);
+        let gas: Vec<_> = program_metadata
+            .gas_object_ids
+            .iter()
+            .map(|x| x.value)
+            .collect();
+        let gas_sponsor = program_metadata.gas_sponsor.map(|x| x.value);
 
-        // the sender is the gas object if gas is provided, otherwise the active address
-        let sender = match gas {
-            Some(gas) => context
-                .get_object_owner(&gas)
+        // the sender is the owner of the gas object if gas is provided and not sponsored,
+        // otherwise the active address
+        let sender = match gas.first() {
+            Some(gas) if gas_sponsor.is_none() => context
+                .get_object_owner(gas)
                 .await
                 .map_err(|_| anyhow!("Could not find owner for gas object ID"))?,
-            None => context
+            _ => context
                 .config
                 .active_address
                 .ok_or_else(|| anyhow!("No active address, cannot execute PTB"))?,
@@ -146,30 +162,35 @@ impl PTB {
             commands: ptb.commands,
         });
 
-        let opts = OptsWithGas {
-            gas: program_metadata.gas_object_id.map(|x| x.value),
-            rest: Opts {
-                dry_run: program_metadata.dry_run_set,
-                gas_budget: program_metadata.gas_budget.map(|x| x.value),
-                serialize_unsigned_transaction: program_metadata.serialize_unsigned_set,
-                serialize_signed_transaction: program_metadata.serialize_signed_set,
-            },
+        let opts = Opts {
+            dry_run: program_metadata.dry_run_set,
+            gas_budget: program_metadata.gas_budget.map(|x| x.value),
+            serialize_unsigned_transaction: program_metadata.serialize_unsigned_set,
+            serialize_signed_transaction: program_metadata.serialize_signed_set,
+            gas_sponsor,
         };
 
+        let gas_payment = (!gas.is_empty()).then_some(gas);
         let transaction_response = dry_run_or_execute_or_serialize(
-            sender, tx_kind, context, None, None, opts.gas, opts.rest,
+            sender,
+            tx_kind,
+            context,
+            gas_payment,
+            None,
+            None,
+            opts,
         )
         .await?;
 
         let transaction_response = match transaction_response {
             SuiClientCommandResult::DryRun(_) => {
                 println!("{}", transaction_response);
-                return Ok(());
+                return Ok(SuiClientCommandResult::NoOutput);
             }
             SuiClientCommandResult::SerializedUnsignedTransaction(_)
             | SuiClientCommandResult::SerializedSignedTransaction(_) => {
                 println!("{}", transaction_response);
-                return Ok(());
+                return Ok(transaction_response);
             }
             SuiClientCommandResult::TransactionBlock(response) => response,
             _ => anyhow::bail!("Internal error, unexpected response from PTB execution."),
@@ -211,7 +232,9 @@ impl PTB {
             println!("{}", transaction_response);
         }
 
-        Ok(())
+        Ok(SuiClientCommandResult::TransactionBlock(
+            transaction_response,
+        ))
     }
 
     /// Exposed for testing
@@ -297,8 +320,15 @@ pub fn ptb_description() -> clap::Command {
         ))
         .arg(arg!(
             --"gas-coin" <ID> ...
-            "The object ID of the gas coin to use. If not specified, it will try to use the first \
-            gas coin that it finds that has at least the requested gas-budget balance."
+            "The object ID of the gas coin to use. Can be repeated to pay with several coins. If \
+            not specified, it will try to use the first gas coin that it finds that has at least \
+            the requested gas-budget balance."
+        ))
+        .arg(arg!(
+            --"gas-sponsor" <ADDRESS>
+            "The address of the sponsor paying for gas, which must own the gas coins. The \
+            transaction is sent by the active address and needs to be signed by the sponsor too, \
+            see `sui keytool co-sign`."
         ))
         .arg(arg!(
             --"gas-budget" <MIST>
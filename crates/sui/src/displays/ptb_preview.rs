@@ -3,7 +3,7 @@
 
 use crate::{
     client_ptb::{
-        ast::{GAS_BUDGET, GAS_COIN, JSON, SUMMARY, WARN_SHADOWS},
+        ast::{GAS_BUDGET, GAS_COIN, GAS_SPONSOR, JSON, SUMMARY, WARN_SHADOWS},
         ptb::PTBPreview,
     },
     sp,
@@ -27,9 +27,12 @@ impl<'a> Display for PTBPreview<'a> {
         if let Some(gas_budget) = self.program_metadata.gas_budget {
             builder.push_record([GAS_BUDGET, gas_budget.value.to_string().as_str()]);
         }
-        if let Some(gas_coin_id) = self.program_metadata.gas_object_id {
+        for gas_coin_id in &self.program_metadata.gas_object_ids {
             builder.push_record([GAS_COIN, gas_coin_id.value.to_string().as_str()]);
         }
+        if let Some(gas_sponsor) = self.program_metadata.gas_sponsor {
+            builder.push_record([GAS_SPONSOR, gas_sponsor.value.to_string().as_str()]);
+        }
         if self.program_metadata.json_set {
             builder.push_record([JSON, "true"]);
         }
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::key_identity::{get_identity_address_from_keystore, KeyIdentity};
 use crate::zklogin_commands_util::{perform_zk_login_test_tx, read_cli_line};
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
 use bip32::DerivationPath;
 use clap::*;
 use fastcrypto::ed25519::Ed25519KeyPair;
@@ -51,7 +51,7 @@ use sui_types::multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit
 use sui_types::multisig_legacy::{MultiSigLegacy, MultiSigPublicKeyLegacy};
 use sui_types::signature::{GenericSignature, VerifyParams};
 use sui_types::signature_verification::VerifiedDigestCache;
-use sui_types::transaction::{TransactionData, TransactionDataAPI};
+use sui_types::transaction::{SenderSignedData, TransactionData, TransactionDataAPI};
 use sui_types::zk_login_authenticator::ZkLoginAuthenticator;
 use tabled::builder::Builder;
 use tabled::settings::Rotate;
@@ -203,6 +203,17 @@ pub enum KeyToolCommand {
         #[clap(long)]
         intent: Option<Intent>,
     },
+    /// Add the signature of the given address (or its alias) in sui keystore to a Base64 encoded
+    /// signed transaction (SenderSignedData), e.g. a gas sponsor co-signing a transaction
+    /// serialized by the sender with `--serialize-signed-transaction`. It prints the Base64
+    /// encoded transaction with both signatures, which can be executed with
+    /// `sui client execute-combined-signed-tx`.
+    CoSign {
+        #[clap(long)]
+        address: KeyIdentity,
+        #[clap(long)]
+        signed_tx_bytes: String,
+    },
     /// Creates a signature by leveraging AWS KMS. Pass in a key-id to leverage Amazon
     /// KMS to sign a message and the base64 pubkey.
     /// Generate PubKey from pem using MystenLabs/base64pemkey
@@ -407,6 +418,16 @@ pub struct SerializedSig {
     serialized_sig_base64: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoSignData {
+    sui_address: SuiAddress,
+    // Base64 encoded `flag || signature || pubkey` of the added signature.
+    sui_signature: String,
+    // Base64 encoded [struct SenderSignedData] holding the transaction data and all signatures.
+    signed_tx_bytes: String,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SignData {
@@ -467,6 +488,7 @@ pub enum CommandOutput {
     PrivateKeyBase64(PrivateKeyBase64),
     Show(Key),
     Sign(SignData),
+    CoSign(CoSignData),
     SignKMS(SerializedSig),
     ZkLoginSignAndExecuteTx(ZkLoginSignAndExecuteTx),
     ZkLoginInsecureSignPersonalMessage(ZkLoginInsecureSignPersonalMessage),
@@ -832,6 +854,41 @@ impl KeyToolCommand {
                 })
             }
 
+            KeyToolCommand::CoSign {
+                address,
+                signed_tx_bytes,
+            } => {
+                let address = get_identity_address_from_keystore(address, keystore)?;
+                let mut signed_data: SenderSignedData =
+                    bcs::from_bytes(&Base64::decode(&signed_tx_bytes).map_err(|e| {
+                        anyhow!("Cannot deserialize data as SenderSignedData {:?}", e)
+                    })?)?;
+                let tx_data = signed_data.transaction_data();
+                if address != tx_data.sender() && address != tx_data.gas_owner() {
+                    bail!(
+                        "Address {address} is neither the sender {} nor the gas sponsor {} of \
+                        the transaction",
+                        tx_data.sender(),
+                        tx_data.gas_owner()
+                    );
+                }
+                let already_signed = signed_data
+                    .tx_signatures()
+                    .iter()
+                    .any(|sig| SuiAddress::try_from(sig).is_ok_and(|signer| signer == address));
+                if already_signed {
+                    bail!("The transaction is already signed by {address}");
+                }
+                let sui_signature =
+                    keystore.sign_secure(&address, tx_data, Intent::sui_transaction())?;
+                signed_data.add_signature(sui_signature.clone());
+                CommandOutput::CoSign(CoSignData {
+                    sui_address: address,
+                    sui_signature: sui_signature.encode_base64(),
+                    signed_tx_bytes: Base64::encode(bcs::to_bytes(&signed_data)?),
+                })
+            }
+
             KeyToolCommand::SignKMS {
                 data,
                 keyid,
@@ -1311,6 +1368,22 @@ impl Display for CommandOutput {
                 table.with(Modify::new(Rows::new(0..)).with(Width::wrap(160).keep_words()));
                 write!(formatter, "{}", table)
             }
+            // Same as Sign, the signedTxBytes string is too long for a JsonTable
+            CommandOutput::CoSign(data) => {
+                let mut builder = Builder::default();
+                builder
+                    .set_header(["suiSignature", "signedTxBytes", "suiAddress"])
+                    .push_record([
+                        &data.sui_signature,
+                        &data.signed_tx_bytes,
+                        &data.sui_address.to_string(),
+                    ]);
+                let mut table = builder.build();
+                table.with(Rotate::Left);
+                table.with(tabled::settings::Style::rounded().horizontals([]));
+                table.with(Modify::new(Rows::new(0..)).with(Width::wrap(160).keep_words()));
+                write!(formatter, "{}", table)
+            }
             _ => {
                 let json_obj = json![self];
                 let mut table = json_to_table(&json_obj);
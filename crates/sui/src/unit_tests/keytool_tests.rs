@@ -36,7 +36,10 @@ use sui_types::crypto::Signature;
 use sui_types::crypto::SignatureScheme;
 use sui_types::crypto::SuiKeyPair;
 use sui_types::crypto::SuiSignatureInner;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::transaction::SenderSignedData;
 use sui_types::transaction::TransactionData;
+use sui_types::transaction::TransactionKind;
 use sui_types::transaction::TEST_ONLY_GAS_UNIT_FOR_TRANSFER;
 use tempfile::TempDir;
 use tokio::test;
@@ -565,3 +568,65 @@ async fn test_sign_command() -> Result<(), anyhow::Error> {
     .await?;
     Ok(())
 }
+
+#[test]
+async fn test_co_sign_command() -> Result<(), anyhow::Error> {
+    let mut keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(3));
+    let binding = keystore.addresses();
+    let (sender, sponsor, other) = (binding[0], binding[1], binding[2]);
+
+    // Create a dummy TransactionData with gas paid by the sponsor, signed by the sender
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+    let gas_price = 1;
+    let tx_data = TransactionData::new_with_gas_coins_allow_sponsor(
+        TransactionKind::ProgrammableTransaction(ProgrammableTransactionBuilder::new().finish()),
+        sender,
+        vec![gas],
+        gas_price * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        gas_price,
+        sponsor,
+    );
+    let signature = keystore.sign_secure(&sender, &tx_data, Intent::sui_transaction())?;
+    let signed_data = SenderSignedData::new_from_sender_signature(tx_data, signature);
+    let signed_tx_bytes = Base64::encode(bcs::to_bytes(&signed_data)?);
+
+    // The sponsor adds its signature
+    let output = KeyToolCommand::CoSign {
+        address: KeyIdentity::Address(sponsor),
+        signed_tx_bytes: signed_tx_bytes.clone(),
+    }
+    .execute(&mut keystore)
+    .await?;
+    let CommandOutput::CoSign(co_sign) = output else {
+        panic!("Unexpected output of co-sign command");
+    };
+    let co_signed_data: SenderSignedData =
+        bcs::from_bytes(&Base64::decode(&co_sign.signed_tx_bytes).unwrap())?;
+    let signers = co_signed_data
+        .tx_signatures()
+        .iter()
+        .map(|sig| SuiAddress::try_from(sig).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(signers, vec![sender, sponsor]);
+
+    // Signing twice, or by an address that is neither the sender nor the sponsor, fails
+    assert!(KeyToolCommand::CoSign {
+        address: KeyIdentity::Address(sponsor),
+        signed_tx_bytes: co_sign.signed_tx_bytes,
+    }
+    .execute(&mut keystore)
+    .await
+    .is_err());
+    assert!(KeyToolCommand::CoSign {
+        address: KeyIdentity::Address(other),
+        signed_tx_bytes,
+    }
+    .execute(&mut keystore)
+    .await
+    .is_err());
+    Ok(())
+}
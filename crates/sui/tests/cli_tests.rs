@@ -10,15 +10,17 @@ use std::{fmt::Write, fs::read_dir, path::PathBuf, str, thread, time::Duration};
 use std::str::FromStr;
 
 use expect_test::expect;
+use fastcrypto::encoding::{Base64, Encoding};
 use move_package::{lock_file::schema::ManagedPackage, BuildConfig as MoveBuildConfig};
 use serde_json::json;
 use sui::client_ptb::ptb::PTB;
 use sui::key_identity::{get_identity_address, KeyIdentity};
+use sui::keytool::KeyToolCommand;
 use sui_sdk::SuiClient;
 use sui_test_transaction_builder::batch_make_transfer_transactions;
 use sui_types::object::Owner;
 use sui_types::transaction::{
-    TEST_ONLY_GAS_UNIT_FOR_GENERIC, TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS,
+    TransactionDataAPI, TEST_ONLY_GAS_UNIT_FOR_GENERIC, TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS,
     TEST_ONLY_GAS_UNIT_FOR_PUBLISH, TEST_ONLY_GAS_UNIT_FOR_SPLIT_COIN,
     TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
 };
@@ -41,7 +43,7 @@ use sui_json_rpc_types::{
     SuiObjectDataFilter, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery,
     SuiTransactionBlockDataAPI, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
 };
-use sui_keys::keystore::AccountKeystore;
+use sui_keys::keystore::{AccountKeystore, InMemKeystore, Keystore};
 use sui_macros::sim_test;
 use sui_move_build::{BuildConfig, SuiPackageHooks};
 use sui_sdk::sui_client_config::SuiClientConfig;
@@ -2771,6 +2773,7 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
             dry_run: false,
             serialize_unsigned_transaction: true,
             serialize_signed_transaction: false,
+            gas_sponsor: None,
         },
     }
     .execute(context)
@@ -2785,6 +2788,7 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
             dry_run: false,
             serialize_unsigned_transaction: false,
             serialize_signed_transaction: true,
+            gas_sponsor: None,
         },
     }
     .execute(context)
@@ -2800,6 +2804,7 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
             dry_run: false,
             serialize_unsigned_transaction: false,
             serialize_signed_transaction: true,
+            gas_sponsor: None,
         },
     }
     .execute(context)
@@ -2829,6 +2834,96 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_ptb_gas_sponsor_co_sign() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let rgp = test_cluster.get_reference_gas_price().await;
+    let recipient = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+    let sender = context.active_address()?;
+
+    // The sponsor's key is kept out of the wallet's keystore
+    let mut sponsor_keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(1));
+    let sponsor = sponsor_keystore.addresses()[0];
+
+    let client = context.get_client().await?;
+    let coins = client
+        .coin_read_api()
+        .get_coins(sender, None, None, None)
+        .await?
+        .data;
+    let (sender_coin, sponsor_coin) = (coins[0].coin_object_id, coins[1].coin_object_id);
+
+    // Fund the sponsor with one of the sender's coins
+    SuiClientCommands::TransferSui {
+        to: KeyIdentity::Address(sponsor),
+        sui_coin_object_id: sponsor_coin,
+        amount: None,
+        opts: Opts::for_testing(rgp * TEST_ONLY_GAS_UNIT_FOR_TRANSFER),
+    }
+    .execute(context)
+    .await?;
+
+    // The sender builds and signs a transaction whose gas is paid by the sponsor
+    let ptb_string = format!(
+        "--split-coins @{sender_coin} [1000] \
+         --assign new_coin \
+         --transfer-objects [new_coin] @{recipient} \
+         --gas-coin @{sponsor_coin} \
+         --gas-sponsor @{sponsor} \
+         --gas-budget 50000000 \
+         --serialize-signed-transaction"
+    );
+    let args = shlex::split(&ptb_string).unwrap();
+    let SuiClientCommandResult::SerializedSignedTransaction(signed) =
+        PTB { args }.execute(context).await?
+    else {
+        panic!("Expected a serialized signed transaction");
+    };
+    assert_eq!(signed.tx_signatures().len(), 1);
+    assert_eq!(signed.transaction_data().gas_owner(), sponsor);
+    let signed_tx_bytes = Base64::encode(bcs::to_bytes(&signed)?);
+
+    // It cannot be executed without the sponsor's signature
+    assert!(SuiClientCommands::ExecuteCombinedSignedTx {
+        signed_tx_bytes: signed_tx_bytes.clone(),
+    }
+    .execute(context)
+    .await
+    .is_err());
+
+    // The sponsor co-signs it, after which it executes
+    let output = KeyToolCommand::CoSign {
+        address: KeyIdentity::Address(sponsor),
+        signed_tx_bytes,
+    }
+    .execute(&mut sponsor_keystore)
+    .await?;
+    let signed_tx_bytes = serde_json::to_value(&output)?["signedTxBytes"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let SuiClientCommandResult::TransactionBlock(response) =
+        SuiClientCommands::ExecuteCombinedSignedTx { signed_tx_bytes }
+            .execute(context)
+            .await?
+    else {
+        panic!("Expected a transaction block");
+    };
+    let effects = response.effects.unwrap();
+    assert!(effects.status().is_ok());
+    let gas = effects.gas_object();
+    assert_eq!(gas.reference.object_id, sponsor_coin);
+    assert_eq!(gas.owner, Owner::AddressOwner(sponsor));
+    assert!(effects
+        .created()
+        .iter()
+        .any(|obj| obj.owner == Owner::AddressOwner(recipient)));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_stake_with_none_amount() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await;
@@ -3646,6 +3741,7 @@ async fn test_gas_estimation() -> Result<(), anyhow::Error> {
             dry_run: false,
             serialize_unsigned_transaction: false,
             serialize_signed_transaction: false,
+            gas_sponsor: None,
         },
     }
     .execute(context)
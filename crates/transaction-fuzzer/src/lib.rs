@@ -5,6 +5,7 @@ pub mod account_universe;
 pub mod config_fuzzer;
 pub mod executor;
 pub mod programmable_transaction_gen;
+pub mod simulacrum_fuzzer;
 pub mod transaction_data_gen;
 pub mod type_arg_fuzzer;
 
@@ -0,0 +1,842 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fuzzes programmable transactions against a [`Simulacrum`], across checkpoints, clock updates
+//! and epochs, checking global invariants after every checkpoint:
+//! - SUI is conserved: SUI held by live objects, plus the gas charged in the epoch so far, adds
+//!   up to the SUI held at genesis.
+//! - Storage rebates are accounted for: every transaction charges exactly the storage rebates of
+//!   the objects it writes, and refunds exactly the storage rebates of the objects it modifies.
+//! - Object versions only increase.
+//!
+//! Generation is guided by the outcomes it covers: transactions whose execution outcome was not
+//! seen before are kept in a corpus, and later transactions are either freshly generated or
+//! mutated from the corpus. A failing run is minimized and can be written out as a transactional
+//! test, replayable with `sui-graphql-e2e-tests`.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::{self, Debug, Write as _},
+    panic::{self, AssertUnwindSafe},
+    time::Duration,
+};
+
+use proptest::{
+    collection::vec,
+    prelude::*,
+    strategy::ValueTree,
+    test_runner::{Config, RngAlgorithm, TestRng, TestRunner},
+};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use simulacrum::Simulacrum;
+use sui_protocol_config::ProtocolVersion;
+use sui_swarm_config::genesis_config::AccountConfig;
+use sui_types::{
+    base_types::{ObjectID, SequenceNumber, SuiAddress},
+    crypto::{get_key_pair_from_rng, AccountKeyPair},
+    digests::TransactionDigest,
+    effects::{TransactionEffects, TransactionEffectsAPI},
+    error::SuiError,
+    execution_status::ExecutionStatus,
+    messages_checkpoint::VerifiedCheckpoint,
+    object::GAS_VALUE_FOR_TESTING,
+    transaction::{
+        Argument, CallArg, Command, GasData, ProgrammableTransaction, Transaction, TransactionData,
+        TransactionKind,
+    },
+};
+
+use crate::programmable_transaction_gen::gen_argument;
+
+const CHAIN_START_TIMESTAMP_MS: u64 = 1;
+/// Same as the default of the transactional test runner, so that minimized failures replay the
+/// same way.
+const GAS_BUDGET: u64 = 5_000_000_000;
+
+const MAX_INPUTS: usize = 8;
+const MAX_COMMANDS: usize = 8;
+const MAX_ARGS: usize = 4;
+// Commands in generated transactions return at most this many values.
+const MAX_RESULTS: usize = MAX_ARGS;
+const MAX_AMOUNT: u64 = 1_000_000_000;
+
+#[derive(Clone, Debug)]
+pub struct FuzzConfig {
+    pub seed: u64,
+    /// Number of accounts sending transactions, at most 26.
+    pub num_accounts: usize,
+    pub num_checkpoints: usize,
+    pub transactions_per_checkpoint: usize,
+    pub checkpoints_per_epoch: usize,
+}
+
+impl Default for FuzzConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            num_accounts: 3,
+            num_checkpoints: 20,
+            transactions_per_checkpoint: 5,
+            checkpoints_per_epoch: 5,
+        }
+    }
+}
+
+/// A pure input of a fuzzed transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FuzzInput {
+    Amount(u64),
+    /// Address of the account with this index.
+    Account(usize),
+}
+
+/// A programmable transaction sent by one of the fuzzed accounts, paid for with its gas coin.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FuzzTransaction {
+    pub sender: usize,
+    pub inputs: Vec<FuzzInput>,
+    pub commands: Vec<FuzzCommand>,
+}
+
+/// A command of a fuzzed transaction. Only commands that can be written out in transactional
+/// tests are generated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FuzzCommand {
+    TransferObjects(Vec<Argument>, Argument),
+    SplitCoins(Argument, Vec<Argument>),
+    MergeCoins(Argument, Vec<Argument>),
+    MakeMoveVec(Vec<Argument>),
+}
+
+impl FuzzCommand {
+    fn to_command(&self) -> Command {
+        match self {
+            FuzzCommand::TransferObjects(objects, recipient) => {
+                Command::TransferObjects(objects.clone(), *recipient)
+            }
+            FuzzCommand::SplitCoins(coin, amounts) => Command::SplitCoins(*coin, amounts.clone()),
+            FuzzCommand::MergeCoins(target, coins) => Command::MergeCoins(*target, coins.clone()),
+            FuzzCommand::MakeMoveVec(elements) => Command::MakeMoveVec(None, elements.clone()),
+        }
+    }
+}
+
+/// One step of a fuzzing run. Steps refer to accounts by index and do not depend on the state of
+/// the chain, so that a run can be replayed from its steps, with or without some of them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FuzzStep {
+    Transaction(FuzzTransaction),
+    AdvanceClock(Duration),
+    CreateCheckpoint,
+    AdvanceEpoch,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Violation {
+    SuiNotConserved {
+        checkpoint: u64,
+        expected: u64,
+        actual: i128,
+    },
+    StorageRebateMismatch {
+        transaction: TransactionDigest,
+        charged: u64,
+        expected: u64,
+    },
+    VersionNotIncreasing {
+        transaction: TransactionDigest,
+        object: ObjectID,
+        previous: SequenceNumber,
+        current: SequenceNumber,
+    },
+    Panic(String),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::SuiNotConserved {
+                checkpoint,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "SUI not conserved at checkpoint {checkpoint}: expected {expected}, found {actual}"
+            ),
+            Violation::StorageRebateMismatch {
+                transaction,
+                charged,
+                expected,
+            } => write!(
+                f,
+                "Transaction {transaction} charged or refunded {charged} MIST of storage, expected {expected}"
+            ),
+            Violation::VersionNotIncreasing {
+                transaction,
+                object,
+                previous,
+                current,
+            } => write!(
+                f,
+                "Transaction {transaction} moved object {object} from version {previous} to {current}"
+            ),
+            Violation::Panic(message) => write!(f, "Panicked: {message}"),
+        }
+    }
+}
+
+/// A violation found by a run, with the minimized steps that reproduce it.
+#[derive(Clone, Debug)]
+pub struct FuzzFailure {
+    pub config: FuzzConfig,
+    pub violation: Violation,
+    pub steps: Vec<FuzzStep>,
+}
+
+#[derive(Clone, Debug)]
+pub struct FuzzReport {
+    pub transactions: usize,
+    pub checkpoints: usize,
+    /// Distinct execution outcomes that were reached.
+    pub coverage: BTreeSet<String>,
+    pub failure: Option<FuzzFailure>,
+}
+
+/// Runs the fuzzer for `config.num_checkpoints` checkpoints, or until an invariant is violated.
+pub fn run(config: FuzzConfig) -> FuzzReport {
+    assert!(
+        (1..=26).contains(&config.num_accounts),
+        "Accounts are named by letter"
+    );
+    let mut runner = TestRunner::new_with_rng(
+        Config::default(),
+        TestRng::from_seed(RngAlgorithm::ChaCha, &seed_bytes(config.seed)),
+    );
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut chain = FuzzChain::new(&config);
+    let mut report = FuzzReport {
+        transactions: 0,
+        checkpoints: 0,
+        coverage: BTreeSet::new(),
+        failure: None,
+    };
+    let mut corpus: Vec<FuzzTransaction> = vec![];
+    let mut steps = vec![];
+
+    for checkpoint in 1..=config.num_checkpoints {
+        let mut round = vec![];
+        for _ in 0..config.transactions_per_checkpoint {
+            let transaction = match corpus.choose(&mut rng) {
+                Some(parent) if rng.gen_bool(0.5) => {
+                    mutate(parent, config.num_accounts, &mut runner, &mut rng)
+                }
+                _ => generate(gen_fuzz_transaction(config.num_accounts), &mut runner),
+            };
+            round.push(FuzzStep::Transaction(transaction));
+        }
+        if rng.gen_bool(0.5) {
+            round.push(FuzzStep::AdvanceClock(Duration::from_millis(
+                rng.gen_range(1..10_000),
+            )));
+        }
+        round.push(if checkpoint % config.checkpoints_per_epoch == 0 {
+            FuzzStep::AdvanceEpoch
+        } else {
+            FuzzStep::CreateCheckpoint
+        });
+
+        for step in round {
+            steps.push(step.clone());
+            match chain.execute(&step) {
+                Ok(Some(outcome)) => {
+                    report.transactions += 1;
+                    if report.coverage.insert(outcome) {
+                        let FuzzStep::Transaction(transaction) = step else {
+                            unreachable!("Only transactions have outcomes");
+                        };
+                        corpus.push(transaction);
+                    }
+                }
+                Ok(None) => (),
+                Err(violation) => {
+                    let steps = minimize(&config, steps, &violation);
+                    report.failure = Some(FuzzFailure {
+                        config,
+                        violation,
+                        steps,
+                    });
+                    return report;
+                }
+            }
+        }
+        report.checkpoints += 1;
+    }
+    report
+}
+
+/// Replays `steps` on a new chain, returning the first violation.
+pub fn replay(config: &FuzzConfig, steps: &[FuzzStep]) -> Result<(), Violation> {
+    let mut chain = FuzzChain::new(config);
+    for step in steps {
+        chain.execute(step)?;
+    }
+    Ok(())
+}
+
+/// Removes steps, then commands, from `steps` as long as they still cause a violation of the same
+/// kind.
+fn minimize(config: &FuzzConfig, mut steps: Vec<FuzzStep>, violation: &Violation) -> Vec<FuzzStep> {
+    let reproduces = |steps: &[FuzzStep]| {
+        matches!(
+            replay(config, steps),
+            Err(v) if std::mem::discriminant(&v) == std::mem::discriminant(violation)
+        )
+    };
+
+    let mut chunk = steps.len() / 2;
+    while chunk > 0 {
+        let mut start = 0;
+        while start < steps.len() {
+            let mut candidate = steps.clone();
+            candidate.drain(start..(start + chunk).min(steps.len()));
+            if reproduces(&candidate) {
+                steps = candidate;
+            } else {
+                start += chunk;
+            }
+        }
+        chunk /= 2;
+    }
+
+    for index in 0..steps.len() {
+        let FuzzStep::Transaction(transaction) = &steps[index] else {
+            continue;
+        };
+        for command in (0..transaction.commands.len()).rev() {
+            let mut candidate = steps.clone();
+            let FuzzStep::Transaction(transaction) = &mut candidate[index] else {
+                unreachable!();
+            };
+            if transaction.commands.len() == 1 {
+                break;
+            }
+            transaction.commands.remove(command);
+            if reproduces(&candidate) {
+                steps = candidate;
+            }
+        }
+    }
+    steps
+}
+
+impl FuzzFailure {
+    /// Renders the failing steps as a transactional test for the simulator.
+    pub fn to_transactional_test(&self) -> String {
+        let names = (0..self.config.num_accounts)
+            .map(account_name)
+            .collect::<Vec<_>>();
+        let mut test = String::new();
+        writeln!(
+            test,
+            "// Found by the transaction fuzzer with seed {}: {}",
+            self.config.seed, self.violation
+        )
+        .unwrap();
+        writeln!(test).unwrap();
+        writeln!(
+            test,
+            "//# init --protocol-version {} --accounts {} --simulator",
+            ProtocolVersion::MAX.as_u64(),
+            names.join(" ")
+        )
+        .unwrap();
+
+        for step in &self.steps {
+            writeln!(test).unwrap();
+            match step {
+                FuzzStep::Transaction(transaction) => {
+                    write!(
+                        test,
+                        "//# programmable --sender {}",
+                        names[transaction.sender]
+                    )
+                    .unwrap();
+                    if !transaction.inputs.is_empty() {
+                        write!(test, " --inputs").unwrap();
+                        for input in &transaction.inputs {
+                            match input {
+                                FuzzInput::Amount(amount) => write!(test, " {amount}u64"),
+                                FuzzInput::Account(account) => {
+                                    write!(test, " @{}", names[*account])
+                                }
+                            }
+                            .unwrap();
+                        }
+                    }
+                    writeln!(test).unwrap();
+                    let commands = transaction
+                        .commands
+                        .iter()
+                        .map(|command| format!("//> {}", command_syntax(command)))
+                        .collect::<Vec<_>>();
+                    writeln!(test, "{}", commands.join(";\n")).unwrap();
+                }
+                FuzzStep::AdvanceClock(duration) => writeln!(
+                    test,
+                    "//# advance-clock --duration-ns {}",
+                    duration.as_nanos()
+                )
+                .unwrap(),
+                FuzzStep::CreateCheckpoint => writeln!(test, "//# create-checkpoint").unwrap(),
+                FuzzStep::AdvanceEpoch => writeln!(test, "//# advance-epoch").unwrap(),
+            }
+        }
+        test
+    }
+}
+
+struct FuzzChain {
+    simulacrum: Simulacrum<StdRng>,
+    accounts: Vec<(SuiAddress, AccountKeyPair)>,
+    // The coin each account pays gas with, as in transactional tests.
+    gas_coins: Vec<ObjectID>,
+    invariants: InvariantChecker,
+}
+
+impl FuzzChain {
+    fn new(config: &FuzzConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let accounts = (0..config.num_accounts)
+            .map(|_| get_key_pair_from_rng(&mut rng))
+            .collect::<Vec<(SuiAddress, AccountKeyPair)>>();
+        let account_configs = accounts
+            .iter()
+            .map(|(address, _)| AccountConfig {
+                address: Some(*address),
+                gas_amounts: vec![GAS_VALUE_FOR_TESTING],
+            })
+            .collect();
+        let simulacrum = Simulacrum::new_with_protocol_version_and_accounts(
+            rng,
+            CHAIN_START_TIMESTAMP_MS,
+            ProtocolVersion::MAX,
+            account_configs,
+        );
+        let gas_coins = accounts
+            .iter()
+            .map(|(address, _)| {
+                simulacrum
+                    .store()
+                    .owned_objects(*address)
+                    .find(|object| object.is_gas_coin())
+                    .expect("Accounts are funded at genesis")
+                    .id()
+            })
+            .collect();
+        let invariants = InvariantChecker::new(&simulacrum);
+        Self {
+            simulacrum,
+            accounts,
+            gas_coins,
+            invariants,
+        }
+    }
+
+    /// Executes `step`, returning the outcome of transactions. Panics are reported as violations.
+    fn execute(&mut self, step: &FuzzStep) -> Result<Option<String>, Violation> {
+        panic::catch_unwind(AssertUnwindSafe(|| self.execute_unchecked(step))).unwrap_or_else(
+            |payload| {
+                let message = payload
+                    .downcast_ref::<String>()
+                    .cloned()
+                    .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+                    .unwrap_or_default();
+                Err(Violation::Panic(message))
+            },
+        )
+    }
+
+    fn execute_unchecked(&mut self, step: &FuzzStep) -> Result<Option<String>, Violation> {
+        let checkpoint = match step {
+            FuzzStep::Transaction(transaction) => {
+                return Ok(Some(self.execute_transaction(transaction)));
+            }
+            FuzzStep::AdvanceClock(duration) => {
+                self.simulacrum.advance_clock(*duration);
+                return Ok(None);
+            }
+            FuzzStep::CreateCheckpoint => self.simulacrum.create_checkpoint(),
+            FuzzStep::AdvanceEpoch => {
+                self.simulacrum.advance_epoch(false);
+                self.simulacrum
+                    .store()
+                    .get_highest_checkpint()
+                    .expect("Advancing the epoch creates a checkpoint")
+            }
+        };
+        self.invariants.check(&self.simulacrum, &checkpoint)?;
+        Ok(None)
+    }
+
+    fn execute_transaction(&mut self, transaction: &FuzzTransaction) -> String {
+        let (sender, key) = &self.accounts[transaction.sender];
+        let Some(gas_coin) = self
+            .simulacrum
+            .store()
+            .get_object(&self.gas_coins[transaction.sender])
+        else {
+            return "gas coin deleted".to_string();
+        };
+        let inputs = transaction
+            .inputs
+            .iter()
+            .map(|input| match input {
+                FuzzInput::Amount(amount) => CallArg::from(*amount),
+                FuzzInput::Account(account) => {
+                    CallArg::Pure(bcs::to_bytes(&self.accounts[*account].0).unwrap())
+                }
+            })
+            .collect();
+        let kind = TransactionKind::ProgrammableTransaction(ProgrammableTransaction {
+            inputs,
+            commands: transaction
+                .commands
+                .iter()
+                .map(FuzzCommand::to_command)
+                .collect(),
+        });
+        let gas_data = GasData {
+            payment: vec![gas_coin.compute_object_reference()],
+            owner: *sender,
+            price: self.simulacrum.reference_gas_price(),
+            budget: GAS_BUDGET,
+        };
+        let data = TransactionData::new_with_gas_data(kind, *sender, gas_data);
+        let transaction = Transaction::from_data_and_signer(data, vec![key]);
+
+        match self.simulacrum.execute_transaction(transaction) {
+            Ok((effects, _)) => effects_outcome(&effects),
+            Err(error) => match error.downcast_ref::<SuiError>() {
+                Some(error) => format!("rejected {}", shape(error, 2)),
+                None => format!("rejected {}", shape(&error, 1)),
+            },
+        }
+    }
+}
+
+/// Checks invariants over the transactions of every new checkpoint.
+struct InvariantChecker {
+    // SUI held by live objects at genesis, excluding storage rebates.
+    genesis_sui: u64,
+    // Last version of every object, or of its tombstone.
+    versions: HashMap<ObjectID, SequenceNumber>,
+}
+
+impl InvariantChecker {
+    fn new(simulacrum: &Simulacrum<StdRng>) -> Self {
+        Self {
+            genesis_sui: total_sui(simulacrum),
+            versions: simulacrum
+                .store()
+                .live_objects()
+                .map(|object| (object.id(), object.version()))
+                .collect(),
+        }
+    }
+
+    fn check(
+        &mut self,
+        simulacrum: &Simulacrum<StdRng>,
+        checkpoint: &VerifiedCheckpoint,
+    ) -> Result<(), Violation> {
+        let store = simulacrum.store();
+        let contents = store
+            .get_checkpoint_contents(&checkpoint.content_digest)
+            .expect("Checkpoint contents are stored with the checkpoint");
+        for digests in contents.iter() {
+            let transaction = store
+                .get_transaction(&digests.transaction)
+                .expect("Checkpointed transactions are stored");
+            let effects = store
+                .get_transaction_effects(&digests.transaction)
+                .expect("Checkpointed effects are stored");
+            self.check_versions(&effects)?;
+            if !transaction.is_system_tx() {
+                self.check_storage_rebates(simulacrum, &effects)?;
+            }
+        }
+
+        // Gas charged during the epoch is returned to the system state when it ends. Rebates can
+        // exceed the costs charged so far, so the net charge may be negative.
+        let charged = if checkpoint.end_of_epoch_data.is_some() {
+            0
+        } else {
+            checkpoint.epoch_rolling_gas_cost_summary.net_gas_usage()
+        };
+        let actual = total_sui(simulacrum) as i128 + charged as i128;
+        if actual != self.genesis_sui as i128 {
+            return Err(Violation::SuiNotConserved {
+                checkpoint: checkpoint.sequence_number,
+                expected: self.genesis_sui,
+                actual,
+            });
+        }
+        Ok(())
+    }
+
+    fn check_versions(&mut self, effects: &TransactionEffects) -> Result<(), Violation> {
+        let lamport_version = effects.lamport_version();
+        let violation = |object, previous, current| Violation::VersionNotIncreasing {
+            transaction: *effects.transaction_digest(),
+            object,
+            previous,
+            current,
+        };
+
+        // Objects are modified at their latest version, which is older than the transaction.
+        for (id, version) in effects.modified_at_versions() {
+            match self.versions.get(&id) {
+                Some(previous) if *previous > version => {
+                    return Err(violation(id, *previous, version))
+                }
+                _ if version >= lamport_version => {
+                    return Err(violation(id, version, lamport_version))
+                }
+                _ => (),
+            }
+        }
+
+        // Everything written or removed by the transaction moves to its lamport version.
+        let changed = effects
+            .all_changed_objects()
+            .into_iter()
+            .map(|(object_ref, _, _)| object_ref)
+            .chain(
+                effects
+                    .all_removed_objects()
+                    .into_iter()
+                    .map(|(object_ref, _)| object_ref),
+            )
+            .chain(effects.unwrapped_then_deleted());
+        for (id, version, _) in changed {
+            let previous = self.versions.insert(id, version);
+            match previous {
+                Some(previous) if previous >= version => {
+                    return Err(violation(id, previous, version))
+                }
+                _ if version != lamport_version => {
+                    return Err(violation(id, previous.unwrap_or_default(), version))
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    fn check_storage_rebates(
+        &self,
+        simulacrum: &Simulacrum<StdRng>,
+        effects: &TransactionEffects,
+    ) -> Result<(), Violation> {
+        let store = simulacrum.store();
+        let gas = effects.gas_cost_summary();
+
+        let written = effects
+            .all_changed_objects()
+            .into_iter()
+            .map(|((id, version, _), _, _)| {
+                store
+                    .get_object_at_version(&id, version)
+                    .expect("Written objects are stored")
+                    .storage_rebate
+            })
+            .sum::<u64>();
+        if gas.storage_cost != written {
+            return Err(Violation::StorageRebateMismatch {
+                transaction: *effects.transaction_digest(),
+                charged: gas.storage_cost,
+                expected: written,
+            });
+        }
+
+        let modified = effects
+            .modified_at_versions()
+            .into_iter()
+            .map(|(id, version)| {
+                store
+                    .get_object_at_version(&id, version)
+                    .expect("Modified objects are stored")
+                    .storage_rebate
+            })
+            .sum::<u64>();
+        let refunded = gas.storage_rebate + gas.non_refundable_storage_fee;
+        if refunded != modified {
+            return Err(Violation::StorageRebateMismatch {
+                transaction: *effects.transaction_digest(),
+                charged: refunded,
+                expected: modified,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// SUI held by live objects, excluding their storage rebates which are held by the storage fund.
+fn total_sui(simulacrum: &Simulacrum<StdRng>) -> u64 {
+    let mut layout_resolver = simulacrum.type_layout_resolver();
+    simulacrum
+        .store()
+        .live_objects()
+        .map(|object| {
+            object
+                .get_total_sui(layout_resolver.as_mut())
+                .expect("Object layouts are known")
+                - object.storage_rebate
+        })
+        .sum()
+}
+
+fn effects_outcome(effects: &TransactionEffects) -> String {
+    match effects.status() {
+        ExecutionStatus::Success => format!(
+            "success created={} mutated={} deleted={} wrapped={} unwrapped={}",
+            effects.created().len().min(3),
+            effects.mutated().len().min(3),
+            effects.deleted().len().min(3),
+            effects.wrapped().len().min(3),
+            effects.unwrapped().len().min(3),
+        ),
+        ExecutionStatus::Failure { error, .. } => format!("failure {}", shape(error, 3)),
+    }
+}
+
+/// The first `depth` type and variant names in the debug representation of `value`, so that
+/// outcomes which only differ in indices, amounts or IDs are the same.
+fn shape(value: &impl Debug, depth: usize) -> String {
+    format!("{value:?}")
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .filter(|token| token.starts_with(|c: char| c.is_ascii_uppercase()))
+        .take(depth)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn generate<T: Debug>(strategy: impl Strategy<Value = T>, runner: &mut TestRunner) -> T {
+    strategy
+        .new_tree(runner)
+        .expect("Strategies do not reject")
+        .current()
+}
+
+fn mutate(
+    parent: &FuzzTransaction,
+    num_accounts: usize,
+    runner: &mut TestRunner,
+    rng: &mut StdRng,
+) -> FuzzTransaction {
+    let mut transaction = parent.clone();
+    let commands = &mut transaction.commands;
+    match rng.gen_range(0..5) {
+        0 => {
+            let index = rng.gen_range(0..commands.len());
+            commands[index] = generate(gen_fuzz_command(), runner);
+        }
+        1 if commands.len() < MAX_COMMANDS => {
+            let index = rng.gen_range(0..=commands.len());
+            commands.insert(index, generate(gen_fuzz_command(), runner));
+        }
+        2 if commands.len() > 1 => {
+            commands.remove(rng.gen_range(0..commands.len()));
+        }
+        3 if !transaction.inputs.is_empty() => {
+            let index = rng.gen_range(0..transaction.inputs.len());
+            transaction.inputs[index] = generate(gen_fuzz_input(num_accounts), runner);
+        }
+        _ => transaction.sender = rng.gen_range(0..num_accounts),
+    }
+    transaction
+}
+
+prop_compose! {
+    pub fn gen_fuzz_transaction(num_accounts: usize)
+        (sender in 0..num_accounts,
+         inputs in vec(gen_fuzz_input(num_accounts), 0..=MAX_INPUTS),
+         commands in vec(gen_fuzz_command(), 1..=MAX_COMMANDS)) -> FuzzTransaction {
+            FuzzTransaction { sender, inputs, commands }
+    }
+}
+
+pub fn gen_fuzz_input(num_accounts: usize) -> impl Strategy<Value = FuzzInput> {
+    prop_oneof![
+        3 => (1..MAX_AMOUNT).prop_map(FuzzInput::Amount),
+        1 => any::<u64>().prop_map(FuzzInput::Amount),
+        2 => (0..num_accounts).prop_map(FuzzInput::Account),
+    ]
+}
+
+pub fn gen_fuzz_command() -> impl Strategy<Value = FuzzCommand> {
+    let args = || vec(gen_fuzz_argument(), 1..=MAX_ARGS);
+    prop_oneof![
+        (args(), gen_fuzz_argument())
+            .prop_map(|(objects, recipient)| FuzzCommand::TransferObjects(objects, recipient)),
+        (gen_fuzz_argument(), args())
+            .prop_map(|(coin, amounts)| FuzzCommand::SplitCoins(coin, amounts)),
+        (gen_fuzz_argument(), args())
+            .prop_map(|(target, coins)| FuzzCommand::MergeCoins(target, coins)),
+        args().prop_map(FuzzCommand::MakeMoveVec),
+    ]
+}
+
+/// Mostly arguments that refer to existing inputs and results, unlike [`gen_argument`] which is
+/// used for the rest.
+pub fn gen_fuzz_argument() -> impl Strategy<Value = Argument> {
+    prop_oneof![
+        2 => Just(Argument::GasCoin),
+        4 => (0..MAX_INPUTS as u16).prop_map(Argument::Input),
+        4 => (0..MAX_COMMANDS as u16).prop_map(Argument::Result),
+        4 => (0..MAX_COMMANDS as u16, 0..MAX_RESULTS as u16)
+            .prop_map(|(command, result)| Argument::NestedResult(command, result)),
+        1 => gen_argument(),
+    ]
+}
+
+fn account_name(index: usize) -> String {
+    ((b'A' + index as u8) as char).to_string()
+}
+
+/// `command` in the syntax of transactional tests.
+fn command_syntax(command: &FuzzCommand) -> String {
+    fn argument(argument: &Argument) -> String {
+        match argument {
+            Argument::GasCoin => "Gas".to_string(),
+            Argument::Input(i) => format!("Input({i})"),
+            Argument::Result(i) => format!("Result({i})"),
+            Argument::NestedResult(i, j) => format!("NestedResult({i}, {j})"),
+        }
+    }
+    fn arguments(arguments: &[Argument]) -> String {
+        let arguments = arguments.iter().map(argument).collect::<Vec<_>>();
+        format!("[{}]", arguments.join(", "))
+    }
+
+    match command {
+        FuzzCommand::TransferObjects(objects, recipient) => format!(
+            "TransferObjects({}, {})",
+            arguments(objects),
+            argument(recipient)
+        ),
+        FuzzCommand::SplitCoins(coin, amounts) => {
+            format!("SplitCoins({}, {})", argument(coin), arguments(amounts))
+        }
+        FuzzCommand::MergeCoins(target, coins) => {
+            format!("MergeCoins({}, {})", argument(target), arguments(coins))
+        }
+        FuzzCommand::MakeMoveVec(elements) => format!("MakeMoveVec({})", arguments(elements)),
+    }
+}
+
+fn seed_bytes(seed: u64) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    bytes[..8].copy_from_slice(&seed.to_le_bytes());
+    bytes
+}
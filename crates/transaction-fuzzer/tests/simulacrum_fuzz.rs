@@ -0,0 +1,31 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use transaction_fuzzer::simulacrum_fuzzer::{self, FuzzConfig};
+
+#[test]
+#[cfg_attr(msim, ignore)]
+fn fuzz_simulacrum_invariants() {
+    for seed in 0..4 {
+        let report = simulacrum_fuzzer::run(FuzzConfig {
+            seed,
+            ..Default::default()
+        });
+        if let Some(failure) = report.failure {
+            panic!(
+                "{}\nReproduce with:\n{}",
+                failure.violation,
+                failure.to_transactional_test()
+            );
+        }
+        assert!(report.checkpoints > 0);
+        assert!(
+            report
+                .coverage
+                .iter()
+                .any(|outcome| outcome.starts_with("success")),
+            "No transaction succeeded with seed {seed}: {:?}",
+            report.coverage
+        );
+    }
+}